@@ -0,0 +1,4 @@
+-- Optimistic concurrency: the version is bumped on every update
+ALTER TABLE "Customer" ADD COLUMN "version" INT8 NOT NULL DEFAULT 1;
+ALTER TABLE "Expense" ADD COLUMN "version" INT8 NOT NULL DEFAULT 1;
+ALTER TABLE "RegularPayment" ADD COLUMN "version" INT8 NOT NULL DEFAULT 1;
@@ -0,0 +1,2 @@
+-- Free text note of an expense, e.g. a merchant name
+ALTER TABLE "Expense" ADD COLUMN "note" STRING NULL;
@@ -0,0 +1,5 @@
+-- Soft delete: rows with "deletedAt" set are in the trash until they are purged
+ALTER TABLE "Customer" ADD COLUMN "deletedAt" TIMESTAMPTZ(3) NULL;
+ALTER TABLE "Expense" ADD COLUMN "deletedAt" TIMESTAMPTZ(3) NULL;
+CREATE INDEX "Customer_deletedAt_idx" ON "Customer" ("deletedAt");
+CREATE INDEX "Expense_deletedAt_idx" ON "Expense" ("deletedAt");
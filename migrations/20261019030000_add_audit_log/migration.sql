@@ -0,0 +1,18 @@
+-- Change history of customers, expenses and regular payments
+CREATE TABLE "AuditLog" (
+    "id" STRING NOT NULL,
+    "entityType" STRING NOT NULL,
+    "entityId" STRING NOT NULL,
+    "ownerId" STRING NULL,
+    "action" STRING NOT NULL,
+    "actorId" STRING NULL,
+    "actorIsAdmin" BOOL NOT NULL DEFAULT false,
+    "impersonatedBy" STRING NULL,
+    "requestId" STRING NULL,
+    "changes" JSONB NOT NULL,
+    "createdAt" TIMESTAMPTZ(3) NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    CONSTRAINT "AuditLog_pkey" PRIMARY KEY ("id")
+);
+CREATE INDEX "AuditLog_entityType_entityId_createdAt_idx" ON "AuditLog" ("entityType", "entityId", "createdAt");
+CREATE INDEX "AuditLog_ownerId_createdAt_idx" ON "AuditLog" ("ownerId", "createdAt");
+CREATE INDEX "AuditLog_actorId_createdAt_idx" ON "AuditLog" ("actorId", "createdAt");
@@ -0,0 +1,4 @@
+-- Id of the transaction in the imported statement (e.g. OFX FITID), re-imports skip known ids
+ALTER TABLE "Expense" ADD COLUMN "externalId" STRING NULL;
+
+CREATE UNIQUE INDEX "Expense_customerId_externalId_key" ON "Expense"("customerId", "externalId");
@@ -0,0 +1,18 @@
+-- Requests to erase all the data of a customer. They outlive the customer, so there is
+-- no foreign key and no personal data besides the customer id
+CREATE TABLE "ErasureRequest" (
+    "id" STRING NOT NULL,
+    "customerId" STRING NOT NULL,
+    "status" STRING NOT NULL,
+    "requestedBy" STRING NULL,
+    "requestId" STRING NULL,
+    "error" STRING NULL,
+    "erasedExpenses" INT8 NOT NULL DEFAULT 0,
+    "erasedRegularPayments" INT8 NOT NULL DEFAULT 0,
+    "anonymizedAuditLogs" INT8 NOT NULL DEFAULT 0,
+    "createdAt" TIMESTAMPTZ(3) NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    "completedAt" TIMESTAMPTZ(3) NULL,
+    CONSTRAINT "ErasureRequest_pkey" PRIMARY KEY ("id")
+);
+CREATE INDEX "ErasureRequest_customerId_createdAt_idx" ON "ErasureRequest" ("customerId", "createdAt");
+CREATE INDEX "ErasureRequest_status_createdAt_idx" ON "ErasureRequest" ("status", "createdAt");
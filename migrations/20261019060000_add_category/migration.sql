@@ -0,0 +1,56 @@
+-- Categories of expenses. System categories have no customer and a code (the former
+-- "ExpenseCategory" values, still accepted by the API), custom ones belong to a customer
+CREATE TABLE "Category" (
+    "id" STRING NOT NULL,
+    "customerId" STRING NULL,
+    "parentId" STRING NULL,
+    "code" STRING NULL,
+    "name" STRING NOT NULL,
+    "icon" STRING NULL,
+    "color" STRING NULL,
+    "archivedAt" TIMESTAMPTZ(3) NULL,
+    "createdAt" TIMESTAMPTZ(3) NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    "updatedAt" TIMESTAMPTZ(3) NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    "version" INT8 NOT NULL DEFAULT 1,
+    CONSTRAINT "Category_pkey" PRIMARY KEY ("id"),
+    CONSTRAINT "Category_customerId_fkey" FOREIGN KEY ("customerId") REFERENCES "Customer"("id") ON DELETE RESTRICT ON UPDATE CASCADE,
+    CONSTRAINT "Category_parentId_fkey" FOREIGN KEY ("parentId") REFERENCES "Category"("id") ON DELETE RESTRICT ON UPDATE CASCADE
+);
+CREATE UNIQUE INDEX "Category_code_key" ON "Category" ("code");
+CREATE INDEX "Category_customerId_idx" ON "Category" ("customerId");
+CREATE INDEX "Category_parentId_idx" ON "Category" ("parentId");
+
+INSERT INTO "Category" ("id", "code", "name") VALUES
+    (gen_random_uuid()::STRING, 'FOOD', 'Food'),
+    (gen_random_uuid()::STRING, 'CLOTHES', 'Clothes'),
+    (gen_random_uuid()::STRING, 'SUBSCRIPTIONS', 'Subscriptions'),
+    (gen_random_uuid()::STRING, 'OTHER', 'Other'),
+    (gen_random_uuid()::STRING, 'MEDICINE', 'Medicine'),
+    (gen_random_uuid()::STRING, 'UTILITY_PAYMENTS', 'Utility payments'),
+    (gen_random_uuid()::STRING, 'ANIMALS', 'Animals'),
+    (gen_random_uuid()::STRING, 'PLACES_TO_EAT', 'Places to eat'),
+    (gen_random_uuid()::STRING, 'EDUCATION', 'Education'),
+    (gen_random_uuid()::STRING, 'BOOKS', 'Books'),
+    (gen_random_uuid()::STRING, 'TAXI', 'Taxi'),
+    (gen_random_uuid()::STRING, 'GIFTS', 'Gifts'),
+    (gen_random_uuid()::STRING, 'DONATIONS', 'Donations'),
+    (gen_random_uuid()::STRING, 'MOBILE_SERVICES', 'Mobile services'),
+    (gen_random_uuid()::STRING, 'SPORTS', 'Sports'),
+    (gen_random_uuid()::STRING, 'ENTERTAINMENT', 'Entertainment'),
+    (gen_random_uuid()::STRING, 'BEAUTY_AND_CARE', 'Beauty and care'),
+    (gen_random_uuid()::STRING, 'HOUSEHOLD', 'Household'),
+    (gen_random_uuid()::STRING, 'PUBLIC_TRANSPORT', 'Public transport'),
+    (gen_random_uuid()::STRING, 'TRAVEL', 'Travel');
+
+-- Expenses refer to categories by id, regular payments keep the enum
+ALTER TABLE "Expense" ADD COLUMN "categoryId" STRING NULL;
+
+UPDATE "Expense" SET "categoryId" = "Category"."id"
+FROM "Category"
+WHERE "Category"."code" = "Expense"."category"::STRING;
+
+ALTER TABLE "Expense" ALTER COLUMN "categoryId" SET NOT NULL;
+ALTER TABLE "Expense" ADD CONSTRAINT "Expense_categoryId_fkey" FOREIGN KEY ("categoryId") REFERENCES "Category"("id") ON DELETE RESTRICT ON UPDATE CASCADE;
+CREATE INDEX "Expense_categoryId_idx" ON "Expense" ("categoryId");
+
+ALTER TABLE "Expense" DROP COLUMN "category";
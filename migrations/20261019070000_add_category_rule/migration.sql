@@ -0,0 +1,24 @@
+-- Rules of a customer which categorize expenses created without a category. Rules are
+-- evaluated by ascending priority, all the set conditions of a rule have to match
+CREATE TABLE "CategoryRule" (
+    "id" STRING NOT NULL,
+    "customerId" STRING NOT NULL,
+    "categoryId" STRING NOT NULL,
+    "name" STRING NOT NULL,
+    "priority" INT8 NOT NULL DEFAULT 0,
+    "isEnabled" BOOL NOT NULL DEFAULT true,
+    "noteContains" STRING NULL,
+    "notePattern" STRING NULL,
+    "amountMin" DECIMAL(10,2) NULL,
+    "amountMax" DECIMAL(10,2) NULL,
+    -- Bit 0 is Monday, bit 6 is Sunday
+    "weekdays" INT8 NULL,
+    "createdAt" TIMESTAMPTZ(3) NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    "updatedAt" TIMESTAMPTZ(3) NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    "version" INT8 NOT NULL DEFAULT 1,
+    CONSTRAINT "CategoryRule_pkey" PRIMARY KEY ("id"),
+    CONSTRAINT "CategoryRule_customerId_fkey" FOREIGN KEY ("customerId") REFERENCES "Customer"("id") ON DELETE RESTRICT ON UPDATE CASCADE,
+    CONSTRAINT "CategoryRule_categoryId_fkey" FOREIGN KEY ("categoryId") REFERENCES "Category"("id") ON DELETE RESTRICT ON UPDATE CASCADE
+);
+CREATE INDEX "CategoryRule_customerId_priority_idx" ON "CategoryRule" ("customerId", "priority");
+CREATE INDEX "CategoryRule_categoryId_idx" ON "CategoryRule" ("categoryId");
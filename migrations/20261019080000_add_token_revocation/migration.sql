@@ -0,0 +1,9 @@
+-- Revoked tokens ("token:<jti>") and revoked sessions of users ("user:<sub>"). A row is
+-- useless once every token it covers expired, so it is kept until "expiresAt" only
+CREATE TABLE "TokenRevocation" (
+    "id" STRING NOT NULL,
+    "revokedAt" TIMESTAMPTZ(6) NOT NULL,
+    "expiresAt" TIMESTAMPTZ(3) NOT NULL,
+    CONSTRAINT "TokenRevocation_pkey" PRIMARY KEY ("id")
+);
+CREATE INDEX "TokenRevocation_expiresAt_idx" ON "TokenRevocation" ("expiresAt");
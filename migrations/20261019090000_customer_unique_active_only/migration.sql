@@ -0,0 +1,7 @@
+-- Customers in the trash should not block new ones with the same user, email or phone
+DROP INDEX IF EXISTS "Customer"@"Customer_userId_key" CASCADE;
+DROP INDEX IF EXISTS "Customer"@"Customer_email_key" CASCADE;
+DROP INDEX IF EXISTS "Customer"@"Customer_phone_key" CASCADE;
+CREATE UNIQUE INDEX "Customer_userId_key" ON "Customer" ("userId") WHERE "deletedAt" IS NULL;
+CREATE UNIQUE INDEX "Customer_email_key" ON "Customer" ("email") WHERE "deletedAt" IS NULL;
+CREATE UNIQUE INDEX "Customer_phone_key" ON "Customer" ("phone") WHERE "deletedAt" IS NULL;
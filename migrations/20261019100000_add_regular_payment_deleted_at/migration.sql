@@ -0,0 +1,3 @@
+-- Regular payments are soft deleted as well, they stay in the trash until they are purged
+ALTER TABLE "RegularPayment" ADD COLUMN "deletedAt" TIMESTAMPTZ(3) NULL;
+CREATE INDEX "RegularPayment_deletedAt_idx" ON "RegularPayment" ("deletedAt");
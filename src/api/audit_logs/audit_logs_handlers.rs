@@ -0,0 +1,19 @@
+use axum::Json;
+use axum::extract::{Query, State};
+use std::sync::Arc;
+
+use crate::api::audit_logs::audit_logs_service::AuditLogsService;
+use crate::api::audit_logs::dto::find_audit_logs_dto::FindAuditLogsDto;
+use crate::api::audit_logs::entities::audit_log_entity::AuditLogEntity;
+use crate::shared::errors::http_error::HttpError;
+
+pub async fn find_many(
+    State(audit_logs_service): State<Arc<AuditLogsService>>,
+    Query(filter): Query<FindAuditLogsDto>,
+) -> Result<AuditLogEntitiesJson, HttpError> {
+    let found_audit_logs = audit_logs_service.find_many(filter).await?;
+
+    Ok(Json(found_audit_logs))
+}
+
+pub type AuditLogEntitiesJson = Json<Vec<AuditLogEntity>>;
@@ -0,0 +1,114 @@
+use async_trait::async_trait;
+use sea_orm::{
+    ColumnTrait, Condition, DatabaseConnection, EntityTrait, IntoActiveModel, QueryFilter,
+    QueryOrder, QuerySelect,
+};
+use std::sync::Arc;
+
+use crate::api::audit_logs::dto::create_audit_log_db_dto::CreateAuditLogDbDto;
+use crate::api::audit_logs::dto::find_audit_logs_dto::FindAuditLogsDto;
+use crate::api::audit_logs::traits::audit_logs_repository::AuditLogsRepositoryTrait;
+use crate::api::audit_logs::types::audit_log_from_db::AuditLogFromDb;
+use crate::shared::errors::http_error::HttpError;
+use crate::shared::modules::db::db_connection::DbConnection;
+use crate::shared::modules::db::entities::audit_log;
+use crate::shared::modules::db::entities::prelude::AuditLog;
+
+const DEFAULT_LIMIT: u64 = 100;
+const MAX_LIMIT: u64 = 1000;
+
+pub struct AuditLogsRepository {
+    connection: DbConnection,
+}
+
+impl AuditLogsRepository {
+    pub fn new(sea_orm_client: Arc<DatabaseConnection>) -> Self {
+        Self {
+            connection: DbConnection::Pool(sea_orm_client),
+        }
+    }
+
+    fn get_filter_condition(filter: &FindAuditLogsDto) -> Condition {
+        Condition::all()
+            .add_option(filter.entity_type.map(|entity_type| {
+                audit_log::Column::EntityType.eq(audit_log::AuditEntityType::from(entity_type))
+            }))
+            .add_option(
+                filter
+                    .entity_id
+                    .clone()
+                    .map(|entity_id| audit_log::Column::EntityId.eq(entity_id)),
+            )
+            .add_option(
+                filter
+                    .owner_id
+                    .clone()
+                    .map(|owner_id| audit_log::Column::OwnerId.eq(owner_id)),
+            )
+            .add_option(
+                filter
+                    .actor_id
+                    .clone()
+                    .map(|actor_id| audit_log::Column::ActorId.eq(actor_id)),
+            )
+            .add_option(
+                filter
+                    .request_id
+                    .clone()
+                    .map(|request_id| audit_log::Column::RequestId.eq(request_id)),
+            )
+            .add_option(
+                filter
+                    .created_from
+                    .map(|created_from| audit_log::Column::CreatedAt.gte(created_from)),
+            )
+            .add_option(
+                filter
+                    .created_to
+                    .map(|created_to| audit_log::Column::CreatedAt.lte(created_to)),
+            )
+    }
+}
+
+#[async_trait]
+impl AuditLogsRepositoryTrait for AuditLogsRepository {
+    fn with_connection(
+        &self,
+        connection: DbConnection,
+    ) -> Arc<dyn AuditLogsRepositoryTrait + Send + Sync> {
+        Arc::new(Self { connection })
+    }
+
+    async fn create_many(&self, create_dtos: Vec<CreateAuditLogDbDto>) -> Result<(), HttpError> {
+        if create_dtos.is_empty() {
+            return Ok(());
+        }
+
+        AuditLog::insert_many(
+            create_dtos
+                .into_iter()
+                .map(IntoActiveModel::into_active_model),
+        )
+        .exec_without_returning(&self.connection)
+        .await?;
+
+        Ok(())
+    }
+
+    async fn find_many(&self, filter: FindAuditLogsDto) -> Result<Vec<AuditLogFromDb>, HttpError> {
+        let limit = filter.limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT);
+
+        let audit_logs_from_db = AuditLog::find()
+            .filter(Self::get_filter_condition(&filter))
+            // Ids are time-ordered, they keep the order of changes made at the same time
+            .order_by_desc(audit_log::Column::CreatedAt)
+            .order_by_desc(audit_log::Column::Id)
+            .limit(limit)
+            .all(&self.connection)
+            .await?;
+
+        let mapped_audit_logs = audit_logs_from_db.into_iter().map(Into::into).collect();
+
+        Ok(mapped_audit_logs)
+    }
+}
@@ -0,0 +1,129 @@
+use serde_json::{Map, Value, json};
+use std::sync::Arc;
+
+use crate::api::audit_logs::dto::create_audit_log_db_dto::CreateAuditLogDbDto;
+use crate::api::audit_logs::dto::find_audit_logs_dto::FindAuditLogsDto;
+use crate::api::audit_logs::entities::audit_log_entity::AuditLogEntity;
+use crate::api::audit_logs::traits::audit_logs_repository::AuditLogsRepositoryTrait;
+use crate::api::audit_logs::traits::audited::Audited;
+use crate::api::audit_logs::types::audit_action::AuditAction;
+use crate::shared::errors::http_error::HttpError;
+use crate::shared::modules::audit::structs::audit_context::AuditContext;
+use crate::shared::modules::db::db_connection::DbConnection;
+use crate::shared::modules::db::utils::generate_id;
+
+/// Bookkeeping fields which change on every write and are not worth recording
+const IGNORED_FIELDS: [&str; 2] = ["updatedAt", "version"];
+
+#[derive(Clone)]
+pub struct AuditLogsService {
+    audit_logs_repository: Arc<dyn AuditLogsRepositoryTrait + Send + Sync>,
+}
+
+impl AuditLogsService {
+    pub fn new(audit_logs_repository: Arc<dyn AuditLogsRepositoryTrait + Send + Sync>) -> Self {
+        Self {
+            audit_logs_repository,
+        }
+    }
+
+    /// Service which queries run on the connection, e.g. a unit of work transaction
+    pub fn with_connection(&self, connection: DbConnection) -> Self {
+        Self {
+            audit_logs_repository: self.audit_logs_repository.with_connection(connection),
+        }
+    }
+
+    pub async fn find_many(
+        &self,
+        filter: FindAuditLogsDto,
+    ) -> Result<Vec<AuditLogEntity>, HttpError> {
+        let audit_log_entities = self
+            .audit_logs_repository
+            .find_many(filter)
+            .await?
+            .into_iter()
+            .map(AuditLogEntity::from)
+            .collect();
+
+        Ok(audit_log_entities)
+    }
+
+    /// Records the changes of entities, `before` and `after` states are matched by id.
+    /// Entities missing in `before` are recorded as created from scratch.
+    /// Should run in the transaction of the change, so it is recorded only if it is committed
+    pub async fn record<T>(
+        &self,
+        audit_context: &AuditContext,
+        action: AuditAction,
+        before: &[T],
+        after: &[T],
+    ) -> Result<(), HttpError>
+    where
+        T: Audited,
+    {
+        let create_db_dtos = after
+            .iter()
+            .map(|after_entity| {
+                let before_entity = before
+                    .iter()
+                    .find(|entity| entity.get_audit_id() == after_entity.get_audit_id());
+
+                Ok(CreateAuditLogDbDto {
+                    id: generate_id(),
+                    entity_type: T::AUDIT_ENTITY_TYPE,
+                    entity_id: after_entity.get_audit_id().to_string(),
+                    owner_id: Some(after_entity.get_audit_owner_id().to_string()),
+                    action,
+                    actor_id: audit_context.actor_id.clone(),
+                    actor_is_admin: audit_context.actor_is_admin,
+                    impersonated_by: audit_context.impersonated_by.clone(),
+                    request_id: audit_context.request_id.clone(),
+                    changes: Self::get_changes(before_entity, after_entity)?,
+                })
+            })
+            .collect::<Result<Vec<_>, HttpError>>()?;
+
+        self.audit_logs_repository.create_many(create_db_dtos).await
+    }
+
+    /// `{ "<field>": { "before": ..., "after": ... } }` of the fields which values differ
+    fn get_changes<T>(before: Option<&T>, after: &T) -> Result<Value, HttpError>
+    where
+        T: Audited,
+    {
+        let before = before.map(Self::to_fields).transpose()?.unwrap_or_default();
+        let after = Self::to_fields(after)?;
+
+        let changes: Map<String, Value> = after
+            .into_iter()
+            .filter(|(field, _)| !IGNORED_FIELDS.contains(&field.as_str()))
+            .filter_map(|(field, after_value)| {
+                let before_value = before.get(&field).cloned().unwrap_or(Value::Null);
+
+                (before_value != after_value).then(|| {
+                    let change = json!({ "before": before_value, "after": after_value });
+
+                    (field, change)
+                })
+            })
+            .collect();
+
+        Ok(Value::Object(changes))
+    }
+
+    fn to_fields<T>(entity: &T) -> Result<Map<String, Value>, HttpError>
+    where
+        T: Audited,
+    {
+        match serde_json::to_value(entity) {
+            Ok(Value::Object(fields)) => Ok(fields),
+            Ok(_) => Ok(Map::new()),
+            Err(err) => Err(HttpError::Internal(format!(
+                "Failed to serialize {:?} '{}' for the audit log: {err}",
+                T::AUDIT_ENTITY_TYPE,
+                entity.get_audit_id()
+            ))),
+        }
+    }
+}
@@ -0,0 +1,20 @@
+use sea_orm::DeriveIntoActiveModel;
+use serde::{Deserialize, Serialize};
+
+use crate::api::audit_logs::types::audit_action::AuditAction;
+use crate::api::audit_logs::types::audit_entity_type::AuditEntityType;
+use crate::shared::modules::db::entities::audit_log::ActiveModel;
+
+#[derive(Serialize, Deserialize, Debug, DeriveIntoActiveModel)]
+pub struct CreateAuditLogDbDto {
+    pub id: String,
+    pub entity_type: AuditEntityType,
+    pub entity_id: String,
+    pub owner_id: Option<String>,
+    pub action: AuditAction,
+    pub actor_id: Option<String>,
+    pub actor_is_admin: bool,
+    pub impersonated_by: Option<String>,
+    pub request_id: Option<String>,
+    pub changes: serde_json::Value,
+}
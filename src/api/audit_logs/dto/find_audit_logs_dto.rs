@@ -0,0 +1,21 @@
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+use crate::api::audit_logs::types::audit_entity_type::AuditEntityType;
+
+#[derive(Debug, Deserialize, Serialize, Default, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct FindAuditLogsDto {
+    pub entity_type: Option<AuditEntityType>,
+    pub entity_id: Option<String>,
+    /// Customer the changed entity belongs to
+    pub owner_id: Option<String>,
+    /// User who made the changes
+    pub actor_id: Option<String>,
+    pub request_id: Option<String>,
+    /// Inclusive bounds of the change time
+    pub created_from: Option<chrono::DateTime<chrono::FixedOffset>>,
+    pub created_to: Option<chrono::DateTime<chrono::FixedOffset>>,
+    /// Latest changes are returned first, 100 by default and at most 1000
+    pub limit: Option<u64>,
+}
@@ -0,0 +1,3 @@
+pub mod create_audit_log_db_dto;
+
+pub mod find_audit_logs_dto;
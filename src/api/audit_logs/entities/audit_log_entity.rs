@@ -0,0 +1,46 @@
+use aide::OperationIo;
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+use crate::api::audit_logs::types::audit_action::AuditAction;
+use crate::api::audit_logs::types::audit_entity_type::AuditEntityType;
+use crate::api::audit_logs::types::audit_log_from_db::AuditLogFromDb;
+
+#[derive(Serialize, Deserialize, Debug, JsonSchema, OperationIo)]
+#[serde(rename_all = "camelCase")]
+pub struct AuditLogEntity {
+    pub id: String,
+    pub entity_type: AuditEntityType,
+    pub entity_id: String,
+    /// Customer the changed entity belongs to
+    pub owner_id: Option<String>,
+    pub action: AuditAction,
+    /// User who made the change, empty for changes made by the app itself
+    pub actor_id: Option<String>,
+    pub actor_is_admin: bool,
+    /// Admin who made the change acting as the actor
+    pub impersonated_by: Option<String>,
+    /// `x-request-id` of the request which made the change
+    pub request_id: Option<String>,
+    /// Changed fields with their values, `{ "<field>": { "before": ..., "after": ... } }`
+    pub changes: serde_json::Value,
+    pub created_at: chrono::DateTime<chrono::FixedOffset>,
+}
+
+impl From<AuditLogFromDb> for AuditLogEntity {
+    fn from(value: AuditLogFromDb) -> Self {
+        Self {
+            id: value.id,
+            entity_type: value.entity_type,
+            entity_id: value.entity_id,
+            owner_id: value.owner_id,
+            action: value.action,
+            actor_id: value.actor_id,
+            actor_is_admin: value.actor_is_admin,
+            impersonated_by: value.impersonated_by,
+            request_id: value.request_id,
+            changes: value.changes,
+            created_at: value.created_at,
+        }
+    }
+}
@@ -0,0 +1,52 @@
+use aide::axum::ApiRouter;
+use aide::axum::routing::get;
+use sea_orm::DatabaseConnection;
+use std::sync::Arc;
+
+use crate::api::audit_logs::audit_logs_repository::AuditLogsRepository;
+use crate::api::audit_logs::audit_logs_service::AuditLogsService;
+use crate::api::audit_logs::types::api_state::AuditLogsApiState;
+use crate::api::customers::customers_repository::CustomerRepository;
+use crate::api::customers::customers_service::CustomersService;
+use crate::shared::modules::auth::enums::roles::Roles;
+use crate::shared::modules::auth::middlewares::role_based_bearer_auth::AuthLayer;
+use crate::shared::modules::auth::services::auth0::Auth0Service;
+use crate::shared::modules::db::unit_of_work::UnitOfWork;
+
+pub mod dto;
+pub mod entities;
+pub mod traits;
+pub mod types;
+
+mod audit_logs_handlers;
+pub mod audit_logs_repository;
+pub mod audit_logs_service;
+
+pub fn get_router(
+    sea_orm_client: Arc<DatabaseConnection>,
+    auth_service: Arc<Auth0Service>,
+) -> ApiRouter {
+    let audit_logs_repository = Arc::new(AuditLogsRepository::new(sea_orm_client.clone()));
+    let audit_logs_service = Arc::new(AuditLogsService::new(audit_logs_repository));
+
+    let customers_repository = Arc::new(CustomerRepository::new(sea_orm_client.clone()));
+    let unit_of_work = Arc::new(UnitOfWork::new(sea_orm_client));
+    let customers_service = Arc::new(CustomersService::new(
+        customers_repository,
+        audit_logs_service.clone(),
+        unit_of_work,
+    ));
+
+    let api_state = AuditLogsApiState { audit_logs_service };
+
+    let auth_layer = AuthLayer::new(auth_service, customers_service);
+
+    let routes = ApiRouter::new().api_route(
+        "/",
+        get(audit_logs_handlers::find_many).route_layer(auth_layer.verify(vec![Roles::Admin])),
+    );
+
+    ApiRouter::new()
+        .nest("/audit-logs", routes)
+        .with_state(api_state)
+}
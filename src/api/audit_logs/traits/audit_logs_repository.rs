@@ -0,0 +1,19 @@
+use async_trait::async_trait;
+use std::sync::Arc;
+
+use crate::api::audit_logs::dto::create_audit_log_db_dto::CreateAuditLogDbDto;
+use crate::api::audit_logs::dto::find_audit_logs_dto::FindAuditLogsDto;
+use crate::api::audit_logs::types::audit_log_from_db::AuditLogFromDb;
+use crate::shared::errors::http_error::HttpError;
+use crate::shared::modules::db::db_connection::DbConnection;
+
+#[async_trait]
+pub trait AuditLogsRepositoryTrait {
+    /// Repository which queries run on the connection, e.g. a unit of work transaction
+    fn with_connection(
+        &self,
+        connection: DbConnection,
+    ) -> Arc<dyn AuditLogsRepositoryTrait + Send + Sync>;
+    async fn create_many(&self, create_dtos: Vec<CreateAuditLogDbDto>) -> Result<(), HttpError>;
+    async fn find_many(&self, filter: FindAuditLogsDto) -> Result<Vec<AuditLogFromDb>, HttpError>;
+}
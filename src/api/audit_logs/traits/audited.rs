@@ -0,0 +1,13 @@
+use serde::Serialize;
+
+use crate::api::audit_logs::types::audit_entity_type::AuditEntityType;
+
+/// Entity which changes are recorded in the audit log, its serialized fields are compared
+pub trait Audited: Serialize {
+    const AUDIT_ENTITY_TYPE: AuditEntityType;
+
+    fn get_audit_id(&self) -> &str;
+
+    /// Customer the entity belongs to
+    fn get_audit_owner_id(&self) -> &str;
+}
@@ -0,0 +1,2 @@
+pub mod audit_logs_repository;
+pub mod audited;
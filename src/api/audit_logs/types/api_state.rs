@@ -0,0 +1,15 @@
+use axum::extract::FromRef;
+use std::sync::Arc;
+
+use crate::api::audit_logs::audit_logs_service::AuditLogsService;
+
+#[derive(Clone)]
+pub struct AuditLogsApiState {
+    pub audit_logs_service: Arc<AuditLogsService>,
+}
+
+impl FromRef<AuditLogsApiState> for Arc<AuditLogsService> {
+    fn from_ref(app_state: &AuditLogsApiState) -> Arc<AuditLogsService> {
+        app_state.audit_logs_service.clone()
+    }
+}
@@ -0,0 +1,44 @@
+use schemars::JsonSchema;
+use sea_orm::{ActiveValue, IntoActiveValue};
+use serde::{Deserialize, Serialize};
+
+use crate::shared::modules::db::entities::audit_log;
+
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq, JsonSchema)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum AuditAction {
+    Create,
+    Update,
+    /// Moved to the trash
+    Delete,
+    /// Moved out of the trash
+    Restore,
+}
+
+impl From<audit_log::AuditAction> for AuditAction {
+    fn from(value: audit_log::AuditAction) -> Self {
+        match value {
+            audit_log::AuditAction::Create => Self::Create,
+            audit_log::AuditAction::Update => Self::Update,
+            audit_log::AuditAction::Delete => Self::Delete,
+            audit_log::AuditAction::Restore => Self::Restore,
+        }
+    }
+}
+
+impl From<AuditAction> for audit_log::AuditAction {
+    fn from(value: AuditAction) -> Self {
+        match value {
+            AuditAction::Create => Self::Create,
+            AuditAction::Update => Self::Update,
+            AuditAction::Delete => Self::Delete,
+            AuditAction::Restore => Self::Restore,
+        }
+    }
+}
+
+impl IntoActiveValue<audit_log::AuditAction> for AuditAction {
+    fn into_active_value(self) -> ActiveValue<audit_log::AuditAction> {
+        ActiveValue::Set(self.into())
+    }
+}
@@ -0,0 +1,39 @@
+use schemars::JsonSchema;
+use sea_orm::{ActiveValue, IntoActiveValue};
+use serde::{Deserialize, Serialize};
+
+use crate::shared::modules::db::entities::audit_log;
+
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq, JsonSchema)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum AuditEntityType {
+    Customer,
+    Expense,
+    RegularPayment,
+}
+
+impl From<audit_log::AuditEntityType> for AuditEntityType {
+    fn from(value: audit_log::AuditEntityType) -> Self {
+        match value {
+            audit_log::AuditEntityType::Customer => Self::Customer,
+            audit_log::AuditEntityType::Expense => Self::Expense,
+            audit_log::AuditEntityType::RegularPayment => Self::RegularPayment,
+        }
+    }
+}
+
+impl From<AuditEntityType> for audit_log::AuditEntityType {
+    fn from(value: AuditEntityType) -> Self {
+        match value {
+            AuditEntityType::Customer => Self::Customer,
+            AuditEntityType::Expense => Self::Expense,
+            AuditEntityType::RegularPayment => Self::RegularPayment,
+        }
+    }
+}
+
+impl IntoActiveValue<audit_log::AuditEntityType> for AuditEntityType {
+    fn into_active_value(self) -> ActiveValue<audit_log::AuditEntityType> {
+        ActiveValue::Set(self.into())
+    }
+}
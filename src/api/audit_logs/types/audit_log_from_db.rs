@@ -0,0 +1,38 @@
+use serde::{Deserialize, Serialize};
+
+use crate::api::audit_logs::types::audit_action::AuditAction;
+use crate::api::audit_logs::types::audit_entity_type::AuditEntityType;
+use crate::shared::modules::db::entities::audit_log;
+
+#[derive(Serialize, Deserialize, Debug)]
+pub struct AuditLogFromDb {
+    pub id: String,
+    pub entity_type: AuditEntityType,
+    pub entity_id: String,
+    pub owner_id: Option<String>,
+    pub action: AuditAction,
+    pub actor_id: Option<String>,
+    pub actor_is_admin: bool,
+    pub impersonated_by: Option<String>,
+    pub request_id: Option<String>,
+    pub changes: serde_json::Value,
+    pub created_at: chrono::DateTime<chrono::FixedOffset>,
+}
+
+impl From<audit_log::Model> for AuditLogFromDb {
+    fn from(value: audit_log::Model) -> Self {
+        Self {
+            id: value.id,
+            entity_type: value.entity_type.into(),
+            entity_id: value.entity_id,
+            owner_id: value.owner_id,
+            action: value.action.into(),
+            actor_id: value.actor_id,
+            actor_is_admin: value.actor_is_admin,
+            impersonated_by: value.impersonated_by,
+            request_id: value.request_id,
+            changes: value.changes,
+            created_at: value.created_at,
+        }
+    }
+}
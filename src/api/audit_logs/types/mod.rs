@@ -0,0 +1,4 @@
+pub mod api_state;
+pub mod audit_action;
+pub mod audit_entity_type;
+pub mod audit_log_from_db;
@@ -0,0 +1,92 @@
+use axum::extract::{Query, State};
+use axum::{Extension, Json};
+use std::sync::Arc;
+
+use crate::api::categories::categories_service::CategoriesService;
+use crate::api::categories::dto::create_category_dto::CreateCategoryDto;
+use crate::api::categories::dto::find_categories_query_dto::FindCategoriesQueryDto;
+use crate::api::categories::dto::update_category_dto::UpdateCategoryDto;
+use crate::api::categories::entities::category_entity::CategoryEntity;
+use crate::shared::errors::http_error::HttpError;
+use crate::shared::modules::auth::structs::user::User;
+use crate::shared::modules::db::structs::id_path::IdPath;
+use crate::shared::modules::versioning::structs::if_match::IfMatch;
+use crate::shared::modules::versioning::structs::versioned_json::VersionedJson;
+
+pub async fn find_many(
+    Extension(user): Extension<User>,
+    State(categories_service): State<Arc<CategoriesService>>,
+    Query(query): Query<FindCategoriesQueryDto>,
+) -> Result<CategoryEntitiesJson, HttpError> {
+    let found_categories = if user.is_admin() {
+        categories_service.find_many(query).await?
+    } else {
+        categories_service
+            .find_many_as_customer(&user.id, query)
+            .await?
+    };
+
+    Ok(Json(found_categories))
+}
+
+pub async fn find_one(
+    IdPath(category_id): IdPath,
+    Extension(user): Extension<User>,
+    State(categories_service): State<Arc<CategoriesService>>,
+) -> Result<CategoryEntityJson, HttpError> {
+    let found_category = if user.is_admin() {
+        categories_service.find_one_as_admin(&category_id).await?
+    } else {
+        categories_service
+            .find_one_as_customer(&category_id, &user.id)
+            .await?
+    };
+
+    Ok(VersionedJson(found_category))
+}
+
+pub async fn create(
+    Extension(user): Extension<User>,
+    State(categories_service): State<Arc<CategoriesService>>,
+    Json(create_category_dto): Json<CreateCategoryDto>,
+) -> Result<CategoryEntityJson, HttpError> {
+    let created_category = if user.is_admin() {
+        categories_service
+            .create_as_admin(create_category_dto)
+            .await?
+    } else {
+        categories_service
+            .create_as_customer(create_category_dto, &user.id)
+            .await?
+    };
+
+    Ok(VersionedJson(created_category))
+}
+
+pub async fn update(
+    Extension(user): Extension<User>,
+    IdPath(category_id): IdPath,
+    IfMatch(expected_versions): IfMatch,
+    State(categories_service): State<Arc<CategoriesService>>,
+    Json(update_category_dto): Json<UpdateCategoryDto>,
+) -> Result<CategoryEntityJson, HttpError> {
+    let updated_category = if user.is_admin() {
+        categories_service
+            .update_as_admin(&category_id, update_category_dto, expected_versions)
+            .await?
+    } else {
+        categories_service
+            .update_as_customer(
+                &category_id,
+                update_category_dto,
+                &user.id,
+                expected_versions,
+            )
+            .await?
+    };
+
+    Ok(VersionedJson(updated_category))
+}
+
+pub type CategoryEntityJson = VersionedJson<CategoryEntity>;
+pub type CategoryEntitiesJson = Json<Vec<CategoryEntity>>;
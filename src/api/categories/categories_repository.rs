@@ -0,0 +1,148 @@
+use async_trait::async_trait;
+use sea_orm::sea_query::Expr;
+use sea_orm::{
+    ColumnTrait, Condition, DatabaseConnection, DatabaseTransaction, EntityTrait, IntoActiveModel,
+    QueryFilter, QueryOrder, QuerySelect,
+};
+use std::sync::Arc;
+
+use crate::api::categories::dto::create_category_db_dto::CreateCategoryDbDto;
+use crate::api::categories::dto::find_categories_dto::FindCategoriesDto;
+use crate::api::categories::dto::update_category_db_dto::UpdateCategoryDbDto;
+use crate::api::categories::traits::categories_repository::CategoriesRepositoryTrait;
+use crate::api::categories::types::category_from_db::CategoryFromDb;
+use crate::shared::errors::http_error::HttpError;
+use crate::shared::modules::db::db_connection::DbConnection;
+use crate::shared::modules::db::entities::category;
+use crate::shared::modules::db::entities::category::ActiveModel as CategoryActiveModel;
+use crate::shared::modules::db::entities::prelude::Category;
+
+pub struct CategoriesRepository {
+    connection: DbConnection,
+}
+
+impl CategoriesRepository {
+    pub fn new(sea_orm_client: Arc<DatabaseConnection>) -> Self {
+        Self {
+            connection: DbConnection::Pool(sea_orm_client),
+        }
+    }
+
+    fn get_not_found_error(id: &str) -> HttpError {
+        HttpError::NotFound(format!("Category with id '{id}' was not found"))
+    }
+
+    fn get_filter_condition(filter: FindCategoriesDto) -> Condition {
+        let owner_condition = if filter.is_system {
+            Some(Condition::all().add(category::Column::CustomerId.is_null()))
+        } else {
+            filter.customer_id.map(|customer_id| {
+                Condition::any()
+                    .add(category::Column::CustomerId.is_null())
+                    .add(category::Column::CustomerId.eq(customer_id))
+            })
+        };
+
+        Condition::all()
+            .add_option(owner_condition)
+            .add_option((!filter.include_archived).then(|| category::Column::ArchivedAt.is_null()))
+    }
+
+    /// Locks the category until the end of the transaction and checks the write preconditions:
+    /// the category belongs to the customer (system ones to `None`) and has one of the expected
+    /// versions
+    async fn lock_for_write(
+        transaction: &DatabaseTransaction,
+        id: &str,
+        customer_id: Option<&str>,
+        expected_versions: Option<&[i64]>,
+    ) -> Result<category::Model, HttpError> {
+        let found_category = Category::find_by_id(id)
+            .lock_exclusive()
+            .one(transaction)
+            .await?
+            .filter(|category| category.customer_id.as_deref() == customer_id)
+            .ok_or_else(|| Self::get_not_found_error(id))?;
+
+        if expected_versions.is_some_and(|versions| !versions.contains(&found_category.version)) {
+            return Err(HttpError::PreconditionFailed(format!(
+                "Category with id '{id}' was modified, If-Match does not match the current version"
+            )));
+        }
+
+        Ok(found_category)
+    }
+}
+
+#[async_trait]
+impl CategoriesRepositoryTrait for CategoriesRepository {
+    fn with_connection(
+        &self,
+        connection: DbConnection,
+    ) -> Arc<dyn CategoriesRepositoryTrait + Send + Sync> {
+        Arc::new(Self { connection })
+    }
+
+    async fn find_one(&self, id: &str) -> Result<CategoryFromDb, HttpError> {
+        let found_category = Category::find_by_id(id)
+            .one(&self.connection)
+            .await?
+            .ok_or_else(|| Self::get_not_found_error(id))?;
+
+        Ok(found_category.into())
+    }
+
+    async fn find_many(&self, filter: FindCategoriesDto) -> Result<Vec<CategoryFromDb>, HttpError> {
+        let found_categories = Category::find()
+            .filter(Self::get_filter_condition(filter))
+            .order_by_asc(category::Column::Name)
+            .order_by_asc(category::Column::Id)
+            .all(&self.connection)
+            .await?
+            .into_iter()
+            .map(Into::into)
+            .collect();
+
+        Ok(found_categories)
+    }
+
+    async fn create(&self, create_dto: CreateCategoryDbDto) -> Result<CategoryFromDb, HttpError> {
+        let created_category = Category::insert(create_dto.into_active_model())
+            .exec_with_returning(&self.connection)
+            .await?;
+
+        Ok(created_category.into())
+    }
+
+    async fn update(
+        &self,
+        id: &str,
+        update_dto: UpdateCategoryDbDto,
+        customer_id: Option<&str>,
+        expected_versions: Option<Vec<i64>>,
+    ) -> Result<CategoryFromDb, HttpError> {
+        let transaction = self.connection.begin().await?;
+
+        Self::lock_for_write(&transaction, id, customer_id, expected_versions.as_deref()).await?;
+
+        let updated_category = Category::update_many()
+            .set(CategoryActiveModel::from(update_dto))
+            .col_expr(
+                category::Column::Version,
+                Expr::col(category::Column::Version).add(1),
+            )
+            .col_expr(
+                category::Column::UpdatedAt,
+                Expr::current_timestamp().into(),
+            )
+            .filter(category::Column::Id.eq(id))
+            .exec_with_returning(&transaction)
+            .await?
+            .pop()
+            .ok_or_else(|| Self::get_not_found_error(id))?;
+
+        transaction.commit().await?;
+
+        Ok(updated_category.into())
+    }
+}
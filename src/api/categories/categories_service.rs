@@ -0,0 +1,361 @@
+use chrono::Utc;
+use std::sync::Arc;
+use uuid::Uuid;
+use validator::Validate;
+
+use crate::api::categories::dto::create_category_db_dto::CreateCategoryDbDto;
+use crate::api::categories::dto::create_category_dto::CreateCategoryDto;
+use crate::api::categories::dto::find_categories_dto::FindCategoriesDto;
+use crate::api::categories::dto::find_categories_query_dto::FindCategoriesQueryDto;
+use crate::api::categories::dto::update_category_db_dto::UpdateCategoryDbDto;
+use crate::api::categories::dto::update_category_dto::UpdateCategoryDto;
+use crate::api::categories::entities::category_entity::CategoryEntity;
+use crate::api::categories::traits::categories_repository::CategoriesRepositoryTrait;
+use crate::api::categories::types::category_resolver::CategoryResolver;
+use crate::api::customers::customers_service::CustomersService;
+use crate::shared::errors::http_error::HttpError;
+use crate::shared::modules::db::db_connection::DbConnection;
+use crate::shared::modules::db::unit_of_work::UnitOfWork;
+use crate::shared::modules::db::utils::generate_id;
+
+/// System categories are managed by admins, custom ones by the customers they belong to
+#[derive(Clone)]
+pub struct CategoriesService {
+    categories_repository: Arc<dyn CategoriesRepositoryTrait + Send + Sync>,
+    customers_service: Arc<CustomersService>,
+    unit_of_work: Arc<UnitOfWork>,
+}
+
+impl CategoriesService {
+    pub fn new(
+        categories_repository: Arc<dyn CategoriesRepositoryTrait + Send + Sync>,
+        customers_service: Arc<CustomersService>,
+        unit_of_work: Arc<UnitOfWork>,
+    ) -> Self {
+        Self {
+            categories_repository,
+            customers_service,
+            unit_of_work,
+        }
+    }
+
+    /// Service which queries run on the connection, e.g. a unit of work transaction
+    pub fn with_connection(&self, connection: DbConnection) -> Self {
+        Self {
+            categories_repository: self
+                .categories_repository
+                .with_connection(connection.clone()),
+            customers_service: Arc::new(self.customers_service.with_connection(connection)),
+            unit_of_work: self.unit_of_work.clone(),
+        }
+    }
+
+    pub async fn find_many(
+        &self,
+        query: FindCategoriesQueryDto,
+    ) -> Result<Vec<CategoryEntity>, HttpError> {
+        let find_dto = FindCategoriesDto {
+            is_system: query.customer_id.is_none(),
+            customer_id: query.customer_id.as_ref().map(Uuid::to_string),
+            include_archived: query.include_archived,
+        };
+
+        self.find_entities(find_dto).await
+    }
+
+    pub async fn find_many_as_customer(
+        &self,
+        user_id: &str,
+        query: FindCategoriesQueryDto,
+    ) -> Result<Vec<CategoryEntity>, HttpError> {
+        let customer = self.customers_service.find_one_by_user_id(user_id).await?;
+
+        let find_dto = FindCategoriesDto {
+            customer_id: Some(customer.id),
+            include_archived: query.include_archived,
+            ..Default::default()
+        };
+
+        self.find_entities(find_dto).await
+    }
+
+    pub async fn find_one_as_admin(&self, id: &str) -> Result<CategoryEntity, HttpError> {
+        let category_entity = self.categories_repository.find_one(id).await?.into();
+
+        Ok(category_entity)
+    }
+
+    /// System categories and the customer's own ones, other customers' ones are not found
+    pub async fn find_one_as_customer(
+        &self,
+        id: &str,
+        user_id: &str,
+    ) -> Result<CategoryEntity, HttpError> {
+        let customer = self.customers_service.find_one_by_user_id(user_id).await?;
+        let category_from_db = self.categories_repository.find_one(id).await?;
+
+        if category_from_db
+            .customer_id
+            .as_ref()
+            .is_some_and(|customer_id| *customer_id != customer.id)
+        {
+            return Err(HttpError::NotFound(format!(
+                "Category with id '{id}' was not found"
+            )));
+        }
+
+        Ok(category_from_db.into())
+    }
+
+    /// Categories the customer can refer to (all of them if not set), including archived ones
+    pub async fn get_resolver(
+        &self,
+        customer_id: Option<&str>,
+    ) -> Result<CategoryResolver, HttpError> {
+        let find_dto = FindCategoriesDto {
+            customer_id: customer_id.map(str::to_string),
+            include_archived: true,
+            ..Default::default()
+        };
+        let categories = self.categories_repository.find_many(find_dto).await?;
+
+        Ok(CategoryResolver::new(categories))
+    }
+
+    /// System categories only, including archived ones
+    pub async fn get_system_resolver(&self) -> Result<CategoryResolver, HttpError> {
+        let find_dto = FindCategoriesDto {
+            is_system: true,
+            include_archived: true,
+            ..Default::default()
+        };
+        let categories = self.categories_repository.find_many(find_dto).await?;
+
+        Ok(CategoryResolver::new(categories))
+    }
+
+    /// Categories the category (an id or a code) and its subcategories are found among:
+    /// system ones and the ones of the customer it belongs to
+    pub async fn get_owner_resolver_of(
+        &self,
+        category: &str,
+    ) -> Result<CategoryResolver, HttpError> {
+        let system_resolver = self.get_system_resolver().await?;
+
+        if system_resolver.find(category).is_ok() {
+            return Ok(system_resolver);
+        }
+
+        let category_from_db = self
+            .categories_repository
+            .find_one(category)
+            .await
+            .map_err(|err| match err {
+                HttpError::NotFound(_) => {
+                    HttpError::BadRequest(format!("Category '{category}' was not found"))
+                }
+                err => err,
+            })?;
+
+        self.get_resolver(category_from_db.customer_id.as_deref())
+            .await
+    }
+
+    pub async fn create_as_admin(
+        &self,
+        create_dto: CreateCategoryDto,
+    ) -> Result<CategoryEntity, HttpError> {
+        self.create(create_dto, None).await
+    }
+
+    pub async fn create_as_customer(
+        &self,
+        create_dto: CreateCategoryDto,
+        user_id: &str,
+    ) -> Result<CategoryEntity, HttpError> {
+        let customer = self.customers_service.find_one_by_user_id(user_id).await?;
+
+        self.create(create_dto, Some(customer.id)).await
+    }
+
+    pub async fn update_as_admin(
+        &self,
+        id: &str,
+        update_dto: UpdateCategoryDto,
+        expected_versions: Option<Vec<i64>>,
+    ) -> Result<CategoryEntity, HttpError> {
+        self.update(id, update_dto, None, expected_versions).await
+    }
+
+    pub async fn update_as_customer(
+        &self,
+        id: &str,
+        update_dto: UpdateCategoryDto,
+        user_id: &str,
+        expected_versions: Option<Vec<i64>>,
+    ) -> Result<CategoryEntity, HttpError> {
+        let customer = self.customers_service.find_one_by_user_id(user_id).await?;
+
+        self.update(id, update_dto, Some(customer.id), expected_versions)
+            .await
+    }
+
+    async fn find_entities(
+        &self,
+        find_dto: FindCategoriesDto,
+    ) -> Result<Vec<CategoryEntity>, HttpError> {
+        let category_entities = self
+            .categories_repository
+            .find_many(find_dto)
+            .await?
+            .into_iter()
+            .map(CategoryEntity::from)
+            .collect();
+
+        Ok(category_entities)
+    }
+
+    /// Custom categories of the customer or system ones if `customer_id` is not set
+    async fn create(
+        &self,
+        create_dto: CreateCategoryDto,
+        customer_id: Option<String>,
+    ) -> Result<CategoryEntity, HttpError> {
+        create_dto
+            .validate()
+            .map_err(|validation_errors| HttpError::BadRequest(validation_errors.to_string()))?;
+
+        self.unit_of_work
+            .run(|connection| async {
+                let categories_service = self.with_connection(connection);
+
+                if let Some(parent_id) = &create_dto.parent_id {
+                    let category_resolver = categories_service
+                        .get_owner_resolver(customer_id.as_deref())
+                        .await?;
+                    Self::check_parent(&category_resolver, &parent_id.to_string())?;
+                }
+
+                let create_db_dto =
+                    Self::map_create_dto_to_create_db_dto(create_dto.clone(), customer_id.clone());
+                let created_category_entity = categories_service
+                    .categories_repository
+                    .create(create_db_dto)
+                    .await?
+                    .into();
+
+                Ok(created_category_entity)
+            })
+            .await
+    }
+
+    async fn update(
+        &self,
+        id: &str,
+        update_dto: UpdateCategoryDto,
+        customer_id: Option<String>,
+        expected_versions: Option<Vec<i64>>,
+    ) -> Result<CategoryEntity, HttpError> {
+        update_dto
+            .validate()
+            .map_err(|validation_errors| HttpError::BadRequest(validation_errors.to_string()))?;
+
+        self.unit_of_work
+            .run(|connection| async {
+                let categories_service = self.with_connection(connection);
+
+                if let Some(Some(parent_id)) = &update_dto.parent_id {
+                    let parent_id = parent_id.to_string();
+                    let category_resolver = categories_service
+                        .get_owner_resolver(customer_id.as_deref())
+                        .await?;
+                    Self::check_parent(&category_resolver, &parent_id)?;
+
+                    // Subcategories of the category can not become its parents
+                    if category_resolver.get_subtree_ids(id).contains(&parent_id) {
+                        return Err(HttpError::BadRequest(
+                            "Category can not be nested into itself or its subcategories".into(),
+                        ));
+                    }
+                }
+
+                let update_db_dto = Self::map_update_dto_to_update_db_dto(update_dto.clone());
+                let updated_category_entity = categories_service
+                    .categories_repository
+                    .update(
+                        id,
+                        update_db_dto,
+                        customer_id.as_deref(),
+                        expected_versions.clone(),
+                    )
+                    .await?
+                    .into();
+
+                Ok(updated_category_entity)
+            })
+            .await
+    }
+
+    /// Categories the owner can nest into: system and own ones of a customer, system ones
+    /// of admins
+    async fn get_owner_resolver(
+        &self,
+        customer_id: Option<&str>,
+    ) -> Result<CategoryResolver, HttpError> {
+        let find_dto = FindCategoriesDto {
+            customer_id: customer_id.map(str::to_string),
+            is_system: customer_id.is_none(),
+            include_archived: true,
+        };
+        let categories = self.categories_repository.find_many(find_dto).await?;
+
+        Ok(CategoryResolver::new(categories))
+    }
+
+    fn check_parent(
+        category_resolver: &CategoryResolver,
+        parent_id: &str,
+    ) -> Result<(), HttpError> {
+        let parent = category_resolver.get(parent_id).ok_or_else(|| {
+            HttpError::BadRequest(format!("Parent category '{parent_id}' was not found"))
+        })?;
+
+        if parent.archived_at.is_some() {
+            return Err(HttpError::BadRequest(format!(
+                "Parent category '{parent_id}' is archived"
+            )));
+        }
+
+        Ok(())
+    }
+
+    fn map_create_dto_to_create_db_dto(
+        create_dto: CreateCategoryDto,
+        customer_id: Option<String>,
+    ) -> CreateCategoryDbDto {
+        CreateCategoryDbDto {
+            id: generate_id(),
+            customer_id,
+            parent_id: create_dto.parent_id.as_ref().map(Uuid::to_string),
+            name: create_dto.name,
+            icon: create_dto.icon,
+            color: create_dto.color,
+        }
+    }
+
+    fn map_update_dto_to_update_db_dto(update_dto: UpdateCategoryDto) -> UpdateCategoryDbDto {
+        let now = Utc::now().fixed_offset();
+
+        UpdateCategoryDbDto {
+            name: update_dto.name,
+            parent_id: update_dto
+                .parent_id
+                .map(|parent_id| parent_id.as_ref().map(Uuid::to_string)),
+            icon: update_dto.icon,
+            color: update_dto.color,
+            archived_at: update_dto
+                .archived
+                .map(|is_archived| is_archived.then_some(now)),
+        }
+    }
+}
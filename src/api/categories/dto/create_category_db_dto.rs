@@ -0,0 +1,14 @@
+use sea_orm::DeriveIntoActiveModel;
+use serde::{Deserialize, Serialize};
+
+use crate::shared::modules::db::entities::category::ActiveModel;
+
+#[derive(Serialize, Deserialize, Debug, Clone, DeriveIntoActiveModel)]
+pub struct CreateCategoryDbDto {
+    pub id: String,
+    pub customer_id: Option<String>,
+    pub parent_id: Option<String>,
+    pub name: String,
+    pub icon: Option<String>,
+    pub color: Option<String>,
+}
@@ -0,0 +1,24 @@
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+use validator::Validate;
+
+use crate::api::categories::utils::validate_color;
+
+#[derive(Debug, Clone, Deserialize, Serialize, Validate, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct CreateCategoryDto {
+    #[validate(length(min = 1, max = 50, message = "Should be 1 to 50 characters"))]
+    pub name: String,
+
+    /// Category to nest the new one in, customers can nest into system categories too
+    pub parent_id: Option<Uuid>,
+
+    /// Name of the icon in the client, e.g. `shopping-cart`
+    #[validate(length(min = 1, max = 50, message = "Should be 1 to 50 characters"))]
+    pub icon: Option<String>,
+
+    /// `#RRGGBB`
+    #[validate(custom(function = "validate_color"))]
+    pub color: Option<String>,
+}
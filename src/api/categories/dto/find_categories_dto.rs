@@ -0,0 +1,12 @@
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct FindCategoriesDto {
+    /// Custom categories of the customer along with the system ones. Custom categories
+    /// of all the customers are included if not set
+    pub customer_id: Option<String>,
+    /// System categories only
+    pub is_system: bool,
+    pub include_archived: bool,
+}
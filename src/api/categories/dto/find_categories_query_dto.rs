@@ -0,0 +1,13 @@
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+#[derive(Debug, Clone, Default, Deserialize, Serialize, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct FindCategoriesQueryDto {
+    /// Admins only, system categories along with the customer's ones. Customers always get
+    /// their own, admins get the system ones if not set
+    pub customer_id: Option<Uuid>,
+    #[serde(default)]
+    pub include_archived: bool,
+}
@@ -0,0 +1,8 @@
+pub mod create_category_db_dto;
+pub mod update_category_db_dto;
+
+pub mod create_category_dto;
+pub mod update_category_dto;
+
+pub mod find_categories_dto;
+pub mod find_categories_query_dto;
@@ -0,0 +1,33 @@
+use chrono::{DateTime, FixedOffset};
+use sea_orm::ActiveValue;
+use serde::{Deserialize, Serialize};
+
+use crate::shared::modules::db::entities::category::ActiveModel as CategoryActiveModel;
+use crate::shared::modules::db::utils::optional_to_active_value;
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct UpdateCategoryDbDto {
+    pub name: Option<String>,
+    pub parent_id: Option<Option<String>>,
+    pub icon: Option<Option<String>>,
+    pub color: Option<Option<String>>,
+    pub archived_at: Option<Option<DateTime<FixedOffset>>>,
+}
+
+impl From<UpdateCategoryDbDto> for CategoryActiveModel {
+    fn from(value: UpdateCategoryDbDto) -> Self {
+        Self {
+            id: ActiveValue::NotSet,
+            customer_id: ActiveValue::NotSet,
+            parent_id: optional_to_active_value(value.parent_id),
+            code: ActiveValue::NotSet,
+            name: optional_to_active_value(value.name),
+            icon: optional_to_active_value(value.icon),
+            color: optional_to_active_value(value.color),
+            archived_at: optional_to_active_value(value.archived_at),
+            created_at: ActiveValue::NotSet,
+            updated_at: ActiveValue::NotSet,
+            version: ActiveValue::NotSet,
+        }
+    }
+}
@@ -0,0 +1,31 @@
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+use validator::Validate;
+
+use crate::api::categories::utils::validate_color;
+use crate::shared::utils::deserialize_nullable;
+
+/// `null` clears `parentId`, `icon` and `color`, omitted fields are kept
+#[derive(Debug, Clone, Deserialize, Serialize, Validate, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct UpdateCategoryDto {
+    #[validate(length(min = 1, max = 50, message = "Should be 1 to 50 characters"))]
+    pub name: Option<String>,
+
+    /// `null` makes the category a top-level one
+    #[serde(default, deserialize_with = "deserialize_nullable")]
+    pub parent_id: Option<Option<Uuid>>,
+
+    #[serde(default, deserialize_with = "deserialize_nullable")]
+    #[validate(length(min = 1, max = 50, message = "Should be 1 to 50 characters"))]
+    pub icon: Option<Option<String>>,
+
+    #[serde(default, deserialize_with = "deserialize_nullable")]
+    #[validate(custom(function = "validate_color"))]
+    pub color: Option<Option<String>>,
+
+    /// Archived categories are hidden from lists by default and can not be assigned
+    /// to expenses, existing expenses keep them
+    pub archived: Option<bool>,
+}
@@ -0,0 +1,52 @@
+use aide::OperationIo;
+use chrono::{DateTime, FixedOffset};
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+use crate::api::categories::types::category_from_db::CategoryFromDb;
+use crate::shared::modules::versioning::traits::versioned::Versioned;
+
+#[derive(Serialize, Deserialize, Debug, Clone, JsonSchema, OperationIo)]
+#[serde(rename_all = "camelCase")]
+pub struct CategoryEntity {
+    /// UUID generated by the server, time-ordered (v7) except for the seeded system categories
+    pub id: String,
+    /// Owner of a custom category, not set for system categories
+    pub customer_id: Option<String>,
+    pub parent_id: Option<String>,
+    /// Code of a system category, e.g. `FOOD`. Expenses accept it in place of the id
+    pub code: Option<String>,
+    pub name: String,
+    pub icon: Option<String>,
+    pub color: Option<String>,
+    /// Set while the category is archived
+    pub archived_at: Option<DateTime<FixedOffset>>,
+    pub created_at: DateTime<FixedOffset>,
+    pub updated_at: DateTime<FixedOffset>,
+    /// Bumped on every update, pass it in `If-Match` to update only this version
+    pub version: i64,
+}
+
+impl From<CategoryFromDb> for CategoryEntity {
+    fn from(value: CategoryFromDb) -> Self {
+        Self {
+            id: value.id,
+            customer_id: value.customer_id,
+            parent_id: value.parent_id,
+            code: value.code,
+            name: value.name,
+            icon: value.icon,
+            color: value.color,
+            archived_at: value.archived_at,
+            created_at: value.created_at,
+            updated_at: value.updated_at,
+            version: value.version,
+        }
+    }
+}
+
+impl Versioned for CategoryEntity {
+    fn get_version(&self) -> i64 {
+        self.version
+    }
+}
@@ -0,0 +1 @@
+pub mod category_entity;
@@ -0,0 +1,82 @@
+use aide::axum::ApiRouter;
+use aide::axum::routing::{get, patch, post};
+use sea_orm::DatabaseConnection;
+use std::sync::Arc;
+
+use crate::api::audit_logs::audit_logs_repository::AuditLogsRepository;
+use crate::api::audit_logs::audit_logs_service::AuditLogsService;
+use crate::api::categories::categories_repository::CategoriesRepository;
+use crate::api::categories::categories_service::CategoriesService;
+use crate::api::categories::types::api_state::CategoriesApiState;
+use crate::api::customers::customers_repository::CustomerRepository;
+use crate::api::customers::customers_service::CustomersService;
+use crate::shared::modules::auth::enums::roles::Roles;
+use crate::shared::modules::auth::middlewares::role_based_bearer_auth::AuthLayer;
+use crate::shared::modules::auth::services::auth0::Auth0Service;
+use crate::shared::modules::cache::AppL2CacheService;
+use crate::shared::modules::db::unit_of_work::UnitOfWork;
+use crate::shared::modules::idempotency::middlewares::idempotency::IdempotencyLayer;
+
+mod categories_handlers;
+pub mod categories_repository;
+pub mod categories_service;
+mod dto;
+pub mod entities;
+pub mod traits;
+pub mod types;
+mod utils;
+
+pub fn get_router(
+    sea_orm_client: Arc<DatabaseConnection>,
+    idempotency_layer: IdempotencyLayer<AppL2CacheService>,
+    auth_service: Arc<Auth0Service>,
+) -> ApiRouter {
+    let audit_logs_repository = Arc::new(AuditLogsRepository::new(sea_orm_client.clone()));
+    let audit_logs_service = Arc::new(AuditLogsService::new(audit_logs_repository));
+
+    let unit_of_work = Arc::new(UnitOfWork::new(sea_orm_client.clone()));
+    let customers_repository = Arc::new(CustomerRepository::new(sea_orm_client.clone()));
+    let customers_service = Arc::new(CustomersService::new(
+        customers_repository,
+        audit_logs_service,
+        unit_of_work.clone(),
+    ));
+
+    let categories_repository = Arc::new(CategoriesRepository::new(sea_orm_client));
+    let categories_service = Arc::new(CategoriesService::new(
+        categories_repository,
+        customers_service.clone(),
+        unit_of_work,
+    ));
+
+    let api_state = CategoriesApiState { categories_service };
+
+    let auth_layer = AuthLayer::new(auth_service, customers_service);
+
+    let routes = ApiRouter::new()
+        .api_route(
+            "/",
+            get(categories_handlers::find_many)
+                .route_layer(auth_layer.verify(vec![Roles::Admin, Roles::Customer])),
+        )
+        .api_route(
+            "/{id}",
+            get(categories_handlers::find_one)
+                .route_layer(auth_layer.verify(vec![Roles::Admin, Roles::Customer])),
+        )
+        .api_route(
+            "/",
+            post(categories_handlers::create)
+                .route_layer(idempotency_layer)
+                .route_layer(auth_layer.verify(vec![Roles::Admin, Roles::Customer])),
+        )
+        .api_route(
+            "/{id}",
+            patch(categories_handlers::update)
+                .route_layer(auth_layer.verify(vec![Roles::Admin, Roles::Customer])),
+        );
+
+    ApiRouter::new()
+        .nest("/categories", routes)
+        .with_state(api_state)
+}
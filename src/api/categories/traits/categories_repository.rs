@@ -0,0 +1,35 @@
+use async_trait::async_trait;
+use std::sync::Arc;
+
+use crate::api::categories::dto::create_category_db_dto::CreateCategoryDbDto;
+use crate::api::categories::dto::find_categories_dto::FindCategoriesDto;
+use crate::api::categories::dto::update_category_db_dto::UpdateCategoryDbDto;
+use crate::api::categories::types::category_from_db::CategoryFromDb;
+use crate::shared::errors::http_error::HttpError;
+use crate::shared::modules::db::db_connection::DbConnection;
+
+#[async_trait]
+pub trait CategoriesRepositoryTrait {
+    /// Repository which queries run on the connection, e.g. a unit of work transaction
+    fn with_connection(
+        &self,
+        connection: DbConnection,
+    ) -> Arc<dyn CategoriesRepositoryTrait + Send + Sync>;
+
+    async fn find_one(&self, id: &str) -> Result<CategoryFromDb, HttpError>;
+
+    /// Categories ordered by name
+    async fn find_many(&self, filter: FindCategoriesDto) -> Result<Vec<CategoryFromDb>, HttpError>;
+
+    async fn create(&self, create_dto: CreateCategoryDbDto) -> Result<CategoryFromDb, HttpError>;
+
+    /// Writes check that the category belongs to `customer_id` in the same transaction,
+    /// system categories are written with `None`
+    async fn update(
+        &self,
+        id: &str,
+        update_dto: UpdateCategoryDbDto,
+        customer_id: Option<&str>,
+        expected_versions: Option<Vec<i64>>,
+    ) -> Result<CategoryFromDb, HttpError>;
+}
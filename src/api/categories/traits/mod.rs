@@ -0,0 +1 @@
+pub mod categories_repository;
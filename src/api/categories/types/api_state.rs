@@ -0,0 +1,15 @@
+use axum::extract::FromRef;
+use std::sync::Arc;
+
+use crate::api::categories::categories_service::CategoriesService;
+
+#[derive(Clone)]
+pub struct CategoriesApiState {
+    pub categories_service: Arc<CategoriesService>,
+}
+
+impl FromRef<CategoriesApiState> for Arc<CategoriesService> {
+    fn from_ref(app_state: &CategoriesApiState) -> Arc<CategoriesService> {
+        app_state.categories_service.clone()
+    }
+}
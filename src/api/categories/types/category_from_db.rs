@@ -0,0 +1,37 @@
+use chrono::{DateTime, FixedOffset};
+use serde::{Deserialize, Serialize};
+
+use crate::shared::modules::db::entities::category;
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct CategoryFromDb {
+    pub id: String,
+    pub customer_id: Option<String>,
+    pub parent_id: Option<String>,
+    pub code: Option<String>,
+    pub name: String,
+    pub icon: Option<String>,
+    pub color: Option<String>,
+    pub archived_at: Option<DateTime<FixedOffset>>,
+    pub created_at: DateTime<FixedOffset>,
+    pub updated_at: DateTime<FixedOffset>,
+    pub version: i64,
+}
+
+impl From<category::Model> for CategoryFromDb {
+    fn from(value: category::Model) -> Self {
+        Self {
+            id: value.id,
+            customer_id: value.customer_id,
+            parent_id: value.parent_id,
+            code: value.code,
+            name: value.name,
+            icon: value.icon,
+            color: value.color,
+            archived_at: value.archived_at,
+            created_at: value.created_at,
+            updated_at: value.updated_at,
+            version: value.version,
+        }
+    }
+}
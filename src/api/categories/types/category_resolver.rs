@@ -0,0 +1,166 @@
+use std::collections::HashMap;
+
+use crate::api::categories::types::category_from_db::CategoryFromDb;
+use crate::shared::errors::http_error::HttpError;
+
+/// Categories available to a customer (or all of them), found by their ids or by codes
+/// of system categories
+pub struct CategoryResolver {
+    categories: HashMap<String, CategoryFromDb>,
+    /// Ids of system categories by their upper-case codes
+    ids_by_code: HashMap<String, String>,
+    /// Ids of subcategories by the ids of their parents
+    children_ids: HashMap<String, Vec<String>>,
+}
+
+impl CategoryResolver {
+    pub fn new(categories: Vec<CategoryFromDb>) -> Self {
+        let mut ids_by_code = HashMap::new();
+        let mut children_ids: HashMap<String, Vec<String>> = HashMap::new();
+
+        for category in &categories {
+            if let Some(code) = &category.code {
+                ids_by_code.insert(code.to_ascii_uppercase(), category.id.clone());
+            }
+
+            if let Some(parent_id) = &category.parent_id {
+                children_ids
+                    .entry(parent_id.clone())
+                    .or_default()
+                    .push(category.id.clone());
+            }
+        }
+
+        Self {
+            categories: categories
+                .into_iter()
+                .map(|category| (category.id.clone(), category))
+                .collect(),
+            ids_by_code,
+            children_ids,
+        }
+    }
+
+    pub fn get(&self, id: &str) -> Option<&CategoryFromDb> {
+        self.categories.get(id)
+    }
+
+    /// Category by its id or code, e.g. `FOOD`
+    pub fn find(&self, category: &str) -> Result<&CategoryFromDb, HttpError> {
+        self.get(category)
+            .or_else(|| {
+                self.ids_by_code
+                    .get(&category.to_ascii_uppercase())
+                    .and_then(|id| self.get(id))
+            })
+            .ok_or_else(|| HttpError::BadRequest(format!("Category '{category}' was not found")))
+    }
+
+    /// Category expenses can be assigned to, archived ones are kept only by existing expenses
+    pub fn find_assignable(&self, category: &str) -> Result<&CategoryFromDb, HttpError> {
+        let found_category = self.find(category)?;
+
+        if found_category.archived_at.is_some() {
+            return Err(HttpError::BadRequest(format!(
+                "Category '{category}' is archived"
+            )));
+        }
+
+        Ok(found_category)
+    }
+
+    /// Ids of the category and all of its subcategories
+    pub fn get_subtree_ids(&self, id: &str) -> Vec<String> {
+        let mut subtree_ids = vec![id.to_string()];
+        let mut next = 0;
+
+        while let Some(parent_id) = subtree_ids.get(next).cloned() {
+            subtree_ids.extend(
+                self.children_ids
+                    .get(&parent_id)
+                    .into_iter()
+                    .flatten()
+                    .cloned(),
+            );
+            next += 1;
+        }
+
+        subtree_ids
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::Utc;
+
+    use super::*;
+
+    fn get_category(id: &str, parent_id: Option<&str>) -> CategoryFromDb {
+        let now = Utc::now().fixed_offset();
+
+        CategoryFromDb {
+            id: id.into(),
+            customer_id: None,
+            parent_id: parent_id.map(str::to_string),
+            code: None,
+            name: id.into(),
+            icon: None,
+            color: None,
+            archived_at: None,
+            created_at: now,
+            updated_at: now,
+            version: 1,
+        }
+    }
+
+    #[test]
+    fn get_subtree_ids() {
+        let category_resolver = CategoryResolver::new(vec![
+            get_category("food", None),
+            get_category("groceries", Some("food")),
+            get_category("restaurants", Some("food")),
+            get_category("fruits", Some("groceries")),
+            get_category("transport", None),
+            get_category("taxi", Some("transport")),
+        ]);
+        let cases: [(&str, &[&str]); 5] = [
+            ("food", &["food", "groceries", "restaurants", "fruits"]),
+            ("groceries", &["groceries", "fruits"]),
+            ("fruits", &["fruits"]),
+            ("transport", &["transport", "taxi"]),
+            ("unknown", &["unknown"]),
+        ];
+
+        for (id, expected) in cases {
+            assert_eq!(category_resolver.get_subtree_ids(id), expected, "{id}");
+        }
+    }
+
+    #[test]
+    fn find() {
+        let category_resolver = CategoryResolver::new(vec![
+            CategoryFromDb {
+                code: Some("FOOD".into()),
+                ..get_category("system-food", None)
+            },
+            get_category("groceries", Some("system-food")),
+        ]);
+        let cases = [
+            ("id", "groceries", Some("groceries")),
+            ("code", "FOOD", Some("system-food")),
+            ("lower-case code", "food", Some("system-food")),
+            ("unknown", "TRANSPORT", None),
+        ];
+
+        for (name, category, expected_id) in cases {
+            assert_eq!(
+                category_resolver
+                    .find(category)
+                    .ok()
+                    .map(|category| category.id.as_str()),
+                expected_id,
+                "{name}"
+            );
+        }
+    }
+}
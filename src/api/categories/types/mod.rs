@@ -0,0 +1,3 @@
+pub mod api_state;
+pub mod category_from_db;
+pub mod category_resolver;
@@ -0,0 +1,13 @@
+use validator::ValidationError;
+
+pub fn validate_color(color: &str) -> Result<(), ValidationError> {
+    let is_valid = color.len() == 7
+        && color.starts_with('#')
+        && color[1..].chars().all(|char| char.is_ascii_hexdigit());
+
+    if !is_valid {
+        return Err(ValidationError::new("format").with_message("Should be #RRGGBB".into()));
+    }
+
+    Ok(())
+}
@@ -0,0 +1,83 @@
+use axum::extract::State;
+use axum::{Extension, Json};
+use std::sync::Arc;
+
+use crate::api::category_rules::category_rules_service::CategoryRulesService;
+use crate::api::category_rules::dto::create_category_rule_dto::CreateCategoryRuleDto;
+use crate::api::category_rules::dto::update_category_rule_dto::UpdateCategoryRuleDto;
+use crate::api::category_rules::entities::category_rule_entity::CategoryRuleEntity;
+use crate::shared::errors::http_error::HttpError;
+use crate::shared::modules::auth::structs::user::User;
+use crate::shared::modules::db::structs::id_path::IdPath;
+use crate::shared::modules::versioning::structs::if_match::IfMatch;
+use crate::shared::modules::versioning::structs::versioned_json::VersionedJson;
+
+pub async fn find_many(
+    Extension(user): Extension<User>,
+    State(category_rules_service): State<Arc<CategoryRulesService>>,
+) -> Result<CategoryRuleEntitiesJson, HttpError> {
+    let found_category_rules = category_rules_service
+        .find_many_as_customer(&user.id)
+        .await?;
+
+    Ok(Json(found_category_rules))
+}
+
+pub async fn find_one(
+    IdPath(category_rule_id): IdPath,
+    Extension(user): Extension<User>,
+    State(category_rules_service): State<Arc<CategoryRulesService>>,
+) -> Result<CategoryRuleEntityJson, HttpError> {
+    let found_category_rule = category_rules_service
+        .find_one_as_customer(&category_rule_id, &user.id)
+        .await?;
+
+    Ok(VersionedJson(found_category_rule))
+}
+
+pub async fn create(
+    Extension(user): Extension<User>,
+    State(category_rules_service): State<Arc<CategoryRulesService>>,
+    Json(create_category_rule_dto): Json<CreateCategoryRuleDto>,
+) -> Result<CategoryRuleEntityJson, HttpError> {
+    let created_category_rule = category_rules_service
+        .create_as_customer(create_category_rule_dto, &user.id)
+        .await?;
+
+    Ok(VersionedJson(created_category_rule))
+}
+
+pub async fn update(
+    Extension(user): Extension<User>,
+    IdPath(category_rule_id): IdPath,
+    IfMatch(expected_versions): IfMatch,
+    State(category_rules_service): State<Arc<CategoryRulesService>>,
+    Json(update_category_rule_dto): Json<UpdateCategoryRuleDto>,
+) -> Result<CategoryRuleEntityJson, HttpError> {
+    let updated_category_rule = category_rules_service
+        .update_as_customer(
+            &category_rule_id,
+            update_category_rule_dto,
+            &user.id,
+            expected_versions,
+        )
+        .await?;
+
+    Ok(VersionedJson(updated_category_rule))
+}
+
+pub async fn remove(
+    Extension(user): Extension<User>,
+    IdPath(category_rule_id): IdPath,
+    IfMatch(expected_versions): IfMatch,
+    State(category_rules_service): State<Arc<CategoryRulesService>>,
+) -> Result<CategoryRuleEntityJson, HttpError> {
+    let deleted_category_rule = category_rules_service
+        .delete_as_customer(&category_rule_id, &user.id, expected_versions)
+        .await?;
+
+    Ok(VersionedJson(deleted_category_rule))
+}
+
+pub type CategoryRuleEntityJson = VersionedJson<CategoryRuleEntity>;
+pub type CategoryRuleEntitiesJson = Json<Vec<CategoryRuleEntity>>;
@@ -0,0 +1,163 @@
+use async_trait::async_trait;
+use sea_orm::sea_query::Expr;
+use sea_orm::{
+    ColumnTrait, Condition, DatabaseConnection, DatabaseTransaction, EntityTrait, IntoActiveModel,
+    QueryFilter, QueryOrder, QuerySelect,
+};
+use std::sync::Arc;
+
+use crate::api::category_rules::dto::create_category_rule_db_dto::CreateCategoryRuleDbDto;
+use crate::api::category_rules::dto::update_category_rule_db_dto::UpdateCategoryRuleDbDto;
+use crate::api::category_rules::traits::category_rules_repository::CategoryRulesRepositoryTrait;
+use crate::api::category_rules::types::category_rule_from_db::CategoryRuleFromDb;
+use crate::shared::errors::http_error::HttpError;
+use crate::shared::modules::db::db_connection::DbConnection;
+use crate::shared::modules::db::entities::category_rule;
+use crate::shared::modules::db::entities::category_rule::ActiveModel as CategoryRuleActiveModel;
+use crate::shared::modules::db::entities::prelude::CategoryRule;
+
+pub struct CategoryRulesRepository {
+    connection: DbConnection,
+}
+
+impl CategoryRulesRepository {
+    pub fn new(sea_orm_client: Arc<DatabaseConnection>) -> Self {
+        Self {
+            connection: DbConnection::Pool(sea_orm_client),
+        }
+    }
+
+    fn get_not_found_error(id: &str) -> HttpError {
+        HttpError::NotFound(format!("Category rule with id '{id}' was not found"))
+    }
+
+    /// Locks the rule until the end of the transaction and checks the write preconditions:
+    /// the rule belongs to the customer and has one of the expected versions
+    async fn lock_for_write(
+        transaction: &DatabaseTransaction,
+        id: &str,
+        customer_id: &str,
+        expected_versions: Option<&[i64]>,
+    ) -> Result<category_rule::Model, HttpError> {
+        let found_category_rule = CategoryRule::find_by_id(id)
+            .filter(category_rule::Column::CustomerId.eq(customer_id))
+            .lock_exclusive()
+            .one(transaction)
+            .await?
+            .ok_or_else(|| Self::get_not_found_error(id))?;
+
+        if expected_versions
+            .is_some_and(|versions| !versions.contains(&found_category_rule.version))
+        {
+            return Err(HttpError::PreconditionFailed(format!(
+                "Category rule with id '{id}' was modified, If-Match does not match the current version"
+            )));
+        }
+
+        Ok(found_category_rule)
+    }
+}
+
+#[async_trait]
+impl CategoryRulesRepositoryTrait for CategoryRulesRepository {
+    fn with_connection(
+        &self,
+        connection: DbConnection,
+    ) -> Arc<dyn CategoryRulesRepositoryTrait + Send + Sync> {
+        Arc::new(Self { connection })
+    }
+
+    async fn find_one(&self, id: &str, customer_id: &str) -> Result<CategoryRuleFromDb, HttpError> {
+        let found_category_rule = CategoryRule::find_by_id(id)
+            .filter(category_rule::Column::CustomerId.eq(customer_id))
+            .one(&self.connection)
+            .await?
+            .ok_or_else(|| Self::get_not_found_error(id))?;
+
+        Ok(found_category_rule.into())
+    }
+
+    async fn find_many(
+        &self,
+        customer_id: &str,
+        only_enabled: bool,
+    ) -> Result<Vec<CategoryRuleFromDb>, HttpError> {
+        let found_category_rules = CategoryRule::find()
+            .filter(
+                Condition::all()
+                    .add(category_rule::Column::CustomerId.eq(customer_id))
+                    .add_option(only_enabled.then(|| category_rule::Column::IsEnabled.eq(true))),
+            )
+            .order_by_asc(category_rule::Column::Priority)
+            .order_by_asc(category_rule::Column::Id)
+            .all(&self.connection)
+            .await?
+            .into_iter()
+            .map(Into::into)
+            .collect();
+
+        Ok(found_category_rules)
+    }
+
+    async fn create(
+        &self,
+        create_dto: CreateCategoryRuleDbDto,
+    ) -> Result<CategoryRuleFromDb, HttpError> {
+        let created_category_rule = CategoryRule::insert(create_dto.into_active_model())
+            .exec_with_returning(&self.connection)
+            .await?;
+
+        Ok(created_category_rule.into())
+    }
+
+    async fn update(
+        &self,
+        id: &str,
+        update_dto: UpdateCategoryRuleDbDto,
+        customer_id: &str,
+        expected_versions: Option<Vec<i64>>,
+    ) -> Result<CategoryRuleFromDb, HttpError> {
+        let transaction = self.connection.begin().await?;
+
+        Self::lock_for_write(&transaction, id, customer_id, expected_versions.as_deref()).await?;
+
+        let updated_category_rule = CategoryRule::update_many()
+            .set(CategoryRuleActiveModel::from(update_dto))
+            .col_expr(
+                category_rule::Column::Version,
+                Expr::col(category_rule::Column::Version).add(1),
+            )
+            .col_expr(
+                category_rule::Column::UpdatedAt,
+                Expr::current_timestamp().into(),
+            )
+            .filter(category_rule::Column::Id.eq(id))
+            .exec_with_returning(&transaction)
+            .await?
+            .pop()
+            .ok_or_else(|| Self::get_not_found_error(id))?;
+
+        transaction.commit().await?;
+
+        Ok(updated_category_rule.into())
+    }
+
+    async fn delete(
+        &self,
+        id: &str,
+        customer_id: &str,
+        expected_versions: Option<Vec<i64>>,
+    ) -> Result<CategoryRuleFromDb, HttpError> {
+        let transaction = self.connection.begin().await?;
+
+        let deleted_category_rule =
+            Self::lock_for_write(&transaction, id, customer_id, expected_versions.as_deref())
+                .await?;
+
+        CategoryRule::delete_by_id(id).exec(&transaction).await?;
+
+        transaction.commit().await?;
+
+        Ok(deleted_category_rule.into())
+    }
+}
@@ -0,0 +1,338 @@
+use std::sync::Arc;
+use validator::Validate;
+
+use crate::api::categories::categories_service::CategoriesService;
+use crate::api::categories::types::category_resolver::CategoryResolver;
+use crate::api::category_rules::dto::category_rule_conditions_dto::CategoryRuleConditionsDto;
+use crate::api::category_rules::dto::create_category_rule_db_dto::CreateCategoryRuleDbDto;
+use crate::api::category_rules::dto::create_category_rule_dto::CreateCategoryRuleDto;
+use crate::api::category_rules::dto::update_category_rule_db_dto::UpdateCategoryRuleDbDto;
+use crate::api::category_rules::dto::update_category_rule_dto::UpdateCategoryRuleDto;
+use crate::api::category_rules::entities::category_rule_entity::CategoryRuleEntity;
+use crate::api::category_rules::traits::category_rules_repository::CategoryRulesRepositoryTrait;
+use crate::api::category_rules::types::category_rule_matcher::CategoryRuleMatcher;
+use crate::api::category_rules::types::category_rules_engine::CategoryRulesEngine;
+use crate::api::category_rules::types::weekday::Weekday;
+use crate::api::customers::customers_service::CustomersService;
+use crate::shared::errors::http_error::HttpError;
+use crate::shared::modules::db::db_connection::DbConnection;
+use crate::shared::modules::db::unit_of_work::UnitOfWork;
+use crate::shared::modules::db::utils::{generate_id, to_decimal_amount};
+
+/// Rules are managed by the customers they belong to
+#[derive(Clone)]
+pub struct CategoryRulesService {
+    category_rules_repository: Arc<dyn CategoryRulesRepositoryTrait + Send + Sync>,
+    customers_service: Arc<CustomersService>,
+    categories_service: Arc<CategoriesService>,
+    unit_of_work: Arc<UnitOfWork>,
+}
+
+impl CategoryRulesService {
+    pub fn new(
+        category_rules_repository: Arc<dyn CategoryRulesRepositoryTrait + Send + Sync>,
+        customers_service: Arc<CustomersService>,
+        categories_service: Arc<CategoriesService>,
+        unit_of_work: Arc<UnitOfWork>,
+    ) -> Self {
+        Self {
+            category_rules_repository,
+            customers_service,
+            categories_service,
+            unit_of_work,
+        }
+    }
+
+    /// Service which queries run on the connection, e.g. a unit of work transaction
+    pub fn with_connection(&self, connection: DbConnection) -> Self {
+        Self {
+            category_rules_repository: self
+                .category_rules_repository
+                .with_connection(connection.clone()),
+            customers_service: Arc::new(self.customers_service.with_connection(connection.clone())),
+            categories_service: Arc::new(self.categories_service.with_connection(connection)),
+            unit_of_work: self.unit_of_work.clone(),
+        }
+    }
+
+    pub async fn find_many_as_customer(
+        &self,
+        user_id: &str,
+    ) -> Result<Vec<CategoryRuleEntity>, HttpError> {
+        let customer = self.customers_service.find_one_by_user_id(user_id).await?;
+
+        let category_rule_entities = self
+            .category_rules_repository
+            .find_many(&customer.id, false)
+            .await?
+            .into_iter()
+            .map(CategoryRuleEntity::from)
+            .collect();
+
+        Ok(category_rule_entities)
+    }
+
+    pub async fn find_one_as_customer(
+        &self,
+        id: &str,
+        user_id: &str,
+    ) -> Result<CategoryRuleEntity, HttpError> {
+        let customer = self.customers_service.find_one_by_user_id(user_id).await?;
+
+        let category_rule_entity = self
+            .category_rules_repository
+            .find_one(id, &customer.id)
+            .await?
+            .into();
+
+        Ok(category_rule_entity)
+    }
+
+    /// Enabled rules of the customer, ready to categorize their expenses. Rules of categories
+    /// archived in the customer's resolver are left out
+    pub async fn get_engine(
+        &self,
+        customer_id: &str,
+        category_resolver: &CategoryResolver,
+    ) -> Result<CategoryRulesEngine, HttpError> {
+        let category_rules = self
+            .category_rules_repository
+            .find_many(customer_id, true)
+            .await?;
+
+        Ok(CategoryRulesEngine::new(category_rules, category_resolver))
+    }
+
+    pub async fn create_as_customer(
+        &self,
+        create_dto: CreateCategoryRuleDto,
+        user_id: &str,
+    ) -> Result<CategoryRuleEntity, HttpError> {
+        create_dto
+            .validate()
+            .map_err(|validation_errors| HttpError::BadRequest(validation_errors.to_string()))?;
+        CategoryRuleMatcher::new(create_dto.conditions.clone())?;
+
+        self.unit_of_work
+            .run(|connection| async {
+                let category_rules_service = self.with_connection(connection);
+                let customer = category_rules_service
+                    .customers_service
+                    .find_one_by_user_id(user_id)
+                    .await?;
+                let category_id = category_rules_service
+                    .find_category_id(&create_dto.category, &customer.id)
+                    .await?;
+
+                let create_db_dto = Self::map_create_dto_to_create_db_dto(
+                    create_dto.clone(),
+                    customer.id,
+                    category_id,
+                )?;
+                let created_category_rule_entity = category_rules_service
+                    .category_rules_repository
+                    .create(create_db_dto)
+                    .await?
+                    .into();
+
+                Ok(created_category_rule_entity)
+            })
+            .await
+    }
+
+    /// Conditions are checked after merging the updated ones into the current ones
+    pub async fn update_as_customer(
+        &self,
+        id: &str,
+        update_dto: UpdateCategoryRuleDto,
+        user_id: &str,
+        expected_versions: Option<Vec<i64>>,
+    ) -> Result<CategoryRuleEntity, HttpError> {
+        update_dto
+            .validate()
+            .map_err(|validation_errors| HttpError::BadRequest(validation_errors.to_string()))?;
+
+        self.unit_of_work
+            .run(|connection| async {
+                let category_rules_service = self.with_connection(connection);
+                let customer = category_rules_service
+                    .customers_service
+                    .find_one_by_user_id(user_id)
+                    .await?;
+                let existing_category_rule = category_rules_service
+                    .category_rules_repository
+                    .find_one(id, &customer.id)
+                    .await?;
+
+                CategoryRuleMatcher::new(Self::merge_conditions(
+                    existing_category_rule.conditions,
+                    &update_dto,
+                ))?;
+
+                let category_id = match &update_dto.category {
+                    Some(category) => Some(
+                        category_rules_service
+                            .find_category_id(category, &customer.id)
+                            .await?,
+                    ),
+                    None => None,
+                };
+
+                let update_db_dto =
+                    Self::map_update_dto_to_update_db_dto(update_dto.clone(), category_id)?;
+                let updated_category_rule_entity = category_rules_service
+                    .category_rules_repository
+                    .update(id, update_db_dto, &customer.id, expected_versions.clone())
+                    .await?
+                    .into();
+
+                Ok(updated_category_rule_entity)
+            })
+            .await
+    }
+
+    pub async fn delete_as_customer(
+        &self,
+        id: &str,
+        user_id: &str,
+        expected_versions: Option<Vec<i64>>,
+    ) -> Result<CategoryRuleEntity, HttpError> {
+        let customer = self.customers_service.find_one_by_user_id(user_id).await?;
+
+        let deleted_category_rule_entity = self
+            .category_rules_repository
+            .delete(id, &customer.id, expected_versions)
+            .await?
+            .into();
+
+        Ok(deleted_category_rule_entity)
+    }
+
+    /// Rules can assign only categories of the customer which are not archived
+    async fn find_category_id(
+        &self,
+        category: &str,
+        customer_id: &str,
+    ) -> Result<String, HttpError> {
+        let category_resolver = self
+            .categories_service
+            .get_resolver(Some(customer_id))
+            .await?;
+
+        Ok(category_resolver.find_assignable(category)?.id.clone())
+    }
+
+    fn merge_conditions(
+        conditions: CategoryRuleConditionsDto,
+        update_dto: &UpdateCategoryRuleDto,
+    ) -> CategoryRuleConditionsDto {
+        CategoryRuleConditionsDto {
+            note_contains: update_dto
+                .note_contains
+                .clone()
+                .unwrap_or(conditions.note_contains),
+            note_pattern: update_dto
+                .note_pattern
+                .clone()
+                .unwrap_or(conditions.note_pattern),
+            amount_min: update_dto.amount_min.unwrap_or(conditions.amount_min),
+            amount_max: update_dto.amount_max.unwrap_or(conditions.amount_max),
+            weekdays: update_dto.weekdays.clone().unwrap_or(conditions.weekdays),
+        }
+    }
+
+    fn map_create_dto_to_create_db_dto(
+        create_dto: CreateCategoryRuleDto,
+        customer_id: String,
+        category_id: String,
+    ) -> Result<CreateCategoryRuleDbDto, HttpError> {
+        let conditions = create_dto.conditions;
+
+        Ok(CreateCategoryRuleDbDto {
+            id: generate_id(),
+            customer_id,
+            category_id,
+            name: create_dto.name,
+            priority: create_dto.priority,
+            is_enabled: create_dto.is_enabled,
+            note_contains: conditions.note_contains,
+            note_pattern: conditions.note_pattern,
+            amount_min: conditions.amount_min.map(to_decimal_amount).transpose()?,
+            amount_max: conditions.amount_max.map(to_decimal_amount).transpose()?,
+            weekdays: conditions.weekdays.as_deref().map(Weekday::to_mask),
+        })
+    }
+
+    fn map_update_dto_to_update_db_dto(
+        update_dto: UpdateCategoryRuleDto,
+        category_id: Option<String>,
+    ) -> Result<UpdateCategoryRuleDbDto, HttpError> {
+        Ok(UpdateCategoryRuleDbDto {
+            category_id,
+            name: update_dto.name,
+            priority: update_dto.priority,
+            is_enabled: update_dto.is_enabled,
+            note_contains: update_dto.note_contains,
+            note_pattern: update_dto.note_pattern,
+            amount_min: update_dto
+                .amount_min
+                .map(|amount_min| amount_min.map(to_decimal_amount).transpose())
+                .transpose()?,
+            amount_max: update_dto
+                .amount_max
+                .map(|amount_max| amount_max.map(to_decimal_amount).transpose())
+                .transpose()?,
+            weekdays: update_dto
+                .weekdays
+                .map(|weekdays| weekdays.as_deref().map(Weekday::to_mask)),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn reject_out_of_range_amounts() {
+        let create_dto: CreateCategoryRuleDto = serde_json::from_value(json!({
+            "name": "Big purchases",
+            "category": "FOOD",
+            "amountMin": 1e12,
+        }))
+        .unwrap();
+        let update_dto: UpdateCategoryRuleDto =
+            serde_json::from_value(json!({"amountMax": f64::MAX})).unwrap();
+
+        assert!(matches!(
+            CategoryRulesService::map_create_dto_to_create_db_dto(
+                create_dto,
+                "customer".into(),
+                "category".into()
+            ),
+            Err(HttpError::BadRequest(_))
+        ));
+        assert!(matches!(
+            CategoryRulesService::map_update_dto_to_update_db_dto(update_dto, None),
+            Err(HttpError::BadRequest(_))
+        ));
+    }
+
+    #[test]
+    fn map_update_dto_to_update_db_dto() {
+        let update_dto =
+            serde_json::from_value(json!({"amountMin": 10.5, "amountMax": null})).unwrap();
+
+        let update_db_dto =
+            CategoryRulesService::map_update_dto_to_update_db_dto(update_dto, None).unwrap();
+
+        assert_eq!(
+            update_db_dto
+                .amount_min
+                .map(|amount| amount.map(|amount| amount.to_string())),
+            Some(Some("10.5".to_string()))
+        );
+        assert_eq!(update_db_dto.amount_max, Some(None));
+    }
+}
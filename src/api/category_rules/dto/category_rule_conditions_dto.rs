@@ -0,0 +1,39 @@
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use validator::Validate;
+
+use crate::api::category_rules::types::weekday::Weekday;
+
+/// Conditions of an expense a rule matches, all the set ones have to match
+#[derive(Debug, Clone, Default, Deserialize, Serialize, Validate, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct CategoryRuleConditionsDto {
+    /// Case-insensitive substring of the note, e.g. a merchant name
+    #[validate(length(min = 1, max = 255, message = "Should be 1 to 255 characters"))]
+    pub note_contains: Option<String>,
+
+    /// Regular expression the note has to match, e.g. `(?i)^(uber|bolt)\b`
+    #[validate(length(min = 1, max = 255, message = "Should be 1 to 255 characters"))]
+    pub note_pattern: Option<String>,
+
+    /// Inclusive bounds of the amount
+    #[validate(range(min = 0.0, message = "Should be more than 0"))]
+    pub amount_min: Option<f64>,
+
+    #[validate(range(min = 0.0, message = "Should be more than 0"))]
+    pub amount_max: Option<f64>,
+
+    /// Days of the week of the expense date
+    #[validate(length(min = 1, message = "Can not be empty"))]
+    pub weekdays: Option<Vec<Weekday>>,
+}
+
+impl CategoryRuleConditionsDto {
+    pub fn is_empty(&self) -> bool {
+        self.note_contains.is_none()
+            && self.note_pattern.is_none()
+            && self.amount_min.is_none()
+            && self.amount_max.is_none()
+            && self.weekdays.is_none()
+    }
+}
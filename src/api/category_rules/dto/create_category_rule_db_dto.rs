@@ -0,0 +1,20 @@
+use sea_orm::DeriveIntoActiveModel;
+use sea_orm::prelude::Decimal;
+use serde::{Deserialize, Serialize};
+
+use crate::shared::modules::db::entities::category_rule::ActiveModel;
+
+#[derive(Serialize, Deserialize, Debug, Clone, DeriveIntoActiveModel)]
+pub struct CreateCategoryRuleDbDto {
+    pub id: String,
+    pub customer_id: String,
+    pub category_id: String,
+    pub name: String,
+    pub priority: i64,
+    pub is_enabled: bool,
+    pub note_contains: Option<String>,
+    pub note_pattern: Option<String>,
+    pub amount_min: Option<Decimal>,
+    pub amount_max: Option<Decimal>,
+    pub weekdays: Option<i64>,
+}
@@ -0,0 +1,32 @@
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use validator::Validate;
+
+use crate::api::category_rules::dto::category_rule_conditions_dto::CategoryRuleConditionsDto;
+
+#[derive(Debug, Clone, Deserialize, Serialize, Validate, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct CreateCategoryRuleDto {
+    #[validate(length(min = 1, max = 100, message = "Should be 1 to 100 characters"))]
+    pub name: String,
+
+    /// Id of the category or code of a system one, e.g. `TAXI`
+    #[serde(alias = "categoryId")]
+    #[validate(length(min = 1, message = "Can not be empty"))]
+    pub category: String,
+
+    /// Rules are evaluated from the lowest priority, the first matching one wins
+    #[serde(default)]
+    pub priority: i64,
+
+    #[serde(default = "default_is_enabled")]
+    pub is_enabled: bool,
+
+    #[serde(flatten)]
+    #[validate(nested)]
+    pub conditions: CategoryRuleConditionsDto,
+}
+
+fn default_is_enabled() -> bool {
+    true
+}
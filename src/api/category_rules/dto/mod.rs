@@ -0,0 +1,6 @@
+pub mod create_category_rule_db_dto;
+pub mod update_category_rule_db_dto;
+
+pub mod category_rule_conditions_dto;
+pub mod create_category_rule_dto;
+pub mod update_category_rule_dto;
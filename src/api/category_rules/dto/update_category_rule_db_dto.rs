@@ -0,0 +1,40 @@
+use sea_orm::ActiveValue;
+use sea_orm::prelude::Decimal;
+use serde::{Deserialize, Serialize};
+
+use crate::shared::modules::db::entities::category_rule::ActiveModel as CategoryRuleActiveModel;
+use crate::shared::modules::db::utils::optional_to_active_value;
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct UpdateCategoryRuleDbDto {
+    pub category_id: Option<String>,
+    pub name: Option<String>,
+    pub priority: Option<i64>,
+    pub is_enabled: Option<bool>,
+    pub note_contains: Option<Option<String>>,
+    pub note_pattern: Option<Option<String>>,
+    pub amount_min: Option<Option<Decimal>>,
+    pub amount_max: Option<Option<Decimal>>,
+    pub weekdays: Option<Option<i64>>,
+}
+
+impl From<UpdateCategoryRuleDbDto> for CategoryRuleActiveModel {
+    fn from(value: UpdateCategoryRuleDbDto) -> Self {
+        Self {
+            id: ActiveValue::NotSet,
+            customer_id: ActiveValue::NotSet,
+            category_id: optional_to_active_value(value.category_id),
+            name: optional_to_active_value(value.name),
+            priority: optional_to_active_value(value.priority),
+            is_enabled: optional_to_active_value(value.is_enabled),
+            note_contains: optional_to_active_value(value.note_contains),
+            note_pattern: optional_to_active_value(value.note_pattern),
+            amount_min: optional_to_active_value(value.amount_min),
+            amount_max: optional_to_active_value(value.amount_max),
+            weekdays: optional_to_active_value(value.weekdays),
+            created_at: ActiveValue::NotSet,
+            updated_at: ActiveValue::NotSet,
+            version: ActiveValue::NotSet,
+        }
+    }
+}
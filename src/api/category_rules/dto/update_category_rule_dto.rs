@@ -0,0 +1,43 @@
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use validator::Validate;
+
+use crate::api::category_rules::types::weekday::Weekday;
+use crate::shared::utils::deserialize_nullable;
+
+/// `null` clears a condition, omitted fields are kept
+#[derive(Debug, Clone, Deserialize, Serialize, Validate, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct UpdateCategoryRuleDto {
+    #[validate(length(min = 1, max = 100, message = "Should be 1 to 100 characters"))]
+    pub name: Option<String>,
+
+    /// Id of the category or code of a system one, e.g. `TAXI`
+    #[serde(alias = "categoryId")]
+    #[validate(length(min = 1, message = "Can not be empty"))]
+    pub category: Option<String>,
+
+    pub priority: Option<i64>,
+
+    pub is_enabled: Option<bool>,
+
+    #[serde(default, deserialize_with = "deserialize_nullable")]
+    #[validate(length(min = 1, max = 255, message = "Should be 1 to 255 characters"))]
+    pub note_contains: Option<Option<String>>,
+
+    #[serde(default, deserialize_with = "deserialize_nullable")]
+    #[validate(length(min = 1, max = 255, message = "Should be 1 to 255 characters"))]
+    pub note_pattern: Option<Option<String>>,
+
+    #[serde(default, deserialize_with = "deserialize_nullable")]
+    #[validate(range(min = 0.0, message = "Should be more than 0"))]
+    pub amount_min: Option<Option<f64>>,
+
+    #[serde(default, deserialize_with = "deserialize_nullable")]
+    #[validate(range(min = 0.0, message = "Should be more than 0"))]
+    pub amount_max: Option<Option<f64>>,
+
+    #[serde(default, deserialize_with = "deserialize_nullable")]
+    #[validate(length(min = 1, message = "Can not be empty"))]
+    pub weekdays: Option<Option<Vec<Weekday>>>,
+}
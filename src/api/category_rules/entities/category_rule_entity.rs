@@ -0,0 +1,51 @@
+use aide::OperationIo;
+use chrono::{DateTime, FixedOffset};
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+use crate::api::category_rules::dto::category_rule_conditions_dto::CategoryRuleConditionsDto;
+use crate::api::category_rules::types::category_rule_from_db::CategoryRuleFromDb;
+use crate::shared::modules::versioning::traits::versioned::Versioned;
+
+#[derive(Serialize, Deserialize, Debug, Clone, JsonSchema, OperationIo)]
+#[serde(rename_all = "camelCase")]
+pub struct CategoryRuleEntity {
+    /// UUID generated by the server, time-ordered (v7)
+    pub id: String,
+    pub customer_id: String,
+    /// Category assigned to the matching expenses
+    pub category_id: String,
+    pub name: String,
+    /// Rules are evaluated from the lowest priority, the first matching one wins
+    pub priority: i64,
+    pub is_enabled: bool,
+    #[serde(flatten)]
+    pub conditions: CategoryRuleConditionsDto,
+    pub created_at: DateTime<FixedOffset>,
+    pub updated_at: DateTime<FixedOffset>,
+    /// Bumped on every update, pass it in `If-Match` to update only this version
+    pub version: i64,
+}
+
+impl From<CategoryRuleFromDb> for CategoryRuleEntity {
+    fn from(value: CategoryRuleFromDb) -> Self {
+        Self {
+            id: value.id,
+            customer_id: value.customer_id,
+            category_id: value.category_id,
+            name: value.name,
+            priority: value.priority,
+            is_enabled: value.is_enabled,
+            conditions: value.conditions,
+            created_at: value.created_at,
+            updated_at: value.updated_at,
+            version: value.version,
+        }
+    }
+}
+
+impl Versioned for CategoryRuleEntity {
+    fn get_version(&self) -> i64 {
+        self.version
+    }
+}
@@ -0,0 +1,98 @@
+use aide::axum::ApiRouter;
+use aide::axum::routing::{delete, get, patch, post};
+use sea_orm::DatabaseConnection;
+use std::sync::Arc;
+
+use crate::api::audit_logs::audit_logs_repository::AuditLogsRepository;
+use crate::api::audit_logs::audit_logs_service::AuditLogsService;
+use crate::api::categories::categories_repository::CategoriesRepository;
+use crate::api::categories::categories_service::CategoriesService;
+use crate::api::category_rules::category_rules_repository::CategoryRulesRepository;
+use crate::api::category_rules::category_rules_service::CategoryRulesService;
+use crate::api::category_rules::types::api_state::CategoryRulesApiState;
+use crate::api::customers::customers_repository::CustomerRepository;
+use crate::api::customers::customers_service::CustomersService;
+use crate::shared::modules::auth::enums::roles::Roles;
+use crate::shared::modules::auth::middlewares::role_based_bearer_auth::AuthLayer;
+use crate::shared::modules::auth::services::auth0::Auth0Service;
+use crate::shared::modules::cache::AppL2CacheService;
+use crate::shared::modules::db::unit_of_work::UnitOfWork;
+use crate::shared::modules::idempotency::middlewares::idempotency::IdempotencyLayer;
+
+mod category_rules_handlers;
+pub mod category_rules_repository;
+pub mod category_rules_service;
+pub mod dto;
+pub mod entities;
+pub mod traits;
+pub mod types;
+
+pub fn get_router(
+    sea_orm_client: Arc<DatabaseConnection>,
+    idempotency_layer: IdempotencyLayer<AppL2CacheService>,
+    auth_service: Arc<Auth0Service>,
+) -> ApiRouter {
+    let audit_logs_repository = Arc::new(AuditLogsRepository::new(sea_orm_client.clone()));
+    let audit_logs_service = Arc::new(AuditLogsService::new(audit_logs_repository));
+
+    let unit_of_work = Arc::new(UnitOfWork::new(sea_orm_client.clone()));
+    let customers_repository = Arc::new(CustomerRepository::new(sea_orm_client.clone()));
+    let customers_service = Arc::new(CustomersService::new(
+        customers_repository,
+        audit_logs_service,
+        unit_of_work.clone(),
+    ));
+
+    let categories_repository = Arc::new(CategoriesRepository::new(sea_orm_client.clone()));
+    let categories_service = Arc::new(CategoriesService::new(
+        categories_repository,
+        customers_service.clone(),
+        unit_of_work.clone(),
+    ));
+
+    let category_rules_repository = Arc::new(CategoryRulesRepository::new(sea_orm_client));
+    let category_rules_service = Arc::new(CategoryRulesService::new(
+        category_rules_repository,
+        customers_service.clone(),
+        categories_service,
+        unit_of_work,
+    ));
+
+    let api_state = CategoryRulesApiState {
+        category_rules_service,
+    };
+
+    let auth_layer = AuthLayer::new(auth_service, customers_service);
+
+    let routes = ApiRouter::new()
+        .api_route(
+            "/",
+            get(category_rules_handlers::find_many)
+                .route_layer(auth_layer.verify(vec![Roles::Customer])),
+        )
+        .api_route(
+            "/{id}",
+            get(category_rules_handlers::find_one)
+                .route_layer(auth_layer.verify(vec![Roles::Customer])),
+        )
+        .api_route(
+            "/",
+            post(category_rules_handlers::create)
+                .route_layer(idempotency_layer)
+                .route_layer(auth_layer.verify(vec![Roles::Customer])),
+        )
+        .api_route(
+            "/{id}",
+            patch(category_rules_handlers::update)
+                .route_layer(auth_layer.verify(vec![Roles::Customer])),
+        )
+        .api_route(
+            "/{id}",
+            delete(category_rules_handlers::remove)
+                .route_layer(auth_layer.verify(vec![Roles::Customer])),
+        );
+
+    ApiRouter::new()
+        .nest("/category-rules", routes)
+        .with_state(api_state)
+}
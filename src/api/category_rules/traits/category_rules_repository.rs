@@ -0,0 +1,48 @@
+use async_trait::async_trait;
+use std::sync::Arc;
+
+use crate::api::category_rules::dto::create_category_rule_db_dto::CreateCategoryRuleDbDto;
+use crate::api::category_rules::dto::update_category_rule_db_dto::UpdateCategoryRuleDbDto;
+use crate::api::category_rules::types::category_rule_from_db::CategoryRuleFromDb;
+use crate::shared::errors::http_error::HttpError;
+use crate::shared::modules::db::db_connection::DbConnection;
+
+#[async_trait]
+pub trait CategoryRulesRepositoryTrait {
+    /// Repository which queries run on the connection, e.g. a unit of work transaction
+    fn with_connection(
+        &self,
+        connection: DbConnection,
+    ) -> Arc<dyn CategoryRulesRepositoryTrait + Send + Sync>;
+
+    async fn find_one(&self, id: &str, customer_id: &str) -> Result<CategoryRuleFromDb, HttpError>;
+
+    /// Rules of the customer in the order they are evaluated: by priority, then by creation
+    async fn find_many(
+        &self,
+        customer_id: &str,
+        only_enabled: bool,
+    ) -> Result<Vec<CategoryRuleFromDb>, HttpError>;
+
+    async fn create(
+        &self,
+        create_dto: CreateCategoryRuleDbDto,
+    ) -> Result<CategoryRuleFromDb, HttpError>;
+
+    /// Writes check that the rule belongs to the customer in the same transaction
+    async fn update(
+        &self,
+        id: &str,
+        update_dto: UpdateCategoryRuleDbDto,
+        customer_id: &str,
+        expected_versions: Option<Vec<i64>>,
+    ) -> Result<CategoryRuleFromDb, HttpError>;
+
+    /// Rules are deleted permanently
+    async fn delete(
+        &self,
+        id: &str,
+        customer_id: &str,
+        expected_versions: Option<Vec<i64>>,
+    ) -> Result<CategoryRuleFromDb, HttpError>;
+}
@@ -0,0 +1 @@
+pub mod category_rules_repository;
@@ -0,0 +1,15 @@
+use axum::extract::FromRef;
+use std::sync::Arc;
+
+use crate::api::category_rules::category_rules_service::CategoryRulesService;
+
+#[derive(Clone)]
+pub struct CategoryRulesApiState {
+    pub category_rules_service: Arc<CategoryRulesService>,
+}
+
+impl FromRef<CategoryRulesApiState> for Arc<CategoryRulesService> {
+    fn from_ref(app_state: &CategoryRulesApiState) -> Arc<CategoryRulesService> {
+        app_state.category_rules_service.clone()
+    }
+}
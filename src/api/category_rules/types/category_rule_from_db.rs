@@ -0,0 +1,47 @@
+use chrono::{DateTime, FixedOffset};
+use serde::{Deserialize, Serialize};
+
+use crate::api::category_rules::dto::category_rule_conditions_dto::CategoryRuleConditionsDto;
+use crate::api::category_rules::types::weekday::Weekday;
+use crate::shared::modules::db::entities::category_rule;
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct CategoryRuleFromDb {
+    pub id: String,
+    pub customer_id: String,
+    pub category_id: String,
+    pub name: String,
+    pub priority: i64,
+    pub is_enabled: bool,
+    pub conditions: CategoryRuleConditionsDto,
+    pub created_at: DateTime<FixedOffset>,
+    pub updated_at: DateTime<FixedOffset>,
+    pub version: i64,
+}
+
+impl From<category_rule::Model> for CategoryRuleFromDb {
+    fn from(value: category_rule::Model) -> Self {
+        Self {
+            id: value.id,
+            customer_id: value.customer_id,
+            category_id: value.category_id,
+            name: value.name,
+            priority: value.priority,
+            is_enabled: value.is_enabled,
+            conditions: CategoryRuleConditionsDto {
+                note_contains: value.note_contains,
+                note_pattern: value.note_pattern,
+                amount_min: value
+                    .amount_min
+                    .map(|amount| amount.try_into().unwrap_or_default()),
+                amount_max: value
+                    .amount_max
+                    .map(|amount| amount.try_into().unwrap_or_default()),
+                weekdays: value.weekdays.map(Weekday::from_mask),
+            },
+            created_at: value.created_at,
+            updated_at: value.updated_at,
+            version: value.version,
+        }
+    }
+}
@@ -0,0 +1,228 @@
+use chrono::{DateTime, Datelike, FixedOffset};
+use regex::{Regex, RegexBuilder};
+
+use crate::api::category_rules::dto::category_rule_conditions_dto::CategoryRuleConditionsDto;
+use crate::api::category_rules::types::weekday::Weekday;
+use crate::shared::errors::http_error::HttpError;
+
+/// Compiled patterns are limited, so that a rule can not take a lot of memory
+const NOTE_PATTERN_SIZE_LIMIT: usize = 256 * 1024;
+
+/// Conditions of a rule ready to be matched, all the set ones have to match
+#[derive(Debug, Clone)]
+pub struct CategoryRuleMatcher {
+    /// Lowercase
+    note_contains: Option<String>,
+    note_pattern: Option<Regex>,
+    amount_min: Option<f64>,
+    amount_max: Option<f64>,
+    weekdays: Option<Vec<Weekday>>,
+}
+
+impl CategoryRuleMatcher {
+    pub fn new(conditions: CategoryRuleConditionsDto) -> Result<Self, HttpError> {
+        if conditions.is_empty() {
+            return Err(HttpError::BadRequest(
+                "Rule should have at least one of noteContains, notePattern, amountMin, amountMax and weekdays".into(),
+            ));
+        }
+
+        if let (Some(amount_min), Some(amount_max)) = (conditions.amount_min, conditions.amount_max)
+            && amount_min > amount_max
+        {
+            return Err(HttpError::BadRequest(
+                "amountMin should not be more than amountMax".into(),
+            ));
+        }
+
+        let note_pattern = conditions
+            .note_pattern
+            .as_deref()
+            .map(|note_pattern| {
+                RegexBuilder::new(note_pattern)
+                    .size_limit(NOTE_PATTERN_SIZE_LIMIT)
+                    .build()
+                    .map_err(|err| {
+                        HttpError::BadRequest(format!("notePattern is not a valid regex: {err}"))
+                    })
+            })
+            .transpose()?;
+
+        Ok(Self {
+            note_contains: conditions.note_contains.map(|note| note.to_lowercase()),
+            note_pattern,
+            amount_min: conditions.amount_min,
+            amount_max: conditions.amount_max,
+            weekdays: conditions.weekdays,
+        })
+    }
+
+    /// Weekday is the one of the date in its own offset. Note conditions never match
+    /// expenses without a note
+    pub fn is_match(&self, amount: f64, date: &DateTime<FixedOffset>, note: Option<&str>) -> bool {
+        let is_note_contains_match = self.note_contains.as_ref().is_none_or(|note_contains| {
+            note.is_some_and(|note| note.to_lowercase().contains(note_contains.as_str()))
+        });
+        let is_note_pattern_match = self
+            .note_pattern
+            .as_ref()
+            .is_none_or(|note_pattern| note.is_some_and(|note| note_pattern.is_match(note)));
+        let is_weekday_match = self
+            .weekdays
+            .as_ref()
+            .is_none_or(|weekdays| weekdays.contains(&date.weekday().into()));
+
+        is_note_contains_match
+            && is_note_pattern_match
+            && is_weekday_match
+            && self
+                .amount_min
+                .is_none_or(|amount_min| amount >= amount_min)
+            && self
+                .amount_max
+                .is_none_or(|amount_max| amount <= amount_max)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn get_matcher(conditions: serde_json::Value) -> CategoryRuleMatcher {
+        CategoryRuleMatcher::new(serde_json::from_value(conditions).unwrap()).unwrap()
+    }
+
+    #[test]
+    fn is_match() {
+        // 2026-10-19 is a Monday in UTC and a Tuesday in +10:00
+        let monday = DateTime::parse_from_rfc3339("2026-10-19T20:00:00+00:00").unwrap();
+        let tuesday = DateTime::parse_from_rfc3339("2026-10-20T06:00:00+10:00").unwrap();
+        let cases = [
+            (
+                "note contains in any case",
+                json!({"noteContains": "Uber"}),
+                10.0,
+                monday,
+                Some("UBER trip"),
+                true,
+            ),
+            (
+                "note does not contain",
+                json!({"noteContains": "uber"}),
+                10.0,
+                monday,
+                Some("Bolt trip"),
+                false,
+            ),
+            (
+                "note condition without a note",
+                json!({"noteContains": "uber"}),
+                10.0,
+                monday,
+                None,
+                false,
+            ),
+            (
+                "note pattern",
+                json!({"notePattern": "(?i)^(uber|bolt)\\b"}),
+                10.0,
+                monday,
+                Some("bolt trip"),
+                true,
+            ),
+            (
+                "note pattern not at the start",
+                json!({"notePattern": "(?i)^(uber|bolt)\\b"}),
+                10.0,
+                monday,
+                Some("Taxi, not bolt"),
+                false,
+            ),
+            (
+                "amount at inclusive bounds",
+                json!({"amountMin": 10.0, "amountMax": 20.0}),
+                20.0,
+                monday,
+                None,
+                true,
+            ),
+            (
+                "amount below min",
+                json!({"amountMin": 10.0}),
+                9.99,
+                monday,
+                None,
+                false,
+            ),
+            (
+                "amount above max",
+                json!({"amountMax": 20.0}),
+                20.01,
+                monday,
+                None,
+                false,
+            ),
+            (
+                "weekday",
+                json!({"weekdays": ["MONDAY", "FRIDAY"]}),
+                10.0,
+                monday,
+                None,
+                true,
+            ),
+            (
+                "weekday in the offset of the date",
+                json!({"weekdays": ["MONDAY"]}),
+                10.0,
+                tuesday,
+                None,
+                false,
+            ),
+            (
+                "all conditions",
+                json!({"noteContains": "uber", "amountMax": 20.0, "weekdays": ["TUESDAY"]}),
+                15.0,
+                tuesday,
+                Some("Uber"),
+                true,
+            ),
+            (
+                "one of all conditions fails",
+                json!({"noteContains": "uber", "amountMax": 20.0, "weekdays": ["TUESDAY"]}),
+                25.0,
+                tuesday,
+                Some("Uber"),
+                false,
+            ),
+        ];
+
+        for (name, conditions, amount, date, note, expected) in cases {
+            let category_rule_matcher = get_matcher(conditions);
+
+            assert_eq!(
+                category_rule_matcher.is_match(amount, &date, note),
+                expected,
+                "{name}"
+            );
+        }
+    }
+
+    #[test]
+    fn new_fails_on_invalid_conditions() {
+        let cases = [
+            ("no conditions", json!({})),
+            (
+                "min above max",
+                json!({"amountMin": 20.0, "amountMax": 10.0}),
+            ),
+            ("invalid pattern", json!({"notePattern": "(uber"})),
+        ];
+
+        for (name, conditions) in cases {
+            let conditions = serde_json::from_value(conditions).unwrap();
+
+            assert!(CategoryRuleMatcher::new(conditions).is_err(), "{name}");
+        }
+    }
+}
@@ -0,0 +1,53 @@
+use chrono::{DateTime, FixedOffset};
+
+use crate::api::categories::types::category_resolver::CategoryResolver;
+use crate::api::category_rules::types::category_rule_from_db::CategoryRuleFromDb;
+use crate::api::category_rules::types::category_rule_matcher::CategoryRuleMatcher;
+
+struct CompiledCategoryRule {
+    category_id: String,
+    matcher: CategoryRuleMatcher,
+}
+
+/// Enabled rules of a customer in the order they are evaluated
+pub struct CategoryRulesEngine {
+    rules: Vec<CompiledCategoryRule>,
+}
+
+impl CategoryRulesEngine {
+    /// Rules are expected ordered by priority. Disabled rules are skipped, as well as the ones
+    /// which no longer compile and the ones of archived categories, expenses can't be moved
+    /// into them
+    pub fn new(rules: Vec<CategoryRuleFromDb>, category_resolver: &CategoryResolver) -> Self {
+        let rules = rules
+            .into_iter()
+            .filter(|rule| rule.is_enabled)
+            .filter(|rule| category_resolver.find_assignable(&rule.category_id).is_ok())
+            .filter_map(|rule| match CategoryRuleMatcher::new(rule.conditions) {
+                Ok(matcher) => Some(CompiledCategoryRule {
+                    category_id: rule.category_id,
+                    matcher,
+                }),
+                Err(err) => {
+                    tracing::warn!("Category rule {} is skipped: '{err}'", rule.id);
+                    None
+                }
+            })
+            .collect();
+
+        Self { rules }
+    }
+
+    /// Category of the first matching rule
+    pub fn find_category_id(
+        &self,
+        amount: f64,
+        date: &DateTime<FixedOffset>,
+        note: Option<&str>,
+    ) -> Option<&str> {
+        self.rules
+            .iter()
+            .find(|rule| rule.matcher.is_match(amount, date, note))
+            .map(|rule| rule.category_id.as_str())
+    }
+}
@@ -0,0 +1,5 @@
+pub mod api_state;
+pub mod category_rule_from_db;
+pub mod category_rule_matcher;
+pub mod category_rules_engine;
+pub mod weekday;
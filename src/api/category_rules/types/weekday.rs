@@ -0,0 +1,98 @@
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq, JsonSchema)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum Weekday {
+    Monday,
+    Tuesday,
+    Wednesday,
+    Thursday,
+    Friday,
+    Saturday,
+    Sunday,
+}
+
+impl Weekday {
+    const ALL: [Self; 7] = [
+        Self::Monday,
+        Self::Tuesday,
+        Self::Wednesday,
+        Self::Thursday,
+        Self::Friday,
+        Self::Saturday,
+        Self::Sunday,
+    ];
+
+    /// Weekdays are stored as a bit mask, bit 0 is Monday
+    pub fn to_mask(weekdays: &[Self]) -> i64 {
+        weekdays
+            .iter()
+            .fold(0, |mask, weekday| mask | (1 << *weekday as i64))
+    }
+
+    pub fn from_mask(mask: i64) -> Vec<Self> {
+        Self::ALL
+            .into_iter()
+            .filter(|weekday| mask & (1 << *weekday as i64) != 0)
+            .collect()
+    }
+}
+
+impl From<chrono::Weekday> for Weekday {
+    fn from(value: chrono::Weekday) -> Self {
+        match value {
+            chrono::Weekday::Mon => Self::Monday,
+            chrono::Weekday::Tue => Self::Tuesday,
+            chrono::Weekday::Wed => Self::Wednesday,
+            chrono::Weekday::Thu => Self::Thursday,
+            chrono::Weekday::Fri => Self::Friday,
+            chrono::Weekday::Sat => Self::Saturday,
+            chrono::Weekday::Sun => Self::Sunday,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn to_mask() {
+        let cases = [
+            ("none", vec![], 0),
+            ("monday", vec![Weekday::Monday], 0b1),
+            ("sunday", vec![Weekday::Sunday], 0b100_0000),
+            (
+                "weekend",
+                vec![Weekday::Saturday, Weekday::Sunday],
+                0b110_0000,
+            ),
+            ("repeated", vec![Weekday::Monday, Weekday::Monday], 0b1),
+            ("all", Weekday::ALL.to_vec(), 0b111_1111),
+        ];
+
+        for (name, weekdays, expected) in cases {
+            assert_eq!(Weekday::to_mask(&weekdays), expected, "{name}");
+        }
+    }
+
+    #[test]
+    fn from_mask() {
+        let cases = [
+            ("none", 0, vec![]),
+            ("monday", 0b1, vec![Weekday::Monday]),
+            (
+                "weekdays in order",
+                0b101_0100,
+                vec![Weekday::Wednesday, Weekday::Friday, Weekday::Sunday],
+            ),
+            ("unknown bits", 0b1000_0001, vec![Weekday::Monday]),
+            ("all", 0b111_1111, Weekday::ALL.to_vec()),
+        ];
+
+        for (name, mask, expected) in cases {
+            assert_eq!(Weekday::from_mask(mask), expected, "{name}");
+        }
+    }
+}
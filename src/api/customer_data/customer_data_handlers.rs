@@ -0,0 +1,56 @@
+use axum::extract::{Query, State};
+use axum::{Extension, Json};
+use std::sync::Arc;
+
+use crate::api::customer_data::customer_data_service::CustomerDataService;
+use crate::api::customer_data::dto::export_customer_data_query_dto::ExportCustomerDataQueryDto;
+use crate::api::customer_data::dto::find_erasure_requests_dto::FindErasureRequestsDto;
+use crate::api::customer_data::entities::erasure_request_entity::ErasureRequestEntity;
+use crate::api::customer_data::entities::exported_customer_data_entity::ExportedCustomerDataEntity;
+use crate::shared::errors::http_error::HttpError;
+use crate::shared::modules::audit::structs::audit_context::AuditContext;
+use crate::shared::modules::auth::structs::user::User;
+use crate::shared::modules::db::structs::id_path::IdPath;
+
+pub async fn export_self(
+    Extension(user): Extension<User>,
+    State(customer_data_service): State<Arc<CustomerDataService>>,
+    Query(query): Query<ExportCustomerDataQueryDto>,
+) -> Result<ExportedCustomerDataEntity, HttpError> {
+    let exported_customer_data = customer_data_service.export(&user.id, query.format).await?;
+
+    Ok(exported_customer_data)
+}
+
+pub async fn erase_self(
+    Extension(user): Extension<User>,
+    audit_context: AuditContext,
+    State(customer_data_service): State<Arc<CustomerDataService>>,
+) -> Result<Json<ErasureRequestEntity>, HttpError> {
+    let erasure_request = customer_data_service
+        .erase_as_customer(&user.id, &audit_context)
+        .await?;
+
+    Ok(Json(erasure_request))
+}
+
+pub async fn erase_one(
+    IdPath(customer_id): IdPath,
+    audit_context: AuditContext,
+    State(customer_data_service): State<Arc<CustomerDataService>>,
+) -> Result<Json<ErasureRequestEntity>, HttpError> {
+    let erasure_request = customer_data_service
+        .erase_as_admin(&customer_id, &audit_context)
+        .await?;
+
+    Ok(Json(erasure_request))
+}
+
+pub async fn find_erasure_requests(
+    State(customer_data_service): State<Arc<CustomerDataService>>,
+    Query(filter): Query<FindErasureRequestsDto>,
+) -> Result<Json<Vec<ErasureRequestEntity>>, HttpError> {
+    let found_erasure_requests = customer_data_service.find_erasure_requests(filter).await?;
+
+    Ok(Json(found_erasure_requests))
+}
@@ -0,0 +1,133 @@
+use async_trait::async_trait;
+use sea_orm::{ColumnTrait, DatabaseConnection, EntityTrait, QueryFilter, QueryOrder, QuerySelect};
+use std::slice;
+use std::sync::Arc;
+
+use crate::api::categories::types::category_from_db::CategoryFromDb;
+use crate::api::category_rules::types::category_rule_from_db::CategoryRuleFromDb;
+use crate::api::customer_data::traits::customer_data_repository::CustomerDataRepositoryTrait;
+use crate::api::customer_data::types::erased_customer_data::ErasedCustomerData;
+use crate::api::customer_data::types::regular_payment_from_db::RegularPaymentFromDb;
+use crate::api::customer_data::utils::delete_customers_with_data;
+use crate::api::customers::types::customer_from_db::CustomerFromDb;
+use crate::api::expenses::types::expense_from_db::ExpenseFromDb;
+use crate::shared::errors::http_error::HttpError;
+use crate::shared::modules::db::db_connection::DbConnection;
+use crate::shared::modules::db::entities::prelude::{
+    Category, CategoryRule, Customer, Expense, RegularPayment,
+};
+use crate::shared::modules::db::entities::{category, category_rule, expense, regular_payment};
+
+pub struct CustomerDataRepository {
+    connection: DbConnection,
+}
+
+impl CustomerDataRepository {
+    pub fn new(sea_orm_client: Arc<DatabaseConnection>) -> Self {
+        Self {
+            connection: DbConnection::Pool(sea_orm_client),
+        }
+    }
+
+    fn get_not_found_error(id: &str) -> HttpError {
+        HttpError::NotFound(format!("Customer with id '{id}' was not found"))
+    }
+}
+
+#[async_trait]
+impl CustomerDataRepositoryTrait for CustomerDataRepository {
+    fn with_connection(
+        &self,
+        connection: DbConnection,
+    ) -> Arc<dyn CustomerDataRepositoryTrait + Send + Sync> {
+        Arc::new(Self { connection })
+    }
+
+    async fn find_customer(&self, id: &str) -> Result<CustomerFromDb, HttpError> {
+        let customer_from_db = Customer::find_by_id(id)
+            .one(&self.connection)
+            .await?
+            .ok_or_else(|| Self::get_not_found_error(id))?;
+
+        Ok(customer_from_db.into())
+    }
+
+    async fn find_expenses(&self, customer_id: &str) -> Result<Vec<ExpenseFromDb>, HttpError> {
+        let found_expenses = Expense::find()
+            .find_also_related(Category)
+            .filter(expense::Column::CustomerId.eq(customer_id))
+            .order_by_asc(expense::Column::Date)
+            .order_by_asc(expense::Column::Id)
+            .all(&self.connection)
+            .await?
+            .into_iter()
+            .map(Into::into)
+            .collect();
+
+        Ok(found_expenses)
+    }
+
+    async fn find_regular_payments(
+        &self,
+        customer_id: &str,
+    ) -> Result<Vec<RegularPaymentFromDb>, HttpError> {
+        let found_regular_payments = RegularPayment::find()
+            .filter(regular_payment::Column::CustomerId.eq(customer_id))
+            .order_by_asc(regular_payment::Column::DateOfCharge)
+            .all(&self.connection)
+            .await?
+            .into_iter()
+            .map(Into::into)
+            .collect();
+
+        Ok(found_regular_payments)
+    }
+
+    async fn find_categories(&self, customer_id: &str) -> Result<Vec<CategoryFromDb>, HttpError> {
+        let found_categories = Category::find()
+            .filter(category::Column::CustomerId.eq(customer_id))
+            .order_by_asc(category::Column::CreatedAt)
+            .all(&self.connection)
+            .await?
+            .into_iter()
+            .map(Into::into)
+            .collect();
+
+        Ok(found_categories)
+    }
+
+    async fn find_category_rules(
+        &self,
+        customer_id: &str,
+    ) -> Result<Vec<CategoryRuleFromDb>, HttpError> {
+        let found_category_rules = CategoryRule::find()
+            .filter(category_rule::Column::CustomerId.eq(customer_id))
+            .order_by_asc(category_rule::Column::Priority)
+            .order_by_asc(category_rule::Column::Id)
+            .all(&self.connection)
+            .await?
+            .into_iter()
+            .map(Into::into)
+            .collect();
+
+        Ok(found_category_rules)
+    }
+
+    async fn erase(&self, customer_id: &str) -> Result<ErasedCustomerData, HttpError> {
+        let transaction = self.connection.begin().await?;
+
+        // Locked, so that no expenses are added to the customer while they are erased
+        let erased_customer = Customer::find_by_id(customer_id)
+            .lock_exclusive()
+            .one(&transaction)
+            .await?
+            .ok_or_else(|| Self::get_not_found_error(customer_id))?;
+
+        let erased_customer_data =
+            delete_customers_with_data(&transaction, slice::from_ref(&erased_customer)).await?;
+
+        transaction.commit().await?;
+
+        Ok(erased_customer_data)
+    }
+}
@@ -0,0 +1,242 @@
+use chrono::Utc;
+use futures_util::future::try_join4;
+use serde::Serialize;
+use std::io::{Cursor, Write};
+use std::sync::Arc;
+use zip::ZipWriter;
+use zip::write::SimpleFileOptions;
+
+use crate::api::customer_data::dto::create_erasure_request_db_dto::CreateErasureRequestDbDto;
+use crate::api::customer_data::dto::find_erasure_requests_dto::FindErasureRequestsDto;
+use crate::api::customer_data::entities::customer_data_entity::CustomerDataEntity;
+use crate::api::customer_data::entities::erasure_request_entity::ErasureRequestEntity;
+use crate::api::customer_data::entities::exported_customer_data_entity::ExportedCustomerDataEntity;
+use crate::api::customer_data::traits::customer_data_repository::CustomerDataRepositoryTrait;
+use crate::api::customer_data::traits::erasure_requests_repository::ErasureRequestsRepositoryTrait;
+use crate::api::customer_data::types::customer_data_format::CustomerDataFormat;
+use crate::api::customer_data::types::erasure_status::ErasureStatus;
+use crate::api::customers::customers_service::CustomersService;
+use crate::shared::errors::http_error::HttpError;
+use crate::shared::modules::audit::structs::audit_context::AuditContext;
+use crate::shared::modules::cache::AppCacheService;
+use crate::shared::modules::cache::structs::cache_generation::CacheGeneration;
+use crate::shared::modules::db::unit_of_work::UnitOfWork;
+use crate::shared::modules::db::utils::generate_id;
+
+/// Data portability and erasure of customers' data
+#[derive(Clone)]
+pub struct CustomerDataService {
+    customer_data_repository: Arc<dyn CustomerDataRepositoryTrait + Send + Sync>,
+    erasure_requests_repository: Arc<dyn ErasureRequestsRepositoryTrait + Send + Sync>,
+    customers_service: Arc<CustomersService>,
+    unit_of_work: Arc<UnitOfWork>,
+    cache_generation: CacheGeneration<AppCacheService>,
+}
+
+impl CustomerDataService {
+    pub fn new(
+        customer_data_repository: Arc<dyn CustomerDataRepositoryTrait + Send + Sync>,
+        erasure_requests_repository: Arc<dyn ErasureRequestsRepositoryTrait + Send + Sync>,
+        customers_service: Arc<CustomersService>,
+        unit_of_work: Arc<UnitOfWork>,
+        cache_generation: CacheGeneration<AppCacheService>,
+    ) -> Self {
+        Self {
+            customer_data_repository,
+            erasure_requests_repository,
+            customers_service,
+            unit_of_work,
+            cache_generation,
+        }
+    }
+
+    pub async fn export(
+        &self,
+        user_id: &str,
+        format: CustomerDataFormat,
+    ) -> Result<ExportedCustomerDataEntity, HttpError> {
+        let customer = self.customers_service.find_one_by_user_id(user_id).await?;
+
+        let (expenses, regular_payments, categories, category_rules) = try_join4(
+            self.customer_data_repository.find_expenses(&customer.id),
+            self.customer_data_repository
+                .find_regular_payments(&customer.id),
+            self.customer_data_repository.find_categories(&customer.id),
+            self.customer_data_repository
+                .find_category_rules(&customer.id),
+        )
+        .await?;
+
+        let customer_data = CustomerDataEntity {
+            exported_at: Utc::now().fixed_offset(),
+            profile: customer,
+            expenses: expenses.into_iter().map(Into::into).collect(),
+            regular_payments: regular_payments.into_iter().map(Into::into).collect(),
+            categories: categories.into_iter().map(Into::into).collect(),
+            category_rules: category_rules.into_iter().map(Into::into).collect(),
+        };
+
+        let exported_customer_data = match format {
+            CustomerDataFormat::Json => ExportedCustomerDataEntity::Json(Box::new(customer_data)),
+            CustomerDataFormat::Zip => {
+                // Compression is CPU-bound, large exports would block the runtime
+                let zip = tokio::task::spawn_blocking(move || Self::write_zip(&customer_data))
+                    .await
+                    .map_err(|err| {
+                        HttpError::Internal(format!("Customer data export is failed: {err}"))
+                    })??;
+
+                ExportedCustomerDataEntity::Zip(zip)
+            }
+        };
+
+        Ok(exported_customer_data)
+    }
+
+    pub async fn erase_as_customer(
+        &self,
+        user_id: &str,
+        audit_context: &AuditContext,
+    ) -> Result<ErasureRequestEntity, HttpError> {
+        let customer = self.customers_service.find_one_by_user_id(user_id).await?;
+
+        self.erase(&customer.id, user_id, audit_context).await
+    }
+
+    /// Customers in the trash can be erased too
+    pub async fn erase_as_admin(
+        &self,
+        customer_id: &str,
+        audit_context: &AuditContext,
+    ) -> Result<ErasureRequestEntity, HttpError> {
+        let customer = self
+            .customer_data_repository
+            .find_customer(customer_id)
+            .await?;
+
+        self.erase(customer_id, &customer.user_id, audit_context)
+            .await
+    }
+
+    pub async fn find_erasure_requests(
+        &self,
+        filter: FindErasureRequestsDto,
+    ) -> Result<Vec<ErasureRequestEntity>, HttpError> {
+        let erasure_request_entities = self
+            .erasure_requests_repository
+            .find_many(filter)
+            .await?
+            .into_iter()
+            .map(ErasureRequestEntity::from)
+            .collect();
+
+        Ok(erasure_request_entities)
+    }
+
+    /// The request is recorded before the erasure, so that a failed erasure is tracked too.
+    /// The data is erased and the request is completed in one transaction.
+    /// Cached responses of the customer are dropped along with the data
+    async fn erase(
+        &self,
+        customer_id: &str,
+        user_id: &str,
+        audit_context: &AuditContext,
+    ) -> Result<ErasureRequestEntity, HttpError> {
+        let erasure_request = self
+            .erasure_requests_repository
+            .create(CreateErasureRequestDbDto {
+                id: generate_id(),
+                customer_id: customer_id.into(),
+                status: ErasureStatus::Pending,
+                requested_by: Self::get_requested_by(audit_context),
+                request_id: audit_context.request_id.clone(),
+            })
+            .await?;
+
+        let erase_result = self
+            .unit_of_work
+            .run(|connection| async {
+                let customer_data_repository = self
+                    .customer_data_repository
+                    .with_connection(connection.clone());
+                let erasure_requests_repository =
+                    self.erasure_requests_repository.with_connection(connection);
+
+                let erased_data = customer_data_repository.erase(customer_id).await?;
+
+                erasure_requests_repository
+                    .complete(&erasure_request.id, erased_data)
+                    .await
+            })
+            .await;
+
+        match erase_result {
+            Ok(completed_request) => {
+                self.cache_generation.bump(user_id).await;
+
+                Ok(completed_request.into())
+            }
+            Err(err) => {
+                tracing::error!("Erasure of customer '{customer_id}' is failed with err: '{err}'");
+                self.erasure_requests_repository
+                    .fail(&erasure_request.id, &err.to_string())
+                    .await?;
+
+                Err(err)
+            }
+        }
+    }
+
+    /// Admin who requested the erasure, customers' own ids are not kept after the erasure
+    fn get_requested_by(audit_context: &AuditContext) -> Option<String> {
+        audit_context.impersonated_by.clone().or_else(|| {
+            audit_context
+                .actor_id
+                .clone()
+                .filter(|_| audit_context.actor_is_admin)
+        })
+    }
+
+    fn write_zip(customer_data: &CustomerDataEntity) -> Result<Vec<u8>, HttpError> {
+        let mut zip_writer = ZipWriter::new(Cursor::new(Vec::new()));
+
+        Self::write_zip_file(&mut zip_writer, "profile.json", &customer_data.profile)?;
+        Self::write_zip_file(&mut zip_writer, "expenses.json", &customer_data.expenses)?;
+        Self::write_zip_file(
+            &mut zip_writer,
+            "regular_payments.json",
+            &customer_data.regular_payments,
+        )?;
+        Self::write_zip_file(
+            &mut zip_writer,
+            "categories.json",
+            &customer_data.categories,
+        )?;
+        Self::write_zip_file(
+            &mut zip_writer,
+            "category_rules.json",
+            &customer_data.category_rules,
+        )?;
+
+        let archive = zip_writer
+            .finish()
+            .map_err(|err| HttpError::Internal(format!("Failed to write ZIP archive: {err}")))?;
+
+        Ok(archive.into_inner())
+    }
+
+    fn write_zip_file(
+        zip_writer: &mut ZipWriter<Cursor<Vec<u8>>>,
+        file_name: &str,
+        content: &impl Serialize,
+    ) -> Result<(), HttpError> {
+        zip_writer
+            .start_file(file_name, SimpleFileOptions::default())
+            .map_err(|err| HttpError::Internal(format!("Failed to write ZIP archive: {err}")))?;
+        zip_writer
+            .write_all(&serde_json::to_vec_pretty(content)?)
+            .map_err(|err| HttpError::Internal(format!("Failed to write ZIP archive: {err}")))?;
+
+        Ok(())
+    }
+}
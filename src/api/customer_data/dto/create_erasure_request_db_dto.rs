@@ -0,0 +1,14 @@
+use sea_orm::DeriveIntoActiveModel;
+use serde::{Deserialize, Serialize};
+
+use crate::api::customer_data::types::erasure_status::ErasureStatus;
+use crate::shared::modules::db::entities::erasure_request::ActiveModel;
+
+#[derive(Serialize, Deserialize, Debug, DeriveIntoActiveModel)]
+pub struct CreateErasureRequestDbDto {
+    pub id: String,
+    pub customer_id: String,
+    pub status: ErasureStatus,
+    pub requested_by: Option<String>,
+    pub request_id: Option<String>,
+}
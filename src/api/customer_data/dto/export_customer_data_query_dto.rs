@@ -0,0 +1,11 @@
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+use crate::api::customer_data::types::customer_data_format::CustomerDataFormat;
+
+#[derive(Debug, Deserialize, Serialize, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct ExportCustomerDataQueryDto {
+    #[serde(default)]
+    pub format: CustomerDataFormat,
+}
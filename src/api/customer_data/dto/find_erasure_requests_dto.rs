@@ -0,0 +1,16 @@
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+use crate::api::customer_data::types::erasure_status::ErasureStatus;
+
+#[derive(Debug, Deserialize, Serialize, Default, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct FindErasureRequestsDto {
+    pub customer_id: Option<String>,
+    pub status: Option<ErasureStatus>,
+    /// Inclusive bounds of the request time
+    pub created_from: Option<chrono::DateTime<chrono::FixedOffset>>,
+    pub created_to: Option<chrono::DateTime<chrono::FixedOffset>>,
+    /// Latest requests are returned first, 100 by default and at most 1000
+    pub limit: Option<u64>,
+}
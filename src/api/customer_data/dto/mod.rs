@@ -0,0 +1,3 @@
+pub mod create_erasure_request_db_dto;
+pub mod export_customer_data_query_dto;
+pub mod find_erasure_requests_dto;
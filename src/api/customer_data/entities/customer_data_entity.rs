@@ -0,0 +1,22 @@
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+use crate::api::categories::entities::category_entity::CategoryEntity;
+use crate::api::category_rules::entities::category_rule_entity::CategoryRuleEntity;
+use crate::api::customer_data::entities::regular_payment_entity::RegularPaymentEntity;
+use crate::api::customers::entities::customer_entity::CustomerEntity;
+use crate::api::expenses::entities::expense_entity::ExpenseEntity;
+
+/// All the data stored about a customer
+#[derive(Serialize, Deserialize, Debug, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct CustomerDataEntity {
+    pub exported_at: chrono::DateTime<chrono::FixedOffset>,
+    pub profile: CustomerEntity,
+    /// Including the ones in the trash
+    pub expenses: Vec<ExpenseEntity>,
+    pub regular_payments: Vec<RegularPaymentEntity>,
+    /// Custom categories, including the archived ones
+    pub categories: Vec<CategoryEntity>,
+    pub category_rules: Vec<CategoryRuleEntity>,
+}
@@ -0,0 +1,45 @@
+use aide::OperationIo;
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+use crate::api::customer_data::types::erasure_request_from_db::ErasureRequestFromDb;
+use crate::api::customer_data::types::erasure_status::ErasureStatus;
+
+#[derive(Serialize, Deserialize, Debug, JsonSchema, OperationIo)]
+#[serde(rename_all = "camelCase")]
+pub struct ErasureRequestEntity {
+    pub id: String,
+    /// Id of the erased customer, the customer itself does not exist after the erasure
+    pub customer_id: String,
+    pub status: ErasureStatus,
+    /// Admin who requested the erasure, empty if the customer requested it
+    pub requested_by: Option<String>,
+    /// `x-request-id` of the request which requested the erasure
+    pub request_id: Option<String>,
+    /// Why the erasure failed
+    pub error: Option<String>,
+    pub erased_expenses: i64,
+    pub erased_regular_payments: i64,
+    /// Audit log entries of the customer which changes were cleared
+    pub anonymized_audit_logs: i64,
+    pub created_at: chrono::DateTime<chrono::FixedOffset>,
+    pub completed_at: Option<chrono::DateTime<chrono::FixedOffset>>,
+}
+
+impl From<ErasureRequestFromDb> for ErasureRequestEntity {
+    fn from(value: ErasureRequestFromDb) -> Self {
+        Self {
+            id: value.id,
+            customer_id: value.customer_id,
+            status: value.status,
+            requested_by: value.requested_by,
+            request_id: value.request_id,
+            error: value.error,
+            erased_expenses: value.erased_expenses,
+            erased_regular_payments: value.erased_regular_payments,
+            anonymized_audit_logs: value.anonymized_audit_logs,
+            created_at: value.created_at,
+            completed_at: value.completed_at,
+        }
+    }
+}
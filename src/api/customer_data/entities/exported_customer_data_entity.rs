@@ -0,0 +1,67 @@
+use aide::OperationOutput;
+use aide::generate::GenContext;
+use aide::openapi::{MediaType, Operation, Response as OpenApiResponse};
+use axum::Json;
+use axum::http::{StatusCode, header};
+use axum::response::{IntoResponse, Response};
+
+use crate::api::customer_data::entities::customer_data_entity::CustomerDataEntity;
+
+const ZIP_CONTENT_TYPE: &str = "application/zip";
+
+/// Customer data as an attachment, so that browsers download it
+pub enum ExportedCustomerDataEntity {
+    Json(Box<CustomerDataEntity>),
+    Zip(Vec<u8>),
+}
+
+impl IntoResponse for ExportedCustomerDataEntity {
+    fn into_response(self) -> Response {
+        match self {
+            Self::Json(customer_data) => (
+                [(
+                    header::CONTENT_DISPOSITION,
+                    "attachment; filename=\"customer-data.json\"",
+                )],
+                Json(*customer_data),
+            )
+                .into_response(),
+            Self::Zip(archive) => (
+                [
+                    (header::CONTENT_TYPE, ZIP_CONTENT_TYPE),
+                    (
+                        header::CONTENT_DISPOSITION,
+                        "attachment; filename=\"customer-data.zip\"",
+                    ),
+                ],
+                archive,
+            )
+                .into_response(),
+        }
+    }
+}
+
+impl OperationOutput for ExportedCustomerDataEntity {
+    type Inner = Self;
+
+    fn operation_response(
+        ctx: &mut GenContext,
+        operation: &mut Operation,
+    ) -> Option<OpenApiResponse> {
+        let mut response = Json::<CustomerDataEntity>::operation_response(ctx, operation)?;
+        response
+            .content
+            .insert(ZIP_CONTENT_TYPE.into(), MediaType::default());
+
+        Some(response)
+    }
+
+    fn inferred_responses(
+        ctx: &mut GenContext,
+        operation: &mut Operation,
+    ) -> Vec<(Option<u16>, OpenApiResponse)> {
+        Self::operation_response(ctx, operation)
+            .map(|response| vec![(Some(StatusCode::OK.as_u16()), response)])
+            .unwrap_or_default()
+    }
+}
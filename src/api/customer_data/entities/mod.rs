@@ -0,0 +1,4 @@
+pub mod customer_data_entity;
+pub mod erasure_request_entity;
+pub mod exported_customer_data_entity;
+pub mod regular_payment_entity;
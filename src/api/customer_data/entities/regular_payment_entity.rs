@@ -0,0 +1,29 @@
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+use crate::api::customer_data::types::regular_payment_from_db::RegularPaymentFromDb;
+use crate::api::expenses::types::expense_category::ExpenseCategory;
+
+#[derive(Serialize, Deserialize, Debug, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct RegularPaymentEntity {
+    pub id: String,
+    pub amount: f64,
+    pub category: ExpenseCategory,
+    pub date_of_charge: chrono::DateTime<chrono::FixedOffset>,
+    pub created_at: chrono::DateTime<chrono::FixedOffset>,
+    pub updated_at: chrono::DateTime<chrono::FixedOffset>,
+}
+
+impl From<RegularPaymentFromDb> for RegularPaymentEntity {
+    fn from(value: RegularPaymentFromDb) -> Self {
+        Self {
+            id: value.id,
+            amount: value.amount,
+            category: value.category,
+            date_of_charge: value.date_of_charge,
+            created_at: value.created_at,
+            updated_at: value.updated_at,
+        }
+    }
+}
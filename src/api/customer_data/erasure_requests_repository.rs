@@ -0,0 +1,158 @@
+use async_trait::async_trait;
+use sea_orm::sea_query::Expr;
+use sea_orm::{
+    ColumnTrait, Condition, DatabaseConnection, EntityTrait, IntoActiveModel, QueryFilter,
+    QueryOrder, QuerySelect,
+};
+use std::sync::Arc;
+
+use crate::api::customer_data::dto::create_erasure_request_db_dto::CreateErasureRequestDbDto;
+use crate::api::customer_data::dto::find_erasure_requests_dto::FindErasureRequestsDto;
+use crate::api::customer_data::traits::erasure_requests_repository::ErasureRequestsRepositoryTrait;
+use crate::api::customer_data::types::erased_customer_data::ErasedCustomerData;
+use crate::api::customer_data::types::erasure_request_from_db::ErasureRequestFromDb;
+use crate::shared::errors::http_error::HttpError;
+use crate::shared::modules::db::db_connection::DbConnection;
+use crate::shared::modules::db::entities::erasure_request;
+use crate::shared::modules::db::entities::prelude::ErasureRequest;
+
+const DEFAULT_LIMIT: u64 = 100;
+const MAX_LIMIT: u64 = 1000;
+
+pub struct ErasureRequestsRepository {
+    connection: DbConnection,
+}
+
+impl ErasureRequestsRepository {
+    pub fn new(sea_orm_client: Arc<DatabaseConnection>) -> Self {
+        Self {
+            connection: DbConnection::Pool(sea_orm_client),
+        }
+    }
+
+    fn get_not_found_error(id: &str) -> HttpError {
+        HttpError::NotFound(format!("Erasure request with id '{id}' was not found"))
+    }
+
+    fn get_filter_condition(filter: &FindErasureRequestsDto) -> Condition {
+        Condition::all()
+            .add_option(
+                filter
+                    .customer_id
+                    .clone()
+                    .map(|customer_id| erasure_request::Column::CustomerId.eq(customer_id)),
+            )
+            .add_option(filter.status.map(|status| {
+                erasure_request::Column::Status.eq(erasure_request::ErasureStatus::from(status))
+            }))
+            .add_option(
+                filter
+                    .created_from
+                    .map(|created_from| erasure_request::Column::CreatedAt.gte(created_from)),
+            )
+            .add_option(
+                filter
+                    .created_to
+                    .map(|created_to| erasure_request::Column::CreatedAt.lte(created_to)),
+            )
+    }
+
+    /// Moves the pending request to its final status
+    async fn finish(
+        &self,
+        id: &str,
+        update: sea_orm::UpdateMany<ErasureRequest>,
+    ) -> Result<ErasureRequestFromDb, HttpError> {
+        let finished_request = update
+            .col_expr(
+                erasure_request::Column::CompletedAt,
+                Expr::current_timestamp().into(),
+            )
+            .filter(erasure_request::Column::Id.eq(id))
+            .filter(erasure_request::Column::Status.eq(erasure_request::ErasureStatus::Pending))
+            .exec_with_returning(&self.connection)
+            .await?
+            .pop()
+            .ok_or_else(|| Self::get_not_found_error(id))?;
+
+        Ok(finished_request.into())
+    }
+}
+
+#[async_trait]
+impl ErasureRequestsRepositoryTrait for ErasureRequestsRepository {
+    fn with_connection(
+        &self,
+        connection: DbConnection,
+    ) -> Arc<dyn ErasureRequestsRepositoryTrait + Send + Sync> {
+        Arc::new(Self { connection })
+    }
+
+    async fn create(
+        &self,
+        create_dto: CreateErasureRequestDbDto,
+    ) -> Result<ErasureRequestFromDb, HttpError> {
+        let created_request = ErasureRequest::insert(create_dto.into_active_model())
+            .exec_with_returning(&self.connection)
+            .await?;
+
+        Ok(created_request.into())
+    }
+
+    async fn complete(
+        &self,
+        id: &str,
+        erased_data: ErasedCustomerData,
+    ) -> Result<ErasureRequestFromDb, HttpError> {
+        let update = ErasureRequest::update_many()
+            .col_expr(
+                erasure_request::Column::Status,
+                Expr::value(erasure_request::ErasureStatus::Completed),
+            )
+            .col_expr(
+                erasure_request::Column::ErasedExpenses,
+                Expr::value(erased_data.expenses as i64),
+            )
+            .col_expr(
+                erasure_request::Column::ErasedRegularPayments,
+                Expr::value(erased_data.regular_payments as i64),
+            )
+            .col_expr(
+                erasure_request::Column::AnonymizedAuditLogs,
+                Expr::value(erased_data.audit_logs as i64),
+            );
+
+        self.finish(id, update).await
+    }
+
+    async fn fail(&self, id: &str, error: &str) -> Result<ErasureRequestFromDb, HttpError> {
+        let update = ErasureRequest::update_many()
+            .col_expr(
+                erasure_request::Column::Status,
+                Expr::value(erasure_request::ErasureStatus::Failed),
+            )
+            .col_expr(erasure_request::Column::Error, Expr::value(error));
+
+        self.finish(id, update).await
+    }
+
+    async fn find_many(
+        &self,
+        filter: FindErasureRequestsDto,
+    ) -> Result<Vec<ErasureRequestFromDb>, HttpError> {
+        let limit = filter.limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT);
+
+        let found_requests = ErasureRequest::find()
+            .filter(Self::get_filter_condition(&filter))
+            .order_by_desc(erasure_request::Column::CreatedAt)
+            .order_by_desc(erasure_request::Column::Id)
+            .limit(limit)
+            .all(&self.connection)
+            .await?
+            .into_iter()
+            .map(Into::into)
+            .collect();
+
+        Ok(found_requests)
+    }
+}
@@ -0,0 +1,91 @@
+use aide::axum::ApiRouter;
+use aide::axum::routing::{get, post};
+use sea_orm::DatabaseConnection;
+use std::sync::Arc;
+
+use crate::api::audit_logs::audit_logs_repository::AuditLogsRepository;
+use crate::api::audit_logs::audit_logs_service::AuditLogsService;
+use crate::api::customer_data::customer_data_repository::CustomerDataRepository;
+use crate::api::customer_data::customer_data_service::CustomerDataService;
+use crate::api::customer_data::erasure_requests_repository::ErasureRequestsRepository;
+use crate::api::customer_data::types::api_state::CustomerDataApiState;
+use crate::api::customers::customers_repository::CustomerRepository;
+use crate::api::customers::customers_service::CustomersService;
+use crate::shared::modules::auth::enums::roles::Roles;
+use crate::shared::modules::auth::middlewares::role_based_bearer_auth::AuthLayer;
+use crate::shared::modules::auth::services::auth0::Auth0Service;
+use crate::shared::modules::cache::AppCacheService;
+use crate::shared::modules::cache::structs::cache_generation::CacheGeneration;
+use crate::shared::modules::db::unit_of_work::UnitOfWork;
+
+pub mod dto;
+pub mod entities;
+pub mod traits;
+pub mod types;
+pub mod utils;
+
+mod customer_data_handlers;
+pub mod customer_data_repository;
+pub mod customer_data_service;
+pub mod erasure_requests_repository;
+
+pub fn get_router(
+    sea_orm_client: Arc<DatabaseConnection>,
+    cache_service: Arc<AppCacheService>,
+    auth_service: Arc<Auth0Service>,
+) -> ApiRouter {
+    let audit_logs_repository = Arc::new(AuditLogsRepository::new(sea_orm_client.clone()));
+    let audit_logs_service = Arc::new(AuditLogsService::new(audit_logs_repository));
+
+    let unit_of_work = Arc::new(UnitOfWork::new(sea_orm_client.clone()));
+    let customers_repository = Arc::new(CustomerRepository::new(sea_orm_client.clone()));
+    let customers_service = Arc::new(CustomersService::new(
+        customers_repository,
+        audit_logs_service,
+        unit_of_work.clone(),
+    ));
+
+    let customer_data_repository = Arc::new(CustomerDataRepository::new(sea_orm_client.clone()));
+    let erasure_requests_repository = Arc::new(ErasureRequestsRepository::new(sea_orm_client));
+    let customer_data_service = Arc::new(CustomerDataService::new(
+        customer_data_repository,
+        erasure_requests_repository,
+        customers_service.clone(),
+        unit_of_work,
+        CacheGeneration::new(cache_service),
+    ));
+
+    let api_state = CustomerDataApiState {
+        customer_data_service,
+    };
+
+    let auth_layer = AuthLayer::new(auth_service, customers_service);
+
+    let customers_routes = ApiRouter::new()
+        .api_route(
+            "/self/export",
+            get(customer_data_handlers::export_self)
+                .route_layer(auth_layer.verify(vec![Roles::Customer])),
+        )
+        .api_route(
+            "/self/erasure",
+            post(customer_data_handlers::erase_self)
+                .route_layer(auth_layer.verify(vec![Roles::Customer])),
+        )
+        .api_route(
+            "/{id}/erasure",
+            post(customer_data_handlers::erase_one)
+                .route_layer(auth_layer.verify(vec![Roles::Admin])),
+        );
+
+    let erasure_requests_routes = ApiRouter::new().api_route(
+        "/",
+        get(customer_data_handlers::find_erasure_requests)
+            .route_layer(auth_layer.verify(vec![Roles::Admin])),
+    );
+
+    ApiRouter::new()
+        .nest("/customers", customers_routes)
+        .nest("/erasure-requests", erasure_requests_routes)
+        .with_state(api_state)
+}
@@ -0,0 +1,44 @@
+use async_trait::async_trait;
+use std::sync::Arc;
+
+use crate::api::categories::types::category_from_db::CategoryFromDb;
+use crate::api::category_rules::types::category_rule_from_db::CategoryRuleFromDb;
+use crate::api::customer_data::types::erased_customer_data::ErasedCustomerData;
+use crate::api::customer_data::types::regular_payment_from_db::RegularPaymentFromDb;
+use crate::api::customers::types::customer_from_db::CustomerFromDb;
+use crate::api::expenses::types::expense_from_db::ExpenseFromDb;
+use crate::shared::errors::http_error::HttpError;
+use crate::shared::modules::db::db_connection::DbConnection;
+
+#[async_trait]
+pub trait CustomerDataRepositoryTrait {
+    /// Repository which queries run on the connection, e.g. a unit of work transaction
+    fn with_connection(
+        &self,
+        connection: DbConnection,
+    ) -> Arc<dyn CustomerDataRepositoryTrait + Send + Sync>;
+
+    /// Customer including the one in the trash
+    async fn find_customer(&self, id: &str) -> Result<CustomerFromDb, HttpError>;
+
+    /// Expenses of the customer including the ones in the trash
+    async fn find_expenses(&self, customer_id: &str) -> Result<Vec<ExpenseFromDb>, HttpError>;
+
+    async fn find_regular_payments(
+        &self,
+        customer_id: &str,
+    ) -> Result<Vec<RegularPaymentFromDb>, HttpError>;
+
+    /// Custom categories of the customer
+    async fn find_categories(&self, customer_id: &str) -> Result<Vec<CategoryFromDb>, HttpError>;
+
+    /// Categorization rules of the customer in the order they are evaluated
+    async fn find_category_rules(
+        &self,
+        customer_id: &str,
+    ) -> Result<Vec<CategoryRuleFromDb>, HttpError>;
+
+    /// Deletes the customer along with their expenses, regular payments, custom categories
+    /// and categorization rules and clears their personal data in the audit log
+    async fn erase(&self, customer_id: &str) -> Result<ErasedCustomerData, HttpError>;
+}
@@ -0,0 +1,36 @@
+use async_trait::async_trait;
+use std::sync::Arc;
+
+use crate::api::customer_data::dto::create_erasure_request_db_dto::CreateErasureRequestDbDto;
+use crate::api::customer_data::dto::find_erasure_requests_dto::FindErasureRequestsDto;
+use crate::api::customer_data::types::erased_customer_data::ErasedCustomerData;
+use crate::api::customer_data::types::erasure_request_from_db::ErasureRequestFromDb;
+use crate::shared::errors::http_error::HttpError;
+use crate::shared::modules::db::db_connection::DbConnection;
+
+#[async_trait]
+pub trait ErasureRequestsRepositoryTrait {
+    /// Repository which queries run on the connection, e.g. a unit of work transaction
+    fn with_connection(
+        &self,
+        connection: DbConnection,
+    ) -> Arc<dyn ErasureRequestsRepositoryTrait + Send + Sync>;
+
+    async fn create(
+        &self,
+        create_dto: CreateErasureRequestDbDto,
+    ) -> Result<ErasureRequestFromDb, HttpError>;
+
+    async fn complete(
+        &self,
+        id: &str,
+        erased_data: ErasedCustomerData,
+    ) -> Result<ErasureRequestFromDb, HttpError>;
+
+    async fn fail(&self, id: &str, error: &str) -> Result<ErasureRequestFromDb, HttpError>;
+
+    async fn find_many(
+        &self,
+        filter: FindErasureRequestsDto,
+    ) -> Result<Vec<ErasureRequestFromDb>, HttpError>;
+}
@@ -0,0 +1,2 @@
+pub mod customer_data_repository;
+pub mod erasure_requests_repository;
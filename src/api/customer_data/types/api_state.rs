@@ -0,0 +1,15 @@
+use axum::extract::FromRef;
+use std::sync::Arc;
+
+use crate::api::customer_data::customer_data_service::CustomerDataService;
+
+#[derive(Clone)]
+pub struct CustomerDataApiState {
+    pub customer_data_service: Arc<CustomerDataService>,
+}
+
+impl FromRef<CustomerDataApiState> for Arc<CustomerDataService> {
+    fn from_ref(app_state: &CustomerDataApiState) -> Arc<CustomerDataService> {
+        app_state.customer_data_service.clone()
+    }
+}
@@ -0,0 +1,12 @@
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq, Default, JsonSchema)]
+#[serde(rename_all = "lowercase")]
+pub enum CustomerDataFormat {
+    /// One JSON document
+    #[default]
+    Json,
+    /// ZIP archive with a JSON file per kind of data
+    Zip,
+}
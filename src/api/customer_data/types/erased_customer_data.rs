@@ -0,0 +1,7 @@
+/// Amounts of rows an erasure deleted or anonymized
+#[derive(Debug, Clone, Copy, Default)]
+pub struct ErasedCustomerData {
+    pub expenses: u64,
+    pub regular_payments: u64,
+    pub audit_logs: u64,
+}
@@ -0,0 +1,37 @@
+use serde::{Deserialize, Serialize};
+
+use crate::api::customer_data::types::erasure_status::ErasureStatus;
+use crate::shared::modules::db::entities::erasure_request;
+
+#[derive(Serialize, Deserialize, Debug)]
+pub struct ErasureRequestFromDb {
+    pub id: String,
+    pub customer_id: String,
+    pub status: ErasureStatus,
+    pub requested_by: Option<String>,
+    pub request_id: Option<String>,
+    pub error: Option<String>,
+    pub erased_expenses: i64,
+    pub erased_regular_payments: i64,
+    pub anonymized_audit_logs: i64,
+    pub created_at: chrono::DateTime<chrono::FixedOffset>,
+    pub completed_at: Option<chrono::DateTime<chrono::FixedOffset>>,
+}
+
+impl From<erasure_request::Model> for ErasureRequestFromDb {
+    fn from(value: erasure_request::Model) -> Self {
+        Self {
+            id: value.id,
+            customer_id: value.customer_id,
+            status: value.status.into(),
+            requested_by: value.requested_by,
+            request_id: value.request_id,
+            error: value.error,
+            erased_expenses: value.erased_expenses,
+            erased_regular_payments: value.erased_regular_payments,
+            anonymized_audit_logs: value.anonymized_audit_logs,
+            created_at: value.created_at,
+            completed_at: value.completed_at,
+        }
+    }
+}
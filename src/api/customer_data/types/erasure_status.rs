@@ -0,0 +1,41 @@
+use schemars::JsonSchema;
+use sea_orm::{ActiveValue, IntoActiveValue};
+use serde::{Deserialize, Serialize};
+
+use crate::shared::modules::db::entities::erasure_request;
+
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq, JsonSchema)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum ErasureStatus {
+    /// The erasure is in progress
+    Pending,
+    Completed,
+    /// The erasure was rolled back, see `error`
+    Failed,
+}
+
+impl From<erasure_request::ErasureStatus> for ErasureStatus {
+    fn from(value: erasure_request::ErasureStatus) -> Self {
+        match value {
+            erasure_request::ErasureStatus::Pending => Self::Pending,
+            erasure_request::ErasureStatus::Completed => Self::Completed,
+            erasure_request::ErasureStatus::Failed => Self::Failed,
+        }
+    }
+}
+
+impl From<ErasureStatus> for erasure_request::ErasureStatus {
+    fn from(value: ErasureStatus) -> Self {
+        match value {
+            ErasureStatus::Pending => Self::Pending,
+            ErasureStatus::Completed => Self::Completed,
+            ErasureStatus::Failed => Self::Failed,
+        }
+    }
+}
+
+impl IntoActiveValue<erasure_request::ErasureStatus> for ErasureStatus {
+    fn into_active_value(self) -> ActiveValue<erasure_request::ErasureStatus> {
+        ActiveValue::Set(self.into())
+    }
+}
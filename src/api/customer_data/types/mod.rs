@@ -0,0 +1,6 @@
+pub mod api_state;
+pub mod customer_data_format;
+pub mod erased_customer_data;
+pub mod erasure_request_from_db;
+pub mod erasure_status;
+pub mod regular_payment_from_db;
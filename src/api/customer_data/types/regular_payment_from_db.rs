@@ -0,0 +1,27 @@
+use serde::{Deserialize, Serialize};
+
+use crate::api::expenses::types::expense_category::ExpenseCategory;
+use crate::shared::modules::db::entities::regular_payment;
+
+#[derive(Serialize, Deserialize, Debug)]
+pub struct RegularPaymentFromDb {
+    pub id: String,
+    pub amount: f64,
+    pub category: ExpenseCategory,
+    pub date_of_charge: chrono::DateTime<chrono::FixedOffset>,
+    pub created_at: chrono::DateTime<chrono::FixedOffset>,
+    pub updated_at: chrono::DateTime<chrono::FixedOffset>,
+}
+
+impl From<regular_payment::Model> for RegularPaymentFromDb {
+    fn from(value: regular_payment::Model) -> Self {
+        Self {
+            id: value.id,
+            amount: value.amount.try_into().unwrap_or_default(),
+            category: value.category.into(),
+            date_of_charge: value.date_of_charge,
+            created_at: value.created_at,
+            updated_at: value.updated_at,
+        }
+    }
+}
@@ -0,0 +1,85 @@
+use sea_orm::sea_query::Expr;
+use sea_orm::{ColumnTrait, ConnectionTrait, EntityTrait, QueryFilter};
+use serde_json::json;
+
+use crate::api::customer_data::types::erased_customer_data::ErasedCustomerData;
+use crate::shared::errors::http_error::HttpError;
+use crate::shared::modules::db::entities::prelude::{
+    AuditLog, Category, CategoryRule, Customer, Expense, RegularPayment,
+};
+use crate::shared::modules::db::entities::{
+    audit_log, category, category_rule, customer, expense, regular_payment,
+};
+
+/// Hard-deletes the customers along with all of their data, has to run in a transaction.
+/// Audit log entries stay for accountability, but their changes of the customers' data
+/// are emptied and the customers are no longer their actors
+pub async fn delete_customers_with_data<C>(
+    connection: &C,
+    customers: &[customer::Model],
+) -> Result<ErasedCustomerData, HttpError>
+where
+    C: ConnectionTrait,
+{
+    let customer_ids: Vec<&str> = customers
+        .iter()
+        .map(|customer| customer.id.as_str())
+        .collect();
+    let user_ids: Vec<&str> = customers
+        .iter()
+        .map(|customer| customer.user_id.as_str())
+        .collect();
+
+    // Dependent rows go first, the foreign keys restrict deleting customers with them
+    let deleted_expenses = Expense::delete_many()
+        .filter(expense::Column::CustomerId.is_in(customer_ids.clone()))
+        .exec(connection)
+        .await?;
+    let deleted_regular_payments = RegularPayment::delete_many()
+        .filter(regular_payment::Column::CustomerId.is_in(customer_ids.clone()))
+        .exec(connection)
+        .await?;
+    // Rules refer to the categories
+    CategoryRule::delete_many()
+        .filter(category_rule::Column::CustomerId.is_in(customer_ids.clone()))
+        .exec(connection)
+        .await?;
+    // Custom categories are nested into each other, so they are detached first
+    Category::update_many()
+        .col_expr(
+            category::Column::ParentId,
+            Expr::value(Option::<String>::None),
+        )
+        .filter(category::Column::CustomerId.is_in(customer_ids.clone()))
+        .exec(connection)
+        .await?;
+    Category::delete_many()
+        .filter(category::Column::CustomerId.is_in(customer_ids.clone()))
+        .exec(connection)
+        .await?;
+
+    let anonymized_audit_logs = AuditLog::update_many()
+        .col_expr(audit_log::Column::Changes, Expr::value(json!({})))
+        .filter(audit_log::Column::OwnerId.is_in(customer_ids.clone()))
+        .exec(connection)
+        .await?;
+    AuditLog::update_many()
+        .col_expr(
+            audit_log::Column::ActorId,
+            Expr::value(Option::<String>::None),
+        )
+        .filter(audit_log::Column::ActorId.is_in(user_ids))
+        .exec(connection)
+        .await?;
+
+    Customer::delete_many()
+        .filter(customer::Column::Id.is_in(customer_ids))
+        .exec(connection)
+        .await?;
+
+    Ok(ErasedCustomerData {
+        expenses: deleted_expenses.rows_affected,
+        regular_payments: deleted_regular_payments.rows_affected,
+        audit_logs: anonymized_audit_logs.rows_affected,
+    })
+}
@@ -1,4 +1,4 @@
-use axum::extract::{Path, State};
+use axum::extract::State;
 use axum::{Extension, Json};
 use std::sync::Arc;
 
@@ -8,15 +8,21 @@ use crate::api::customers::{
     entities::customer_entity::CustomerEntity,
 };
 use crate::shared::errors::http_error::HttpError;
+use crate::shared::modules::audit::structs::audit_context::AuditContext;
 use crate::shared::modules::auth::structs::user::User;
+use crate::shared::modules::cache::AppCacheService;
+use crate::shared::modules::cache::structs::cache_generation::CacheGeneration;
+use crate::shared::modules::db::structs::id_path::IdPath;
+use crate::shared::modules::versioning::structs::if_match::IfMatch;
+use crate::shared::modules::versioning::structs::versioned_json::VersionedJson;
 
 pub async fn find_one(
-    Path(customer_id): Path<String>,
+    IdPath(customer_id): IdPath,
     State(customers_service): State<Arc<CustomersService>>,
 ) -> Result<CustomerEntityJson, HttpError> {
     let found_customer = customers_service.find_one_by_id(&customer_id).await?;
 
-    Ok(Json(found_customer))
+    Ok(VersionedJson(found_customer))
 }
 
 pub async fn find_one_by_user_id(
@@ -25,7 +31,7 @@ pub async fn find_one_by_user_id(
 ) -> Result<CustomerEntityJson, HttpError> {
     let found_customer = customers_service.find_one_by_user_id(&user.id).await?;
 
-    Ok(Json(found_customer))
+    Ok(VersionedJson(found_customer))
 }
 
 pub async fn find_many(
@@ -38,50 +44,94 @@ pub async fn find_many(
 
 pub async fn create(
     Extension(user): Extension<User>,
+    audit_context: AuditContext,
     State(customers_service): State<Arc<CustomersService>>,
+    State(cache_generation): State<CacheGeneration<AppCacheService>>,
     Json(create_customer_dto): Json<CreateCustomerDto>,
 ) -> Result<CustomerEntityJson, HttpError> {
     let created_customer = customers_service
-        .create(create_customer_dto, &user.id, &user.email)
+        .create(create_customer_dto, &user.id, &user.email, &audit_context)
         .await?;
 
-    Ok(Json(created_customer))
+    cache_generation.bump(&created_customer.user_id).await;
+
+    Ok(VersionedJson(created_customer))
 }
 
 pub async fn update(
     Extension(user): Extension<User>,
-    Path(customer_id): Path<String>,
+    IdPath(customer_id): IdPath,
+    IfMatch(expected_versions): IfMatch,
+    audit_context: AuditContext,
     State(customers_service): State<Arc<CustomersService>>,
+    State(cache_generation): State<CacheGeneration<AppCacheService>>,
     Json(update_customer_dto): Json<UpdateCustomerDto>,
 ) -> Result<CustomerEntityJson, HttpError> {
     let updated_customer = if user.is_admin() {
         customers_service
-            .update_as_admin(&customer_id, update_customer_dto)
+            .update_as_admin(
+                &customer_id,
+                update_customer_dto,
+                expected_versions,
+                &audit_context,
+            )
             .await?
     } else {
         customers_service
-            .update_as_customer(&customer_id, update_customer_dto, &user.id, &user.email)
+            .update_as_customer(
+                &customer_id,
+                update_customer_dto,
+                &user.id,
+                &user.email,
+                expected_versions,
+                &audit_context,
+            )
             .await?
     };
 
-    Ok(Json(updated_customer))
+    // Admins update customers of other users, so it's the customer's user cache which is dropped
+    cache_generation.bump(&updated_customer.user_id).await;
+
+    Ok(VersionedJson(updated_customer))
 }
 
 pub async fn remove(
     Extension(user): Extension<User>,
-    Path(customer_id): Path<String>,
+    IdPath(customer_id): IdPath,
+    IfMatch(expected_versions): IfMatch,
+    audit_context: AuditContext,
     State(customers_service): State<Arc<CustomersService>>,
+    State(cache_generation): State<CacheGeneration<AppCacheService>>,
 ) -> Result<CustomerEntityJson, HttpError> {
     let deleted_customer = if user.is_admin() {
-        customers_service.delete_as_admin(&customer_id).await?
+        customers_service
+            .delete_as_admin(&customer_id, expected_versions, &audit_context)
+            .await?
     } else {
         customers_service
-            .delete_as_customer(&customer_id, &user.id)
+            .delete_as_customer(&customer_id, &user.id, expected_versions, &audit_context)
             .await?
     };
 
-    Ok(Json(deleted_customer))
+    cache_generation.bump(&deleted_customer.user_id).await;
+
+    Ok(VersionedJson(deleted_customer))
+}
+
+pub async fn restore(
+    IdPath(customer_id): IdPath,
+    audit_context: AuditContext,
+    State(customers_service): State<Arc<CustomersService>>,
+    State(cache_generation): State<CacheGeneration<AppCacheService>>,
+) -> Result<CustomerEntityJson, HttpError> {
+    let restored_customer = customers_service
+        .restore_as_admin(&customer_id, &audit_context)
+        .await?;
+
+    cache_generation.bump(&restored_customer.user_id).await;
+
+    Ok(VersionedJson(restored_customer))
 }
 
-pub type CustomerEntityJson = Json<CustomerEntity>;
+pub type CustomerEntityJson = VersionedJson<CustomerEntity>;
 pub type CustomerEntitiesJson = Json<Vec<CustomerEntity>>;
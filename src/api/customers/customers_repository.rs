@@ -1,36 +1,94 @@
+use crate::api::customer_data::utils::delete_customers_with_data;
 use crate::api::customers::dto::create_customer_db_dto::CreateCustomerDbDto;
 use crate::api::customers::dto::update_customer_db_dto::UpdateCustomerDbDto;
 use crate::api::customers::traits::customers_repository::CustomersRepositoryTrait;
 use crate::api::customers::types::customer_from_db::CustomerFromDb;
 use async_trait::async_trait;
-use sea_orm::{ColumnTrait, DatabaseConnection, EntityTrait, IntoActiveModel, QueryFilter};
+use sea_orm::prelude::DateTimeWithTimeZone;
+use sea_orm::sea_query::Expr;
+use sea_orm::{
+    ColumnTrait, DatabaseConnection, DatabaseTransaction, EntityTrait, IntoActiveModel,
+    QueryFilter, QuerySelect,
+};
 use std::sync::Arc;
 
 use crate::shared::errors::http_error::HttpError;
+use crate::shared::modules::db::db_connection::DbConnection;
 use crate::shared::modules::db::entities::customer;
 use crate::shared::modules::db::entities::customer::ActiveModel as CustomerActiveModel;
 use crate::shared::modules::db::entities::prelude::Customer;
 
 #[derive(Clone)]
 pub struct CustomerRepository {
-    sea_orm_client: Arc<DatabaseConnection>,
+    connection: DbConnection,
 }
 
 impl CustomerRepository {
     pub fn new(sea_orm_client: Arc<DatabaseConnection>) -> Self {
-        Self { sea_orm_client }
+        Self {
+            connection: DbConnection::Pool(sea_orm_client),
+        }
+    }
+
+    fn get_not_found_error(id: &str) -> HttpError {
+        HttpError::NotFound(format!("Customer with id '{id}' was not found"))
+    }
+
+    /// Locks the customer until the end of the transaction and checks the write preconditions:
+    /// the customer is active or in the trash (`is_deleted`), belongs to the user (if given)
+    /// and has one of the expected versions
+    async fn lock_for_write(
+        transaction: &DatabaseTransaction,
+        id: &str,
+        user_id: Option<&str>,
+        expected_versions: Option<&[i64]>,
+        is_deleted: bool,
+    ) -> Result<customer::Model, HttpError> {
+        let found_customer = Customer::find_by_id(id)
+            .lock_exclusive()
+            .one(transaction)
+            .await?
+            .filter(|customer| {
+                customer.deleted_at.is_some() == is_deleted
+                    && user_id.is_none_or(|user_id| user_id == customer.user_id)
+            })
+            .ok_or_else(|| Self::get_not_found_error(id))?;
+
+        if expected_versions.is_some_and(|versions| !versions.contains(&found_customer.version)) {
+            return Err(HttpError::PreconditionFailed(format!(
+                "Customer with id '{id}' was modified, If-Match does not match the current version"
+            )));
+        }
+
+        Ok(found_customer)
     }
 }
 
 #[async_trait]
 impl CustomersRepositoryTrait for CustomerRepository {
+    fn with_connection(
+        &self,
+        connection: DbConnection,
+    ) -> Arc<dyn CustomersRepositoryTrait + Send + Sync> {
+        Arc::new(Self { connection })
+    }
+
     async fn find_one(&self, id: &str) -> Result<CustomerFromDb, HttpError> {
         let customer_from_db = Customer::find_by_id(id)
-            .one(self.sea_orm_client.as_ref())
+            .filter(customer::Column::DeletedAt.is_null())
+            .one(&self.connection)
             .await?
-            .ok_or(HttpError::NotFound(format!(
-                "Customer with id '{id}' was not found"
-            )))?;
+            .ok_or_else(|| Self::get_not_found_error(id))?;
+
+        Ok(customer_from_db.into())
+    }
+
+    async fn find_one_deleted(&self, id: &str) -> Result<CustomerFromDb, HttpError> {
+        let customer_from_db = Customer::find_by_id(id)
+            .filter(customer::Column::DeletedAt.is_not_null())
+            .one(&self.connection)
+            .await?
+            .ok_or_else(|| Self::get_not_found_error(id))?;
 
         Ok(customer_from_db.into())
     }
@@ -38,7 +96,8 @@ impl CustomersRepositoryTrait for CustomerRepository {
     async fn find_one_by_user_id(&self, user_id: &str) -> Result<CustomerFromDb, HttpError> {
         let customer_from_db = Customer::find()
             .filter(customer::Column::UserId.eq(user_id))
-            .one(self.sea_orm_client.as_ref())
+            .filter(customer::Column::DeletedAt.is_null())
+            .one(&self.connection)
             .await?
             .ok_or(HttpError::NotFound(format!(
                 "Customer with user_id '{user_id}' was not found"
@@ -48,7 +107,10 @@ impl CustomersRepositoryTrait for CustomerRepository {
     }
 
     async fn find_many(&self) -> Result<Vec<CustomerFromDb>, HttpError> {
-        let customers_from_db = Customer::find().all(&*self.sea_orm_client).await?;
+        let customers_from_db = Customer::find()
+            .filter(customer::Column::DeletedAt.is_null())
+            .all(&self.connection)
+            .await?;
 
         let mapped_customers = customers_from_db.into_iter().map(Into::into).collect();
 
@@ -57,7 +119,7 @@ impl CustomersRepositoryTrait for CustomerRepository {
 
     async fn create(&self, create_dto: CreateCustomerDbDto) -> Result<CustomerFromDb, HttpError> {
         let created_customer_from_db = Customer::insert(create_dto.into_active_model())
-            .exec_with_returning(self.sea_orm_client.as_ref())
+            .exec_with_returning(&self.connection)
             .await?;
 
         Ok(created_customer_from_db.into())
@@ -67,25 +129,129 @@ impl CustomersRepositoryTrait for CustomerRepository {
         &self,
         id: &str,
         update_dto: UpdateCustomerDbDto,
+        user_id: Option<&str>,
+        expected_versions: Option<Vec<i64>>,
     ) -> Result<CustomerFromDb, HttpError> {
-        let updated_customer_from_db = Customer::update(CustomerActiveModel::from(update_dto))
+        let transaction = self.connection.begin().await?;
+
+        Self::lock_for_write(
+            &transaction,
+            id,
+            user_id,
+            expected_versions.as_deref(),
+            false,
+        )
+        .await?;
+
+        let updated_customer_from_db = Customer::update_many()
+            .set(CustomerActiveModel::from(update_dto))
+            .col_expr(
+                customer::Column::Version,
+                Expr::col(customer::Column::Version).add(1),
+            )
+            .col_expr(
+                customer::Column::UpdatedAt,
+                Expr::current_timestamp().into(),
+            )
             .filter(customer::Column::Id.eq(id))
-            .exec(self.sea_orm_client.as_ref())
-            .await?;
+            .exec_with_returning(&transaction)
+            .await?
+            .pop()
+            .ok_or_else(|| Self::get_not_found_error(id))?;
+
+        transaction.commit().await?;
 
         Ok(updated_customer_from_db.into())
     }
 
-    async fn delete(&self, id: &str) -> Result<CustomerFromDb, HttpError> {
-        let deleted_customer = Customer::delete_by_id(id)
-            .exec_with_returning(self.sea_orm_client.as_ref())
+    async fn delete(
+        &self,
+        id: &str,
+        user_id: Option<&str>,
+        expected_versions: Option<Vec<i64>>,
+    ) -> Result<CustomerFromDb, HttpError> {
+        let transaction = self.connection.begin().await?;
+
+        Self::lock_for_write(
+            &transaction,
+            id,
+            user_id,
+            expected_versions.as_deref(),
+            false,
+        )
+        .await?;
+
+        let deleted_customer = Customer::update_many()
+            .col_expr(
+                customer::Column::DeletedAt,
+                Expr::current_timestamp().into(),
+            )
+            .col_expr(
+                customer::Column::Version,
+                Expr::col(customer::Column::Version).add(1),
+            )
+            .col_expr(
+                customer::Column::UpdatedAt,
+                Expr::current_timestamp().into(),
+            )
+            .filter(customer::Column::Id.eq(id))
+            .exec_with_returning(&transaction)
             .await?
-            .first()
-            .cloned()
-            .ok_or(HttpError::NotFound(format!(
-                "Customer with id '{id}' was not found"
-            )))?;
+            .pop()
+            .ok_or_else(|| Self::get_not_found_error(id))?;
+
+        transaction.commit().await?;
+
+        Ok(deleted_customer.into())
+    }
+
+    async fn restore(&self, id: &str) -> Result<CustomerFromDb, HttpError> {
+        let transaction = self.connection.begin().await?;
+
+        Self::lock_for_write(&transaction, id, None, None, true).await?;
+
+        let restored_customer = Customer::update_many()
+            .col_expr(
+                customer::Column::DeletedAt,
+                Expr::value(Option::<DateTimeWithTimeZone>::None),
+            )
+            .col_expr(
+                customer::Column::Version,
+                Expr::col(customer::Column::Version).add(1),
+            )
+            .col_expr(
+                customer::Column::UpdatedAt,
+                Expr::current_timestamp().into(),
+            )
+            .filter(customer::Column::Id.eq(id))
+            .exec_with_returning(&transaction)
+            .await?
+            .pop()
+            .ok_or_else(|| Self::get_not_found_error(id))?;
+
+        transaction.commit().await?;
+
+        Ok(restored_customer.into())
+    }
+
+    async fn purge_deleted(&self, deleted_before: DateTimeWithTimeZone) -> Result<u64, HttpError> {
+        let transaction = self.connection.begin().await?;
+
+        // Locked, so that the customers are not restored while they are purged
+        let purged_customers = Customer::find()
+            .filter(customer::Column::DeletedAt.lt(deleted_before))
+            .lock_exclusive()
+            .all(&transaction)
+            .await?;
+
+        if purged_customers.is_empty() {
+            return Ok(0);
+        }
+
+        delete_customers_with_data(&transaction, &purged_customers).await?;
+
+        transaction.commit().await?;
 
-        Ok(deleted_customer.clone().into())
+        Ok(purged_customers.len() as u64)
     }
 }
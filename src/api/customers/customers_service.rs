@@ -1,5 +1,10 @@
+use async_trait::async_trait;
+use std::slice;
 use std::sync::Arc;
 
+use crate::api::audit_logs::audit_logs_service::AuditLogsService;
+use crate::api::audit_logs::types::audit_action::AuditAction;
+
 use crate::api::customers::dto::create_customer_db_dto::CreateCustomerDbDto;
 use crate::api::customers::dto::update_customer_db_dto::UpdateCustomerDbDto;
 use crate::api::customers::{
@@ -8,15 +13,43 @@ use crate::api::customers::{
     traits::customers_repository::CustomersRepositoryTrait,
 };
 use crate::shared::errors::http_error::HttpError;
+use crate::shared::modules::audit::structs::audit_context::AuditContext;
+use crate::shared::modules::auth::enums::roles::Roles;
+use crate::shared::modules::auth::errors::AuthError;
+use crate::shared::modules::auth::structs::user::User;
+use crate::shared::modules::auth::traits::user_resolver::UserResolver;
+use crate::shared::modules::db::db_connection::DbConnection;
+use crate::shared::modules::db::unit_of_work::UnitOfWork;
+use crate::shared::modules::db::utils::generate_id;
 
+/// Writes are recorded in the audit log in the same transaction
 #[derive(Clone)]
 pub struct CustomersService {
     customers_repository: Arc<dyn CustomersRepositoryTrait + Send + Sync>,
+    audit_logs_service: Arc<AuditLogsService>,
+    unit_of_work: Arc<UnitOfWork>,
 }
 impl CustomersService {
-    pub fn new(customers_repository: Arc<dyn CustomersRepositoryTrait + Send + Sync>) -> Self {
+    pub fn new(
+        customers_repository: Arc<dyn CustomersRepositoryTrait + Send + Sync>,
+        audit_logs_service: Arc<AuditLogsService>,
+        unit_of_work: Arc<UnitOfWork>,
+    ) -> Self {
         Self {
             customers_repository,
+            audit_logs_service,
+            unit_of_work,
+        }
+    }
+
+    /// Service which queries run on the connection, e.g. a unit of work transaction
+    pub fn with_connection(&self, connection: DbConnection) -> Self {
+        Self {
+            customers_repository: self
+                .customers_repository
+                .with_connection(connection.clone()),
+            audit_logs_service: Arc::new(self.audit_logs_service.with_connection(connection)),
+            unit_of_work: self.unit_of_work.clone(),
         }
     }
 
@@ -53,17 +86,35 @@ impl CustomersService {
         create_dto: CreateCustomerDto,
         user_id: &str,
         email: &str,
+        audit_context: &AuditContext,
     ) -> Result<CustomerEntity, HttpError> {
         let create_customer_db_dto =
             Self::map_create_dto_to_create_db_dto(create_dto, user_id, email);
-        let created_customer_from_db = self
-            .customers_repository
-            .create(create_customer_db_dto)
-            .await?;
 
-        let created_customer_entity = created_customer_from_db.into();
+        self.unit_of_work
+            .run(|connection| async {
+                let customers_repository = self
+                    .customers_repository
+                    .with_connection(connection.clone());
+                let audit_logs_service = self.audit_logs_service.with_connection(connection);
 
-        Ok(created_customer_entity)
+                let created_customer_entity: CustomerEntity = customers_repository
+                    .create(create_customer_db_dto.clone())
+                    .await?
+                    .into();
+
+                audit_logs_service
+                    .record(
+                        audit_context,
+                        AuditAction::Create,
+                        &[],
+                        slice::from_ref(&created_customer_entity),
+                    )
+                    .await?;
+
+                Ok(created_customer_entity)
+            })
+            .await
     }
 
     pub async fn update_as_customer(
@@ -72,63 +123,165 @@ impl CustomersService {
         update_dto: UpdateCustomerDto,
         user_id: &str,
         user_email: &str,
+        expected_versions: Option<Vec<i64>>,
+        audit_context: &AuditContext,
     ) -> Result<CustomerEntity, HttpError> {
-        let found_customer = self.customers_repository.find_one(id).await?;
-
-        if found_customer.user_id != user_id {
-            return Err(HttpError::NotFound("The customer was not found".into()));
-        }
-
         let update_db_dto = CustomersService::map_update_dto_to_update_db_dto(
             update_dto,
             Some(user_id.into()),
             Some(user_email.into()),
         );
-        let updated_customer_entity = self
-            .customers_repository
-            .update(id, update_db_dto)
-            .await?
-            .into();
 
-        Ok(updated_customer_entity)
+        self.update(
+            id,
+            update_db_dto,
+            Some(user_id),
+            expected_versions,
+            audit_context,
+        )
+        .await
     }
 
     pub async fn update_as_admin(
         &self,
         id: &str,
         update_dto: UpdateCustomerDto,
+        expected_versions: Option<Vec<i64>>,
+        audit_context: &AuditContext,
     ) -> Result<CustomerEntity, HttpError> {
         let update_db_dto =
             CustomersService::map_update_dto_to_update_db_dto(update_dto, None, None);
-        let updated_customer_entity = self
-            .customers_repository
-            .update(id, update_db_dto)
-            .await?
-            .into();
 
-        Ok(updated_customer_entity)
+        self.update(id, update_db_dto, None, expected_versions, audit_context)
+            .await
     }
 
-    pub async fn delete_as_admin(&self, id: &str) -> Result<CustomerEntity, HttpError> {
-        let deleted_customer_entity = self.customers_repository.delete(id).await?.into();
-
-        Ok(deleted_customer_entity)
+    pub async fn delete_as_admin(
+        &self,
+        id: &str,
+        expected_versions: Option<Vec<i64>>,
+        audit_context: &AuditContext,
+    ) -> Result<CustomerEntity, HttpError> {
+        self.delete(id, None, expected_versions, audit_context)
+            .await
     }
 
     pub async fn delete_as_customer(
         &self,
         id: &str,
         user_id: &str,
+        expected_versions: Option<Vec<i64>>,
+        audit_context: &AuditContext,
     ) -> Result<CustomerEntity, HttpError> {
-        let found_customer = self.customers_repository.find_one(id).await?;
+        self.delete(id, Some(user_id), expected_versions, audit_context)
+            .await
+    }
 
-        if found_customer.user_id != user_id {
-            return Err(HttpError::NotFound("The customer was not found".into()));
-        }
+    pub async fn restore_as_admin(
+        &self,
+        id: &str,
+        audit_context: &AuditContext,
+    ) -> Result<CustomerEntity, HttpError> {
+        self.unit_of_work
+            .run(|connection| async {
+                let customers_repository = self
+                    .customers_repository
+                    .with_connection(connection.clone());
+                let audit_logs_service = self.audit_logs_service.with_connection(connection);
+
+                let deleted_customer_entity: CustomerEntity =
+                    customers_repository.find_one_deleted(id).await?.into();
+                let restored_customer_entity: CustomerEntity =
+                    customers_repository.restore(id).await?.into();
+
+                audit_logs_service
+                    .record(
+                        audit_context,
+                        AuditAction::Restore,
+                        slice::from_ref(&deleted_customer_entity),
+                        slice::from_ref(&restored_customer_entity),
+                    )
+                    .await?;
+
+                Ok(restored_customer_entity)
+            })
+            .await
+    }
+
+    async fn update(
+        &self,
+        id: &str,
+        update_db_dto: UpdateCustomerDbDto,
+        user_id: Option<&str>,
+        expected_versions: Option<Vec<i64>>,
+        audit_context: &AuditContext,
+    ) -> Result<CustomerEntity, HttpError> {
+        self.unit_of_work
+            .run(|connection| async {
+                let customers_repository = self
+                    .customers_repository
+                    .with_connection(connection.clone());
+                let audit_logs_service = self.audit_logs_service.with_connection(connection);
+
+                let found_customer_entity: CustomerEntity =
+                    customers_repository.find_one(id).await?.into();
+                let updated_customer_entity: CustomerEntity = customers_repository
+                    .update(
+                        id,
+                        update_db_dto.clone(),
+                        user_id,
+                        expected_versions.clone(),
+                    )
+                    .await?
+                    .into();
+
+                audit_logs_service
+                    .record(
+                        audit_context,
+                        AuditAction::Update,
+                        slice::from_ref(&found_customer_entity),
+                        slice::from_ref(&updated_customer_entity),
+                    )
+                    .await?;
 
-        let deleted_customer_entity = self.customers_repository.delete(id).await?.into();
+                Ok(updated_customer_entity)
+            })
+            .await
+    }
+
+    async fn delete(
+        &self,
+        id: &str,
+        user_id: Option<&str>,
+        expected_versions: Option<Vec<i64>>,
+        audit_context: &AuditContext,
+    ) -> Result<CustomerEntity, HttpError> {
+        self.unit_of_work
+            .run(|connection| async {
+                let customers_repository = self
+                    .customers_repository
+                    .with_connection(connection.clone());
+                let audit_logs_service = self.audit_logs_service.with_connection(connection);
+
+                let found_customer_entity: CustomerEntity =
+                    customers_repository.find_one(id).await?.into();
+                let deleted_customer_entity: CustomerEntity = customers_repository
+                    .delete(id, user_id, expected_versions.clone())
+                    .await?
+                    .into();
 
-        Ok(deleted_customer_entity)
+                audit_logs_service
+                    .record(
+                        audit_context,
+                        AuditAction::Delete,
+                        slice::from_ref(&found_customer_entity),
+                        slice::from_ref(&deleted_customer_entity),
+                    )
+                    .await?;
+
+                Ok(deleted_customer_entity)
+            })
+            .await
     }
 
     fn map_create_dto_to_create_db_dto(
@@ -137,6 +290,7 @@ impl CustomersService {
         email: &str,
     ) -> CreateCustomerDbDto {
         CreateCustomerDbDto {
+            id: generate_id(),
             user_id: user_id.into(),
             email: email.into(),
             first_name: create_dto.first_name,
@@ -163,3 +317,31 @@ impl CustomersService {
         }
     }
 }
+
+#[async_trait]
+impl UserResolver for CustomersService {
+    async fn resolve_user(&self, user_id: &str) -> Result<User, AuthError> {
+        // Only a missing customer is a missing user, DB failures must not turn into 404s
+        let customer = self
+            .find_one_by_user_id(user_id)
+            .await
+            .map_err(|err| match err {
+                HttpError::NotFound(_) => {
+                    AuthError::UserNotFound(format!("User '{user_id}' was not found"))
+                }
+                err => AuthError::UserResolutionFailed(format!(
+                    "Failed to resolve user '{user_id}': {err}"
+                )),
+            })?;
+
+        Ok(User {
+            id: customer.user_id,
+            name: format!("{} {}", customer.first_name, customer.last_name),
+            nickname: customer.first_name,
+            email: customer.email,
+            email_verified: false,
+            roles: vec![Roles::Customer],
+            impersonated_by: None,
+        })
+    }
+}
@@ -6,8 +6,9 @@ use crate::api::customers::types::sex::Sex;
 
 use crate::shared::modules::db::entities::customer::ActiveModel;
 
-#[derive(Serialize, Deserialize, Debug, DeriveIntoActiveModel)]
+#[derive(Serialize, Deserialize, Debug, Clone, DeriveIntoActiveModel)]
 pub struct CreateCustomerDbDto {
+    pub id: String,
     pub user_id: String,
     pub first_name: String,
     pub last_name: String,
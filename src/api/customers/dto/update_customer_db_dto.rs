@@ -1,5 +1,5 @@
 use crate::api::customers::types::sex::Sex;
-use chrono::{DateTime, FixedOffset, Utc};
+use chrono::{DateTime, FixedOffset};
 use sea_orm::ActiveValue;
 use serde::{Deserialize, Serialize};
 
@@ -7,7 +7,7 @@ use crate::shared::modules::db::entities::customer::ActiveModel as CustomerActiv
 use crate::shared::modules::db::entities::sea_orm_active_enums;
 use crate::shared::modules::db::utils::optional_to_active_value;
 
-#[derive(Serialize, Deserialize, Debug)]
+#[derive(Serialize, Deserialize, Debug, Clone)]
 pub struct UpdateCustomerDbDto {
     pub user_id: Option<String>,
     pub first_name: Option<String>,
@@ -20,7 +20,8 @@ pub struct UpdateCustomerDbDto {
 
 impl From<UpdateCustomerDbDto> for CustomerActiveModel {
     fn from(value: UpdateCustomerDbDto) -> Self {
-        let mapped_sex: Option<sea_orm_active_enums::Sex> = value.sex.map(sea_orm_active_enums::Sex::from);
+        let mapped_sex: Option<sea_orm_active_enums::Sex> =
+            value.sex.map(sea_orm_active_enums::Sex::from);
 
         Self {
             id: ActiveValue::NotSet,
@@ -30,9 +31,11 @@ impl From<UpdateCustomerDbDto> for CustomerActiveModel {
             first_name: optional_to_active_value(value.first_name),
             last_name: optional_to_active_value(value.last_name),
             sex: optional_to_active_value(mapped_sex),
-            birthdate: optional_to_active_value(value.birthdate.into()),
-            updated_at: ActiveValue::Set(Utc::now().into()),
+            birthdate: optional_to_active_value(value.birthdate),
+            updated_at: ActiveValue::NotSet,
             created_at: ActiveValue::NotSet,
+            version: ActiveValue::NotSet,
+            deleted_at: ActiveValue::NotSet,
         }
     }
 }
@@ -1,4 +1,7 @@
+use crate::api::audit_logs::traits::audited::Audited;
+use crate::api::audit_logs::types::audit_entity_type::AuditEntityType;
 use crate::api::customers::types::{customer_from_db::CustomerFromDb, sex::Sex};
+use crate::shared::modules::versioning::traits::versioned::Versioned;
 use aide::OperationIo;
 use chrono::{DateTime, FixedOffset};
 use schemars::JsonSchema;
@@ -7,6 +10,7 @@ use serde::{Deserialize, Serialize};
 #[derive(Serialize, Deserialize, Debug, JsonSchema, OperationIo)]
 #[serde(rename_all = "camelCase")]
 pub struct CustomerEntity {
+    /// Time-ordered UUID (v7) generated by the server
     pub id: String,
     pub user_id: String,
     pub first_name: String,
@@ -15,6 +19,10 @@ pub struct CustomerEntity {
     pub birthdate: DateTime<FixedOffset>,
     pub phone: Option<String>,
     pub sex: Sex,
+    /// Bumped on every update, pass it in `If-Match` to update or delete only this version
+    pub version: i64,
+    /// Set while the customer is in the trash
+    pub deleted_at: Option<DateTime<FixedOffset>>,
 }
 
 impl From<CustomerFromDb> for CustomerEntity {
@@ -28,6 +36,26 @@ impl From<CustomerFromDb> for CustomerEntity {
             birthdate: value.birthdate,
             phone: value.phone,
             sex: value.sex,
+            version: value.version,
+            deleted_at: value.deleted_at,
         }
     }
 }
+
+impl Versioned for CustomerEntity {
+    fn get_version(&self) -> i64 {
+        self.version
+    }
+}
+
+impl Audited for CustomerEntity {
+    const AUDIT_ENTITY_TYPE: AuditEntityType = AuditEntityType::Customer;
+
+    fn get_audit_id(&self) -> &str {
+        &self.id
+    }
+
+    fn get_audit_owner_id(&self) -> &str {
+        &self.id
+    }
+}
@@ -1,3 +1,5 @@
+use crate::api::audit_logs::audit_logs_repository::AuditLogsRepository;
+use crate::api::audit_logs::audit_logs_service::AuditLogsService;
 use crate::api::customers::customers_repository::CustomerRepository;
 use crate::api::customers::{
     customers_service::CustomersService, types::api_state::CustomersApiState,
@@ -6,7 +8,10 @@ use crate::shared::modules::auth::enums::roles::Roles;
 use crate::shared::modules::auth::middlewares::role_based_bearer_auth::AuthLayer;
 use crate::shared::modules::auth::services::auth0::Auth0Service;
 use crate::shared::modules::cache::middlewares::json_cache::JsonCacheLayer;
-use crate::shared::modules::redis::redis_service::RedisService;
+use crate::shared::modules::cache::structs::cache_generation::CacheGeneration;
+use crate::shared::modules::cache::{AppCacheService, AppL2CacheService};
+use crate::shared::modules::db::unit_of_work::UnitOfWork;
+use crate::shared::modules::idempotency::middlewares::idempotency::IdempotencyLayer;
 use aide::axum::ApiRouter;
 use aide::axum::routing::{delete, get, patch, post};
 use sea_orm::DatabaseConnection;
@@ -16,27 +21,41 @@ mod customers_handlers;
 pub mod customers_repository;
 pub mod customers_service;
 mod dto;
-mod entities;
-mod traits;
-mod types;
+pub mod entities;
+pub mod traits;
+pub mod types;
 
 pub fn get_router(
     sea_orm_client: Arc<DatabaseConnection>,
-    redis_service: Arc<RedisService>,
+    cache_service: Arc<AppCacheService>,
+    idempotency_layer: IdempotencyLayer<AppL2CacheService>,
     auth_service: Arc<Auth0Service>,
 ) -> ApiRouter {
-    let customers_repository = Arc::new(CustomerRepository::new(sea_orm_client));
-    let customers_service = Arc::new(CustomersService::new(customers_repository));
-    let api_state = CustomersApiState { customers_service };
+    let audit_logs_repository = Arc::new(AuditLogsRepository::new(sea_orm_client.clone()));
+    let audit_logs_service = Arc::new(AuditLogsService::new(audit_logs_repository));
 
-    let auth_layer = AuthLayer::new(auth_service.clone());
-    let cache_layer = JsonCacheLayer::new(redis_service, auth_service);
+    let customers_repository = Arc::new(CustomerRepository::new(sea_orm_client.clone()));
+    let unit_of_work = Arc::new(UnitOfWork::new(sea_orm_client));
+    let customers_service = Arc::new(CustomersService::new(
+        customers_repository,
+        audit_logs_service,
+        unit_of_work,
+    ));
+    let api_state = CustomersApiState {
+        customers_service: customers_service.clone(),
+        cache_generation: CacheGeneration::new(cache_service.clone()),
+    };
+
+    let auth_layer = AuthLayer::new(auth_service.clone(), customers_service);
+    // Responses are per user and have to be revalidated with ETags
+    let cache_layer =
+        JsonCacheLayer::new(cache_service, auth_service).with_cache_control("private, no-cache");
 
     let routes = ApiRouter::new()
         .api_route(
             "/self",
             get(customers_handlers::find_one_by_user_id)
-                .route_layer(cache_layer.clone())
+                .route_layer(cache_layer.clone().with_early_expiration(1.0))
                 .route_layer(auth_layer.verify(vec![Roles::Customer])),
         )
         .api_route(
@@ -46,12 +65,18 @@ pub fn get_router(
         .api_route(
             "/",
             get(customers_handlers::find_many)
-                .route_layer(cache_layer)
+                .route_layer(
+                    cache_layer
+                        .with_stale_while_revalidate(30)
+                        .with_early_expiration(1.0),
+                )
                 .route_layer(auth_layer.verify(vec![Roles::Admin])),
         )
         .api_route(
             "/",
-            post(customers_handlers::create).route_layer(auth_layer.verify(vec![Roles::Customer])),
+            post(customers_handlers::create)
+                .route_layer(idempotency_layer)
+                .route_layer(auth_layer.verify(vec![Roles::Customer])),
         )
         .api_route(
             "/{id}",
@@ -62,6 +87,10 @@ pub fn get_router(
             "/{id}",
             delete(customers_handlers::remove)
                 .route_layer(auth_layer.verify(vec![Roles::Admin, Roles::Customer])),
+        )
+        .api_route(
+            "/{id}/restore",
+            post(customers_handlers::restore).route_layer(auth_layer.verify(vec![Roles::Admin])),
         );
 
     ApiRouter::new()
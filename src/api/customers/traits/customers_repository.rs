@@ -1,25 +1,53 @@
 use async_trait::async_trait;
+use sea_orm::prelude::DateTimeWithTimeZone;
+use std::sync::Arc;
 
 use crate::api::customers::dto::create_customer_db_dto::CreateCustomerDbDto;
 use crate::api::customers::dto::update_customer_db_dto::UpdateCustomerDbDto;
 use crate::api::customers::types::customer_from_db::CustomerFromDb;
 use crate::shared::errors::http_error::HttpError;
+use crate::shared::modules::db::db_connection::DbConnection;
 
 #[async_trait]
 pub trait CustomersRepositoryTrait {
+    /// Repository which queries run on the connection, e.g. a unit of work transaction
+    fn with_connection(
+        &self,
+        connection: DbConnection,
+    ) -> Arc<dyn CustomersRepositoryTrait + Send + Sync>;
+
     async fn find_one(&self, id: &str) -> Result<CustomerFromDb, HttpError>;
 
+    /// Customer in the trash
+    async fn find_one_deleted(&self, id: &str) -> Result<CustomerFromDb, HttpError>;
+
     async fn find_one_by_user_id(&self, user_id: &str) -> Result<CustomerFromDb, HttpError>;
 
     async fn find_many(&self) -> Result<Vec<CustomerFromDb>, HttpError>;
 
     async fn create(&self, create_dto: CreateCustomerDbDto) -> Result<CustomerFromDb, HttpError>;
 
+    /// Writes check that the customer belongs to `user_id` (if given) in the same transaction
     async fn update(
         &self,
         id: &str,
         update_dto: UpdateCustomerDbDto,
+        user_id: Option<&str>,
+        expected_versions: Option<Vec<i64>>,
+    ) -> Result<CustomerFromDb, HttpError>;
+
+    /// Moves the customer to the trash, their data is kept until it is purged
+    async fn delete(
+        &self,
+        id: &str,
+        user_id: Option<&str>,
+        expected_versions: Option<Vec<i64>>,
     ) -> Result<CustomerFromDb, HttpError>;
 
-    async fn delete(&self, id: &str) -> Result<CustomerFromDb, HttpError>;
+    async fn restore(&self, id: &str) -> Result<CustomerFromDb, HttpError>;
+
+    /// Hard-deletes customers moved to the trash before the date along with their expenses,
+    /// regular payments, custom categories and categorization rules, and anonymizes their
+    /// audit log entries the way an erasure does. Returns the amount of customers
+    async fn purge_deleted(&self, deleted_before: DateTimeWithTimeZone) -> Result<u64, HttpError>;
 }
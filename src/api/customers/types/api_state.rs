@@ -2,10 +2,13 @@ use axum::extract::FromRef;
 use std::sync::Arc;
 
 use crate::api::customers::customers_service::CustomersService;
+use crate::shared::modules::cache::AppCacheService;
+use crate::shared::modules::cache::structs::cache_generation::CacheGeneration;
 
 #[derive(Clone)]
 pub struct CustomersApiState {
     pub customers_service: Arc<CustomersService>,
+    pub cache_generation: CacheGeneration<AppCacheService>,
 }
 
 impl FromRef<CustomersApiState> for Arc<CustomersService> {
@@ -13,3 +16,9 @@ impl FromRef<CustomersApiState> for Arc<CustomersService> {
         app_state.customers_service.clone()
     }
 }
+
+impl FromRef<CustomersApiState> for CacheGeneration<AppCacheService> {
+    fn from_ref(app_state: &CustomersApiState) -> CacheGeneration<AppCacheService> {
+        app_state.cache_generation.clone()
+    }
+}
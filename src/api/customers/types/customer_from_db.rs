@@ -14,6 +14,8 @@ pub struct CustomerFromDb {
     pub phone: Option<String>,
     pub birthdate: DateTime<FixedOffset>,
     pub sex: Sex,
+    pub version: i64,
+    pub deleted_at: Option<DateTime<FixedOffset>>,
 }
 
 impl From<customer::Model> for CustomerFromDb {
@@ -27,6 +29,8 @@ impl From<customer::Model> for CustomerFromDb {
             phone: value.phone,
             birthdate: value.birthdate,
             sex: value.sex.into(),
+            version: value.version,
+            deleted_at: value.deleted_at,
         }
     }
 }
@@ -3,7 +3,7 @@ use schemars::JsonSchema;
 use sea_orm::{ActiveValue, IntoActiveValue};
 use serde::{Deserialize, Serialize};
 
-#[derive(Serialize, Deserialize, Debug, JsonSchema)]
+#[derive(Serialize, Deserialize, Debug, Clone, JsonSchema)]
 pub enum Sex {
     #[serde(rename = "MALE")]
     Male,
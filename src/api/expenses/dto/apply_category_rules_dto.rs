@@ -0,0 +1,16 @@
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+/// Active expenses the rules are re-applied to, all of them if nothing is set
+#[derive(Debug, Clone, Deserialize, Serialize, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct ApplyCategoryRulesDto {
+    /// Id of the category or code of a system one, expenses of its subcategories match too
+    pub category: Option<String>,
+    /// Inclusive bounds of the expense date
+    pub date_from: Option<chrono::DateTime<chrono::FixedOffset>>,
+    pub date_to: Option<chrono::DateTime<chrono::FixedOffset>>,
+    /// Find the expenses which would be recategorized without updating them
+    #[serde(default)]
+    pub dry_run: bool,
+}
@@ -0,0 +1,84 @@
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use crate::api::expenses::dto::find_expenses_dto::FindExpensesDto;
+use crate::shared::errors::http_error::HttpError;
+
+const MAX_BULK_IDS: usize = 1000;
+
+/// Expenses a bulk operation applies to, either `ids` or `filter` has to be set
+#[derive(Debug, Clone, Deserialize, Serialize, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct BulkExpensesSelectorDto {
+    /// At most 1000 ids, ids of other customers' expenses are ignored
+    pub ids: Option<Vec<Uuid>>,
+    pub filter: Option<BulkExpensesFilterDto>,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct BulkExpensesFilterDto {
+    /// Id of the category or code of a system one, expenses of its subcategories match too
+    pub category: Option<String>,
+    /// Inclusive bounds of the expense date
+    pub date_from: Option<chrono::DateTime<chrono::FixedOffset>>,
+    pub date_to: Option<chrono::DateTime<chrono::FixedOffset>>,
+}
+
+impl BulkExpensesSelectorDto {
+    /// Category of the filter, it has to be resolved into `category_ids` of `to_find_dto`
+    pub fn get_category(&self) -> Option<&str> {
+        self.filter.as_ref()?.category.as_deref()
+    }
+
+    /// Filter of the customer's expenses. An empty filter is rejected, so that a mistake
+    /// does not affect all of them
+    pub fn to_find_dto(
+        &self,
+        customer_id: &str,
+        category_ids: Option<Vec<String>>,
+    ) -> Result<FindExpensesDto, HttpError> {
+        let find_dto = match (&self.ids, &self.filter) {
+            (Some(ids), None) => {
+                if ids.is_empty() || ids.len() > MAX_BULK_IDS {
+                    return Err(HttpError::BadRequest(format!(
+                        "ids should have from 1 to {MAX_BULK_IDS} items"
+                    )));
+                }
+
+                FindExpensesDto {
+                    ids: Some(ids.iter().map(Uuid::to_string).collect()),
+                    ..Default::default()
+                }
+            }
+            (None, Some(filter)) => {
+                if filter.category.is_none()
+                    && filter.date_from.is_none()
+                    && filter.date_to.is_none()
+                {
+                    return Err(HttpError::BadRequest(
+                        "filter should have at least one of category, dateFrom and dateTo".into(),
+                    ));
+                }
+
+                FindExpensesDto {
+                    category_ids,
+                    date_from: filter.date_from,
+                    date_to: filter.date_to,
+                    ..Default::default()
+                }
+            }
+            _ => {
+                return Err(HttpError::BadRequest(
+                    "Either ids or filter should be set".into(),
+                ));
+            }
+        };
+
+        Ok(FindExpensesDto {
+            customer_id: Some(customer_id.to_string()),
+            ..find_dto
+        })
+    }
+}
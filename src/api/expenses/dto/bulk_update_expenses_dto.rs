@@ -0,0 +1,14 @@
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+use crate::api::expenses::dto::bulk_expenses_selector_dto::BulkExpensesSelectorDto;
+use crate::api::expenses::dto::update_expense_dto::UpdateExpenseDto;
+
+#[derive(Debug, Deserialize, Serialize, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct BulkUpdateExpensesDto {
+    #[serde(flatten)]
+    pub selector: BulkExpensesSelectorDto,
+    /// Fields set on all the selected expenses
+    pub update: UpdateExpenseDto,
+}
@@ -1,14 +1,16 @@
-use sea_orm::{DeriveIntoActiveModel};
+use sea_orm::DeriveIntoActiveModel;
 use sea_orm::prelude::Decimal;
 use serde::{Deserialize, Serialize};
-use crate::api::expenses::types::expense_category::ExpenseCategory;
 
 use crate::shared::modules::db::entities::expense::ActiveModel;
 
-#[derive(Serialize, Deserialize, Debug, DeriveIntoActiveModel)]
+#[derive(Serialize, Deserialize, Debug, Clone, DeriveIntoActiveModel)]
 pub struct CreateExpenseDbDto {
+    pub id: String,
     pub customer_id: String,
     pub amount: Decimal,
     pub date: chrono::DateTime<chrono::FixedOffset>,
-    pub category: ExpenseCategory,
+    pub category_id: String,
+    pub note: Option<String>,
+    pub external_id: Option<String>,
 }
@@ -2,9 +2,7 @@ use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 use validator::Validate;
 
-use crate::api::expenses::types::expense_category::ExpenseCategory;
-
-#[derive(Debug, Deserialize, Serialize, Validate, JsonSchema)]
+#[derive(Debug, Clone, Deserialize, Serialize, Validate, JsonSchema)]
 #[serde(rename_all = "camelCase")]
 pub struct CreateExpenseDto {
     #[validate(range(min = 0.0, message = "Should be more than 0"))]
@@ -12,5 +10,17 @@ pub struct CreateExpenseDto {
 
     pub date: chrono::DateTime<chrono::FixedOffset>,
 
-    pub category: ExpenseCategory,
+    /// Id of the category or code of a system one, e.g. `FOOD`. Assigned by the categorization
+    /// rules of the customer if not set, `OTHER` if none of them matches
+    #[serde(alias = "categoryId")]
+    #[validate(length(min = 1, message = "Can not be empty"))]
+    pub category: Option<String>,
+
+    #[validate(length(max = 500, message = "Should be at most 500 characters"))]
+    pub note: Option<String>,
+
+    /// Id of the transaction in its source, e.g. a bank statement. Expenses with an id
+    /// the customer already has are skipped as duplicates
+    #[validate(length(min = 1, max = 255, message = "Should be 1 to 255 characters"))]
+    pub external_id: Option<String>,
 }
@@ -0,0 +1,15 @@
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+use crate::api::expenses::types::duplicate_policy::DuplicatePolicy;
+
+#[derive(Debug, Deserialize, Serialize, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct CreateExpensesQueryDto {
+    #[serde(default)]
+    pub duplicate_policy: DuplicatePolicy,
+    /// Create all the expenses or none of them, otherwise every item is created on its own
+    /// and failed items are listed in `errors`
+    #[serde(default)]
+    pub atomic: bool,
+}
@@ -0,0 +1,17 @@
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+use crate::api::expenses::dto::find_expenses_query_dto::FindExpensesQueryDto;
+use crate::api::expenses::types::export_format::ExportFormat;
+
+#[derive(Debug, Deserialize, Serialize, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct ExportExpensesQueryDto {
+    pub format: ExportFormat,
+    /// Locale of dates, e.g. `en-GB` or `de`. Dates are ISO 8601 if not set
+    pub locale: Option<String>,
+    /// Time zone offset of dates, e.g. `+02:00`, UTC by default
+    pub utc_offset: Option<String>,
+    #[serde(flatten)]
+    pub filter: FindExpensesQueryDto,
+}
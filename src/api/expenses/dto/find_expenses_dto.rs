@@ -1,7 +1,15 @@
 use serde::{Deserialize, Serialize};
 
-#[derive(Debug, Serialize, Deserialize, Default)]
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
 #[serde(rename_all = "camelCase")]
 pub struct FindExpensesDto {
     pub customer_id: Option<String>,
+    pub ids: Option<Vec<String>>,
+    /// Expenses of any of the categories, e.g. a category and its subcategories
+    pub category_ids: Option<Vec<String>>,
+    /// Inclusive bounds of the expense date
+    pub date_from: Option<chrono::DateTime<chrono::FixedOffset>>,
+    pub date_to: Option<chrono::DateTime<chrono::FixedOffset>>,
+    /// Expenses in the trash instead of the active ones
+    pub is_deleted: bool,
 }
@@ -0,0 +1,15 @@
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+#[derive(Debug, Clone, Default, Deserialize, Serialize, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct FindExpensesQueryDto {
+    /// Admins only, customers always get their own expenses
+    pub customer_id: Option<Uuid>,
+    /// Id of the category or code of a system one, expenses of its subcategories match too
+    pub category: Option<String>,
+    /// Inclusive bounds of the expense date
+    pub date_from: Option<chrono::DateTime<chrono::FixedOffset>>,
+    pub date_to: Option<chrono::DateTime<chrono::FixedOffset>>,
+}
@@ -0,0 +1,50 @@
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+use crate::api::expenses::types::duplicate_policy::DuplicatePolicy;
+use crate::api::expenses::types::import_format::ImportFormat;
+
+#[derive(Debug, Deserialize, Serialize, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct ImportExpensesQueryDto {
+    /// Detected by the content of the file if not set
+    pub format: Option<ImportFormat>,
+    /// `,` by default, CSV only
+    pub delimiter: Option<char>,
+    /// Decimal separator of amounts, CSV and QIF only. `.` by default for CSV,
+    /// detected by the separators of each amount for QIF
+    pub decimal_separator: Option<char>,
+    /// `chrono` format of dates of CSV and QIF files, e.g. `%d.%m.%Y`.
+    /// RFC 3339 and `%Y-%m-%d` are accepted by default, QIF also accepts US dates
+    pub date_format: Option<String>,
+    /// CSV only. The first row has column names, otherwise columns are referred by zero-based indexes
+    #[serde(default = "default_has_headers")]
+    pub has_headers: bool,
+    /// Column of amounts, `amount` by default. Columns are CSV only
+    pub amount_column: Option<String>,
+    /// Column of dates, `date` by default
+    pub date_column: Option<String>,
+    /// Column of categories, `category` by default
+    pub category_column: Option<String>,
+    /// Column of notes, `note` by default. Optional unless set explicitly
+    pub note_column: Option<String>,
+    /// JSON object of category names in the file to category ids or codes, e.g. `{"Groceries":"FOOD"}`.
+    /// Names matching category codes (e.g. `Places to eat`) don't have to be mapped
+    pub category_mapping: Option<String>,
+    /// Category id or code of rows with an empty or unknown category. Otherwise rows with
+    /// an empty category are categorized by the rules of the customer, rows of CSV files with
+    /// an unknown one fail
+    pub default_category: Option<String>,
+    #[serde(default)]
+    pub duplicate_policy: DuplicatePolicy,
+    /// Import all the rows or none of them
+    #[serde(default)]
+    pub atomic: bool,
+    /// Parse and check the rows without creating expenses
+    #[serde(default)]
+    pub dry_run: bool,
+}
+
+fn default_has_headers() -> bool {
+    true
+}
@@ -1,7 +1,15 @@
 pub mod create_expense_db_dto;
 pub mod update_expense_db_dto;
 
+pub mod apply_category_rules_dto;
+pub mod bulk_expenses_selector_dto;
+pub mod bulk_update_expenses_dto;
 pub mod create_expense_dto;
+pub mod create_expenses_query_dto;
 pub mod update_expense_dto;
 
+pub mod export_expenses_query_dto;
 pub mod find_expenses_dto;
+pub mod find_expenses_query_dto;
+pub mod import_expenses_query_dto;
+pub mod test_category_rule_dto;
@@ -0,0 +1,17 @@
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use validator::Validate;
+
+use crate::api::category_rules::dto::category_rule_conditions_dto::CategoryRuleConditionsDto;
+
+/// Conditions of a rule to try on the existing expenses before it is saved
+#[derive(Debug, Clone, Deserialize, Serialize, Validate, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct TestCategoryRuleDto {
+    #[serde(flatten)]
+    #[validate(nested)]
+    pub conditions: CategoryRuleConditionsDto,
+    /// Inclusive bounds of the expense date
+    pub date_from: Option<chrono::DateTime<chrono::FixedOffset>>,
+    pub date_to: Option<chrono::DateTime<chrono::FixedOffset>>,
+}
@@ -2,9 +2,7 @@ use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 use validator::Validate;
 
-use crate::api::expenses::types::expense_category::ExpenseCategory;
-
-#[derive(Debug, Deserialize, Serialize, Validate, JsonSchema)]
+#[derive(Debug, Clone, Deserialize, Serialize, Validate, JsonSchema)]
 #[serde(rename_all = "camelCase")]
 pub struct UpdateExpenseDto {
     #[validate(range(min = 0.0, message = "Should be more than 0"))]
@@ -12,5 +10,20 @@ pub struct UpdateExpenseDto {
 
     pub date: Option<chrono::DateTime<chrono::FixedOffset>>,
 
-    pub category: Option<ExpenseCategory>,
+    /// Id of the category or code of a system one, e.g. `FOOD`
+    #[serde(alias = "categoryId")]
+    #[validate(length(min = 1, message = "Can not be empty"))]
+    pub category: Option<String>,
+
+    #[validate(length(max = 500, message = "Should be at most 500 characters"))]
+    pub note: Option<String>,
+}
+
+impl UpdateExpenseDto {
+    pub fn is_empty(&self) -> bool {
+        self.amount.is_none()
+            && self.date.is_none()
+            && self.category.is_none()
+            && self.note.is_none()
+    }
 }
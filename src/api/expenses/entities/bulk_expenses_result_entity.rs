@@ -0,0 +1,22 @@
+use aide::OperationIo;
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+use crate::api::expenses::types::expense_from_db::ExpenseFromDb;
+
+#[derive(Serialize, Deserialize, Debug, JsonSchema, OperationIo)]
+#[serde(rename_all = "camelCase")]
+pub struct BulkExpensesResultEntity {
+    /// Amount of affected expenses
+    pub count: usize,
+    pub ids: Vec<String>,
+}
+
+impl From<Vec<ExpenseFromDb>> for BulkExpensesResultEntity {
+    fn from(value: Vec<ExpenseFromDb>) -> Self {
+        Self {
+            count: value.len(),
+            ids: value.into_iter().map(|expense| expense.id).collect(),
+        }
+    }
+}
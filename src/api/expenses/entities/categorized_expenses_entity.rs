@@ -0,0 +1,16 @@
+use aide::OperationIo;
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+use crate::api::expenses::entities::expense_entity::ExpenseEntity;
+
+#[derive(Serialize, Deserialize, Debug, JsonSchema, OperationIo)]
+#[serde(rename_all = "camelCase")]
+pub struct CategorizedExpensesEntity {
+    /// Expenses were not updated, `expenses` are the ones which would be
+    pub is_dry_run: bool,
+    /// Expenses which category was changed by the rules
+    pub expenses: Vec<ExpenseEntity>,
+    /// More expenses are left to recategorize, applying the rules again continues with them
+    pub has_more: bool,
+}
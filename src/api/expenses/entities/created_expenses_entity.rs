@@ -0,0 +1,127 @@
+use aide::OperationOutput;
+use aide::generate::GenContext;
+use aide::openapi::{Operation, Response as OpenApiResponse};
+use axum::Json;
+use axum::http::{HeaderValue, StatusCode};
+use axum::response::{IntoResponse, Response};
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+use crate::api::expenses::entities::expense_entity::ExpenseEntity;
+use crate::shared::errors::http_error::HttpError;
+
+pub const X_DUPLICATE_EXPENSES_HEADER_NAME: &str = "x-duplicate-expenses";
+
+/// Result of a bulk creation. Responded with the created expenses only and duplicates in
+/// the `x-duplicate-expenses` header as `index=duplicateOfId` pairs, or in full with
+/// 207 Multi-Status if some items failed
+#[derive(Serialize, Deserialize, Debug, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct CreatedExpensesEntity {
+    /// Created expenses in the order of the request items. Failed items and merged
+    /// duplicates are skipped
+    pub expenses: Vec<ExpenseEntity>,
+    /// Items of the request which look like duplicates, see `duplicatePolicy`
+    pub duplicates: Vec<DuplicateExpenseEntity>,
+    /// Items which were not created, always empty for atomic requests
+    pub errors: Vec<ExpenseItemErrorEntity>,
+}
+
+/// Same customer, amount, category and note with dates close to each other,
+/// or the same external id
+#[derive(Serialize, Deserialize, Debug, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct DuplicateExpenseEntity {
+    /// Index of the item in the request
+    pub index: usize,
+    /// Existing expense or expense created by the same request the item matches
+    pub duplicate_of_id: String,
+}
+
+/// Same fields as error responses plus the index of the item in the request
+#[derive(Serialize, Deserialize, Debug, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct ExpenseItemErrorEntity {
+    pub index: usize,
+    pub code: u16,
+    pub error: String,
+    pub message: String,
+}
+
+impl ExpenseItemErrorEntity {
+    pub fn new(index: usize, http_error: &HttpError) -> Self {
+        Self {
+            index,
+            code: http_error.get_status_code().as_u16(),
+            error: http_error.get_name(),
+            message: http_error.to_string(),
+        }
+    }
+}
+
+impl CreatedExpensesEntity {
+    fn get_duplicates_header_value(&self) -> Option<HeaderValue> {
+        if self.duplicates.is_empty() {
+            return None;
+        }
+
+        let header_value = self
+            .duplicates
+            .iter()
+            .map(|duplicate| format!("{}={}", duplicate.index, duplicate.duplicate_of_id))
+            .collect::<Vec<_>>()
+            .join(",");
+
+        HeaderValue::from_str(&header_value).ok()
+    }
+}
+
+impl IntoResponse for CreatedExpensesEntity {
+    fn into_response(self) -> Response {
+        let duplicates_header_value = self.get_duplicates_header_value();
+        let mut response = if self.errors.is_empty() {
+            Json(self.expenses).into_response()
+        } else {
+            (StatusCode::MULTI_STATUS, Json(self)).into_response()
+        };
+
+        if let Some(header_value) = duplicates_header_value {
+            response
+                .headers_mut()
+                .insert(X_DUPLICATE_EXPENSES_HEADER_NAME, header_value);
+        }
+
+        response
+    }
+}
+
+impl OperationOutput for CreatedExpensesEntity {
+    type Inner = Self;
+
+    fn operation_response(
+        ctx: &mut GenContext,
+        operation: &mut Operation,
+    ) -> Option<OpenApiResponse> {
+        Json::<Vec<ExpenseEntity>>::operation_response(ctx, operation)
+    }
+
+    fn inferred_responses(
+        ctx: &mut GenContext,
+        operation: &mut Operation,
+    ) -> Vec<(Option<u16>, OpenApiResponse)> {
+        let Some(expenses_response) = Self::operation_response(ctx, operation) else {
+            return Vec::new();
+        };
+        let Some(multi_status_response) = Json::<Self>::operation_response(ctx, operation) else {
+            return Vec::new();
+        };
+
+        vec![
+            (Some(StatusCode::OK.as_u16()), expenses_response),
+            (
+                Some(StatusCode::MULTI_STATUS.as_u16()),
+                multi_status_response,
+            ),
+        ]
+    }
+}
@@ -2,19 +2,31 @@ use aide::OperationIo;
 use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 
-use crate::api::expenses::types::expense_category::ExpenseCategory;
+use crate::api::audit_logs::traits::audited::Audited;
+use crate::api::audit_logs::types::audit_entity_type::AuditEntityType;
 use crate::api::expenses::types::expense_from_db::ExpenseFromDb;
+use crate::shared::modules::versioning::traits::versioned::Versioned;
 
 #[derive(Serialize, Deserialize, Debug, JsonSchema, OperationIo)]
 #[serde(rename_all = "camelCase")]
 pub struct ExpenseEntity {
+    /// Time-ordered UUID (v7) generated by the server
     pub id: String,
     pub customer_id: String,
     pub amount: f64,
     pub date: chrono::DateTime<chrono::FixedOffset>,
-    pub category: ExpenseCategory,
+    pub category_id: String,
+    /// Code of a system category, e.g. `FOOD`
+    pub category: Option<String>,
+    pub note: Option<String>,
     pub created_at: chrono::DateTime<chrono::FixedOffset>,
     pub updated_at: chrono::DateTime<chrono::FixedOffset>,
+    /// Bumped on every update, pass it in `If-Match` to update or delete only this version
+    pub version: i64,
+    /// Set while the expense is in the trash
+    pub deleted_at: Option<chrono::DateTime<chrono::FixedOffset>>,
+    /// Id of the transaction in its source, e.g. a bank statement
+    pub external_id: Option<String>,
 }
 
 impl From<ExpenseFromDb> for ExpenseEntity {
@@ -24,9 +36,32 @@ impl From<ExpenseFromDb> for ExpenseEntity {
             customer_id: value.customer_id,
             amount: value.amount,
             date: value.date,
-            category: value.category,
+            category_id: value.category_id,
+            category: value.category_code,
+            note: value.note,
             created_at: value.created_at,
             updated_at: value.updated_at,
+            version: value.version,
+            deleted_at: value.deleted_at,
+            external_id: value.external_id,
         }
     }
 }
+
+impl Versioned for ExpenseEntity {
+    fn get_version(&self) -> i64 {
+        self.version
+    }
+}
+
+impl Audited for ExpenseEntity {
+    const AUDIT_ENTITY_TYPE: AuditEntityType = AuditEntityType::Expense;
+
+    fn get_audit_id(&self) -> &str {
+        &self.id
+    }
+
+    fn get_audit_owner_id(&self) -> &str {
+        &self.customer_id
+    }
+}
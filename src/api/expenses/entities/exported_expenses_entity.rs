@@ -0,0 +1,65 @@
+use aide::OperationOutput;
+use aide::generate::GenContext;
+use aide::openapi::{MediaType, Operation, Response as OpenApiResponse};
+use axum::body::Body;
+use axum::http::{StatusCode, header};
+use axum::response::{IntoResponse, Response};
+
+use crate::api::expenses::types::export_format::ExportFormat;
+
+/// File of exported expenses, the body is streamed while the expenses are read
+pub struct ExportedExpensesEntity {
+    pub format: ExportFormat,
+    pub body: Body,
+}
+
+impl IntoResponse for ExportedExpensesEntity {
+    fn into_response(self) -> Response {
+        let content_disposition = format!(
+            "attachment; filename=\"expenses.{}\"",
+            self.format.get_file_extension()
+        );
+
+        (
+            [
+                (
+                    header::CONTENT_TYPE,
+                    self.format.get_content_type().to_string(),
+                ),
+                (header::CONTENT_DISPOSITION, content_disposition),
+            ],
+            self.body,
+        )
+            .into_response()
+    }
+}
+
+impl OperationOutput for ExportedExpensesEntity {
+    type Inner = Self;
+
+    fn operation_response(
+        _ctx: &mut GenContext,
+        _operation: &mut Operation,
+    ) -> Option<OpenApiResponse> {
+        let mut response = OpenApiResponse {
+            description: "File of exported expenses".into(),
+            ..Default::default()
+        };
+        for format in ExportFormat::ALL {
+            response
+                .content
+                .insert(format.get_content_type().into(), MediaType::default());
+        }
+
+        Some(response)
+    }
+
+    fn inferred_responses(
+        ctx: &mut GenContext,
+        operation: &mut Operation,
+    ) -> Vec<(Option<u16>, OpenApiResponse)> {
+        Self::operation_response(ctx, operation)
+            .map(|response| vec![(Some(StatusCode::OK.as_u16()), response)])
+            .unwrap_or_default()
+    }
+}
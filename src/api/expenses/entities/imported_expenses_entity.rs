@@ -0,0 +1,58 @@
+use aide::OperationOutput;
+use aide::generate::GenContext;
+use aide::openapi::{Operation, Response as OpenApiResponse};
+use axum::Json;
+use axum::http::StatusCode;
+use axum::response::{IntoResponse, Response};
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+use crate::api::expenses::entities::created_expenses_entity::CreatedExpensesEntity;
+
+/// Result of an import, indexes of duplicates and errors are the lines of the rows in the file.
+/// Responded with 207 Multi-Status if some rows failed
+#[derive(Serialize, Deserialize, Debug, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct ImportedExpensesEntity {
+    /// Expenses were not created, `expenses` are the ones which would be
+    pub is_dry_run: bool,
+    #[serde(flatten)]
+    pub result: CreatedExpensesEntity,
+}
+
+impl IntoResponse for ImportedExpensesEntity {
+    fn into_response(self) -> Response {
+        let status = if self.result.errors.is_empty() {
+            StatusCode::OK
+        } else {
+            StatusCode::MULTI_STATUS
+        };
+
+        (status, Json(self)).into_response()
+    }
+}
+
+impl OperationOutput for ImportedExpensesEntity {
+    type Inner = Self;
+
+    fn operation_response(
+        ctx: &mut GenContext,
+        operation: &mut Operation,
+    ) -> Option<OpenApiResponse> {
+        Json::<Self>::operation_response(ctx, operation)
+    }
+
+    fn inferred_responses(
+        ctx: &mut GenContext,
+        operation: &mut Operation,
+    ) -> Vec<(Option<u16>, OpenApiResponse)> {
+        let Some(response) = Self::operation_response(ctx, operation) else {
+            return Vec::new();
+        };
+
+        vec![
+            (Some(StatusCode::OK.as_u16()), response.clone()),
+            (Some(StatusCode::MULTI_STATUS.as_u16()), response),
+        ]
+    }
+}
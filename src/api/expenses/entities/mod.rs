@@ -1 +1,7 @@
+pub mod bulk_expenses_result_entity;
+pub mod categorized_expenses_entity;
+pub mod created_expenses_entity;
 pub mod expense_entity;
+pub mod exported_expenses_entity;
+pub mod imported_expenses_entity;
+pub mod tested_category_rule_entity;
@@ -0,0 +1,14 @@
+use aide::OperationIo;
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+use crate::api::expenses::entities::expense_entity::ExpenseEntity;
+
+#[derive(Serialize, Deserialize, Debug, JsonSchema, OperationIo)]
+#[serde(rename_all = "camelCase")]
+pub struct TestedCategoryRuleEntity {
+    /// Amount of the expenses the rule matches
+    pub matched: usize,
+    /// The earliest matching expenses, at most 100
+    pub expenses: Vec<ExpenseEntity>,
+}
@@ -1,28 +1,66 @@
+use crate::api::audit_logs::entities::audit_log_entity::AuditLogEntity;
+use crate::api::expenses::dto::apply_category_rules_dto::ApplyCategoryRulesDto;
+use crate::api::expenses::dto::bulk_expenses_selector_dto::BulkExpensesSelectorDto;
+use crate::api::expenses::dto::bulk_update_expenses_dto::BulkUpdateExpensesDto;
 use crate::api::expenses::dto::create_expense_dto::CreateExpenseDto;
+use crate::api::expenses::dto::create_expenses_query_dto::CreateExpensesQueryDto;
+use crate::api::expenses::dto::export_expenses_query_dto::ExportExpensesQueryDto;
+use crate::api::expenses::dto::find_expenses_query_dto::FindExpensesQueryDto;
+use crate::api::expenses::dto::import_expenses_query_dto::ImportExpensesQueryDto;
+use crate::api::expenses::dto::test_category_rule_dto::TestCategoryRuleDto;
 use crate::api::expenses::dto::update_expense_dto::UpdateExpenseDto;
+use crate::api::expenses::entities::bulk_expenses_result_entity::BulkExpensesResultEntity;
+use crate::api::expenses::entities::categorized_expenses_entity::CategorizedExpensesEntity;
+use crate::api::expenses::entities::created_expenses_entity::CreatedExpensesEntity;
 use crate::api::expenses::entities::expense_entity::ExpenseEntity;
+use crate::api::expenses::entities::exported_expenses_entity::ExportedExpensesEntity;
+use crate::api::expenses::entities::imported_expenses_entity::ImportedExpensesEntity;
+use crate::api::expenses::entities::tested_category_rule_entity::TestedCategoryRuleEntity;
 use crate::api::expenses::expenses_service::ExpensesService;
+use crate::api::expenses::parsers::get_expenses_parser;
 use crate::shared::errors::http_error::HttpError;
+use crate::shared::modules::audit::structs::audit_context::AuditContext;
 use crate::shared::modules::auth::structs::user::User;
-use axum::extract::{Path, State};
+use crate::shared::modules::db::structs::id_path::IdPath;
+use crate::shared::modules::upload::structs::uploaded_file::UploadedFile;
+use crate::shared::modules::versioning::structs::if_match::IfMatch;
+use crate::shared::modules::versioning::structs::versioned_json::VersionedJson;
+use axum::extract::{Query, State};
 use axum::{Extension, Json};
 use std::sync::Arc;
 
 pub async fn find_many(
     Extension(user): Extension<User>,
     State(expenses_service): State<Arc<ExpensesService>>,
+    Query(query): Query<FindExpensesQueryDto>,
 ) -> Result<ExpenseEntitiesJson, HttpError> {
     let found_expenses = if user.is_admin() {
-        expenses_service.find_many().await?
+        expenses_service.find_many(query).await?
     } else {
-        expenses_service.find_many_as_customer(&user.id).await?
+        expenses_service
+            .find_many_as_customer(&user.id, query)
+            .await?
     };
 
     Ok(Json(found_expenses))
 }
 
+pub async fn export(
+    Extension(user): Extension<User>,
+    State(expenses_service): State<Arc<ExpensesService>>,
+    Query(query): Query<ExportExpensesQueryDto>,
+) -> Result<ExportedExpensesEntity, HttpError> {
+    let exported_expenses = if user.is_admin() {
+        expenses_service.export(query).await?
+    } else {
+        expenses_service.export_as_customer(&user.id, query).await?
+    };
+
+    Ok(exported_expenses)
+}
+
 pub async fn find_one(
-    Path(expense_id): Path<String>,
+    IdPath(expense_id): IdPath,
     Extension(user): Extension<User>,
     State(expenses_service): State<Arc<ExpensesService>>,
 ) -> Result<ExpenseEntityJson, HttpError> {
@@ -34,43 +72,179 @@ pub async fn find_one(
             .await?
     };
 
-    Ok(Json(found_expense))
+    Ok(VersionedJson(found_expense))
+}
+
+pub async fn find_trash(
+    Extension(user): Extension<User>,
+    State(expenses_service): State<Arc<ExpensesService>>,
+) -> Result<ExpenseEntitiesJson, HttpError> {
+    let deleted_expenses = if user.is_admin() {
+        expenses_service.find_trash().await?
+    } else {
+        expenses_service.find_trash_as_customer(&user.id).await?
+    };
+
+    Ok(Json(deleted_expenses))
 }
 
 pub async fn create_many(
     Extension(user): Extension<User>,
+    audit_context: AuditContext,
     State(expenses_service): State<Arc<ExpensesService>>,
+    Query(query): Query<CreateExpensesQueryDto>,
     Json(expense_entities): Json<Vec<CreateExpenseDto>>,
-) -> Result<ExpenseEntitiesJson, HttpError> {
+) -> Result<CreatedExpensesEntity, HttpError> {
     let created_expenses = expenses_service
-        .create_many(expense_entities, &user.id)
+        .create_many(
+            expense_entities,
+            &user.id,
+            query.duplicate_policy,
+            query.atomic,
+            false,
+            &audit_context,
+        )
         .await?;
 
-    Ok(Json(created_expenses))
+    Ok(created_expenses)
+}
+
+pub async fn import(
+    Extension(user): Extension<User>,
+    audit_context: AuditContext,
+    State(expenses_service): State<Arc<ExpensesService>>,
+    Query(query): Query<ImportExpensesQueryDto>,
+    uploaded_file: UploadedFile,
+) -> Result<ImportedExpensesEntity, HttpError> {
+    let expenses_parser = get_expenses_parser(&query, &uploaded_file.content)?;
+    let content = uploaded_file.content;
+    // Parsing is CPU-bound, large statements would block the runtime
+    let imported_rows = tokio::task::spawn_blocking(move || expenses_parser.parse(&content))
+        .await
+        .map_err(|err| HttpError::Internal(format!("Expenses import is failed: {err}")))??;
+
+    let imported_expenses = expenses_service
+        .import(
+            imported_rows,
+            &user.id,
+            query.duplicate_policy,
+            query.atomic,
+            query.dry_run,
+            &audit_context,
+        )
+        .await?;
+
+    Ok(imported_expenses)
 }
 
 pub async fn update_one(
-    Path(expense_id): Path<String>,
+    IdPath(expense_id): IdPath,
     Extension(user): Extension<User>,
+    audit_context: AuditContext,
+    IfMatch(expected_versions): IfMatch,
     State(expenses_service): State<Arc<ExpensesService>>,
     Json(update_dto): Json<UpdateExpenseDto>,
 ) -> Result<ExpenseEntityJson, HttpError> {
     let updated_expense = expenses_service
-        .update(&expense_id, update_dto, &user.id)
+        .update(
+            &expense_id,
+            update_dto,
+            &user.id,
+            expected_versions,
+            &audit_context,
+        )
         .await?;
 
-    Ok(Json(updated_expense))
+    Ok(VersionedJson(updated_expense))
 }
 
 pub async fn delete_one(
-    Path(expense_id): Path<String>,
+    IdPath(expense_id): IdPath,
+    Extension(user): Extension<User>,
+    audit_context: AuditContext,
+    IfMatch(expected_versions): IfMatch,
+    State(expenses_service): State<Arc<ExpensesService>>,
+) -> Result<ExpenseEntityJson, HttpError> {
+    let deleted_expense = expenses_service
+        .delete(&expense_id, &user.id, expected_versions, &audit_context)
+        .await?;
+
+    Ok(VersionedJson(deleted_expense))
+}
+
+pub async fn restore_one(
+    IdPath(expense_id): IdPath,
     Extension(user): Extension<User>,
+    audit_context: AuditContext,
     State(expenses_service): State<Arc<ExpensesService>>,
 ) -> Result<ExpenseEntityJson, HttpError> {
-    let deleted_expense = expenses_service.delete(&expense_id, &user.id).await?;
+    let restored_expense = expenses_service
+        .restore(&expense_id, &user.id, &audit_context)
+        .await?;
+
+    Ok(VersionedJson(restored_expense))
+}
+
+pub async fn update_many(
+    Extension(user): Extension<User>,
+    audit_context: AuditContext,
+    State(expenses_service): State<Arc<ExpensesService>>,
+    Json(bulk_update_dto): Json<BulkUpdateExpensesDto>,
+) -> Result<Json<BulkExpensesResultEntity>, HttpError> {
+    let updated_expenses = expenses_service
+        .update_many(bulk_update_dto, &user.id, &audit_context)
+        .await?;
+
+    Ok(Json(updated_expenses))
+}
+
+pub async fn test_category_rule(
+    Extension(user): Extension<User>,
+    State(expenses_service): State<Arc<ExpensesService>>,
+    Json(test_category_rule_dto): Json<TestCategoryRuleDto>,
+) -> Result<Json<TestedCategoryRuleEntity>, HttpError> {
+    let tested_category_rule = expenses_service
+        .test_category_rule(test_category_rule_dto, &user.id)
+        .await?;
+
+    Ok(Json(tested_category_rule))
+}
+
+pub async fn apply_category_rules(
+    Extension(user): Extension<User>,
+    audit_context: AuditContext,
+    State(expenses_service): State<Arc<ExpensesService>>,
+    Json(apply_category_rules_dto): Json<ApplyCategoryRulesDto>,
+) -> Result<Json<CategorizedExpensesEntity>, HttpError> {
+    let categorized_expenses = expenses_service
+        .apply_category_rules(apply_category_rules_dto, &user.id, &audit_context)
+        .await?;
+
+    Ok(Json(categorized_expenses))
+}
+
+pub async fn delete_many(
+    Extension(user): Extension<User>,
+    audit_context: AuditContext,
+    State(expenses_service): State<Arc<ExpensesService>>,
+    Json(selector): Json<BulkExpensesSelectorDto>,
+) -> Result<Json<BulkExpensesResultEntity>, HttpError> {
+    let deleted_expenses = expenses_service
+        .delete_many(selector, &user.id, &audit_context)
+        .await?;
+
+    Ok(Json(deleted_expenses))
+}
+
+pub async fn find_history(
+    IdPath(expense_id): IdPath,
+    Extension(user): Extension<User>,
+    State(expenses_service): State<Arc<ExpensesService>>,
+) -> Result<Json<Vec<AuditLogEntity>>, HttpError> {
+    let history = expenses_service.find_history(&expense_id, &user).await?;
 
-    Ok(Json(deleted_expense))
+    Ok(Json(history))
 }
 
-pub type ExpenseEntityJson = Json<ExpenseEntity>;
+pub type ExpenseEntityJson = VersionedJson<ExpenseEntity>;
 pub type ExpenseEntitiesJson = Json<Vec<ExpenseEntity>>;
@@ -4,31 +4,163 @@ use crate::api::expenses::dto::update_expense_db_dto::UpdateExpenseDbDto;
 use crate::api::expenses::traits::expenses_repository::ExpensesRepositoryTrait;
 use crate::api::expenses::types::expense_from_db::ExpenseFromDb;
 use crate::shared::errors::http_error::HttpError;
+use crate::shared::modules::db::db_connection::DbConnection;
 use async_trait::async_trait;
-use sea_orm::{ColumnTrait, DatabaseConnection, EntityTrait, IntoActiveModel, QueryFilter};
+use futures_util::StreamExt;
+use futures_util::stream::BoxStream;
+use sea_orm::prelude::DateTimeWithTimeZone;
+use sea_orm::sea_query::Expr;
+use sea_orm::{
+    ColumnTrait, Condition, ConnectionTrait, DatabaseConnection, DatabaseTransaction, EntityTrait,
+    IntoActiveModel, ModelTrait, QueryFilter, QueryOrder, QuerySelect,
+};
+use std::collections::HashMap;
 use std::sync::Arc;
 
-use crate::shared::modules::db::entities::expense;
-use crate::shared::modules::db::entities::prelude::Expense;
 use crate::shared::modules::db::entities::expense::ActiveModel as ExpenseActiveModel;
+use crate::shared::modules::db::entities::prelude::{Category, Expense};
+use crate::shared::modules::db::entities::{category, expense};
 
 pub struct ExpensesRepository {
-    sea_orm_client: Arc<DatabaseConnection>,
+    connection: DbConnection,
 }
 
 impl ExpensesRepository {
     pub fn new(sea_orm_client: Arc<DatabaseConnection>) -> Self {
-        Self { sea_orm_client }
+        Self {
+            connection: DbConnection::Pool(sea_orm_client),
+        }
+    }
+
+    fn get_not_found_error(id: &str) -> HttpError {
+        HttpError::NotFound(format!("Expense with id {id} not found"))
+    }
+
+    fn get_filter_condition(filter: FindExpensesDto) -> Condition {
+        Condition::all()
+            .add_option(
+                filter
+                    .customer_id
+                    .map(|customer_id| expense::Column::CustomerId.eq(customer_id)),
+            )
+            .add_option(filter.ids.map(|ids| expense::Column::Id.is_in(ids)))
+            .add_option(
+                filter
+                    .category_ids
+                    .map(|category_ids| expense::Column::CategoryId.is_in(category_ids)),
+            )
+            .add_option(
+                filter
+                    .date_from
+                    .map(|date_from| expense::Column::Date.gte(date_from)),
+            )
+            .add_option(
+                filter
+                    .date_to
+                    .map(|date_to| expense::Column::Date.lte(date_to)),
+            )
+            .add(if filter.is_deleted {
+                expense::Column::DeletedAt.is_not_null()
+            } else {
+                expense::Column::DeletedAt.is_null()
+            })
+    }
+
+    /// Moves matching expenses to the trash, they stay there until they are purged
+    fn soft_delete_many() -> sea_orm::UpdateMany<Expense> {
+        Expense::update_many()
+            .col_expr(expense::Column::DeletedAt, Expr::current_timestamp().into())
+            .col_expr(
+                expense::Column::Version,
+                Expr::col(expense::Column::Version).add(1),
+            )
+            .col_expr(expense::Column::UpdatedAt, Expr::current_timestamp().into())
+    }
+
+    /// Expense returned by a write with its category, found ones are joined with it instead
+    async fn with_category(
+        connection: &impl ConnectionTrait,
+        expense: expense::Model,
+    ) -> Result<ExpenseFromDb, HttpError> {
+        let category = expense.find_related(Category).one(connection).await?;
+
+        Ok((expense, category).into())
+    }
+
+    async fn with_categories(
+        connection: &impl ConnectionTrait,
+        expenses: Vec<expense::Model>,
+    ) -> Result<Vec<ExpenseFromDb>, HttpError> {
+        let category_ids: Vec<String> = expenses
+            .iter()
+            .map(|expense| expense.category_id.clone())
+            .collect();
+        let categories: HashMap<String, category::Model> = Category::find()
+            .filter(category::Column::Id.is_in(category_ids))
+            .all(connection)
+            .await?
+            .into_iter()
+            .map(|category| (category.id.clone(), category))
+            .collect();
+
+        let expenses = expenses
+            .into_iter()
+            .map(|expense| {
+                let category = categories.get(&expense.category_id).cloned();
+
+                (expense, category).into()
+            })
+            .collect();
+
+        Ok(expenses)
+    }
+
+    /// Locks the expense until the end of the transaction and checks the write preconditions:
+    /// the expense is active or in the trash (`is_deleted`), belongs to the customer (if given)
+    /// and has one of the expected versions
+    async fn lock_for_write(
+        transaction: &DatabaseTransaction,
+        id: &str,
+        customer_id: Option<&str>,
+        expected_versions: Option<&[i64]>,
+        is_deleted: bool,
+    ) -> Result<expense::Model, HttpError> {
+        let found_expense = Expense::find_by_id(id)
+            .lock_exclusive()
+            .one(transaction)
+            .await?
+            .filter(|expense| {
+                expense.deleted_at.is_some() == is_deleted
+                    && customer_id.is_none_or(|customer_id| customer_id == expense.customer_id)
+            })
+            .ok_or_else(|| Self::get_not_found_error(id))?;
+
+        if expected_versions.is_some_and(|versions| !versions.contains(&found_expense.version)) {
+            return Err(HttpError::PreconditionFailed(format!(
+                "Expense with id {id} was modified, If-Match does not match the current version"
+            )));
+        }
+
+        Ok(found_expense)
     }
 }
 
 #[async_trait]
 impl ExpensesRepositoryTrait for ExpensesRepository {
+    fn with_connection(
+        &self,
+        connection: DbConnection,
+    ) -> Arc<dyn ExpensesRepositoryTrait + Send + Sync> {
+        Arc::new(Self { connection })
+    }
+
     async fn find_one(&self, id: &str) -> Result<ExpenseFromDb, HttpError> {
         let found_expense = Expense::find_by_id(id)
-            .one(self.sea_orm_client.as_ref())
+            .find_also_related(Category)
+            .filter(expense::Column::DeletedAt.is_null())
+            .one(&self.connection)
             .await?
-            .ok_or(HttpError::NotFound(format!("Expense with id {id} not found")))?
+            .ok_or_else(|| Self::get_not_found_error(id))?
             .into();
 
         Ok(found_expense)
@@ -38,22 +170,49 @@ impl ExpensesRepositoryTrait for ExpensesRepository {
         &self,
         filter: Option<FindExpensesDto>,
     ) -> Result<Vec<ExpenseFromDb>, HttpError> {
-        let found_expenses = if let Some(filter) = filter {
-            Expense::find()
-                .filter(expense::Column::CustomerId.eq(filter.customer_id))
-                .all(self.sea_orm_client.as_ref())
-                .await?
-                .into_iter()
-                .map(Into::into)
-                .collect()
-        } else {
-            Expense::find()
-                .all(self.sea_orm_client.as_ref())
-                .await?
-                .into_iter()
-                .map(Into::into)
-                .collect()
-        };
+        let found_expenses = Expense::find()
+            .find_also_related(Category)
+            .filter(Self::get_filter_condition(filter.unwrap_or_default()))
+            .all(&self.connection)
+            .await?
+            .into_iter()
+            .map(Into::into)
+            .collect();
+
+        Ok(found_expenses)
+    }
+
+    async fn stream_many(
+        &self,
+        filter: FindExpensesDto,
+    ) -> Result<BoxStream<'_, Result<ExpenseFromDb, HttpError>>, HttpError> {
+        let found_expenses = Expense::find()
+            .find_also_related(Category)
+            .filter(Self::get_filter_condition(filter))
+            .order_by_asc(expense::Column::Date)
+            .order_by_asc(expense::Column::Id)
+            .stream(&self.connection)
+            .await?
+            .map(|expense| Ok(expense?.into()))
+            .boxed();
+
+        Ok(found_expenses)
+    }
+
+    async fn find_by_external_ids(
+        &self,
+        customer_id: &str,
+        external_ids: Vec<String>,
+    ) -> Result<Vec<ExpenseFromDb>, HttpError> {
+        let found_expenses = Expense::find()
+            .find_also_related(Category)
+            .filter(expense::Column::CustomerId.eq(customer_id))
+            .filter(expense::Column::ExternalId.is_in(external_ids))
+            .all(&self.connection)
+            .await?
+            .into_iter()
+            .map(Into::into)
+            .collect();
 
         Ok(found_expenses)
     }
@@ -67,36 +226,157 @@ impl ExpensesRepositoryTrait for ExpensesRepository {
                 .into_iter()
                 .map(|create_dto| create_dto.into_active_model()),
         )
-        .exec_with_returning_many(self.sea_orm_client.as_ref())
+        .exec_with_returning_many(&self.connection)
         .await?;
 
-        Ok(created_expenses.into_iter().map(Into::into).collect())
+        Self::with_categories(&self.connection, created_expenses).await
+    }
+
+    async fn create_one(&self, create_dto: CreateExpenseDbDto) -> Result<ExpenseFromDb, HttpError> {
+        let transaction = self.connection.begin().await?;
+
+        let created_expense = Expense::insert(create_dto.into_active_model())
+            .exec_with_returning(&transaction)
+            .await?;
+        let created_expense = Self::with_category(&transaction, created_expense).await?;
+
+        transaction.commit().await?;
+
+        Ok(created_expense)
     }
 
     async fn update_one(
         &self,
         id: &str,
         update_dto: UpdateExpenseDbDto,
+        customer_id: Option<&str>,
+        expected_versions: Option<Vec<i64>>,
     ) -> Result<ExpenseFromDb, HttpError> {
-        let updated_expense = Expense::update(ExpenseActiveModel::from(update_dto))
+        let transaction = self.connection.begin().await?;
+
+        Self::lock_for_write(
+            &transaction,
+            id,
+            customer_id,
+            expected_versions.as_deref(),
+            false,
+        )
+        .await?;
+
+        let updated_expense = Expense::update_many()
+            .set(ExpenseActiveModel::from(update_dto))
+            .col_expr(
+                expense::Column::Version,
+                Expr::col(expense::Column::Version).add(1),
+            )
+            .col_expr(expense::Column::UpdatedAt, Expr::current_timestamp().into())
             .filter(expense::Column::Id.eq(id))
-            .exec(self.sea_orm_client.as_ref())
-            .await?;
+            .exec_with_returning(&transaction)
+            .await?
+            .pop()
+            .ok_or_else(|| Self::get_not_found_error(id))?;
+        let updated_expense = Self::with_category(&transaction, updated_expense).await?;
 
-        Ok(updated_expense.into())
+        transaction.commit().await?;
+
+        Ok(updated_expense)
     }
 
-    async fn delete_one(&self, id: &str) -> Result<ExpenseFromDb, HttpError> {
-        let deleted_expense = Expense::delete_by_id(id)
-            .exec_with_returning(self.sea_orm_client.as_ref())
+    async fn delete_one(
+        &self,
+        id: &str,
+        customer_id: Option<&str>,
+        expected_versions: Option<Vec<i64>>,
+    ) -> Result<ExpenseFromDb, HttpError> {
+        let transaction = self.connection.begin().await?;
+
+        Self::lock_for_write(
+            &transaction,
+            id,
+            customer_id,
+            expected_versions.as_deref(),
+            false,
+        )
+        .await?;
+
+        let deleted_expense = Self::soft_delete_many()
+            .filter(expense::Column::Id.eq(id))
+            .exec_with_returning(&transaction)
             .await?
-            .first()
-            .ok_or(HttpError::NotFound(format!(
-                "Expense with id {id} not found"
-            )))?
-            .clone()
-            .into();
+            .pop()
+            .ok_or_else(|| Self::get_not_found_error(id))?;
+        let deleted_expense = Self::with_category(&transaction, deleted_expense).await?;
+
+        transaction.commit().await?;
 
         Ok(deleted_expense)
     }
+
+    async fn update_many(
+        &self,
+        filter: FindExpensesDto,
+        update_dto: UpdateExpenseDbDto,
+    ) -> Result<Vec<ExpenseFromDb>, HttpError> {
+        let updated_expenses = Expense::update_many()
+            .set(ExpenseActiveModel::from(update_dto))
+            .col_expr(
+                expense::Column::Version,
+                Expr::col(expense::Column::Version).add(1),
+            )
+            .col_expr(expense::Column::UpdatedAt, Expr::current_timestamp().into())
+            .filter(Self::get_filter_condition(filter))
+            .exec_with_returning(&self.connection)
+            .await?;
+
+        Self::with_categories(&self.connection, updated_expenses).await
+    }
+
+    async fn delete_many(&self, filter: FindExpensesDto) -> Result<Vec<ExpenseFromDb>, HttpError> {
+        let deleted_expenses = Self::soft_delete_many()
+            .filter(Self::get_filter_condition(filter))
+            .exec_with_returning(&self.connection)
+            .await?;
+
+        Self::with_categories(&self.connection, deleted_expenses).await
+    }
+
+    async fn restore_one(
+        &self,
+        id: &str,
+        customer_id: Option<&str>,
+    ) -> Result<ExpenseFromDb, HttpError> {
+        let transaction = self.connection.begin().await?;
+
+        Self::lock_for_write(&transaction, id, customer_id, None, true).await?;
+
+        let restored_expense = Expense::update_many()
+            .col_expr(
+                expense::Column::DeletedAt,
+                Expr::value(Option::<DateTimeWithTimeZone>::None),
+            )
+            .col_expr(
+                expense::Column::Version,
+                Expr::col(expense::Column::Version).add(1),
+            )
+            .col_expr(expense::Column::UpdatedAt, Expr::current_timestamp().into())
+            .filter(expense::Column::Id.eq(id))
+            .exec_with_returning(&transaction)
+            .await?
+            .pop()
+            .ok_or_else(|| Self::get_not_found_error(id))?;
+        let restored_expense = Self::with_category(&transaction, restored_expense).await?;
+
+        transaction.commit().await?;
+
+        Ok(restored_expense)
+    }
+
+    async fn purge_deleted(&self, deleted_before: DateTimeWithTimeZone) -> Result<u64, HttpError> {
+        let delete_result = Expense::delete_many()
+            .filter(expense::Column::DeletedAt.lt(deleted_before))
+            .exec(&self.connection)
+            .await?;
+
+        Ok(delete_result.rows_affected)
+    }
 }
@@ -1,29 +1,106 @@
-use futures_util::future::try_join;
-use std::sync::Arc;
-use sea_orm::prelude::Decimal;
+use crate::api::audit_logs::audit_logs_service::AuditLogsService;
+use crate::api::audit_logs::dto::find_audit_logs_dto::FindAuditLogsDto;
+use crate::api::audit_logs::entities::audit_log_entity::AuditLogEntity;
+use crate::api::audit_logs::types::audit_action::AuditAction;
+use crate::api::audit_logs::types::audit_entity_type::AuditEntityType;
+use crate::api::categories::categories_service::CategoriesService;
+use crate::api::categories::types::category_from_db::CategoryFromDb;
+use crate::api::categories::types::category_resolver::CategoryResolver;
+use crate::api::category_rules::category_rules_service::CategoryRulesService;
+use crate::api::category_rules::types::category_rule_matcher::CategoryRuleMatcher;
+use crate::api::category_rules::types::category_rules_engine::CategoryRulesEngine;
 use crate::api::customers::customers_service::CustomersService;
+use crate::api::expenses::dto::apply_category_rules_dto::ApplyCategoryRulesDto;
+use crate::api::expenses::dto::bulk_expenses_selector_dto::BulkExpensesSelectorDto;
+use crate::api::expenses::dto::bulk_update_expenses_dto::BulkUpdateExpensesDto;
 use crate::api::expenses::dto::create_expense_db_dto::CreateExpenseDbDto;
 use crate::api::expenses::dto::create_expense_dto::CreateExpenseDto;
+use crate::api::expenses::dto::export_expenses_query_dto::ExportExpensesQueryDto;
 use crate::api::expenses::dto::find_expenses_dto::FindExpensesDto;
+use crate::api::expenses::dto::find_expenses_query_dto::FindExpensesQueryDto;
+use crate::api::expenses::dto::test_category_rule_dto::TestCategoryRuleDto;
+use crate::api::expenses::dto::update_expense_db_dto::UpdateExpenseDbDto;
 use crate::api::expenses::dto::update_expense_dto::UpdateExpenseDto;
+use crate::api::expenses::entities::bulk_expenses_result_entity::BulkExpensesResultEntity;
+use crate::api::expenses::entities::categorized_expenses_entity::CategorizedExpensesEntity;
+use crate::api::expenses::entities::created_expenses_entity::{
+    CreatedExpensesEntity, DuplicateExpenseEntity, ExpenseItemErrorEntity,
+};
 use crate::api::expenses::entities::expense_entity::ExpenseEntity;
+use crate::api::expenses::entities::exported_expenses_entity::ExportedExpensesEntity;
+use crate::api::expenses::entities::imported_expenses_entity::ImportedExpensesEntity;
+use crate::api::expenses::entities::tested_category_rule_entity::TestedCategoryRuleEntity;
+use crate::api::expenses::exporters::get_expenses_exporter;
+use crate::api::expenses::traits::expenses_exporter::ExpensesExporter;
 use crate::api::expenses::traits::expenses_repository::ExpensesRepositoryTrait;
+use crate::api::expenses::types::date_localizer::DateLocalizer;
+use crate::api::expenses::types::duplicate_policy::DuplicatePolicy;
+use crate::api::expenses::types::expense_category::ExpenseCategory;
+use crate::api::expenses::types::expense_from_db::ExpenseFromDb;
+use crate::api::expenses::types::export_format::ExportFormat;
+use crate::api::expenses::types::exported_expense::ExportedExpense;
+use crate::api::expenses::types::imported_row::ImportedRow;
 use crate::shared::errors::http_error::HttpError;
+use crate::shared::modules::audit::structs::audit_context::AuditContext;
+use crate::shared::modules::auth::structs::user::User;
+use crate::shared::modules::db::unit_of_work::UnitOfWork;
+use crate::shared::modules::db::utils::generate_id;
+use axum::body::{Body, Bytes};
+use chrono::{DateTime, FixedOffset, TimeDelta, Utc};
+use futures_util::future::try_join;
+use futures_util::{StreamExt, stream};
+use sea_orm::prelude::Decimal;
+use std::collections::HashMap;
+use std::mem;
+use std::slice;
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::io::AsyncReadExt;
+use tokio::sync::mpsc;
+use tokio::sync::mpsc::error::SendTimeoutError;
+use validator::Validate;
+
+/// Size of the chunks an export is sent to the client in
+const EXPORT_CHUNK_SIZE: usize = 64 * 1024;
+/// Chunks written ahead of the client before the export waits for it
+const EXPORT_BUFFERED_CHUNKS: usize = 4;
+/// Exports of clients which stop reading are aborted, so they don't hold a DB connection
+const EXPORT_SEND_TIMEOUT: Duration = Duration::from_secs(30);
+
+/// Expenses with the same amount, category and note this close to each other are likely duplicates
+const DUPLICATE_WINDOW_HOURS: i64 = 24;
+
+/// Matching expenses returned by a rule test
+const TESTED_RULE_EXPENSES_LIMIT: usize = 100;
+/// Expenses recategorized by one application of the rules, keeps the transaction short
+const APPLIED_CATEGORY_RULES_LIMIT: usize = 1000;
 
 #[derive(Clone)]
 pub struct ExpensesService {
     pub expenses_repository: Arc<dyn ExpensesRepositoryTrait + Send + Sync>,
     pub customers_service: Arc<CustomersService>,
+    pub categories_service: Arc<CategoriesService>,
+    pub category_rules_service: Arc<CategoryRulesService>,
+    pub audit_logs_service: Arc<AuditLogsService>,
+    pub unit_of_work: Arc<UnitOfWork>,
 }
 
 impl ExpensesService {
     pub fn new(
         expenses_repository: Arc<dyn ExpensesRepositoryTrait + Send + Sync>,
         customers_service: Arc<CustomersService>,
+        categories_service: Arc<CategoriesService>,
+        category_rules_service: Arc<CategoryRulesService>,
+        audit_logs_service: Arc<AuditLogsService>,
+        unit_of_work: Arc<UnitOfWork>,
     ) -> Self {
         Self {
             expenses_repository,
             customers_service,
+            categories_service,
+            category_rules_service,
+            audit_logs_service,
+            unit_of_work,
         }
     }
 
@@ -53,10 +130,15 @@ impl ExpensesService {
         Ok(expense_from_db.into())
     }
 
-    pub async fn find_many(&self) -> Result<Vec<ExpenseEntity>, HttpError> {
+    pub async fn find_many(
+        &self,
+        query: FindExpensesQueryDto,
+    ) -> Result<Vec<ExpenseEntity>, HttpError> {
+        let find_dto = self.map_query_to_find_dto(query, None).await?;
+
         let expense_entities = self
             .expenses_repository
-            .find_many(None)
+            .find_many(Some(find_dto))
             .await?
             .into_iter()
             .map(ExpenseEntity::from)
@@ -68,11 +150,26 @@ impl ExpensesService {
     pub async fn find_many_as_customer(
         &self,
         user_id: &str,
+        query: FindExpensesQueryDto,
     ) -> Result<Vec<ExpenseEntity>, HttpError> {
         let customer = self.customers_service.find_one_by_user_id(user_id).await?;
+        let find_dto = self.map_query_to_find_dto(query, Some(customer.id)).await?;
+
+        let expense_entities = self
+            .expenses_repository
+            .find_many(Some(find_dto))
+            .await?
+            .into_iter()
+            .map(Into::into)
+            .collect();
+
+        Ok(expense_entities)
+    }
 
+    pub async fn find_trash(&self) -> Result<Vec<ExpenseEntity>, HttpError> {
         let find_dto = FindExpensesDto {
-            customer_id: Some(customer.id),
+            is_deleted: true,
+            ..Default::default()
         };
 
         let expense_entities = self
@@ -86,26 +183,413 @@ impl ExpensesService {
         Ok(expense_entities)
     }
 
-    pub async fn create_many(
+    pub async fn find_trash_as_customer(
         &self,
-        create_dtos: Vec<CreateExpenseDto>,
         user_id: &str,
     ) -> Result<Vec<ExpenseEntity>, HttpError> {
         let customer = self.customers_service.find_one_by_user_id(user_id).await?;
-        let create_dtos = create_dtos
-            .into_iter()
-            .map(|create_dto| Self::map_create_dto_to_create_db_dto(create_dto, &customer.id))
-            .collect();
 
-        let created_expenses_entities = self
+        let find_dto = FindExpensesDto {
+            customer_id: Some(customer.id),
+            is_deleted: true,
+            ..Default::default()
+        };
+
+        let expense_entities = self
             .expenses_repository
-            .create_many(create_dtos)
+            .find_many(Some(find_dto))
             .await?
             .into_iter()
             .map(Into::into)
             .collect();
 
-        Ok(created_expenses_entities)
+        Ok(expense_entities)
+    }
+
+    pub async fn export(
+        &self,
+        query: ExportExpensesQueryDto,
+    ) -> Result<ExportedExpensesEntity, HttpError> {
+        let date_localizer =
+            DateLocalizer::new(query.locale.as_deref(), query.utc_offset.as_deref())?;
+        let find_dto = self.map_query_to_find_dto(query.filter, None).await?;
+        // Custom categories of all the customers are not loaded for exports of all of them,
+        // their expenses are exported with category ids only
+        let category_resolver = match find_dto.customer_id.as_deref() {
+            Some(customer_id) => {
+                self.categories_service
+                    .get_resolver(Some(customer_id))
+                    .await?
+            }
+            None => self.categories_service.get_system_resolver().await?,
+        };
+
+        self.export_many(find_dto, query.format, date_localizer, category_resolver)
+    }
+
+    pub async fn export_as_customer(
+        &self,
+        user_id: &str,
+        query: ExportExpensesQueryDto,
+    ) -> Result<ExportedExpensesEntity, HttpError> {
+        let date_localizer =
+            DateLocalizer::new(query.locale.as_deref(), query.utc_offset.as_deref())?;
+        let customer = self.customers_service.find_one_by_user_id(user_id).await?;
+        let category_resolver = self
+            .categories_service
+            .get_resolver(Some(&customer.id))
+            .await?;
+        let find_dto = self
+            .map_query_to_find_dto(query.filter, Some(customer.id))
+            .await?;
+
+        self.export_many(find_dto, query.format, date_localizer, category_resolver)
+    }
+
+    /// Responds before the expenses are read: they are streamed from the database and written
+    /// to the response body in a background task, at most `EXPORT_BUFFERED_CHUNKS` chunks ahead
+    /// of the client
+    fn export_many(
+        &self,
+        find_dto: FindExpensesDto,
+        format: ExportFormat,
+        date_localizer: DateLocalizer,
+        category_resolver: CategoryResolver,
+    ) -> Result<ExportedExpensesEntity, HttpError> {
+        let expenses_exporter = get_expenses_exporter(format)?;
+        let expenses_repository = self.expenses_repository.clone();
+        let (sender, receiver) = mpsc::channel(EXPORT_BUFFERED_CHUNKS);
+
+        tokio::spawn(async move {
+            let export_result = Self::write_export(
+                expenses_repository.as_ref(),
+                find_dto,
+                expenses_exporter,
+                &date_localizer,
+                &category_resolver,
+                &sender,
+            )
+            .await;
+
+            if let Err(err) = export_result {
+                tracing::error!("Expenses export is failed with err: '{err}'");
+                // Aborts the response, so that the client does not take a part of the file
+                // for the whole one
+                let _ = sender.send_timeout(Err(err), EXPORT_SEND_TIMEOUT).await;
+            }
+        });
+
+        let body = Body::from_stream(stream::unfold(receiver, |mut receiver| async move {
+            receiver.recv().await.map(|chunk| (chunk, receiver))
+        }));
+
+        Ok(ExportedExpensesEntity { format, body })
+    }
+
+    async fn write_export(
+        expenses_repository: &(dyn ExpensesRepositoryTrait + Send + Sync),
+        find_dto: FindExpensesDto,
+        mut expenses_exporter: Box<dyn ExpensesExporter>,
+        date_localizer: &DateLocalizer,
+        category_resolver: &CategoryResolver,
+        sender: &mpsc::Sender<Result<Bytes, HttpError>>,
+    ) -> Result<(), HttpError> {
+        let mut expenses = expenses_repository.stream_many(find_dto).await?;
+        let mut buffer = Vec::with_capacity(EXPORT_CHUNK_SIZE);
+
+        expenses_exporter.write_header(&mut buffer)?;
+        while let Some(expense) = expenses.next().await {
+            let exported_expense =
+                ExportedExpense::new(expense?, date_localizer, category_resolver);
+            expenses_exporter.write_row(&exported_expense, &mut buffer)?;
+
+            if buffer.len() >= EXPORT_CHUNK_SIZE && !Self::send_chunk(sender, &mut buffer).await? {
+                return Ok(());
+            }
+        }
+
+        // Writing the rest of an XLSX file compresses the whole file
+        let rest_file = tokio::task::spawn_blocking(move || expenses_exporter.finish())
+            .await
+            .map_err(|err| HttpError::Internal(format!("Expenses export is failed: {err}")))??;
+
+        if let Some(rest_file) = rest_file {
+            let mut rest_file = tokio::fs::File::from_std(rest_file);
+
+            loop {
+                let read = rest_file.read_buf(&mut buffer).await.map_err(|err| {
+                    HttpError::Internal(format!("Failed to read expenses export: {err}"))
+                })?;
+
+                if read == 0 {
+                    break;
+                }
+                if buffer.len() >= EXPORT_CHUNK_SIZE
+                    && !Self::send_chunk(sender, &mut buffer).await?
+                {
+                    return Ok(());
+                }
+            }
+        }
+
+        if !buffer.is_empty() {
+            Self::send_chunk(sender, &mut buffer).await?;
+        }
+
+        Ok(())
+    }
+
+    /// `false` if the client is gone
+    async fn send_chunk(
+        sender: &mpsc::Sender<Result<Bytes, HttpError>>,
+        buffer: &mut Vec<u8>,
+    ) -> Result<bool, HttpError> {
+        let chunk = mem::replace(buffer, Vec::with_capacity(EXPORT_CHUNK_SIZE));
+
+        match sender
+            .send_timeout(Ok(chunk.into()), EXPORT_SEND_TIMEOUT)
+            .await
+        {
+            Ok(_) => Ok(true),
+            Err(SendTimeoutError::Closed(_)) => Ok(false),
+            Err(SendTimeoutError::Timeout(_)) => Err(HttpError::Internal(format!(
+                "Client did not read the expenses export for {EXPORT_SEND_TIMEOUT:?}"
+            ))),
+        }
+    }
+
+    pub async fn create_many(
+        &self,
+        create_dtos: Vec<CreateExpenseDto>,
+        user_id: &str,
+        duplicate_policy: DuplicatePolicy,
+        is_atomic: bool,
+        is_dry_run: bool,
+        audit_context: &AuditContext,
+    ) -> Result<CreatedExpensesEntity, HttpError> {
+        let invalid_items: Vec<ExpenseItemErrorEntity> = create_dtos
+            .iter()
+            .enumerate()
+            .filter_map(|(index, create_dto)| {
+                let validation_errors = create_dto.validate().err()?;
+
+                Some(ExpenseItemErrorEntity::new(
+                    index,
+                    &HttpError::BadRequest(validation_errors.to_string()),
+                ))
+            })
+            .collect();
+
+        if is_atomic && !invalid_items.is_empty() {
+            return Err(Self::get_invalid_items_error(&invalid_items));
+        }
+
+        let (created_expenses, duplicates, mut errors) = self
+            .unit_of_work
+            .run(|connection| async {
+                let customers_service = self.customers_service.with_connection(connection.clone());
+                let categories_service =
+                    self.categories_service.with_connection(connection.clone());
+                let category_rules_service = self
+                    .category_rules_service
+                    .with_connection(connection.clone());
+                let expenses_repository =
+                    self.expenses_repository.with_connection(connection.clone());
+                let audit_logs_service = self.audit_logs_service.with_connection(connection);
+
+                let customer = customers_service.find_one_by_user_id(user_id).await?;
+                let category_resolver = categories_service.get_resolver(Some(&customer.id)).await?;
+                let category_rules_engine = category_rules_service
+                    .get_engine(&customer.id, &category_resolver)
+                    .await?;
+
+                let mut errors = Vec::new();
+                let mut create_db_dtos = Vec::with_capacity(create_dtos.len());
+
+                for (index, create_dto) in create_dtos.iter().enumerate() {
+                    if invalid_items.iter().any(|item| item.index == index) {
+                        continue;
+                    }
+
+                    let category = match &create_dto.category {
+                        Some(category) => category_resolver.find_assignable(category),
+                        None => {
+                            Self::categorize(&category_resolver, &category_rules_engine, create_dto)
+                        }
+                    };
+
+                    match category {
+                        Ok(category) => create_db_dtos.push((
+                            index,
+                            Self::map_create_dto_to_create_db_dto(
+                                create_dto.clone(),
+                                &customer.id,
+                                &category.id,
+                            ),
+                        )),
+                        Err(err) => errors.push(ExpenseItemErrorEntity::new(index, &err)),
+                    }
+                }
+
+                if is_atomic && !errors.is_empty() {
+                    return Err(Self::get_invalid_items_error(&errors));
+                }
+
+                let (create_db_dtos, known_duplicates) = Self::find_known_duplicates(
+                    expenses_repository.as_ref(),
+                    &customer.id,
+                    create_db_dtos,
+                )
+                .await?;
+                let existing_expenses = Self::find_duplicate_candidates(
+                    expenses_repository.as_ref(),
+                    &customer.id,
+                    &create_db_dtos,
+                )
+                .await?;
+                let (create_db_dtos, mut duplicates) =
+                    Self::find_duplicates(create_db_dtos, &existing_expenses, duplicate_policy);
+
+                if duplicate_policy == DuplicatePolicy::Reject && !duplicates.is_empty() {
+                    if is_atomic {
+                        return Err(Self::get_duplicates_error(&duplicates));
+                    }
+
+                    errors.extend(duplicates.iter().map(|duplicate| {
+                        let http_error = HttpError::Conflict(format!(
+                            "Looks like a duplicate of expense {}",
+                            duplicate.duplicate_of_id
+                        ));
+
+                        ExpenseItemErrorEntity::new(duplicate.index, &http_error)
+                    }));
+                }
+
+                // Known transactions are skipped with any policy, so re-imports are safe
+                duplicates.extend(known_duplicates);
+                duplicates.sort_by_key(|duplicate| duplicate.index);
+
+                if is_dry_run {
+                    let preview_expenses = create_db_dtos
+                        .into_iter()
+                        .map(|(_, create_db_dto)| {
+                            Self::map_create_db_dto_to_preview(create_db_dto, &category_resolver)
+                        })
+                        .collect();
+
+                    return Ok((preview_expenses, duplicates, errors));
+                }
+
+                let created_expenses = if is_atomic {
+                    let create_db_dtos: Vec<CreateExpenseDbDto> = create_db_dtos
+                        .into_iter()
+                        .map(|(_, create_db_dto)| create_db_dto)
+                        .collect();
+
+                    if create_db_dtos.is_empty() {
+                        Vec::new()
+                    } else {
+                        expenses_repository
+                            .create_many(create_db_dtos)
+                            .await
+                            .map_err(Self::map_create_error)?
+                    }
+                } else {
+                    let mut created_expenses = Vec::with_capacity(create_db_dtos.len());
+
+                    for (index, create_db_dto) in create_db_dtos {
+                        match expenses_repository.create_one(create_db_dto).await {
+                            Ok(created_expense) => created_expenses.push(created_expense),
+                            // The whole unit of work has to be retried
+                            Err(err @ HttpError::TransactionConflict(_)) => return Err(err),
+                            Err(err @ HttpError::Conflict(_)) => {
+                                return Err(Self::map_create_error(err));
+                            }
+                            Err(err) => errors.push(ExpenseItemErrorEntity::new(index, &err)),
+                        }
+                    }
+
+                    created_expenses
+                };
+
+                let created_expenses: Vec<ExpenseEntity> =
+                    created_expenses.into_iter().map(Into::into).collect();
+                audit_logs_service
+                    .record(audit_context, AuditAction::Create, &[], &created_expenses)
+                    .await?;
+
+                Ok((created_expenses, duplicates, errors))
+            })
+            .await?;
+
+        errors.extend(invalid_items);
+        errors.sort_by_key(|error| error.index);
+
+        Ok(CreatedExpensesEntity {
+            expenses: created_expenses,
+            duplicates,
+            errors,
+        })
+    }
+
+    /// Creates expenses from the rows of an imported file, rows which failed to parse
+    /// are reported along with the ones which failed to be created
+    pub async fn import(
+        &self,
+        imported_rows: Vec<ImportedRow>,
+        user_id: &str,
+        duplicate_policy: DuplicatePolicy,
+        is_atomic: bool,
+        is_dry_run: bool,
+        audit_context: &AuditContext,
+    ) -> Result<ImportedExpensesEntity, HttpError> {
+        if imported_rows.is_empty() {
+            return Err(HttpError::BadRequest("File has no rows to import".into()));
+        }
+
+        let mut rows = Vec::with_capacity(imported_rows.len());
+        let mut create_dtos = Vec::with_capacity(imported_rows.len());
+        let mut invalid_rows = Vec::new();
+
+        for imported_row in imported_rows {
+            match imported_row.expense {
+                Ok(create_dto) => {
+                    rows.push(imported_row.row);
+                    create_dtos.push(create_dto);
+                }
+                Err(message) => invalid_rows.push(ExpenseItemErrorEntity::new(
+                    imported_row.row,
+                    &HttpError::BadRequest(message),
+                )),
+            }
+        }
+
+        if is_atomic && !invalid_rows.is_empty() {
+            return Err(Self::get_invalid_items_error(&invalid_rows));
+        }
+
+        let mut result = self
+            .create_many(
+                create_dtos,
+                user_id,
+                duplicate_policy,
+                is_atomic,
+                is_dry_run,
+                audit_context,
+            )
+            .await?;
+
+        // Indexes of the parsed rows are mapped back to the lines of the file
+        for duplicate in &mut result.duplicates {
+            duplicate.index = rows[duplicate.index];
+        }
+        for error in &mut result.errors {
+            error.index = rows[error.index];
+        }
+        result.errors.extend(invalid_rows);
+        result.errors.sort_by_key(|error| error.index);
+
+        Ok(ImportedExpensesEntity { is_dry_run, result })
     }
 
     pub async fn update(
@@ -113,37 +597,944 @@ impl ExpensesService {
         id: &str,
         update_dto: UpdateExpenseDto,
         user_id: &str,
+        expected_versions: Option<Vec<i64>>,
+        audit_context: &AuditContext,
     ) -> Result<ExpenseEntity, HttpError> {
-        // Checking if expense exists and belongs to the customer
-        self.find_one_as_customer(id, user_id).await?;
+        update_dto
+            .validate()
+            .map_err(|validation_errors| HttpError::BadRequest(validation_errors.to_string()))?;
 
         let updated_expense_entity = self
-            .expenses_repository
-            .update_one(id, update_dto.into())
-            .await?
-            .into();
+            .unit_of_work
+            .run(|connection| async {
+                let customers_service = self.customers_service.with_connection(connection.clone());
+                let categories_service =
+                    self.categories_service.with_connection(connection.clone());
+                let expenses_repository =
+                    self.expenses_repository.with_connection(connection.clone());
+                let audit_logs_service = self.audit_logs_service.with_connection(connection);
+
+                let customer = customers_service.find_one_by_user_id(user_id).await?;
+                let update_db_dto = Self::map_update_dto_to_update_db_dto(
+                    &categories_service,
+                    update_dto.clone(),
+                    &customer.id,
+                )
+                .await?;
+
+                let found_expense_entity: ExpenseEntity =
+                    expenses_repository.find_one(id).await?.into();
+                let updated_expense_entity: ExpenseEntity = expenses_repository
+                    .update_one(
+                        id,
+                        update_db_dto,
+                        Some(&customer.id),
+                        expected_versions.clone(),
+                    )
+                    .await?
+                    .into();
+
+                audit_logs_service
+                    .record(
+                        audit_context,
+                        AuditAction::Update,
+                        slice::from_ref(&found_expense_entity),
+                        slice::from_ref(&updated_expense_entity),
+                    )
+                    .await?;
+
+                Ok(updated_expense_entity)
+            })
+            .await?;
 
         Ok(updated_expense_entity)
     }
 
-    pub async fn delete(&self, id: &str, user_id: &str) -> Result<ExpenseEntity, HttpError> {
-        // Checking if expense exists and belongs to the customer
-        self.find_one_as_customer(id, user_id).await?;
+    pub async fn delete(
+        &self,
+        id: &str,
+        user_id: &str,
+        expected_versions: Option<Vec<i64>>,
+        audit_context: &AuditContext,
+    ) -> Result<ExpenseEntity, HttpError> {
+        let deleted_expense_entity = self
+            .unit_of_work
+            .run(|connection| async {
+                let customers_service = self.customers_service.with_connection(connection.clone());
+                let expenses_repository =
+                    self.expenses_repository.with_connection(connection.clone());
+                let audit_logs_service = self.audit_logs_service.with_connection(connection);
+
+                let customer = customers_service.find_one_by_user_id(user_id).await?;
 
-        let deleted_expense_entity = self.expenses_repository.delete_one(id).await?.into();
+                let found_expense_entity: ExpenseEntity =
+                    expenses_repository.find_one(id).await?.into();
+                let deleted_expense_entity: ExpenseEntity = expenses_repository
+                    .delete_one(id, Some(&customer.id), expected_versions.clone())
+                    .await?
+                    .into();
+
+                audit_logs_service
+                    .record(
+                        audit_context,
+                        AuditAction::Delete,
+                        slice::from_ref(&found_expense_entity),
+                        slice::from_ref(&deleted_expense_entity),
+                    )
+                    .await?;
+
+                Ok(deleted_expense_entity)
+            })
+            .await?;
 
         Ok(deleted_expense_entity)
     }
 
+    pub async fn restore(
+        &self,
+        id: &str,
+        user_id: &str,
+        audit_context: &AuditContext,
+    ) -> Result<ExpenseEntity, HttpError> {
+        let restored_expense_entity = self
+            .unit_of_work
+            .run(|connection| async {
+                let customers_service = self.customers_service.with_connection(connection.clone());
+                let expenses_repository =
+                    self.expenses_repository.with_connection(connection.clone());
+                let audit_logs_service = self.audit_logs_service.with_connection(connection);
+
+                let customer = customers_service.find_one_by_user_id(user_id).await?;
+
+                let find_dto = FindExpensesDto {
+                    ids: Some(vec![id.to_string()]),
+                    is_deleted: true,
+                    ..Default::default()
+                };
+                let found_expense_entities: Vec<ExpenseEntity> = expenses_repository
+                    .find_many(Some(find_dto))
+                    .await?
+                    .into_iter()
+                    .map(Into::into)
+                    .collect();
+                let restored_expense_entity: ExpenseEntity = expenses_repository
+                    .restore_one(id, Some(&customer.id))
+                    .await?
+                    .into();
+
+                audit_logs_service
+                    .record(
+                        audit_context,
+                        AuditAction::Restore,
+                        &found_expense_entities,
+                        slice::from_ref(&restored_expense_entity),
+                    )
+                    .await?;
+
+                Ok(restored_expense_entity)
+            })
+            .await?;
+
+        Ok(restored_expense_entity)
+    }
+
+    pub async fn update_many(
+        &self,
+        bulk_update_dto: BulkUpdateExpensesDto,
+        user_id: &str,
+        audit_context: &AuditContext,
+    ) -> Result<BulkExpensesResultEntity, HttpError> {
+        if bulk_update_dto.update.is_empty() {
+            return Err(HttpError::BadRequest(
+                "update should have at least one field to set".into(),
+            ));
+        }
+
+        bulk_update_dto
+            .update
+            .validate()
+            .map_err(|validation_errors| HttpError::BadRequest(validation_errors.to_string()))?;
+
+        let selector = bulk_update_dto.selector;
+        let update_dto = bulk_update_dto.update;
+        let updated_expenses = self
+            .unit_of_work
+            .run(|connection| async {
+                let customers_service = self.customers_service.with_connection(connection.clone());
+                let categories_service =
+                    self.categories_service.with_connection(connection.clone());
+                let expenses_repository =
+                    self.expenses_repository.with_connection(connection.clone());
+                let audit_logs_service = self.audit_logs_service.with_connection(connection);
+
+                let customer = customers_service.find_one_by_user_id(user_id).await?;
+                let category_ids = Self::find_category_ids(
+                    &categories_service,
+                    selector.get_category(),
+                    Some(&customer.id),
+                )
+                .await?;
+                let find_dto = selector.to_find_dto(&customer.id, category_ids)?;
+                let update_db_dto = Self::map_update_dto_to_update_db_dto(
+                    &categories_service,
+                    update_dto.clone(),
+                    &customer.id,
+                )
+                .await?;
+
+                let found_expenses = expenses_repository
+                    .find_many(Some(find_dto.clone()))
+                    .await?;
+                let updated_expenses = expenses_repository
+                    .update_many(find_dto, update_db_dto)
+                    .await?;
+
+                Self::record_many(
+                    &audit_logs_service,
+                    audit_context,
+                    AuditAction::Update,
+                    &found_expenses,
+                    &updated_expenses,
+                )
+                .await?;
+
+                Ok(updated_expenses)
+            })
+            .await?;
+
+        Ok(updated_expenses.into())
+    }
+
+    pub async fn delete_many(
+        &self,
+        selector: BulkExpensesSelectorDto,
+        user_id: &str,
+        audit_context: &AuditContext,
+    ) -> Result<BulkExpensesResultEntity, HttpError> {
+        let deleted_expenses = self
+            .unit_of_work
+            .run(|connection| async {
+                let customers_service = self.customers_service.with_connection(connection.clone());
+                let categories_service =
+                    self.categories_service.with_connection(connection.clone());
+                let expenses_repository =
+                    self.expenses_repository.with_connection(connection.clone());
+                let audit_logs_service = self.audit_logs_service.with_connection(connection);
+
+                let customer = customers_service.find_one_by_user_id(user_id).await?;
+                let category_ids = Self::find_category_ids(
+                    &categories_service,
+                    selector.get_category(),
+                    Some(&customer.id),
+                )
+                .await?;
+                let find_dto = selector.to_find_dto(&customer.id, category_ids)?;
+
+                let found_expenses = expenses_repository
+                    .find_many(Some(find_dto.clone()))
+                    .await?;
+                let deleted_expenses = expenses_repository.delete_many(find_dto).await?;
+
+                Self::record_many(
+                    &audit_logs_service,
+                    audit_context,
+                    AuditAction::Delete,
+                    &found_expenses,
+                    &deleted_expenses,
+                )
+                .await?;
+
+                Ok(deleted_expenses)
+            })
+            .await?;
+
+        Ok(deleted_expenses.into())
+    }
+
+    /// Tries the conditions of a rule on the customer's active expenses without saving it
+    pub async fn test_category_rule(
+        &self,
+        test_dto: TestCategoryRuleDto,
+        user_id: &str,
+    ) -> Result<TestedCategoryRuleEntity, HttpError> {
+        test_dto
+            .validate()
+            .map_err(|validation_errors| HttpError::BadRequest(validation_errors.to_string()))?;
+
+        let category_rule_matcher = CategoryRuleMatcher::new(test_dto.conditions)?;
+        let customer = self.customers_service.find_one_by_user_id(user_id).await?;
+
+        let find_dto = FindExpensesDto {
+            customer_id: Some(customer.id),
+            date_from: test_dto.date_from,
+            date_to: test_dto.date_to,
+            ..Default::default()
+        };
+        let mut found_expenses = self.expenses_repository.stream_many(find_dto).await?;
+
+        let mut matched = 0;
+        let mut matched_expenses = Vec::new();
+
+        while let Some(expense) = found_expenses.next().await {
+            let expense = expense?;
+
+            if !category_rule_matcher.is_match(
+                expense.amount,
+                &expense.date,
+                expense.note.as_deref(),
+            ) {
+                continue;
+            }
+
+            matched += 1;
+
+            if matched_expenses.len() < TESTED_RULE_EXPENSES_LIMIT {
+                matched_expenses.push(expense.into());
+            }
+        }
+
+        Ok(TestedCategoryRuleEntity {
+            matched,
+            expenses: matched_expenses,
+        })
+    }
+
+    /// Re-applies the customer's rules to their active expenses. Expenses no rule matches
+    /// keep their category. At most `APPLIED_CATEGORY_RULES_LIMIT` expenses are recategorized
+    /// at once, applying the rules again continues with the rest
+    pub async fn apply_category_rules(
+        &self,
+        apply_dto: ApplyCategoryRulesDto,
+        user_id: &str,
+        audit_context: &AuditContext,
+    ) -> Result<CategorizedExpensesEntity, HttpError> {
+        let (categorized_expenses, has_more) = self
+            .unit_of_work
+            .run(|connection| async {
+                let customers_service = self.customers_service.with_connection(connection.clone());
+                let categories_service =
+                    self.categories_service.with_connection(connection.clone());
+                let category_rules_service = self
+                    .category_rules_service
+                    .with_connection(connection.clone());
+                let expenses_repository =
+                    self.expenses_repository.with_connection(connection.clone());
+                let audit_logs_service = self.audit_logs_service.with_connection(connection);
+
+                let customer = customers_service.find_one_by_user_id(user_id).await?;
+                let category_resolver = categories_service.get_resolver(Some(&customer.id)).await?;
+                let category_rules_engine = category_rules_service
+                    .get_engine(&customer.id, &category_resolver)
+                    .await?;
+                let category_ids = Self::find_category_ids(
+                    &categories_service,
+                    apply_dto.category.as_deref(),
+                    Some(&customer.id),
+                )
+                .await?;
+
+                let find_dto = FindExpensesDto {
+                    customer_id: Some(customer.id.clone()),
+                    category_ids,
+                    date_from: apply_dto.date_from,
+                    date_to: apply_dto.date_to,
+                    ..Default::default()
+                };
+                let mut recategorized_expenses = Vec::new();
+                let mut recategorized_ids: HashMap<String, Vec<String>> = HashMap::new();
+                let mut has_more = false;
+
+                // Dropped before the updates, the stream holds the connection
+                {
+                    let mut found_expenses = expenses_repository.stream_many(find_dto).await?;
+
+                    while let Some(expense) = found_expenses.next().await {
+                        let expense = expense?;
+                        let Some(category_id) = category_rules_engine.find_category_id(
+                            expense.amount,
+                            &expense.date,
+                            expense.note.as_deref(),
+                        ) else {
+                            continue;
+                        };
+
+                        if category_id == expense.category_id {
+                            continue;
+                        }
+
+                        if recategorized_expenses.len() == APPLIED_CATEGORY_RULES_LIMIT {
+                            has_more = true;
+                            break;
+                        }
+
+                        recategorized_ids
+                            .entry(category_id.to_string())
+                            .or_default()
+                            .push(expense.id.clone());
+                        recategorized_expenses.push((expense, category_id.to_string()));
+                    }
+                }
+
+                if apply_dto.dry_run {
+                    let preview_expenses = recategorized_expenses
+                        .into_iter()
+                        .map(|(expense, category_id)| {
+                            ExpenseFromDb {
+                                category_code: category_resolver
+                                    .get(&category_id)
+                                    .and_then(|category| category.code.clone()),
+                                category_id,
+                                ..expense
+                            }
+                            .into()
+                        })
+                        .collect();
+
+                    return Ok((preview_expenses, has_more));
+                }
+
+                let mut updated_expenses = Vec::with_capacity(recategorized_expenses.len());
+
+                for (category_id, ids) in recategorized_ids {
+                    let find_dto = FindExpensesDto {
+                        customer_id: Some(customer.id.clone()),
+                        ids: Some(ids),
+                        ..Default::default()
+                    };
+                    let update_db_dto = UpdateExpenseDbDto {
+                        amount: None,
+                        date: None,
+                        category_id: Some(category_id),
+                        note: None,
+                    };
+
+                    updated_expenses.extend(
+                        expenses_repository
+                            .update_many(find_dto, update_db_dto)
+                            .await?,
+                    );
+                }
+
+                updated_expenses.sort_by(|a, b| a.date.cmp(&b.date).then_with(|| a.id.cmp(&b.id)));
+
+                let found_expenses: Vec<ExpenseFromDb> = recategorized_expenses
+                    .into_iter()
+                    .map(|(expense, _)| expense)
+                    .collect();
+                Self::record_many(
+                    &audit_logs_service,
+                    audit_context,
+                    AuditAction::Update,
+                    &found_expenses,
+                    &updated_expenses,
+                )
+                .await?;
+
+                Ok((
+                    updated_expenses.into_iter().map(Into::into).collect(),
+                    has_more,
+                ))
+            })
+            .await?;
+
+        Ok(CategorizedExpensesEntity {
+            is_dry_run: apply_dto.dry_run,
+            expenses: categorized_expenses,
+            has_more,
+        })
+    }
+
+    /// Change history of the expense, latest changes first
+    pub async fn find_history(
+        &self,
+        id: &str,
+        user: &User,
+    ) -> Result<Vec<AuditLogEntity>, HttpError> {
+        let owner_id = if user.is_admin() {
+            None
+        } else {
+            let customer = self.customers_service.find_one_by_user_id(&user.id).await?;
+
+            Some(customer.id)
+        };
+
+        let find_dto = FindAuditLogsDto {
+            entity_type: Some(AuditEntityType::Expense),
+            entity_id: Some(id.to_string()),
+            owner_id,
+            ..Default::default()
+        };
+        let audit_log_entities = self.audit_logs_service.find_many(find_dto).await?;
+
+        // Other customers' expenses are not found rather than forbidden
+        if audit_log_entities.is_empty() {
+            return Err(HttpError::NotFound(format!(
+                "Expense with id {id} not found"
+            )));
+        }
+
+        Ok(audit_log_entities)
+    }
+
+    async fn record_many(
+        audit_logs_service: &AuditLogsService,
+        audit_context: &AuditContext,
+        action: AuditAction,
+        before: &[ExpenseFromDb],
+        after: &[ExpenseFromDb],
+    ) -> Result<(), HttpError> {
+        let to_entities = |expenses: &[ExpenseFromDb]| -> Vec<ExpenseEntity> {
+            expenses.iter().cloned().map(Into::into).collect()
+        };
+
+        audit_logs_service
+            .record(
+                audit_context,
+                action,
+                &to_entities(before),
+                &to_entities(after),
+            )
+            .await
+    }
+
+    /// Splits off the expenses with external ids the customer already has
+    /// or which are repeated in the request
+    async fn find_known_duplicates(
+        expenses_repository: &(dyn ExpensesRepositoryTrait + Send + Sync),
+        customer_id: &str,
+        create_db_dtos: Vec<(usize, CreateExpenseDbDto)>,
+    ) -> Result<
+        (
+            Vec<(usize, CreateExpenseDbDto)>,
+            Vec<DuplicateExpenseEntity>,
+        ),
+        HttpError,
+    > {
+        let external_ids: Vec<String> = create_db_dtos
+            .iter()
+            .filter_map(|(_, create_db_dto)| create_db_dto.external_id.clone())
+            .collect();
+
+        if external_ids.is_empty() {
+            return Ok((create_db_dtos, Vec::new()));
+        }
+
+        let mut known_ids: HashMap<String, String> = expenses_repository
+            .find_by_external_ids(customer_id, external_ids)
+            .await?
+            .into_iter()
+            .filter_map(|expense| Some((expense.external_id?, expense.id)))
+            .collect();
+
+        let mut accepted_db_dtos = Vec::with_capacity(create_db_dtos.len());
+        let mut duplicates = Vec::new();
+
+        for (index, create_db_dto) in create_db_dtos {
+            let Some(external_id) = create_db_dto.external_id.clone() else {
+                accepted_db_dtos.push((index, create_db_dto));
+                continue;
+            };
+
+            if let Some(duplicate_of_id) = known_ids.get(&external_id) {
+                duplicates.push(DuplicateExpenseEntity {
+                    index,
+                    duplicate_of_id: duplicate_of_id.clone(),
+                });
+                continue;
+            }
+
+            known_ids.insert(external_id, create_db_dto.id.clone());
+            accepted_db_dtos.push((index, create_db_dto));
+        }
+
+        Ok((accepted_db_dtos, duplicates))
+    }
+
+    /// Expenses of the customer with dates close enough to the created ones to be their duplicates
+    async fn find_duplicate_candidates(
+        expenses_repository: &(dyn ExpensesRepositoryTrait + Send + Sync),
+        customer_id: &str,
+        create_db_dtos: &[(usize, CreateExpenseDbDto)],
+    ) -> Result<Vec<ExpenseFromDb>, HttpError> {
+        let dates = create_db_dtos
+            .iter()
+            .map(|(_, create_db_dto)| create_db_dto.date);
+        let (Some(date_from), Some(date_to)) = (dates.clone().min(), dates.max()) else {
+            return Ok(Vec::new());
+        };
+
+        let window = TimeDelta::hours(DUPLICATE_WINDOW_HOURS);
+        let find_dto = FindExpensesDto {
+            customer_id: Some(customer_id.to_string()),
+            date_from: Some(date_from - window),
+            date_to: Some(date_to + window),
+            ..Default::default()
+        };
+
+        expenses_repository.find_many(Some(find_dto)).await
+    }
+
+    /// Splits the created expenses (with their indexes in the request) into the ones to insert
+    /// and the duplicates of existing or previously listed ones. Duplicates are inserted
+    /// as well only with `DuplicatePolicy::Warn`
+    fn find_duplicates(
+        create_db_dtos: Vec<(usize, CreateExpenseDbDto)>,
+        existing_expenses: &[ExpenseFromDb],
+        duplicate_policy: DuplicatePolicy,
+    ) -> (
+        Vec<(usize, CreateExpenseDbDto)>,
+        Vec<DuplicateExpenseEntity>,
+    ) {
+        let mut accepted_db_dtos: Vec<(usize, CreateExpenseDbDto)> =
+            Vec::with_capacity(create_db_dtos.len());
+        let mut duplicates = Vec::new();
+
+        for (index, create_db_dto) in create_db_dtos {
+            let existing_duplicate = existing_expenses.iter().find(|existing_expense| {
+                Self::is_likely_duplicate(
+                    &create_db_dto,
+                    Decimal::try_from(existing_expense.amount).unwrap_or_default(),
+                    existing_expense.date,
+                    &existing_expense.category_id,
+                    existing_expense.note.as_deref(),
+                )
+            });
+            let duplicate_of_id = match existing_duplicate {
+                Some(existing_expense) => Some(existing_expense.id.clone()),
+                None => accepted_db_dtos
+                    .iter()
+                    .find(|(_, accepted_db_dto)| {
+                        Self::is_likely_duplicate(
+                            &create_db_dto,
+                            accepted_db_dto.amount,
+                            accepted_db_dto.date,
+                            &accepted_db_dto.category_id,
+                            accepted_db_dto.note.as_deref(),
+                        )
+                    })
+                    .map(|(_, accepted_db_dto)| accepted_db_dto.id.clone()),
+            };
+
+            if let Some(duplicate_of_id) = duplicate_of_id {
+                duplicates.push(DuplicateExpenseEntity {
+                    index,
+                    duplicate_of_id,
+                });
+
+                if duplicate_policy != DuplicatePolicy::Warn {
+                    continue;
+                }
+            }
+
+            accepted_db_dtos.push((index, create_db_dto));
+        }
+
+        (accepted_db_dtos, duplicates)
+    }
+
+    fn is_likely_duplicate(
+        create_db_dto: &CreateExpenseDbDto,
+        amount: Decimal,
+        date: DateTime<FixedOffset>,
+        category_id: &str,
+        note: Option<&str>,
+    ) -> bool {
+        create_db_dto.amount.round_dp(2) == amount.round_dp(2)
+            && create_db_dto.category_id == category_id
+            && (create_db_dto.date - date).abs() <= TimeDelta::hours(DUPLICATE_WINDOW_HOURS)
+            && Self::normalize_note(create_db_dto.note.as_deref()) == Self::normalize_note(note)
+    }
+
+    /// Notes differing only in case or surrounding whitespace are the same
+    fn normalize_note(note: Option<&str>) -> Option<String> {
+        note.map(str::trim)
+            .filter(|note| !note.is_empty())
+            .map(str::to_lowercase)
+    }
+
+    /// Expenses are only conflicting on external ids, the ones created by a concurrent request
+    /// after the check. The unit of work is retried then, so they are found as known duplicates
+    fn map_create_error(err: HttpError) -> HttpError {
+        match err {
+            HttpError::Conflict(msg) => HttpError::TransactionConflict(msg),
+            err => err,
+        }
+    }
+
+    fn get_duplicates_error(duplicates: &[DuplicateExpenseEntity]) -> HttpError {
+        let indexes = duplicates
+            .iter()
+            .map(|duplicate| duplicate.index.to_string())
+            .collect::<Vec<_>>()
+            .join(", ");
+
+        HttpError::Conflict(format!(
+            "Expenses at indexes {indexes} look like duplicates, pass duplicatePolicy=warn to create them anyway"
+        ))
+    }
+
+    fn get_invalid_items_error(invalid_items: &[ExpenseItemErrorEntity]) -> HttpError {
+        let messages = invalid_items
+            .iter()
+            .map(|item| format!("{}: {}", item.index, item.message))
+            .collect::<Vec<_>>()
+            .join("; ");
+
+        HttpError::BadRequest(format!("Expenses are invalid, {messages}"))
+    }
+
+    /// Expense as it would be created, for dry runs
+    fn map_create_db_dto_to_preview(
+        create_db_dto: CreateExpenseDbDto,
+        category_resolver: &CategoryResolver,
+    ) -> ExpenseEntity {
+        let now = Utc::now().fixed_offset();
+        let category = category_resolver
+            .get(&create_db_dto.category_id)
+            .and_then(|category| category.code.clone());
+
+        ExpenseEntity {
+            id: create_db_dto.id,
+            customer_id: create_db_dto.customer_id,
+            amount: create_db_dto.amount.try_into().unwrap_or_default(),
+            date: create_db_dto.date,
+            category_id: create_db_dto.category_id,
+            category,
+            note: create_db_dto.note,
+            created_at: now,
+            updated_at: now,
+            version: 1,
+            deleted_at: None,
+            external_id: create_db_dto.external_id,
+        }
+    }
+
+    /// Category of the first matching rule, `OTHER` if none of them matches
+    fn categorize<'a>(
+        category_resolver: &'a CategoryResolver,
+        category_rules_engine: &CategoryRulesEngine,
+        create_dto: &CreateExpenseDto,
+    ) -> Result<&'a CategoryFromDb, HttpError> {
+        let category_id = category_rules_engine
+            .find_category_id(
+                create_dto.amount,
+                &create_dto.date,
+                create_dto.note.as_deref(),
+            )
+            .unwrap_or(ExpenseCategory::Other.get_code());
+
+        category_resolver.find_assignable(category_id)
+    }
+
     fn map_create_dto_to_create_db_dto(
         create_dto: CreateExpenseDto,
         customer_id: &str,
+        category_id: &str,
     ) -> CreateExpenseDbDto {
         CreateExpenseDbDto {
+            id: generate_id(),
             customer_id: customer_id.to_string(),
             amount: Decimal::try_from(create_dto.amount).unwrap_or_default(),
             date: create_dto.date,
-            category: create_dto.category,
+            category_id: category_id.to_string(),
+            note: create_dto.note,
+            external_id: create_dto.external_id,
+        }
+    }
+
+    /// Filter of the query, the category matches its subcategories too
+    async fn map_query_to_find_dto(
+        &self,
+        query: FindExpensesQueryDto,
+        customer_id: Option<String>,
+    ) -> Result<FindExpensesDto, HttpError> {
+        let customer_id = customer_id.or(query.customer_id.map(|id| id.to_string()));
+        let category_ids = Self::find_category_ids(
+            &self.categories_service,
+            query.category.as_deref(),
+            customer_id.as_deref(),
+        )
+        .await?;
+
+        Ok(FindExpensesDto {
+            customer_id,
+            category_ids,
+            date_from: query.date_from,
+            date_to: query.date_to,
+            ..Default::default()
+        })
+    }
+
+    /// Ids of the category (an id or a code) and its subcategories
+    async fn find_category_ids(
+        categories_service: &CategoriesService,
+        category: Option<&str>,
+        customer_id: Option<&str>,
+    ) -> Result<Option<Vec<String>>, HttpError> {
+        let Some(category) = category else {
+            return Ok(None);
+        };
+
+        let category_resolver = match customer_id {
+            Some(customer_id) => categories_service.get_resolver(Some(customer_id)).await?,
+            // Expenses of all the customers, only the categories of the category's owner
+            // are loaded
+            None => categories_service.get_owner_resolver_of(category).await?,
+        };
+        let category_id = &category_resolver.find(category)?.id;
+
+        Ok(Some(category_resolver.get_subtree_ids(category_id)))
+    }
+
+    async fn map_update_dto_to_update_db_dto(
+        categories_service: &CategoriesService,
+        update_dto: UpdateExpenseDto,
+        customer_id: &str,
+    ) -> Result<UpdateExpenseDbDto, HttpError> {
+        let category_id = match &update_dto.category {
+            Some(category) => {
+                let category_resolver = categories_service.get_resolver(Some(customer_id)).await?;
+
+                Some(category_resolver.find_assignable(category)?.id.clone())
+            }
+            None => None,
+        };
+
+        Ok(UpdateExpenseDbDto {
+            amount: update_dto.amount,
+            date: update_dto.date,
+            category_id,
+            note: update_dto.note,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn get_create_db_dto(note: Option<&str>) -> CreateExpenseDbDto {
+        CreateExpenseDbDto {
+            id: "expense".into(),
+            customer_id: "customer".into(),
+            amount: Decimal::new(1050, 2),
+            date: DateTime::parse_from_rfc3339("2026-10-19T12:00:00+00:00").unwrap(),
+            category_id: "FOOD".into(),
+            note: note.map(str::to_string),
+            external_id: None,
+        }
+    }
+
+    #[test]
+    fn is_likely_duplicate() {
+        let create_db_dto = get_create_db_dto(Some("Lunch"));
+        let date = create_db_dto.date;
+        let cases = [
+            (
+                "same fields",
+                Decimal::new(1050, 2),
+                date,
+                "FOOD",
+                Some("Lunch"),
+                true,
+            ),
+            (
+                "amount rounds to the same cents",
+                Decimal::new(10501, 3),
+                date,
+                "FOOD",
+                Some("Lunch"),
+                true,
+            ),
+            (
+                "note differs in case and whitespace",
+                Decimal::new(1050, 2),
+                date,
+                "FOOD",
+                Some(" lunch "),
+                true,
+            ),
+            (
+                "date at the window edge",
+                Decimal::new(1050, 2),
+                date - TimeDelta::hours(DUPLICATE_WINDOW_HOURS),
+                "FOOD",
+                Some("Lunch"),
+                true,
+            ),
+            (
+                "date outside the window",
+                Decimal::new(1050, 2),
+                date + TimeDelta::hours(DUPLICATE_WINDOW_HOURS + 1),
+                "FOOD",
+                Some("Lunch"),
+                false,
+            ),
+            (
+                "different amount",
+                Decimal::new(1051, 2),
+                date,
+                "FOOD",
+                Some("Lunch"),
+                false,
+            ),
+            (
+                "different category",
+                Decimal::new(1050, 2),
+                date,
+                "TRANSPORT",
+                Some("Lunch"),
+                false,
+            ),
+            (
+                "different note",
+                Decimal::new(1050, 2),
+                date,
+                "FOOD",
+                Some("Dinner"),
+                false,
+            ),
+            (
+                "missing note",
+                Decimal::new(1050, 2),
+                date,
+                "FOOD",
+                None,
+                false,
+            ),
+        ];
+
+        for (name, amount, date, category_id, note, expected) in cases {
+            assert_eq!(
+                ExpensesService::is_likely_duplicate(
+                    &create_db_dto,
+                    amount,
+                    date,
+                    category_id,
+                    note
+                ),
+                expected,
+                "{name}"
+            );
+        }
+    }
+
+    #[test]
+    fn normalize_note() {
+        let cases = [
+            (None, None),
+            (Some(""), None),
+            (Some("   "), None),
+            (Some("Lunch"), Some("lunch")),
+            (Some("  Team LUNCH \n"), Some("team lunch")),
+        ];
+
+        for (note, expected) in cases {
+            assert_eq!(
+                ExpensesService::normalize_note(note).as_deref(),
+                expected,
+                "{note:?}"
+            );
         }
     }
 }
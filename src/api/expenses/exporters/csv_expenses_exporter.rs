@@ -0,0 +1,58 @@
+use serde_json::Value;
+use std::fs::File;
+
+use crate::api::expenses::exporters::escape_formula;
+use crate::api::expenses::traits::expenses_exporter::ExpensesExporter;
+use crate::api::expenses::types::exported_expense::{EXPORTED_COLUMNS, ExportedExpense};
+use crate::shared::errors::http_error::HttpError;
+
+pub struct CsvExpensesExporter {
+    writer_builder: csv::WriterBuilder,
+}
+
+impl CsvExpensesExporter {
+    pub fn new() -> Self {
+        let mut writer_builder = csv::WriterBuilder::new();
+        writer_builder.has_headers(false);
+
+        Self { writer_builder }
+    }
+
+    /// Writes a record straight to the buffer, so that it can be sent right away
+    fn write(
+        &self,
+        buffer: &mut Vec<u8>,
+        write_record: impl FnOnce(&mut csv::Writer<&mut Vec<u8>>) -> csv::Result<()>,
+    ) -> Result<(), HttpError> {
+        let mut writer = self.writer_builder.from_writer(buffer);
+
+        write_record(&mut writer)
+            .and_then(|_| Ok(writer.flush()?))
+            .map_err(|err| HttpError::Internal(format!("Failed to write CSV export: {err}")))
+    }
+}
+
+impl ExpensesExporter for CsvExpensesExporter {
+    fn write_header(&mut self, buffer: &mut Vec<u8>) -> Result<(), HttpError> {
+        self.write(buffer, |writer| writer.write_record(EXPORTED_COLUMNS))
+    }
+
+    fn write_row(
+        &mut self,
+        expense: &ExportedExpense,
+        buffer: &mut Vec<u8>,
+    ) -> Result<(), HttpError> {
+        let cells = serde_json::to_value(expense)?;
+        let record = EXPORTED_COLUMNS.map(|name| match &cells[name] {
+            Value::String(string) => escape_formula(string).into_owned(),
+            Value::Null => String::new(),
+            value => value.to_string(),
+        });
+
+        self.write(buffer, |writer| writer.write_record(&record))
+    }
+
+    fn finish(self: Box<Self>) -> Result<Option<File>, HttpError> {
+        Ok(None)
+    }
+}
@@ -0,0 +1,28 @@
+use std::fs::File;
+
+use crate::api::expenses::traits::expenses_exporter::ExpensesExporter;
+use crate::api::expenses::types::exported_expense::ExportedExpense;
+use crate::shared::errors::http_error::HttpError;
+
+pub struct JsonlExpensesExporter;
+
+impl ExpensesExporter for JsonlExpensesExporter {
+    fn write_header(&mut self, _buffer: &mut Vec<u8>) -> Result<(), HttpError> {
+        Ok(())
+    }
+
+    fn write_row(
+        &mut self,
+        expense: &ExportedExpense,
+        buffer: &mut Vec<u8>,
+    ) -> Result<(), HttpError> {
+        serde_json::to_writer(&mut *buffer, expense)?;
+        buffer.push(b'\n');
+
+        Ok(())
+    }
+
+    fn finish(self: Box<Self>) -> Result<Option<File>, HttpError> {
+        Ok(None)
+    }
+}
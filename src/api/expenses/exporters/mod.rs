@@ -0,0 +1,55 @@
+use std::borrow::Cow;
+
+use crate::api::expenses::exporters::csv_expenses_exporter::CsvExpensesExporter;
+use crate::api::expenses::exporters::jsonl_expenses_exporter::JsonlExpensesExporter;
+use crate::api::expenses::exporters::xlsx_expenses_exporter::XlsxExpensesExporter;
+use crate::api::expenses::traits::expenses_exporter::ExpensesExporter;
+use crate::api::expenses::types::export_format::ExportFormat;
+use crate::shared::errors::http_error::HttpError;
+
+pub mod csv_expenses_exporter;
+pub mod jsonl_expenses_exporter;
+pub mod xlsx_expenses_exporter;
+
+/// Spreadsheets run cells starting with these as formulas
+const FORMULA_PREFIXES: [char; 6] = ['=', '+', '-', '@', '\t', '\r'];
+
+pub fn get_expenses_exporter(format: ExportFormat) -> Result<Box<dyn ExpensesExporter>, HttpError> {
+    let expenses_exporter: Box<dyn ExpensesExporter> = match format {
+        ExportFormat::Csv => Box::new(CsvExpensesExporter::new()),
+        ExportFormat::Jsonl => Box::new(JsonlExpensesExporter),
+        ExportFormat::Xlsx => Box::new(XlsxExpensesExporter::new()?),
+    };
+
+    Ok(expenses_exporter)
+}
+
+/// Text which spreadsheets would run as a formula is prefixed with `'`, so it stays text
+pub fn escape_formula(value: &str) -> Cow<'_, str> {
+    if value.starts_with(FORMULA_PREFIXES) {
+        Cow::Owned(format!("'{value}"))
+    } else {
+        Cow::Borrowed(value)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    #[test]
+    fn escape_formula() {
+        let cases = [
+            ("=SUM(A1:A2)", "'=SUM(A1:A2)"),
+            ("+1", "'+1"),
+            ("-1", "'-1"),
+            ("@cmd", "'@cmd"),
+            ("\tLunch", "'\tLunch"),
+            ("Lunch = 5", "Lunch = 5"),
+            ("'quoted", "'quoted"),
+            ("", ""),
+        ];
+
+        for (value, expected) in cases {
+            assert_eq!(super::escape_formula(value), expected, "{value}");
+        }
+    }
+}
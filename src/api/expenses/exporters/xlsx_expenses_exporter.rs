@@ -0,0 +1,93 @@
+use rust_xlsxwriter::{Workbook, Worksheet, XlsxError};
+use serde_json::Value;
+use std::fs::File;
+use std::io::{Seek, SeekFrom};
+
+use crate::api::expenses::exporters::escape_formula;
+use crate::api::expenses::traits::expenses_exporter::ExpensesExporter;
+use crate::api::expenses::types::exported_expense::{EXPORTED_COLUMNS, ExportedExpense};
+use crate::shared::errors::http_error::HttpError;
+
+const WORKSHEET_NAME: &str = "Expenses";
+
+/// XLSX files are zip archives and can't be sent before they are complete. Rows are kept in
+/// a temporary file rather than in memory, the complete file is written to another one
+pub struct XlsxExpensesExporter {
+    workbook: Workbook,
+    row: u32,
+}
+
+impl XlsxExpensesExporter {
+    pub fn new() -> Result<Self, HttpError> {
+        let mut workbook = Workbook::new();
+        workbook
+            .add_worksheet_with_constant_memory()
+            .set_name(WORKSHEET_NAME)
+            .map_err(Self::get_write_error)?;
+
+        Ok(Self { workbook, row: 0 })
+    }
+
+    fn get_write_error(err: XlsxError) -> HttpError {
+        HttpError::Internal(format!("Failed to write XLSX export: {err}"))
+    }
+
+    fn get_worksheet(&mut self) -> Result<&mut Worksheet, HttpError> {
+        self.workbook
+            .worksheet_from_index(0)
+            .map_err(Self::get_write_error)
+    }
+}
+
+impl ExpensesExporter for XlsxExpensesExporter {
+    fn write_header(&mut self, _buffer: &mut Vec<u8>) -> Result<(), HttpError> {
+        let worksheet = self.get_worksheet()?;
+        for (column, name) in (0..).zip(EXPORTED_COLUMNS) {
+            worksheet
+                .write_string(0, column, name)
+                .map_err(Self::get_write_error)?;
+        }
+
+        self.row = 1;
+
+        Ok(())
+    }
+
+    fn write_row(
+        &mut self,
+        expense: &ExportedExpense,
+        _buffer: &mut Vec<u8>,
+    ) -> Result<(), HttpError> {
+        let row = self.row;
+        let cells = serde_json::to_value(expense)?;
+        let worksheet = self.get_worksheet()?;
+
+        for (column, name) in (0..).zip(EXPORTED_COLUMNS) {
+            match &cells[name] {
+                Value::Number(number) => worksheet
+                    .write_number(row, column, number.as_f64().unwrap_or_default())
+                    .map_err(Self::get_write_error)?,
+                Value::String(string) => worksheet
+                    .write_string(row, column, escape_formula(string))
+                    .map_err(Self::get_write_error)?,
+                _ => continue,
+            };
+        }
+
+        self.row += 1;
+
+        Ok(())
+    }
+
+    fn finish(mut self: Box<Self>) -> Result<Option<File>, HttpError> {
+        let mut file = tempfile::tempfile().map_err(|err| Self::get_write_error(err.into()))?;
+
+        self.workbook
+            .save_to_writer(&mut file)
+            .map_err(Self::get_write_error)?;
+        file.seek(SeekFrom::Start(0))
+            .map_err(|err| Self::get_write_error(err.into()))?;
+
+        Ok(Some(file))
+    }
+}
@@ -1,3 +1,9 @@
+use crate::api::audit_logs::audit_logs_repository::AuditLogsRepository;
+use crate::api::audit_logs::audit_logs_service::AuditLogsService;
+use crate::api::categories::categories_repository::CategoriesRepository;
+use crate::api::categories::categories_service::CategoriesService;
+use crate::api::category_rules::category_rules_repository::CategoryRulesRepository;
+use crate::api::category_rules::category_rules_service::CategoryRulesService;
 use crate::api::customers::customers_repository::CustomerRepository;
 use crate::api::customers::customers_service::CustomersService;
 use crate::api::expenses::expenses_repository::ExpensesRepository;
@@ -12,59 +18,159 @@ use crate::shared::modules::auth::enums::roles::Roles;
 use crate::shared::modules::auth::middlewares::role_based_bearer_auth::AuthLayer;
 use crate::shared::modules::auth::services::auth0::Auth0Service;
 use crate::shared::modules::cache::middlewares::json_cache::JsonCacheLayer;
-use crate::shared::modules::redis::redis_service::RedisService;
+use crate::shared::modules::cache::middlewares::json_cache_invalidation::JsonCacheInvalidationLayer;
+use crate::shared::modules::cache::{AppCacheService, AppL2CacheService};
+use crate::shared::modules::db::unit_of_work::UnitOfWork;
+use crate::shared::modules::idempotency::middlewares::idempotency::IdempotencyLayer;
 
 mod dto;
-mod entities;
-mod traits;
-mod types;
+pub mod entities;
+mod exporters;
+mod parsers;
+pub mod traits;
+pub mod types;
+mod utils;
 
 mod expenses_handlers;
-mod expenses_repository;
+pub mod expenses_repository;
 mod expenses_service;
 
 pub fn get_router(
     sea_orm_client: Arc<DatabaseConnection>,
-    redis_service: Arc<RedisService>,
+    cache_service: Arc<AppCacheService>,
+    idempotency_layer: IdempotencyLayer<AppL2CacheService>,
     auth_service: Arc<Auth0Service>,
 ) -> ApiRouter {
+    let audit_logs_repository = Arc::new(AuditLogsRepository::new(sea_orm_client.clone()));
+    let audit_logs_service = Arc::new(AuditLogsService::new(audit_logs_repository));
+
+    let unit_of_work = Arc::new(UnitOfWork::new(sea_orm_client.clone()));
     let customers_repository = Arc::new(CustomerRepository::new(sea_orm_client.clone()));
-    let customers_service = Arc::new(CustomersService::new(customers_repository));
+    let customers_service = Arc::new(CustomersService::new(
+        customers_repository,
+        audit_logs_service.clone(),
+        unit_of_work.clone(),
+    ));
+
+    let categories_repository = Arc::new(CategoriesRepository::new(sea_orm_client.clone()));
+    let categories_service = Arc::new(CategoriesService::new(
+        categories_repository,
+        customers_service.clone(),
+        unit_of_work.clone(),
+    ));
+
+    let category_rules_repository = Arc::new(CategoryRulesRepository::new(sea_orm_client.clone()));
+    let category_rules_service = Arc::new(CategoryRulesService::new(
+        category_rules_repository,
+        customers_service.clone(),
+        categories_service.clone(),
+        unit_of_work.clone(),
+    ));
 
     let expenses_repository = Arc::new(ExpensesRepository::new(sea_orm_client));
-    let expenses_service = Arc::new(ExpensesService::new(expenses_repository, customers_service));
+    let expenses_service = Arc::new(ExpensesService::new(
+        expenses_repository,
+        customers_service.clone(),
+        categories_service,
+        category_rules_service,
+        audit_logs_service,
+        unit_of_work,
+    ));
 
     let api_state = ExpensesApiState { expenses_service };
 
-    let auth_layer = AuthLayer::new(auth_service.clone());
-    let cache_layer = JsonCacheLayer::new(redis_service, auth_service);
+    let auth_layer = AuthLayer::new(auth_service.clone(), customers_service);
+    // Responses are per user and have to be revalidated with ETags
+    let cache_layer = JsonCacheLayer::new(cache_service.clone(), auth_service)
+        .with_cache_control("private, no-cache");
+    let cache_invalidation_layer = JsonCacheInvalidationLayer::new(cache_service);
 
     let routes = ApiRouter::new()
         .api_route(
             "/",
             get(expenses_handlers::find_many)
-                .route_layer(cache_layer.clone())
+                .route_layer(
+                    cache_layer
+                        .clone()
+                        .with_stale_while_revalidate(30)
+                        .with_early_expiration(1.0),
+                )
+                .route_layer(auth_layer.verify(vec![Roles::Admin, Roles::Customer])),
+        )
+        .api_route(
+            "/export",
+            get(expenses_handlers::export)
+                .route_layer(auth_layer.verify(vec![Roles::Admin, Roles::Customer])),
+        )
+        .api_route(
+            "/trash",
+            get(expenses_handlers::find_trash)
+                .route_layer(auth_layer.verify(vec![Roles::Admin, Roles::Customer])),
+        )
+        .api_route(
+            "/{id}/history",
+            get(expenses_handlers::find_history)
                 .route_layer(auth_layer.verify(vec![Roles::Admin, Roles::Customer])),
         )
         .api_route(
             "/{id}",
             get(expenses_handlers::find_one)
-                .route_layer(cache_layer)
+                .route_layer(cache_layer.with_early_expiration(1.0))
                 .route_layer(auth_layer.verify(vec![Roles::Admin, Roles::Customer])),
         )
         .api_route(
             "/",
             post(expenses_handlers::create_many)
+                .route_layer(cache_invalidation_layer.clone())
+                .route_layer(idempotency_layer.clone())
+                .route_layer(auth_layer.verify(vec![Roles::Customer])),
+        )
+        .api_route(
+            "/import",
+            post(expenses_handlers::import)
+                .route_layer(cache_invalidation_layer.clone())
+                .route_layer(idempotency_layer)
+                .route_layer(auth_layer.verify(vec![Roles::Customer])),
+        )
+        .api_route(
+            "/categorize",
+            post(expenses_handlers::apply_category_rules)
+                .route_layer(cache_invalidation_layer.clone())
+                .route_layer(auth_layer.verify(vec![Roles::Customer])),
+        )
+        .api_route(
+            "/categorize/test",
+            post(expenses_handlers::test_category_rule)
+                .route_layer(auth_layer.verify(vec![Roles::Customer])),
+        )
+        .api_route(
+            "/{id}/restore",
+            post(expenses_handlers::restore_one)
+                .route_layer(cache_invalidation_layer.clone())
+                .route_layer(auth_layer.verify(vec![Roles::Customer])),
+        )
+        .api_route(
+            "/",
+            patch(expenses_handlers::update_many)
+                .route_layer(cache_invalidation_layer.clone())
+                .route_layer(auth_layer.verify(vec![Roles::Customer])),
+        )
+        .api_route(
+            "/",
+            delete(expenses_handlers::delete_many)
+                .route_layer(cache_invalidation_layer.clone())
                 .route_layer(auth_layer.verify(vec![Roles::Customer])),
         )
         .api_route(
             "/{id}",
             patch(expenses_handlers::update_one)
+                .route_layer(cache_invalidation_layer.clone())
                 .route_layer(auth_layer.verify(vec![Roles::Customer])),
         )
         .api_route(
             "/{id}",
             delete(expenses_handlers::delete_one)
+                .route_layer(cache_invalidation_layer)
                 .route_layer(auth_layer.verify(vec![Roles::Customer])),
         );
 
@@ -0,0 +1,368 @@
+use chrono::{DateTime, FixedOffset};
+use quick_xml::Reader;
+use quick_xml::escape::resolve_xml_entity;
+use quick_xml::events::Event;
+use std::collections::HashMap;
+
+use crate::api::expenses::traits::expenses_parser::ExpensesParser;
+use crate::api::expenses::types::imported_row::ImportedRow;
+use crate::api::expenses::types::statement_options::StatementOptions;
+use crate::api::expenses::types::statement_transaction::StatementTransaction;
+use crate::api::expenses::utils::parse_date_with_format;
+use crate::shared::errors::http_error::HttpError;
+
+const STATEMENT_ELEMENT: &str = "BkToCstmrStmt";
+const ACCOUNT_STATEMENT_ELEMENT: &str = "Stmt";
+/// Paths of the account ids of statements, an IBAN or another scheme
+const ACCOUNT_ID_PATHS: [&str; 2] = ["Stmt/Acct/Id/IBAN", "Stmt/Acct/Id/Othr/Id"];
+const ENTRY_ELEMENT: &str = "Ntry";
+const DEBIT_INDICATOR: &str = "DBIT";
+/// Placeholder of references which were not set
+const NOT_PROVIDED: &str = "NOTPROVIDED";
+
+/// Parses ISO 20022 CAMT.053 statements, every entry (`Ntry`) is a transaction.
+/// Batched entries with several transaction details are imported as one.
+/// References are unique per account only, so external ids are `camt053:<account id>:<reference>`
+pub struct Camt053ExpensesParser {
+    options: StatementOptions,
+}
+
+/// Texts of the elements of an entry by their paths inside of it, e.g. `BookgDt/Dt`.
+/// Only the first text of repeated elements is kept
+struct CamtEntry {
+    row: usize,
+    /// Id of the account of the statement of the entry
+    account: String,
+    fields: HashMap<String, String>,
+}
+
+impl CamtEntry {
+    fn get_first(&self, paths: &[&str]) -> Option<String> {
+        paths
+            .iter()
+            .filter_map(|path| self.fields.get(*path))
+            .find(|value| *value != NOT_PROVIDED)
+            .cloned()
+    }
+}
+
+impl Camt053ExpensesParser {
+    pub fn new(options: StatementOptions) -> Self {
+        Self { options }
+    }
+
+    fn parse_transaction(entry: &CamtEntry) -> Result<StatementTransaction, String> {
+        let amount = entry.get_first(&["Amt"]).ok_or("Amt is missing")?;
+        let amount: f64 = amount
+            .parse()
+            .map_err(|_| format!("Amt '{amount}' is not a number"))?;
+        let is_debit = entry
+            .get_first(&["CdtDbtInd"])
+            .ok_or("CdtDbtInd is missing")?
+            == DEBIT_INDICATOR;
+        let date = entry
+            .get_first(&["BookgDt/Dt", "BookgDt/DtTm", "ValDt/Dt", "ValDt/DtTm"])
+            .ok_or("BookgDt is missing")?;
+
+        // The other party is the creditor of debits and the debtor of credits
+        let payee_paths: [&str; 2] = if is_debit {
+            [
+                "NtryDtls/TxDtls/RltdPties/Cdtr/Nm",
+                "NtryDtls/TxDtls/RltdPties/Cdtr/Pty/Nm",
+            ]
+        } else {
+            [
+                "NtryDtls/TxDtls/RltdPties/Dbtr/Nm",
+                "NtryDtls/TxDtls/RltdPties/Dbtr/Pty/Nm",
+            ]
+        };
+
+        let external_id = entry
+            .get_first(&[
+                "AcctSvcrRef",
+                "NtryDtls/TxDtls/Refs/AcctSvcrRef",
+                "NtryDtls/TxDtls/Refs/TxId",
+                "NtryDtls/TxDtls/Refs/EndToEndId",
+                "NtryRef",
+            ])
+            .map(|reference| format!("camt053:{}:{reference}", entry.account));
+
+        Ok(StatementTransaction {
+            external_id,
+            date: Self::parse_date(&date)?,
+            amount: if is_debit { -amount } else { amount },
+            payee: entry.get_first(&payee_paths),
+            memo: entry.get_first(&[
+                "NtryDtls/TxDtls/RmtInf/Ustrd",
+                "NtryDtls/TxDtls/AddtlTxInf",
+                "AddtlNtryInf",
+            ]),
+            category: None,
+        })
+    }
+
+    fn parse_date(value: &str) -> Result<DateTime<FixedOffset>, String> {
+        DateTime::parse_from_rfc3339(value)
+            .ok()
+            .or_else(|| parse_date_with_format(value, "%Y-%m-%dT%H:%M:%S%.f"))
+            .or_else(|| parse_date_with_format(value, "%Y-%m-%d"))
+            .ok_or_else(|| format!("Date '{value}' is not an ISO 8601 date"))
+    }
+
+    fn read_entries(content: &[u8]) -> Result<Vec<CamtEntry>, HttpError> {
+        let mut reader = Reader::from_reader(content);
+        let mut buffer = Vec::new();
+
+        let mut path: Vec<String> = Vec::new();
+        let mut text = String::new();
+        let mut is_statement = false;
+        let mut account = String::new();
+        let mut entries = Vec::new();
+        // Entry being read with the depth of its element
+        let mut entry: Option<(usize, CamtEntry)> = None;
+        let mut line = 1;
+        let mut line_position = 0;
+
+        loop {
+            let position = reader.buffer_position() as usize;
+            let event = reader.read_event_into(&mut buffer).map_err(|err| {
+                HttpError::BadRequest(format!(
+                    "Failed to parse CAMT.053 XML at position {}: {err}",
+                    reader.error_position()
+                ))
+            })?;
+
+            match event {
+                Event::Start(element) => {
+                    let name = String::from_utf8_lossy(element.local_name().as_ref()).to_string();
+
+                    is_statement |= name == STATEMENT_ELEMENT;
+
+                    if name == ACCOUNT_STATEMENT_ELEMENT && entry.is_none() {
+                        account.clear();
+                    }
+
+                    if name == ENTRY_ELEMENT && entry.is_none() {
+                        line += content[line_position..position]
+                            .iter()
+                            .filter(|byte| **byte == b'\n')
+                            .count();
+                        line_position = position;
+
+                        entry = Some((
+                            path.len() + 1,
+                            CamtEntry {
+                                row: line,
+                                account: account.clone(),
+                                fields: HashMap::new(),
+                            },
+                        ));
+                    }
+
+                    path.push(name);
+                    text.clear();
+                }
+                Event::Text(value) => {
+                    text.push_str(&value.decode().unwrap_or_default());
+                }
+                Event::GeneralRef(reference) => {
+                    let resolved = match reference.resolve_char_ref() {
+                        Ok(Some(char)) => Some(char.to_string()),
+                        _ => reference
+                            .decode()
+                            .ok()
+                            .and_then(|name| resolve_xml_entity(&name))
+                            .map(str::to_string),
+                    };
+
+                    text.push_str(&resolved.unwrap_or_default());
+                }
+                Event::End(_) => {
+                    let value = text.trim();
+
+                    if entry.is_none() && !value.is_empty() {
+                        let element_path = path.join("/");
+
+                        if ACCOUNT_ID_PATHS
+                            .iter()
+                            .any(|account_path| element_path.ends_with(account_path))
+                        {
+                            account = value.to_string();
+                        }
+                    }
+
+                    if let Some((depth, camt_entry)) = entry.as_mut() {
+                        if path.len() > *depth && !value.is_empty() {
+                            camt_entry
+                                .fields
+                                .entry(path[*depth..].join("/"))
+                                .or_insert_with(|| value.to_string());
+                        }
+
+                        if path.len() == *depth {
+                            entries.extend(entry.take().map(|(_, camt_entry)| camt_entry));
+                        }
+                    }
+
+                    path.pop();
+                    text.clear();
+                }
+                Event::Eof => break,
+                _ => {}
+            }
+
+            buffer.clear();
+        }
+
+        if !is_statement {
+            return Err(HttpError::BadRequest(
+                "File is not a CAMT.053 statement".into(),
+            ));
+        }
+
+        Ok(entries)
+    }
+}
+
+impl ExpensesParser for Camt053ExpensesParser {
+    fn parse(&self, content: &[u8]) -> Result<Vec<ImportedRow>, HttpError> {
+        let imported_rows = Self::read_entries(content)?
+            .iter()
+            .filter_map(|entry| {
+                self.options
+                    .to_imported_row(entry.row, Self::parse_transaction(entry))
+            })
+            .collect();
+
+        Ok(imported_rows)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+
+    fn get_parser(query: serde_json::Value) -> Camt053ExpensesParser {
+        let query = serde_json::from_value(query).unwrap();
+
+        Camt053ExpensesParser::new(StatementOptions::new(&query).unwrap())
+    }
+
+    const STATEMENT: &str = r#"<?xml version="1.0" encoding="UTF-8"?>
+<Document xmlns="urn:iso:std:iso:20022:tech:xsd:camt.053.001.02">
+  <BkToCstmrStmt>
+    <Stmt>
+      <Acct><Id><IBAN>DE89370400440532013000</IBAN></Id></Acct>
+      <Ntry>
+        <Amt Ccy="EUR">12.50</Amt>
+        <CdtDbtInd>DBIT</CdtDbtInd>
+        <BookgDt><Dt>2026-10-19</Dt></BookgDt>
+        <AcctSvcrRef>NOTPROVIDED</AcctSvcrRef>
+        <NtryDtls>
+          <TxDtls>
+            <Refs><TxId>TX-1</TxId></Refs>
+            <RltdPties>
+              <Dbtr><Nm>Me</Nm></Dbtr>
+              <Cdtr><Nm>Caf&#233; &amp; Bar</Nm></Cdtr>
+            </RltdPties>
+            <RmtInf><Ustrd>Lunch</Ustrd></RmtInf>
+          </TxDtls>
+        </NtryDtls>
+      </Ntry>
+      <Ntry>
+        <Amt Ccy="EUR">1000.00</Amt>
+        <CdtDbtInd>CRDT</CdtDbtInd>
+        <BookgDt><DtTm>2026-10-20T09:30:00+02:00</DtTm></BookgDt>
+        <AcctSvcrRef>REF-2</AcctSvcrRef>
+        <NtryDtls>
+          <TxDtls>
+            <RltdPties>
+              <Dbtr><Nm>Employer</Nm></Dbtr>
+              <Cdtr><Nm>Me</Nm></Cdtr>
+            </RltdPties>
+          </TxDtls>
+        </NtryDtls>
+      </Ntry>
+      <Ntry>
+        <Amt Ccy="EUR">abc</Amt>
+        <CdtDbtInd>DBIT</CdtDbtInd>
+        <BookgDt><Dt>2026-10-21</Dt></BookgDt>
+      </Ntry>
+    </Stmt>
+  </BkToCstmrStmt>
+</Document>
+"#;
+
+    #[test]
+    fn parse() {
+        let imported_rows = get_parser(json!({})).parse(STATEMENT.as_bytes()).unwrap();
+
+        assert_eq!(imported_rows.len(), 2);
+        assert_eq!(imported_rows[0].row, 6);
+
+        let expense = imported_rows[0].expense.as_ref().unwrap();
+        assert_eq!(expense.amount, 12.5);
+        assert_eq!(expense.date.to_rfc3339(), "2026-10-19T00:00:00+00:00");
+        assert_eq!(expense.note.as_deref(), Some("Café & Bar - Lunch"));
+        assert_eq!(
+            expense.external_id.as_deref(),
+            Some("camt053:DE89370400440532013000:TX-1")
+        );
+
+        assert_eq!(imported_rows[1].row, 36);
+        assert!(
+            imported_rows[1]
+                .expense
+                .as_ref()
+                .unwrap_err()
+                .contains("Amt")
+        );
+    }
+
+    #[test]
+    fn namespace_external_ids_by_account() {
+        let entry = "<Ntry>
+          <Amt>1.00</Amt>
+          <CdtDbtInd>DBIT</CdtDbtInd>
+          <BookgDt><Dt>2026-10-19</Dt></BookgDt>
+          <AcctSvcrRef>REF-1</AcctSvcrRef>
+        </Ntry>";
+        let statement = format!(
+            "<Document><BkToCstmrStmt>
+              <Stmt><Acct><Id><IBAN>DE89370400440532013000</IBAN></Id></Acct>{entry}</Stmt>
+              <Stmt><Acct><Id><Othr><Id>ACC-2</Id></Othr></Id></Acct>{entry}</Stmt>
+              <Stmt>{entry}</Stmt>
+            </BkToCstmrStmt></Document>"
+        );
+
+        let external_ids: Vec<_> = get_parser(json!({}))
+            .parse(statement.as_bytes())
+            .unwrap()
+            .into_iter()
+            .map(|row| row.expense.unwrap().external_id.unwrap())
+            .collect();
+
+        assert_eq!(
+            external_ids,
+            [
+                "camt053:DE89370400440532013000:REF-1",
+                "camt053:ACC-2:REF-1",
+                "camt053::REF-1",
+            ]
+        );
+    }
+
+    #[test]
+    fn parse_fails_on_other_files() {
+        let parser = get_parser(json!({}));
+
+        assert!(parser.parse(b"<Document><Other/></Document>").is_err());
+        assert!(
+            parser
+                .parse(b"<Document><BkToCstmrStmt></Document>")
+                .is_err()
+        );
+    }
+}
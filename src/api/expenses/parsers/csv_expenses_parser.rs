@@ -0,0 +1,343 @@
+use chrono::{DateTime, FixedOffset};
+use csv::{ReaderBuilder, StringRecord, Trim};
+
+use crate::api::expenses::dto::create_expense_dto::CreateExpenseDto;
+use crate::api::expenses::dto::import_expenses_query_dto::ImportExpensesQueryDto;
+use crate::api::expenses::traits::expenses_parser::ExpensesParser;
+use crate::api::expenses::types::category_mapper::CategoryMapper;
+use crate::api::expenses::types::imported_row::ImportedRow;
+use crate::api::expenses::utils::parse_date_with_format;
+use crate::shared::errors::http_error::HttpError;
+
+const DEFAULT_DELIMITER: char = ',';
+const DEFAULT_DECIMAL_SEPARATOR: char = '.';
+const DEFAULT_DATE_FORMAT: &str = "%Y-%m-%d";
+const DEFAULT_AMOUNT_COLUMN: &str = "amount";
+const DEFAULT_DATE_COLUMN: &str = "date";
+const DEFAULT_CATEGORY_COLUMN: &str = "category";
+const DEFAULT_NOTE_COLUMN: &str = "note";
+
+/// Parses CSV exports of spreadsheets, columns are mapped onto expense fields by name or index
+pub struct CsvExpensesParser {
+    delimiter: u8,
+    decimal_separator: char,
+    date_format: Option<String>,
+    has_headers: bool,
+    amount_column: String,
+    date_column: String,
+    category_column: String,
+    note_column: Option<String>,
+    is_note_column_required: bool,
+    category_mapper: CategoryMapper,
+}
+
+/// Indexes of the mapped columns in the rows
+struct ColumnIndexes {
+    amount: usize,
+    date: usize,
+    category: usize,
+    note: Option<usize>,
+}
+
+impl CsvExpensesParser {
+    pub fn new(query: &ImportExpensesQueryDto) -> Result<Self, HttpError> {
+        let delimiter = query.delimiter.unwrap_or(DEFAULT_DELIMITER);
+
+        if !delimiter.is_ascii() {
+            return Err(HttpError::BadRequest(
+                "delimiter should be an ASCII character".into(),
+            ));
+        }
+
+        Ok(Self {
+            delimiter: delimiter as u8,
+            decimal_separator: query.decimal_separator.unwrap_or(DEFAULT_DECIMAL_SEPARATOR),
+            date_format: query.date_format.clone(),
+            has_headers: query.has_headers,
+            amount_column: Self::get_column(&query.amount_column, DEFAULT_AMOUNT_COLUMN),
+            date_column: Self::get_column(&query.date_column, DEFAULT_DATE_COLUMN),
+            category_column: Self::get_column(&query.category_column, DEFAULT_CATEGORY_COLUMN),
+            note_column: query
+                .note_column
+                .clone()
+                .or_else(|| query.has_headers.then(|| DEFAULT_NOTE_COLUMN.to_string())),
+            is_note_column_required: query.note_column.is_some(),
+            category_mapper: CategoryMapper::new(
+                query.category_mapping.as_deref(),
+                query.default_category.clone(),
+            )?,
+        })
+    }
+
+    fn get_column(column: &Option<String>, default_column: &str) -> String {
+        column.clone().unwrap_or_else(|| default_column.to_string())
+    }
+
+    fn get_column_indexes(
+        &self,
+        headers: Option<&StringRecord>,
+    ) -> Result<ColumnIndexes, HttpError> {
+        let note = match &self.note_column {
+            Some(note_column) => match self.find_column(headers, note_column) {
+                Ok(index) => Some(index),
+                Err(err) if self.is_note_column_required => return Err(err),
+                Err(_) => None,
+            },
+            None => None,
+        };
+
+        Ok(ColumnIndexes {
+            amount: self.find_column(headers, &self.amount_column)?,
+            date: self.find_column(headers, &self.date_column)?,
+            category: self.find_column(headers, &self.category_column)?,
+            note,
+        })
+    }
+
+    /// Column by its name (case-insensitive) in the headers, or by its index without headers
+    fn find_column(
+        &self,
+        headers: Option<&StringRecord>,
+        column: &str,
+    ) -> Result<usize, HttpError> {
+        let Some(headers) = headers else {
+            return column.trim().parse().map_err(|_| {
+                HttpError::BadRequest(format!(
+                    "Column '{column}' should be a zero-based index for files without headers"
+                ))
+            });
+        };
+
+        headers
+            .iter()
+            .position(|header| header.trim().eq_ignore_ascii_case(column.trim()))
+            .ok_or_else(|| HttpError::BadRequest(format!("Column '{column}' was not found")))
+    }
+
+    fn parse_record(
+        &self,
+        record: &StringRecord,
+        columns: &ColumnIndexes,
+    ) -> Result<CreateExpenseDto, String> {
+        let get_cell = |index: usize| record.get(index).map(str::trim).unwrap_or_default();
+
+        let note = columns
+            .note
+            .map(get_cell)
+            .filter(|note| !note.is_empty())
+            .map(str::to_string);
+
+        Ok(CreateExpenseDto {
+            amount: self.parse_amount(get_cell(columns.amount))?,
+            date: self.parse_date(get_cell(columns.date))?,
+            category: self.parse_category(get_cell(columns.category))?,
+            note,
+            external_id: None,
+        })
+    }
+
+    /// Thousands separators and currency signs are dropped. Negative amounts fail the row,
+    /// they are credits in bank exports rather than expenses
+    fn parse_amount(&self, cell: &str) -> Result<f64, String> {
+        let normalized_amount: String = cell
+            .chars()
+            .filter(|char| char.is_ascii_digit() || *char == '-' || *char == self.decimal_separator)
+            .map(|char| {
+                if char == self.decimal_separator {
+                    '.'
+                } else {
+                    char
+                }
+            })
+            .collect();
+
+        let amount = normalized_amount
+            .parse::<f64>()
+            .map_err(|_| format!("Amount '{cell}' is not a number"))?;
+
+        if amount < 0.0 {
+            return Err(format!("Amount '{cell}' should not be negative"));
+        }
+
+        Ok(amount)
+    }
+
+    /// Dates without time are midnight UTC
+    fn parse_date(&self, cell: &str) -> Result<DateTime<FixedOffset>, String> {
+        let date = match &self.date_format {
+            Some(date_format) => parse_date_with_format(cell, date_format),
+            None => DateTime::parse_from_rfc3339(cell)
+                .ok()
+                .or_else(|| parse_date_with_format(cell, DEFAULT_DATE_FORMAT)),
+        };
+
+        date.ok_or_else(|| format!("Date '{cell}' does not match the date format"))
+    }
+
+    /// Empty cells are left to the categorization rules unless there is a default category
+    fn parse_category(&self, cell: &str) -> Result<Option<String>, String> {
+        match self.category_mapper.map(cell) {
+            Some(category) => Ok(Some(category)),
+            None if cell.is_empty() => Ok(None),
+            None => Err(format!(
+                "Category '{cell}' is unknown, map it with categoryMapping or set defaultCategory"
+            )),
+        }
+    }
+}
+
+impl ExpensesParser for CsvExpensesParser {
+    fn parse(&self, content: &[u8]) -> Result<Vec<ImportedRow>, HttpError> {
+        let mut reader = ReaderBuilder::new()
+            .delimiter(self.delimiter)
+            .has_headers(self.has_headers)
+            .flexible(true)
+            .trim(Trim::All)
+            .from_reader(content);
+
+        let headers = if self.has_headers {
+            let headers = reader.headers().map_err(|err| {
+                HttpError::BadRequest(format!("Failed to read CSV headers: {err}"))
+            })?;
+
+            Some(headers.clone())
+        } else {
+            None
+        };
+        let columns = self.get_column_indexes(headers.as_ref())?;
+
+        let imported_rows = reader
+            .records()
+            .enumerate()
+            .map(|(index, record)| {
+                // Header row and rows before the failed one are the best guess of its line
+                let fallback_row = index + 1 + usize::from(self.has_headers);
+
+                match record {
+                    Ok(record) => ImportedRow {
+                        row: record
+                            .position()
+                            .map_or(fallback_row, |position| position.line() as usize),
+                        expense: self.parse_record(&record, &columns),
+                    },
+                    Err(err) => ImportedRow {
+                        row: fallback_row,
+                        expense: Err(format!("Failed to read the row: {err}")),
+                    },
+                }
+            })
+            .collect();
+
+        Ok(imported_rows)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+
+    fn get_parser(query: serde_json::Value) -> CsvExpensesParser {
+        let query: ImportExpensesQueryDto = serde_json::from_value(query).unwrap();
+
+        CsvExpensesParser::new(&query).unwrap()
+    }
+
+    #[test]
+    fn parse() {
+        let parser = get_parser(json!({}));
+        let content = "date,amount,category,note\n\
+            2026-10-19,12.50,Food,Lunch\n\
+            2026-10-20,-3.00,Taxi,\n\
+            not a date,1.00,Food,\n\
+            2026-10-21,7,Unknown,\n";
+
+        let imported_rows = parser.parse(content.as_bytes()).unwrap();
+
+        assert_eq!(imported_rows.len(), 4);
+        assert_eq!(
+            imported_rows.iter().map(|row| row.row).collect::<Vec<_>>(),
+            vec![2, 3, 4, 5]
+        );
+
+        let expense = imported_rows[0].expense.as_ref().unwrap();
+        assert_eq!(expense.amount, 12.5);
+        assert_eq!(expense.date.to_rfc3339(), "2026-10-19T00:00:00+00:00");
+        assert_eq!(expense.category.as_deref(), Some("FOOD"));
+        assert_eq!(expense.note.as_deref(), Some("Lunch"));
+
+        assert!(
+            imported_rows[1]
+                .expense
+                .as_ref()
+                .unwrap_err()
+                .contains("negative")
+        );
+        assert!(
+            imported_rows[2]
+                .expense
+                .as_ref()
+                .unwrap_err()
+                .contains("Date")
+        );
+        assert!(
+            imported_rows[3]
+                .expense
+                .as_ref()
+                .unwrap_err()
+                .contains("Category")
+        );
+    }
+
+    #[test]
+    fn parse_without_headers() {
+        let parser = get_parser(json!({
+            "hasHeaders": false,
+            "delimiter": ";",
+            "amountColumn": "1",
+            "dateColumn": "0",
+            "categoryColumn": "2",
+            "dateFormat": "%d.%m.%Y",
+        }));
+
+        let imported_rows = parser.parse("19.10.2026;5;FOOD\n".as_bytes()).unwrap();
+
+        assert_eq!(imported_rows[0].row, 1);
+
+        let expense = imported_rows[0].expense.as_ref().unwrap();
+        assert_eq!(expense.amount, 5.0);
+        assert_eq!(expense.date.to_rfc3339(), "2026-10-19T00:00:00+00:00");
+        assert_eq!(expense.note, None);
+    }
+
+    #[test]
+    fn parse_fails_on_missing_column() {
+        let parser = get_parser(json!({ "noteColumn": "comment" }));
+
+        assert!(parser.parse("date,amount,category\n".as_bytes()).is_err());
+    }
+
+    #[test]
+    fn parse_amount() {
+        let cases = [
+            ('.', "12.50", Ok(12.5)),
+            ('.', "$1,234.56", Ok(1234.56)),
+            (',', "1.234,56 EUR", Ok(1234.56)),
+            ('.', "0", Ok(0.0)),
+            ('.', "-12.50", Err(())),
+            ('.', "abc", Err(())),
+            ('.', "", Err(())),
+        ];
+
+        for (decimal_separator, cell, expected) in cases {
+            let parser = get_parser(json!({ "decimalSeparator": decimal_separator }));
+
+            assert_eq!(
+                parser.parse_amount(cell).map_err(|_| ()),
+                expected,
+                "{cell}"
+            );
+        }
+    }
+}
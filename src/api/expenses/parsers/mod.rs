@@ -0,0 +1,39 @@
+use crate::api::expenses::dto::import_expenses_query_dto::ImportExpensesQueryDto;
+use crate::api::expenses::parsers::camt053_expenses_parser::Camt053ExpensesParser;
+use crate::api::expenses::parsers::csv_expenses_parser::CsvExpensesParser;
+use crate::api::expenses::parsers::ofx_expenses_parser::OfxExpensesParser;
+use crate::api::expenses::parsers::qif_expenses_parser::QifExpensesParser;
+use crate::api::expenses::traits::expenses_parser::ExpensesParser;
+use crate::api::expenses::types::import_format::ImportFormat;
+use crate::api::expenses::types::statement_options::StatementOptions;
+use crate::shared::errors::http_error::HttpError;
+
+pub mod camt053_expenses_parser;
+pub mod csv_expenses_parser;
+pub mod ofx_expenses_parser;
+pub mod qif_expenses_parser;
+
+/// Parser of the requested format, or of the format detected by the content
+pub fn get_expenses_parser(
+    query: &ImportExpensesQueryDto,
+    content: &[u8],
+) -> Result<Box<dyn ExpensesParser>, HttpError> {
+    let format = query
+        .format
+        .unwrap_or_else(|| ImportFormat::detect(content));
+
+    let expenses_parser: Box<dyn ExpensesParser> = match format {
+        ImportFormat::Csv => Box::new(CsvExpensesParser::new(query)?),
+        ImportFormat::Ofx => Box::new(OfxExpensesParser::new(StatementOptions::new(query)?)),
+        ImportFormat::Qif => Box::new(QifExpensesParser::new(
+            query.date_format.clone(),
+            query.decimal_separator,
+            StatementOptions::new(query)?,
+        )),
+        ImportFormat::Camt053 => {
+            Box::new(Camt053ExpensesParser::new(StatementOptions::new(query)?))
+        }
+    };
+
+    Ok(expenses_parser)
+}
@@ -0,0 +1,289 @@
+use chrono::{DateTime, FixedOffset, NaiveDateTime, TimeZone};
+
+use crate::api::expenses::traits::expenses_parser::ExpensesParser;
+use crate::api::expenses::types::imported_row::ImportedRow;
+use crate::api::expenses::types::statement_options::StatementOptions;
+use crate::api::expenses::types::statement_transaction::StatementTransaction;
+use crate::shared::errors::http_error::HttpError;
+
+const TRANSACTION_START_TAG: &str = "<STMTTRN>";
+const TRANSACTION_END_TAG: &str = "</STMTTRN>";
+/// Aggregates of the bank and credit card accounts of statements
+const ACCOUNT_TAGS: [&str; 2] = ["<BANKACCTFROM>", "<CCACCTFROM>"];
+
+/// Parses OFX (and QFX) statements. Both SGML and XML versions are read the same way,
+/// values are the text after the opening tags, so closing tags of values are optional.
+/// FITIDs are unique per account only, so external ids are `ofx:BANKID:ACCTID:FITID`
+pub struct OfxExpensesParser {
+    options: StatementOptions,
+}
+
+impl OfxExpensesParser {
+    pub fn new(options: StatementOptions) -> Self {
+        Self { options }
+    }
+
+    /// `account` is `BANKID:ACCTID` of the statement of the transaction
+    fn parse_transaction(block: &str, account: &str) -> Result<StatementTransaction, String> {
+        let amount = Self::get_value(block, "TRNAMT").ok_or("TRNAMT is missing")?;
+        let date = Self::get_value(block, "DTPOSTED").ok_or("DTPOSTED is missing")?;
+
+        Ok(StatementTransaction {
+            external_id: Self::get_value(block, "FITID")
+                .map(|fitid| format!("ofx:{account}:{fitid}")),
+            date: Self::parse_date(&date)?,
+            amount: amount
+                .replace(',', ".")
+                .parse()
+                .map_err(|_| format!("TRNAMT '{amount}' is not a number"))?,
+            payee: Self::get_value(block, "NAME"),
+            memo: Self::get_value(block, "MEMO"),
+            category: None,
+        })
+    }
+
+    /// `BANKID:ACCTID` of the last account aggregate of the part, credit cards have no BANKID
+    fn get_account(part: &str) -> Option<String> {
+        let account_start = ACCOUNT_TAGS
+            .iter()
+            .filter_map(|tag| part.rfind(tag))
+            .max()?;
+        let account = &part[account_start..];
+
+        Some(format!(
+            "{}:{}",
+            Self::get_value(account, "BANKID").unwrap_or_default(),
+            Self::get_value(account, "ACCTID").unwrap_or_default()
+        ))
+    }
+
+    fn get_value(block: &str, tag: &str) -> Option<String> {
+        let opening_tag = format!("<{tag}>");
+        let value_start = block.find(&opening_tag)? + opening_tag.len();
+        let value = block[value_start..].split('<').next()?.trim();
+
+        (!value.is_empty()).then(|| Self::unescape(value))
+    }
+
+    fn unescape(value: &str) -> String {
+        value
+            .replace("&lt;", "<")
+            .replace("&gt;", ">")
+            .replace("&quot;", "\"")
+            .replace("&apos;", "'")
+            .replace("&nbsp;", " ")
+            .replace("&amp;", "&")
+    }
+
+    /// `YYYYMMDD[HHMM[SS[.XXX]]][[offset[:TZ]]]`, e.g. `20240105120000.000[-5:EST]`. UTC by default
+    fn parse_date(value: &str) -> Result<DateTime<FixedOffset>, String> {
+        let get_error = || format!("DTPOSTED '{value}' is not an OFX date");
+
+        let (date_time, time_zone) = value.split_once('[').unwrap_or((value, ""));
+        let date_time = date_time.split('.').next().unwrap_or_default();
+        let date_time = match date_time.len() {
+            8 => format!("{date_time}000000"),
+            12 => format!("{date_time}00"),
+            _ => date_time.to_string(),
+        };
+        let date_time =
+            NaiveDateTime::parse_from_str(&date_time, "%Y%m%d%H%M%S").map_err(|_| get_error())?;
+
+        let offset_hours = time_zone
+            .trim_end_matches(']')
+            .split(':')
+            .next()
+            .filter(|offset| !offset.is_empty())
+            .map(str::parse::<f64>)
+            .transpose()
+            .map_err(|_| get_error())?
+            .unwrap_or_default();
+        let offset = FixedOffset::east_opt((offset_hours * 3600.0) as i32).ok_or_else(get_error)?;
+
+        offset
+            .from_local_datetime(&date_time)
+            .single()
+            .ok_or_else(get_error)
+    }
+}
+
+impl ExpensesParser for OfxExpensesParser {
+    fn parse(&self, content: &[u8]) -> Result<Vec<ImportedRow>, HttpError> {
+        let content = String::from_utf8_lossy(content);
+
+        if !content.contains("<OFX>") {
+            return Err(HttpError::BadRequest("File is not an OFX statement".into()));
+        }
+
+        let mut imported_rows = Vec::new();
+        let mut offset = 0;
+        // Lines are counted from the previous block on, not from the start of the file
+        let mut line = 1;
+        let mut line_position = 0;
+        // Files can have statements of several accounts, each one precedes its transactions
+        let mut account = String::from(":");
+
+        while let Some(block_start) = content[offset..].find(TRANSACTION_START_TAG) {
+            let block_start = offset + block_start;
+            if let Some(statement_account) = Self::get_account(&content[offset..block_start]) {
+                account = statement_account;
+            }
+            let block_end = content[block_start..]
+                .find(TRANSACTION_END_TAG)
+                .map_or(content.len(), |block_end| block_start + block_end);
+            line += content[line_position..block_start].matches('\n').count();
+            line_position = block_start;
+            let transaction = Self::parse_transaction(&content[block_start..block_end], &account);
+
+            imported_rows.extend(self.options.to_imported_row(line, transaction));
+            offset = block_end;
+        }
+
+        Ok(imported_rows)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+
+    fn get_parser(query: serde_json::Value) -> OfxExpensesParser {
+        let query = serde_json::from_value(query).unwrap();
+
+        OfxExpensesParser::new(StatementOptions::new(&query).unwrap())
+    }
+
+    const STATEMENT: &str = "OFXHEADER:100
+<OFX>
+<STMTRS>
+<BANKACCTFROM>
+<BANKID>111000025
+<ACCTID>123456
+</BANKACCTFROM>
+<BANKTRANLIST>
+<STMTTRN>
+<TRNTYPE>DEBIT
+<DTPOSTED>20261019120000.000[-5:EST]
+<TRNAMT>-12.50
+<FITID>1001
+<NAME>Coffee &amp; Co
+<MEMO>Card payment
+</STMTTRN>
+<STMTTRN>
+<TRNTYPE>CREDIT
+<DTPOSTED>20261020
+<TRNAMT>100,00
+<FITID>1002
+<NAME>Refund
+</STMTTRN>
+<STMTTRN>
+<TRNTYPE>DEBIT
+<DTPOSTED>20261021
+<TRNAMT>abc
+</STMTTRN>
+</BANKTRANLIST>
+</STMTRS>
+</OFX>
+";
+
+    #[test]
+    fn parse() {
+        let imported_rows = get_parser(json!({})).parse(STATEMENT.as_bytes()).unwrap();
+
+        assert_eq!(imported_rows.len(), 2);
+        assert_eq!(imported_rows[0].row, 9);
+
+        let expense = imported_rows[0].expense.as_ref().unwrap();
+        assert_eq!(expense.amount, 12.5);
+        assert_eq!(expense.date.to_rfc3339(), "2026-10-19T12:00:00-05:00");
+        assert_eq!(expense.note.as_deref(), Some("Coffee & Co - Card payment"));
+        assert_eq!(
+            expense.external_id.as_deref(),
+            Some("ofx:111000025:123456:1001")
+        );
+        assert_eq!(expense.category, None);
+
+        assert_eq!(imported_rows[1].row, 24);
+        assert!(
+            imported_rows[1]
+                .expense
+                .as_ref()
+                .unwrap_err()
+                .contains("TRNAMT")
+        );
+    }
+
+    #[test]
+    fn namespace_external_ids_by_account() {
+        let statement = "<OFX>
+<STMTRS>
+<BANKACCTFROM>
+<BANKID>111000025
+<ACCTID>123456
+</BANKACCTFROM>
+<BANKTRANLIST>
+<STMTTRN><DTPOSTED>20261019<TRNAMT>-1.00<FITID>1001</STMTTRN>
+</BANKTRANLIST>
+</STMTRS>
+<CCSTMTRS>
+<CCACCTFROM>
+<ACCTID>4111111111111111
+</CCACCTFROM>
+<BANKTRANLIST>
+<STMTTRN><DTPOSTED>20261019<TRNAMT>-1.00<FITID>1001</STMTTRN>
+<STMTTRN><DTPOSTED>20261019<TRNAMT>-2.00<FITID>1002</STMTTRN>
+</BANKTRANLIST>
+</CCSTMTRS>
+</OFX>
+";
+
+        let external_ids: Vec<_> = get_parser(json!({}))
+            .parse(statement.as_bytes())
+            .unwrap()
+            .into_iter()
+            .map(|row| row.expense.unwrap().external_id.unwrap())
+            .collect();
+
+        assert_eq!(
+            external_ids,
+            [
+                "ofx:111000025:123456:1001",
+                "ofx::4111111111111111:1001",
+                "ofx::4111111111111111:1002",
+            ]
+        );
+    }
+
+    #[test]
+    fn parse_fails_on_other_files() {
+        assert!(get_parser(json!({})).parse(b"date,amount").is_err());
+    }
+
+    #[test]
+    fn parse_date() {
+        let cases = [
+            ("20261019", Some("2026-10-19T00:00:00+00:00")),
+            ("202610191230", Some("2026-10-19T12:30:00+00:00")),
+            ("20261019123045.123", Some("2026-10-19T12:30:45+00:00")),
+            ("20261019123045[+3:MSK]", Some("2026-10-19T12:30:45+03:00")),
+            (
+                "20261019123045[-3.5:NST]",
+                Some("2026-10-19T12:30:45-03:30"),
+            ),
+            ("2026-10-19", None),
+            ("", None),
+        ];
+
+        for (value, expected) in cases {
+            assert_eq!(
+                OfxExpensesParser::parse_date(value)
+                    .ok()
+                    .map(|date| date.to_rfc3339()),
+                expected.map(str::to_string),
+                "{value}"
+            );
+        }
+    }
+}
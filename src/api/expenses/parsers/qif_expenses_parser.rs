@@ -0,0 +1,332 @@
+use chrono::{DateTime, FixedOffset};
+use sha2::{Digest, Sha256};
+use std::collections::HashMap;
+
+use crate::api::expenses::traits::expenses_parser::ExpensesParser;
+use crate::api::expenses::types::imported_row::ImportedRow;
+use crate::api::expenses::types::statement_options::StatementOptions;
+use crate::api::expenses::types::statement_transaction::StatementTransaction;
+use crate::api::expenses::utils::parse_date_with_format;
+use crate::shared::errors::http_error::HttpError;
+
+/// QIF has no standard date format, US dates are the most common ones.
+/// Two-digit years go first, `%Y` would read them as years of the first century
+const DEFAULT_DATE_FORMATS: [&str; 4] = ["%m/%d/%y", "%m/%d/%Y", "%Y-%m-%d", "%d.%m.%Y"];
+/// Lists which records are not transactions
+const NON_TRANSACTION_TYPES: [&str; 6] =
+    ["account", "cat", "class", "memorized", "prices", "security"];
+const RECORD_END: &str = "^";
+
+/// Parses QIF statements. QIF has no transaction ids, so ids are derived from the fields
+/// of transactions and their order among identical ones
+pub struct QifExpensesParser {
+    date_format: Option<String>,
+    decimal_separator: Option<char>,
+    options: StatementOptions,
+}
+
+/// Fields of a record by their codes, e.g. `D` for the date
+struct QifRecord {
+    row: usize,
+    fields: HashMap<char, String>,
+}
+
+impl QifExpensesParser {
+    pub fn new(
+        date_format: Option<String>,
+        decimal_separator: Option<char>,
+        options: StatementOptions,
+    ) -> Self {
+        Self {
+            date_format,
+            decimal_separator,
+            options,
+        }
+    }
+
+    fn parse_transaction(
+        &self,
+        record: &QifRecord,
+        occurrences: &mut HashMap<String, usize>,
+    ) -> Result<StatementTransaction, String> {
+        let get_field = |code: char| record.fields.get(&code).cloned();
+
+        let date = get_field('D').ok_or("Date (D) is missing")?;
+        let amount = get_field('T')
+            .or_else(|| get_field('U'))
+            .ok_or("Amount (T) is missing")?;
+        let payee = get_field('P');
+        let memo = get_field('M');
+        // Transfers to other accounts are in brackets, they are not categories
+        let category = get_field('L').filter(|category| !category.starts_with('['));
+
+        let fingerprint = format!(
+            "{date}|{amount}|{}|{}",
+            payee.as_deref().unwrap_or_default(),
+            memo.as_deref().unwrap_or_default()
+        );
+        let occurrence = occurrences.entry(fingerprint.clone()).or_default();
+        *occurrence += 1;
+
+        Ok(StatementTransaction {
+            external_id: Some(Self::get_external_id(&fingerprint, *occurrence)),
+            date: self.parse_date(&date)?,
+            amount: self.parse_amount(&amount)?,
+            payee,
+            memo,
+            category,
+        })
+    }
+
+    /// Identical transactions of a statement get different ids by their order
+    fn get_external_id(fingerprint: &str, occurrence: usize) -> String {
+        let hash: String = Sha256::digest(format!("{fingerprint}|{occurrence}"))
+            .iter()
+            .take(16)
+            .map(|byte| format!("{byte:02x}"))
+            .collect();
+
+        format!("qif:{hash}")
+    }
+
+    /// Dates like `1/ 5'24` are normalized to `1/5/24`
+    fn parse_date(&self, value: &str) -> Result<DateTime<FixedOffset>, String> {
+        let normalized_date = value.replace(' ', "").replace('\'', "/");
+
+        let date = match &self.date_format {
+            Some(date_format) => parse_date_with_format(&normalized_date, date_format),
+            None => DEFAULT_DATE_FORMATS
+                .iter()
+                .find_map(|date_format| parse_date_with_format(&normalized_date, date_format)),
+        };
+
+        date.ok_or_else(|| format!("Date '{value}' does not match the date format"))
+    }
+
+    /// Dots, commas, spaces and apostrophes other than the decimal separator are
+    /// thousands separators, e.g. `-1,234.56`, `-1.234,56` or `-1 234,56`
+    fn parse_amount(&self, value: &str) -> Result<f64, String> {
+        let decimal_separator = self
+            .decimal_separator
+            .unwrap_or_else(|| Self::detect_decimal_separator(value));
+
+        let normalized_amount: String = value
+            .trim()
+            .chars()
+            .filter(|char| *char == decimal_separator || !matches!(char, '.' | ',' | ' ' | '\''))
+            .map(|char| if char == decimal_separator { '.' } else { char })
+            .collect();
+
+        normalized_amount
+            .parse()
+            .map_err(|_| format!("Amount '{value}' is not a number"))
+    }
+
+    /// The last dot or comma, unless it is repeated, e.g. `1,234,567`. Amounts with
+    /// thousands only, like `1.234`, are ambiguous, `decimalSeparator` has to be set for them
+    fn detect_decimal_separator(value: &str) -> char {
+        let Some(separator) = value.chars().rev().find(|char| matches!(char, '.' | ',')) else {
+            return '.';
+        };
+
+        match (separator, value.matches(separator).count()) {
+            (separator, 1) => separator,
+            ('.', _) => ',',
+            _ => '.',
+        }
+    }
+
+    fn read_records(content: &str) -> Vec<QifRecord> {
+        let mut records = Vec::new();
+        let mut is_transaction_type = true;
+        let mut record: Option<QifRecord> = None;
+
+        for (index, line) in content.lines().enumerate() {
+            let line = line.trim_start_matches('\u{feff}').trim();
+
+            if let Some(header) = line.strip_prefix('!') {
+                let header = header.to_lowercase();
+
+                if let Some(record_type) = header.strip_prefix("type:") {
+                    is_transaction_type = !NON_TRANSACTION_TYPES.contains(&record_type.trim());
+                } else if header == "account" {
+                    is_transaction_type = false;
+                }
+                continue;
+            }
+
+            if line == RECORD_END {
+                records.extend(record.take().filter(|_| is_transaction_type));
+                continue;
+            }
+
+            let mut chars = line.chars();
+            let Some(code) = chars.next() else {
+                continue;
+            };
+            let record = record.get_or_insert_with(|| QifRecord {
+                row: index + 1,
+                fields: HashMap::new(),
+            });
+
+            // Split lines (S, E, $) repeat codes, the first value is the transaction's one
+            record
+                .fields
+                .entry(code)
+                .or_insert_with(|| chars.as_str().trim().to_string());
+        }
+
+        records.extend(record.filter(|_| is_transaction_type));
+
+        records
+    }
+}
+
+impl ExpensesParser for QifExpensesParser {
+    fn parse(&self, content: &[u8]) -> Result<Vec<ImportedRow>, HttpError> {
+        let content = String::from_utf8_lossy(content);
+        let mut occurrences = HashMap::new();
+
+        let imported_rows = Self::read_records(&content)
+            .iter()
+            .filter_map(|record| {
+                let transaction = self.parse_transaction(record, &mut occurrences);
+
+                self.options.to_imported_row(record.row, transaction)
+            })
+            .collect();
+
+        Ok(imported_rows)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+
+    fn get_parser(query: serde_json::Value) -> QifExpensesParser {
+        let query: crate::api::expenses::dto::import_expenses_query_dto::ImportExpensesQueryDto =
+            serde_json::from_value(query).unwrap();
+
+        QifExpensesParser::new(
+            query.date_format.clone(),
+            query.decimal_separator,
+            StatementOptions::new(&query).unwrap(),
+        )
+    }
+
+    const STATEMENT: &str = "!Account
+NChecking
+^
+!Type:Bank
+D10/19'26
+T-1,234.56
+PGrocery store
+MWeekly
+LGroceries
+^
+D10/19'26
+T-1,234.56
+PGrocery store
+MWeekly
+^
+D10/20/2026
+T-20.00
+PSavings
+L[Savings]
+^
+D10/21/2026
+T500.00
+PSalary
+^
+!Type:Cat
+NFood
+^
+";
+
+    #[test]
+    fn parse() {
+        let imported_rows = get_parser(json!({ "categoryMapping": r#"{"Groceries":"FOOD"}"# }))
+            .parse(STATEMENT.as_bytes())
+            .unwrap();
+
+        assert_eq!(imported_rows.len(), 3);
+        assert_eq!(
+            imported_rows.iter().map(|row| row.row).collect::<Vec<_>>(),
+            vec![5, 11, 16]
+        );
+
+        let expense = imported_rows[0].expense.as_ref().unwrap();
+        assert_eq!(expense.amount, 1234.56);
+        assert_eq!(expense.date.to_rfc3339(), "2026-10-19T00:00:00+00:00");
+        assert_eq!(expense.category.as_deref(), Some("FOOD"));
+        assert_eq!(expense.note.as_deref(), Some("Grocery store - Weekly"));
+
+        // Identical transactions get different ids
+        let duplicate = imported_rows[1].expense.as_ref().unwrap();
+        assert!(duplicate.external_id.as_ref().unwrap().starts_with("qif:"));
+        assert_ne!(duplicate.external_id, expense.external_id);
+
+        // Transfers are not categories
+        assert_eq!(imported_rows[2].expense.as_ref().unwrap().category, None);
+    }
+
+    #[test]
+    fn parse_is_stable() {
+        let parser = get_parser(json!({}));
+        let get_external_ids = |imported_rows: Vec<ImportedRow>| -> Vec<Option<String>> {
+            imported_rows
+                .into_iter()
+                .map(|row| row.expense.unwrap().external_id)
+                .collect()
+        };
+
+        assert_eq!(
+            get_external_ids(parser.parse(STATEMENT.as_bytes()).unwrap()),
+            get_external_ids(parser.parse(STATEMENT.as_bytes()).unwrap())
+        );
+    }
+
+    #[test]
+    fn parse_with_date_format() {
+        let imported_rows = get_parser(json!({ "dateFormat": "%d/%m/%Y" }))
+            .parse(b"!Type:Bank\nD19/10/2026\nT-5\n^\nD10/19/2026\nT-5\n^\n")
+            .unwrap();
+
+        let expense = imported_rows[0].expense.as_ref().unwrap();
+        assert_eq!(expense.date.to_rfc3339(), "2026-10-19T00:00:00+00:00");
+        assert!(
+            imported_rows[1]
+                .expense
+                .as_ref()
+                .unwrap_err()
+                .contains("Date")
+        );
+    }
+
+    #[test]
+    fn parse_amount() {
+        let cases = [
+            (None, "-12.50", Some(-12.5)),
+            (None, "-12,50", Some(-12.5)),
+            (None, "-1,234.56", Some(-1234.56)),
+            (None, "-1.234,56", Some(-1234.56)),
+            (None, "-1 234,56", Some(-1234.56)),
+            (None, "1,234,567", Some(1234567.0)),
+            (None, "1.234.567,8", Some(1234567.8)),
+            (None, "100", Some(100.0)),
+            (Some(','), "-1.234", Some(-1234.0)),
+            (Some('.'), "1,234", Some(1234.0)),
+            (None, "abc", None),
+            (None, "", None),
+        ];
+
+        for (decimal_separator, value, expected) in cases {
+            let parser = get_parser(json!({ "decimalSeparator": decimal_separator }));
+
+            assert_eq!(parser.parse_amount(value).ok(), expected, "{value}");
+        }
+    }
+}
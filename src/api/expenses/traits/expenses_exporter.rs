@@ -0,0 +1,18 @@
+use std::fs::File;
+
+use crate::api::expenses::types::exported_expense::ExportedExpense;
+use crate::shared::errors::http_error::HttpError;
+
+/// Writer of an export file. Written bytes are appended to the buffer, so that they can be
+/// sent to the client before the whole file is written
+pub trait ExpensesExporter: Send {
+    fn write_header(&mut self, buffer: &mut Vec<u8>) -> Result<(), HttpError>;
+    fn write_row(
+        &mut self,
+        expense: &ExportedExpense,
+        buffer: &mut Vec<u8>,
+    ) -> Result<(), HttpError>;
+    /// File with the rest of the export after the last row, for formats which can't be
+    /// written row by row
+    fn finish(self: Box<Self>) -> Result<Option<File>, HttpError>;
+}
@@ -0,0 +1,8 @@
+use crate::api::expenses::types::imported_row::ImportedRow;
+use crate::shared::errors::http_error::HttpError;
+
+/// Parses imported files into expenses. Errors are returned for files which can't be
+/// parsed at all, invalid rows are returned as failed ones
+pub trait ExpensesParser: Send {
+    fn parse(&self, content: &[u8]) -> Result<Vec<ImportedRow>, HttpError>;
+}
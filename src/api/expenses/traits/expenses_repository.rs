@@ -1,26 +1,72 @@
 use async_trait::async_trait;
+use futures_util::stream::BoxStream;
+use sea_orm::prelude::DateTimeWithTimeZone;
+use std::sync::Arc;
 
 use crate::api::expenses::dto::create_expense_db_dto::CreateExpenseDbDto;
 use crate::api::expenses::dto::find_expenses_dto::FindExpensesDto;
 use crate::api::expenses::dto::update_expense_db_dto::UpdateExpenseDbDto;
 use crate::api::expenses::types::expense_from_db::ExpenseFromDb;
 use crate::shared::errors::http_error::HttpError;
+use crate::shared::modules::db::db_connection::DbConnection;
 
 #[async_trait]
 pub trait ExpensesRepositoryTrait {
+    /// Repository which queries run on the connection, e.g. a unit of work transaction
+    fn with_connection(
+        &self,
+        connection: DbConnection,
+    ) -> Arc<dyn ExpensesRepositoryTrait + Send + Sync>;
     async fn find_one(&self, id: &str) -> Result<ExpenseFromDb, HttpError>;
     async fn find_many(
         &self,
         filter: Option<FindExpensesDto>,
     ) -> Result<Vec<ExpenseFromDb>, HttpError>;
+    /// Expenses matching the filter ordered by date, rows are fetched as the stream is polled
+    async fn stream_many(
+        &self,
+        filter: FindExpensesDto,
+    ) -> Result<BoxStream<'_, Result<ExpenseFromDb, HttpError>>, HttpError>;
+    /// Expenses of the customer with the external ids, including the ones in the trash
+    async fn find_by_external_ids(
+        &self,
+        customer_id: &str,
+        external_ids: Vec<String>,
+    ) -> Result<Vec<ExpenseFromDb>, HttpError>;
     async fn create_many(
         &self,
         create_dto: Vec<CreateExpenseDbDto>,
     ) -> Result<Vec<ExpenseFromDb>, HttpError>;
+    /// Runs in its own savepoint inside a transaction, so a failure does not abort the transaction
+    async fn create_one(&self, create_dto: CreateExpenseDbDto) -> Result<ExpenseFromDb, HttpError>;
+    /// Writes check that the expense belongs to `customer_id` (if given) in the same transaction
     async fn update_one(
         &self,
         id: &str,
         update_dto: UpdateExpenseDbDto,
+        customer_id: Option<&str>,
+        expected_versions: Option<Vec<i64>>,
+    ) -> Result<ExpenseFromDb, HttpError>;
+    /// Moves the expense to the trash
+    async fn delete_one(
+        &self,
+        id: &str,
+        customer_id: Option<&str>,
+        expected_versions: Option<Vec<i64>>,
+    ) -> Result<ExpenseFromDb, HttpError>;
+    /// Updates all the expenses matching the filter in one statement, returns the updated ones
+    async fn update_many(
+        &self,
+        filter: FindExpensesDto,
+        update_dto: UpdateExpenseDbDto,
+    ) -> Result<Vec<ExpenseFromDb>, HttpError>;
+    async fn delete_many(&self, filter: FindExpensesDto) -> Result<Vec<ExpenseFromDb>, HttpError>;
+    /// Moves the expense out of the trash
+    async fn restore_one(
+        &self,
+        id: &str,
+        customer_id: Option<&str>,
     ) -> Result<ExpenseFromDb, HttpError>;
-    async fn delete_one(&self, id: &str) -> Result<ExpenseFromDb, HttpError>;
+    /// Hard-deletes expenses moved to the trash before the date, returns their amount
+    async fn purge_deleted(&self, deleted_before: DateTimeWithTimeZone) -> Result<u64, HttpError>;
 }
@@ -1 +1,3 @@
+pub mod expenses_exporter;
+pub mod expenses_parser;
 pub mod expenses_repository;
@@ -0,0 +1,89 @@
+use std::collections::HashMap;
+
+use crate::api::expenses::types::expense_category::ExpenseCategory;
+use crate::shared::errors::http_error::HttpError;
+
+/// Maps category names of imported files onto category ids or codes, they are resolved
+/// when the expenses are created
+pub struct CategoryMapper {
+    /// Keys are lowercase
+    category_mapping: HashMap<String, String>,
+    default_category: Option<String>,
+}
+
+impl CategoryMapper {
+    /// `category_mapping` is a JSON object of names to categories
+    pub fn new(
+        category_mapping: Option<&str>,
+        default_category: Option<String>,
+    ) -> Result<Self, HttpError> {
+        let category_mapping: HashMap<String, String> = category_mapping
+            .map(serde_json::from_str)
+            .transpose()
+            .map_err(|err| {
+                HttpError::BadRequest(format!(
+                    "categoryMapping should be a JSON object of names to categories: {err}"
+                ))
+            })?
+            .unwrap_or_default();
+
+        Ok(Self {
+            category_mapping: category_mapping
+                .into_iter()
+                .map(|(name, category)| (name.trim().to_lowercase(), category))
+                .collect(),
+            default_category,
+        })
+    }
+
+    /// Mapped category of the name, the system category it names, or the default one
+    pub fn map(&self, name: &str) -> Option<String> {
+        self.category_mapping
+            .get(&name.trim().to_lowercase())
+            .cloned()
+            .or_else(|| ExpenseCategory::from_name(name).map(|category| category.get_code().into()))
+            .or_else(|| self.default_category.clone())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn map() {
+        let category_mapper = CategoryMapper::new(
+            Some(r#"{" Groceries ": "FOOD", "Cafe": "custom-category-id"}"#),
+            None,
+        )
+        .unwrap();
+        let cases = [
+            ("groceries", Some("FOOD")),
+            ("  CAFE ", Some("custom-category-id")),
+            ("Places to eat", Some("PLACES_TO_EAT")),
+            ("beauty & care", Some("BEAUTY_AND_CARE")),
+            ("public_transport", Some("PUBLIC_TRANSPORT")),
+            ("Unknown", None),
+            ("", None),
+        ];
+
+        for (name, expected) in cases {
+            assert_eq!(category_mapper.map(name).as_deref(), expected, "{name}");
+        }
+    }
+
+    #[test]
+    fn map_to_default_category() {
+        let category_mapper = CategoryMapper::new(None, Some("OTHER".into())).unwrap();
+
+        assert_eq!(category_mapper.map("Food").as_deref(), Some("FOOD"));
+        assert_eq!(category_mapper.map("Unknown").as_deref(), Some("OTHER"));
+        assert_eq!(category_mapper.map("").as_deref(), Some("OTHER"));
+    }
+
+    #[test]
+    fn new_fails_on_invalid_mapping() {
+        assert!(CategoryMapper::new(Some("[]"), None).is_err());
+        assert!(CategoryMapper::new(Some(r#"{"Food": 1}"#), None).is_err());
+    }
+}
@@ -0,0 +1,82 @@
+use chrono::{DateTime, FixedOffset};
+
+use crate::shared::errors::http_error::HttpError;
+
+/// Date formats of locales, a `language-region` locale falls back to its language
+const LOCALE_DATE_FORMATS: [(&str, &str); 24] = [
+    ("en", "%m/%d/%Y %H:%M"),
+    ("en-au", "%d/%m/%Y %H:%M"),
+    ("en-gb", "%d/%m/%Y %H:%M"),
+    ("en-ie", "%d/%m/%Y %H:%M"),
+    ("en-in", "%d/%m/%Y %H:%M"),
+    ("en-nz", "%d/%m/%Y %H:%M"),
+    ("en-ca", "%Y-%m-%d %H:%M"),
+    ("de", "%d.%m.%Y %H:%M"),
+    ("uk", "%d.%m.%Y %H:%M"),
+    ("ru", "%d.%m.%Y %H:%M"),
+    ("pl", "%d.%m.%Y %H:%M"),
+    ("cs", "%d.%m.%Y %H:%M"),
+    ("fi", "%d.%m.%Y %H:%M"),
+    ("nb", "%d.%m.%Y %H:%M"),
+    ("tr", "%d.%m.%Y %H:%M"),
+    ("fr", "%d/%m/%Y %H:%M"),
+    ("es", "%d/%m/%Y %H:%M"),
+    ("it", "%d/%m/%Y %H:%M"),
+    ("pt", "%d/%m/%Y %H:%M"),
+    ("nl", "%d-%m-%Y %H:%M"),
+    ("sv", "%Y-%m-%d %H:%M"),
+    ("ja", "%Y/%m/%d %H:%M"),
+    ("zh", "%Y/%m/%d %H:%M"),
+    ("ko", "%Y. %m. %d. %H:%M"),
+];
+
+/// ISO 8601 dates are exported if the locale is not set
+const DEFAULT_DATE_FORMAT: &str = "%Y-%m-%d %H:%M";
+
+/// Formats dates of exported expenses in the time zone offset and the format of a locale
+#[derive(Debug, Clone)]
+pub struct DateLocalizer {
+    format: &'static str,
+    offset: FixedOffset,
+}
+
+impl DateLocalizer {
+    /// `locale` is a BCP 47 tag, e.g. `en-GB`, `utc_offset` is e.g. `+02:00`
+    pub fn new(locale: Option<&str>, utc_offset: Option<&str>) -> Result<Self, HttpError> {
+        let format = match locale {
+            Some(locale) => Self::get_locale_date_format(locale).ok_or_else(|| {
+                HttpError::BadRequest(format!("Locale {locale} is not supported"))
+            })?,
+            None => DEFAULT_DATE_FORMAT,
+        };
+
+        let offset = match utc_offset {
+            Some(utc_offset) => utc_offset.parse::<FixedOffset>().map_err(|_| {
+                HttpError::BadRequest(format!(
+                    "UTC offset {utc_offset} is invalid, expected e.g. +02:00"
+                ))
+            })?,
+            None => FixedOffset::east_opt(0).expect("UTC offset is valid"),
+        };
+
+        Ok(Self { format, offset })
+    }
+
+    pub fn format(&self, date: &DateTime<FixedOffset>) -> String {
+        date.with_timezone(&self.offset)
+            .format(self.format)
+            .to_string()
+    }
+
+    fn get_locale_date_format(locale: &str) -> Option<&'static str> {
+        let locale = locale.replace('_', "-").to_lowercase();
+        let language = locale.split('-').next().unwrap_or_default();
+
+        [locale.as_str(), language].into_iter().find_map(|tag| {
+            LOCALE_DATE_FORMATS
+                .iter()
+                .find(|(locale, _)| *locale == tag)
+                .map(|(_, format)| *format)
+        })
+    }
+}
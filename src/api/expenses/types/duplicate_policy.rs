@@ -0,0 +1,15 @@
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+/// What happens to created expenses which look like already existing ones
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, Default, PartialEq, Eq, JsonSchema)]
+#[serde(rename_all = "lowercase")]
+pub enum DuplicatePolicy {
+    /// Nothing is created, the request fails with 409
+    Reject,
+    /// Duplicates are created and listed in the response
+    #[default]
+    Warn,
+    /// Duplicates are not created, the response points to the expenses they match
+    Merge,
+}
@@ -3,7 +3,7 @@ use schemars::JsonSchema;
 use sea_orm::{ActiveValue, IntoActiveValue};
 use serde::{Deserialize, Serialize};
 
-#[derive(Serialize, Deserialize, Debug, JsonSchema)]
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq, JsonSchema)]
 pub enum ExpenseCategory {
     #[serde(rename = "FOOD")]
     Food,
@@ -106,3 +106,44 @@ impl IntoActiveValue<sea_orm_active_enums::ExpenseCategory> for ExpenseCategory
         ActiveValue::Set(self.into())
     }
 }
+
+impl ExpenseCategory {
+    /// Category by its code or a human-readable form of it, e.g. `Places to eat` or `beauty & care`
+    pub fn from_name(name: &str) -> Option<Self> {
+        let code = name
+            .replace('&', " and ")
+            .split(|char: char| !char.is_alphanumeric())
+            .filter(|word| !word.is_empty())
+            .map(str::to_uppercase)
+            .collect::<Vec<_>>()
+            .join("_");
+
+        serde_json::from_value(serde_json::Value::String(code)).ok()
+    }
+
+    /// Code of the system category, e.g. `PLACES_TO_EAT`
+    pub fn get_code(&self) -> &'static str {
+        match self {
+            Self::Food => "FOOD",
+            Self::Clothes => "CLOTHES",
+            Self::Subscriptions => "SUBSCRIPTIONS",
+            Self::Other => "OTHER",
+            Self::UtilityPayments => "UTILITY_PAYMENTS",
+            Self::Animals => "ANIMALS",
+            Self::PlacesToEat => "PLACES_TO_EAT",
+            Self::Education => "EDUCATION",
+            Self::Books => "BOOKS",
+            Self::Taxi => "TAXI",
+            Self::Gifts => "GIFTS",
+            Self::Donations => "DONATIONS",
+            Self::MobileServices => "MOBILE_SERVICES",
+            Self::Sports => "SPORTS",
+            Self::Entertainment => "ENTERTAINMENT",
+            Self::BeautyAndCare => "BEAUTY_AND_CARE",
+            Self::Household => "HOUSEHOLD",
+            Self::PublicTransport => "PUBLIC_TRANSPORT",
+            Self::Travel => "TRAVEL",
+            Self::Medicine => "MEDICINE",
+        }
+    }
+}
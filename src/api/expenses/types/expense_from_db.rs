@@ -1,29 +1,39 @@
 use serde::{Deserialize, Serialize};
 
-use crate::api::expenses::types::expense_category::ExpenseCategory;
-use crate::shared::modules::db::entities::expense;
+use crate::shared::modules::db::entities::{category, expense};
 
-#[derive(Serialize, Deserialize, Debug)]
+#[derive(Serialize, Deserialize, Debug, Clone)]
 pub struct ExpenseFromDb {
     pub id: String,
     pub customer_id: String,
     pub amount: f64,
     pub date: chrono::DateTime<chrono::FixedOffset>,
-    pub category: ExpenseCategory,
+    pub category_id: String,
+    pub note: Option<String>,
     pub created_at: chrono::DateTime<chrono::FixedOffset>,
     pub updated_at: chrono::DateTime<chrono::FixedOffset>,
+    pub version: i64,
+    pub deleted_at: Option<chrono::DateTime<chrono::FixedOffset>>,
+    pub external_id: Option<String>,
+    /// Code of the category if it is a system one
+    pub category_code: Option<String>,
 }
 
-impl From<expense::Model> for ExpenseFromDb {
-    fn from(value: expense::Model) -> Self {
+impl From<(expense::Model, Option<category::Model>)> for ExpenseFromDb {
+    fn from((value, category): (expense::Model, Option<category::Model>)) -> Self {
         Self {
             id: value.id,
             customer_id: value.customer_id,
             amount: value.amount.try_into().unwrap_or_default(),
             date: value.date,
-            category: value.category.into(),
+            category_id: value.category_id,
+            note: value.note,
             created_at: value.created_at,
             updated_at: value.updated_at,
+            version: value.version,
+            deleted_at: value.deleted_at,
+            external_id: value.external_id,
+            category_code: category.and_then(|category| category.code),
         }
     }
 }
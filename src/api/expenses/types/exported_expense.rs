@@ -0,0 +1,57 @@
+use serde::Serialize;
+
+use crate::api::categories::types::category_resolver::CategoryResolver;
+use crate::api::expenses::types::date_localizer::DateLocalizer;
+use crate::api::expenses::types::expense_from_db::ExpenseFromDb;
+
+/// Columns of exported files in the order of `ExportedExpense` fields
+pub const EXPORTED_COLUMNS: [&str; 9] = [
+    "id",
+    "customerId",
+    "date",
+    "amount",
+    "categoryId",
+    "category",
+    "categoryName",
+    "note",
+    "externalId",
+];
+
+/// Row of an exported file
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ExportedExpense {
+    pub id: String,
+    pub customer_id: String,
+    /// Date in the format and the time zone offset of the export
+    pub date: String,
+    pub amount: f64,
+    pub category_id: String,
+    /// Code of a system category
+    pub category: Option<String>,
+    pub category_name: Option<String>,
+    pub note: Option<String>,
+    pub external_id: Option<String>,
+}
+
+impl ExportedExpense {
+    pub fn new(
+        expense: ExpenseFromDb,
+        date_localizer: &DateLocalizer,
+        category_resolver: &CategoryResolver,
+    ) -> Self {
+        let category = category_resolver.get(&expense.category_id);
+
+        Self {
+            date: date_localizer.format(&expense.date),
+            category: category.and_then(|category| category.code.clone()),
+            category_name: category.map(|category| category.name.clone()),
+            id: expense.id,
+            customer_id: expense.customer_id,
+            amount: expense.amount,
+            category_id: expense.category_id,
+            note: expense.note,
+            external_id: expense.external_id,
+        }
+    }
+}
@@ -0,0 +1,35 @@
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+/// Bytes of the file start checked to detect its format
+const DETECTED_HEAD_LENGTH: usize = 1024;
+
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq, JsonSchema)]
+#[serde(rename_all = "lowercase")]
+pub enum ImportFormat {
+    Csv,
+    /// OFX 1.x (SGML) and 2.x (XML), QFX files are OFX ones
+    #[serde(alias = "qfx")]
+    Ofx,
+    Qif,
+    /// ISO 20022 bank to customer statement
+    Camt053,
+}
+
+impl ImportFormat {
+    /// Format of the file by its content, CSV if it is not a known statement format
+    pub fn detect(content: &[u8]) -> Self {
+        let head = String::from_utf8_lossy(&content[..content.len().min(DETECTED_HEAD_LENGTH)]);
+        let head = head.trim_start_matches('\u{feff}').trim_start();
+
+        if head.starts_with("OFXHEADER") || head.contains("<OFX>") {
+            Self::Ofx
+        } else if head.starts_with("!Type") || head.starts_with("!Account") {
+            Self::Qif
+        } else if head.contains("camt.053") || head.contains("<BkToCstmrStmt>") {
+            Self::Camt053
+        } else {
+            Self::Csv
+        }
+    }
+}
@@ -0,0 +1,10 @@
+use crate::api::expenses::dto::create_expense_dto::CreateExpenseDto;
+
+/// Expense parsed from a row of an imported file
+#[derive(Debug)]
+pub struct ImportedRow {
+    /// Line of the row in the file, starting from 1
+    pub row: usize,
+    /// Reason the row can't be imported otherwise
+    pub expense: Result<CreateExpenseDto, String>,
+}
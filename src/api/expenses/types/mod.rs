@@ -1,3 +1,12 @@
 pub mod api_state;
+pub mod category_mapper;
+pub mod date_localizer;
+pub mod duplicate_policy;
 pub mod expense_category;
 pub mod expense_from_db;
+pub mod export_format;
+pub mod exported_expense;
+pub mod import_format;
+pub mod imported_row;
+pub mod statement_options;
+pub mod statement_transaction;
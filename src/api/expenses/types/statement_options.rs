@@ -0,0 +1,101 @@
+use crate::api::expenses::dto::create_expense_dto::CreateExpenseDto;
+use crate::api::expenses::dto::import_expenses_query_dto::ImportExpensesQueryDto;
+use crate::api::expenses::types::category_mapper::CategoryMapper;
+use crate::api::expenses::types::imported_row::ImportedRow;
+use crate::api::expenses::types::statement_transaction::StatementTransaction;
+use crate::api::expenses::utils::join_note;
+use crate::shared::errors::http_error::HttpError;
+
+/// Turns transactions of bank statements into expenses, the same way for all the formats
+pub struct StatementOptions {
+    category_mapper: CategoryMapper,
+}
+
+impl StatementOptions {
+    pub fn new(query: &ImportExpensesQueryDto) -> Result<Self, HttpError> {
+        Ok(Self {
+            category_mapper: CategoryMapper::new(
+                query.category_mapping.as_deref(),
+                query.default_category.clone(),
+            )?,
+        })
+    }
+
+    /// Row of the transaction, `None` unless it's a debit: credits (income, refunds) and zero
+    /// amounts are not expenses. Statements rarely have categories, transactions without
+    /// a known one are left to the categorization rules
+    pub fn to_imported_row(
+        &self,
+        row: usize,
+        transaction: Result<StatementTransaction, String>,
+    ) -> Option<ImportedRow> {
+        let transaction = match transaction {
+            Ok(transaction) => transaction,
+            Err(message) => {
+                return Some(ImportedRow {
+                    row,
+                    expense: Err(message),
+                });
+            }
+        };
+
+        if transaction.amount >= 0.0 {
+            return None;
+        }
+
+        let category = self
+            .category_mapper
+            .map(transaction.category.as_deref().unwrap_or_default());
+
+        Some(ImportedRow {
+            row,
+            expense: Ok(CreateExpenseDto {
+                amount: transaction.amount.abs(),
+                date: transaction.date,
+                category,
+                note: join_note([transaction.payee.as_deref(), transaction.memo.as_deref()]),
+                external_id: transaction.external_id,
+            }),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::DateTime;
+    use serde_json::json;
+
+    fn get_transaction(amount: f64) -> StatementTransaction {
+        StatementTransaction {
+            external_id: None,
+            date: DateTime::parse_from_rfc3339("2026-10-19T12:00:00+00:00").unwrap(),
+            amount,
+            payee: Some("Shop".into()),
+            memo: None,
+            category: None,
+        }
+    }
+
+    #[test]
+    fn import_debits_only() {
+        let query = serde_json::from_value(json!({})).unwrap();
+        let statement_options = StatementOptions::new(&query).unwrap();
+        let cases = [
+            ("debit", -12.5, Some(12.5)),
+            ("credit", 100.0, None),
+            ("zero", 0.0, None),
+            ("negative zero", -0.0, None),
+        ];
+
+        for (name, amount, expected_amount) in cases {
+            let imported_row = statement_options.to_imported_row(1, Ok(get_transaction(amount)));
+
+            assert_eq!(
+                imported_row.map(|row| row.expense.unwrap().amount),
+                expected_amount,
+                "{name}"
+            );
+        }
+    }
+}
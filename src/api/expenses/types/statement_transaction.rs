@@ -0,0 +1,15 @@
+use chrono::{DateTime, FixedOffset};
+
+/// Transaction of a bank statement
+#[derive(Debug)]
+pub struct StatementTransaction {
+    /// Id of the transaction in the statement, e.g. OFX FITID
+    pub external_id: Option<String>,
+    pub date: DateTime<FixedOffset>,
+    /// Negative for debits
+    pub amount: f64,
+    pub payee: Option<String>,
+    pub memo: Option<String>,
+    /// Category name set in the statement, only some formats have it
+    pub category: Option<String>,
+}
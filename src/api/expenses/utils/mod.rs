@@ -0,0 +1,33 @@
+use chrono::{DateTime, FixedOffset, NaiveDate, NaiveDateTime};
+
+/// Same as the length limit of notes of created expenses
+const MAX_NOTE_LENGTH: usize = 500;
+
+/// Parses dates with or without time and offset, dates without them are midnight UTC
+pub fn parse_date_with_format(value: &str, date_format: &str) -> Option<DateTime<FixedOffset>> {
+    DateTime::parse_from_str(value, date_format)
+        .ok()
+        .or_else(|| {
+            NaiveDateTime::parse_from_str(value, date_format)
+                .ok()
+                .map(|date_time| date_time.and_utc().fixed_offset())
+        })
+        .or_else(|| {
+            NaiveDate::parse_from_str(value, date_format)
+                .ok()
+                .map(|date| date.and_time(Default::default()).and_utc().fixed_offset())
+        })
+}
+
+/// Note of the non-empty parts, cut to the length limit of notes
+pub fn join_note<'a>(parts: impl IntoIterator<Item = Option<&'a str>>) -> Option<String> {
+    let note = parts
+        .into_iter()
+        .flatten()
+        .map(str::trim)
+        .filter(|part| !part.is_empty())
+        .collect::<Vec<_>>()
+        .join(" - ");
+
+    (!note.is_empty()).then(|| note.chars().take(MAX_NOTE_LENGTH).collect())
+}
@@ -1,32 +1,75 @@
 use crate::shared::errors::http_error::HttpError;
 use crate::shared::modules::auth::services::auth0::Auth0Service;
-use crate::shared::modules::redis::redis_service::RedisService;
+use crate::shared::modules::auth::traits::token_revocation_list::DynamicTokenRevocationList;
+use crate::shared::modules::cache::{AppCacheService, AppL2CacheService};
+use crate::shared::modules::idempotency::middlewares::idempotency::IdempotencyLayer;
 use aide::axum::routing::get;
 use aide::axum::{ApiRouter, IntoApiResponse};
 use axum::response::IntoResponse;
 use sea_orm::DatabaseConnection;
 use std::{env, sync::Arc};
 
+mod audit_logs;
+mod categories;
+mod category_rules;
+mod customer_data;
 mod customers;
 mod expenses;
+mod regular_payments;
+mod sessions;
+pub mod trash_purge_job;
 
 pub async fn get_router(
     sea_orm_client: Arc<DatabaseConnection>,
-    redis_service: Arc<RedisService>,
+    cache_service: Arc<AppCacheService>,
+    idempotency_layer: IdempotencyLayer<AppL2CacheService>,
     auth_service: Arc<Auth0Service>,
+    token_revocation_list: Arc<DynamicTokenRevocationList>,
 ) -> ApiRouter {
     let api_v1_router = ApiRouter::new().nest(
         "/v1",
         ApiRouter::new()
+            .merge(audit_logs::get_router(
+                sea_orm_client.clone(),
+                auth_service.clone(),
+            ))
+            .merge(categories::get_router(
+                sea_orm_client.clone(),
+                idempotency_layer.clone(),
+                auth_service.clone(),
+            ))
+            .merge(category_rules::get_router(
+                sea_orm_client.clone(),
+                idempotency_layer.clone(),
+                auth_service.clone(),
+            ))
+            .merge(customer_data::get_router(
+                sea_orm_client.clone(),
+                cache_service.clone(),
+                auth_service.clone(),
+            ))
             .merge(customers::get_router(
                 sea_orm_client.clone(),
-                redis_service.clone(),
+                cache_service.clone(),
+                idempotency_layer.clone(),
                 auth_service.clone(),
             ))
             .merge(expenses::get_router(
+                sea_orm_client.clone(),
+                cache_service.clone(),
+                idempotency_layer.clone(),
+                auth_service.clone(),
+            ))
+            .merge(regular_payments::get_router(
+                sea_orm_client.clone(),
+                cache_service,
+                idempotency_layer,
+                auth_service.clone(),
+            ))
+            .merge(sessions::get_router(
                 sea_orm_client,
-                redis_service,
                 auth_service,
+                token_revocation_list,
             )),
     );
 
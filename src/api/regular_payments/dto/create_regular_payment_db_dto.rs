@@ -0,0 +1,15 @@
+use sea_orm::DeriveIntoActiveModel;
+use sea_orm::prelude::Decimal;
+use serde::{Deserialize, Serialize};
+
+use crate::api::expenses::types::expense_category::ExpenseCategory;
+use crate::shared::modules::db::entities::regular_payment::ActiveModel;
+
+#[derive(Serialize, Deserialize, Debug, Clone, DeriveIntoActiveModel)]
+pub struct CreateRegularPaymentDbDto {
+    pub id: String,
+    pub customer_id: String,
+    pub amount: Decimal,
+    pub category: ExpenseCategory,
+    pub date_of_charge: chrono::DateTime<chrono::FixedOffset>,
+}
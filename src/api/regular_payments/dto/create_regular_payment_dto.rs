@@ -0,0 +1,16 @@
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use validator::Validate;
+
+use crate::api::expenses::types::expense_category::ExpenseCategory;
+
+#[derive(Debug, Clone, Deserialize, Serialize, Validate, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct CreateRegularPaymentDto {
+    #[validate(range(min = 0.0, message = "Should be more than 0"))]
+    pub amount: f64,
+
+    pub category: ExpenseCategory,
+
+    pub date_of_charge: chrono::DateTime<chrono::FixedOffset>,
+}
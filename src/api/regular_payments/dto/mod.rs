@@ -0,0 +1,5 @@
+pub mod create_regular_payment_db_dto;
+pub mod update_regular_payment_db_dto;
+
+pub mod create_regular_payment_dto;
+pub mod update_regular_payment_dto;
@@ -0,0 +1,30 @@
+use sea_orm::ActiveValue;
+use sea_orm::prelude::Decimal;
+use serde::{Deserialize, Serialize};
+
+use crate::shared::modules::db::entities::regular_payment::ActiveModel as RegularPaymentActiveModel;
+use crate::shared::modules::db::entities::sea_orm_active_enums::ExpenseCategory;
+use crate::shared::modules::db::utils::optional_to_active_value;
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct UpdateRegularPaymentDbDto {
+    pub amount: Option<Decimal>,
+    pub category: Option<ExpenseCategory>,
+    pub date_of_charge: Option<chrono::DateTime<chrono::FixedOffset>>,
+}
+
+impl From<UpdateRegularPaymentDbDto> for RegularPaymentActiveModel {
+    fn from(value: UpdateRegularPaymentDbDto) -> Self {
+        Self {
+            id: ActiveValue::NotSet,
+            customer_id: ActiveValue::NotSet,
+            amount: optional_to_active_value(value.amount),
+            created_at: ActiveValue::NotSet,
+            updated_at: ActiveValue::NotSet,
+            version: ActiveValue::NotSet,
+            category: optional_to_active_value(value.category),
+            date_of_charge: optional_to_active_value(value.date_of_charge),
+            deleted_at: ActiveValue::NotSet,
+        }
+    }
+}
@@ -0,0 +1,16 @@
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use validator::Validate;
+
+use crate::api::expenses::types::expense_category::ExpenseCategory;
+
+#[derive(Debug, Clone, Deserialize, Serialize, Validate, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct UpdateRegularPaymentDto {
+    #[validate(range(min = 0.0, message = "Should be more than 0"))]
+    pub amount: Option<f64>,
+
+    pub category: Option<ExpenseCategory>,
+
+    pub date_of_charge: Option<chrono::DateTime<chrono::FixedOffset>>,
+}
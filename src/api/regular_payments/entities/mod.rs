@@ -0,0 +1 @@
+pub mod regular_payment_entity;
@@ -0,0 +1,60 @@
+use aide::OperationIo;
+use chrono::{DateTime, FixedOffset};
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+use crate::api::audit_logs::traits::audited::Audited;
+use crate::api::audit_logs::types::audit_entity_type::AuditEntityType;
+use crate::api::expenses::types::expense_category::ExpenseCategory;
+use crate::api::regular_payments::types::regular_payment_from_db::RegularPaymentFromDb;
+use crate::shared::modules::versioning::traits::versioned::Versioned;
+
+#[derive(Serialize, Deserialize, Debug, Clone, JsonSchema, OperationIo)]
+#[serde(rename_all = "camelCase")]
+pub struct RegularPaymentEntity {
+    pub id: String,
+    pub customer_id: String,
+    pub amount: f64,
+    pub category: ExpenseCategory,
+    pub date_of_charge: DateTime<FixedOffset>,
+    pub created_at: DateTime<FixedOffset>,
+    pub updated_at: DateTime<FixedOffset>,
+    /// Bumped on every update, pass it in `If-Match` to update only this version
+    pub version: i64,
+    /// Set while the payment is in the trash
+    pub deleted_at: Option<DateTime<FixedOffset>>,
+}
+
+impl From<RegularPaymentFromDb> for RegularPaymentEntity {
+    fn from(value: RegularPaymentFromDb) -> Self {
+        Self {
+            id: value.id,
+            customer_id: value.customer_id,
+            amount: value.amount,
+            category: value.category,
+            date_of_charge: value.date_of_charge,
+            created_at: value.created_at,
+            updated_at: value.updated_at,
+            version: value.version,
+            deleted_at: value.deleted_at,
+        }
+    }
+}
+
+impl Versioned for RegularPaymentEntity {
+    fn get_version(&self) -> i64 {
+        self.version
+    }
+}
+
+impl Audited for RegularPaymentEntity {
+    const AUDIT_ENTITY_TYPE: AuditEntityType = AuditEntityType::RegularPayment;
+
+    fn get_audit_id(&self) -> &str {
+        &self.id
+    }
+
+    fn get_audit_owner_id(&self) -> &str {
+        &self.customer_id
+    }
+}
@@ -0,0 +1,113 @@
+use aide::axum::ApiRouter;
+use aide::axum::routing::{delete, get, patch, post};
+use sea_orm::DatabaseConnection;
+use std::sync::Arc;
+
+use crate::api::audit_logs::audit_logs_repository::AuditLogsRepository;
+use crate::api::audit_logs::audit_logs_service::AuditLogsService;
+use crate::api::customers::customers_repository::CustomerRepository;
+use crate::api::customers::customers_service::CustomersService;
+use crate::api::regular_payments::regular_payments_repository::RegularPaymentsRepository;
+use crate::api::regular_payments::regular_payments_service::RegularPaymentsService;
+use crate::api::regular_payments::types::api_state::RegularPaymentsApiState;
+use crate::shared::modules::auth::enums::roles::Roles;
+use crate::shared::modules::auth::middlewares::role_based_bearer_auth::AuthLayer;
+use crate::shared::modules::auth::services::auth0::Auth0Service;
+use crate::shared::modules::cache::middlewares::json_cache::JsonCacheLayer;
+use crate::shared::modules::cache::middlewares::json_cache_invalidation::JsonCacheInvalidationLayer;
+use crate::shared::modules::cache::{AppCacheService, AppL2CacheService};
+use crate::shared::modules::db::unit_of_work::UnitOfWork;
+use crate::shared::modules::idempotency::middlewares::idempotency::IdempotencyLayer;
+
+pub mod dto;
+pub mod entities;
+pub mod traits;
+pub mod types;
+
+mod regular_payments_handlers;
+pub mod regular_payments_repository;
+pub mod regular_payments_service;
+
+pub fn get_router(
+    sea_orm_client: Arc<DatabaseConnection>,
+    cache_service: Arc<AppCacheService>,
+    idempotency_layer: IdempotencyLayer<AppL2CacheService>,
+    auth_service: Arc<Auth0Service>,
+) -> ApiRouter {
+    let audit_logs_repository = Arc::new(AuditLogsRepository::new(sea_orm_client.clone()));
+    let audit_logs_service = Arc::new(AuditLogsService::new(audit_logs_repository));
+
+    let unit_of_work = Arc::new(UnitOfWork::new(sea_orm_client.clone()));
+    let customers_repository = Arc::new(CustomerRepository::new(sea_orm_client.clone()));
+    let customers_service = Arc::new(CustomersService::new(
+        customers_repository,
+        audit_logs_service.clone(),
+        unit_of_work.clone(),
+    ));
+
+    let regular_payments_repository = Arc::new(RegularPaymentsRepository::new(sea_orm_client));
+    let regular_payments_service = Arc::new(RegularPaymentsService::new(
+        regular_payments_repository,
+        customers_service.clone(),
+        audit_logs_service,
+        unit_of_work,
+    ));
+
+    let api_state = RegularPaymentsApiState {
+        regular_payments_service,
+    };
+
+    let auth_layer = AuthLayer::new(auth_service.clone(), customers_service);
+    // Responses are per user and have to be revalidated with ETags
+    let cache_layer = JsonCacheLayer::new(cache_service.clone(), auth_service)
+        .with_cache_control("private, no-cache");
+    let cache_invalidation_layer = JsonCacheInvalidationLayer::new(cache_service);
+
+    let routes = ApiRouter::new()
+        .api_route(
+            "/",
+            get(regular_payments_handlers::find_many)
+                .route_layer(cache_layer.clone())
+                .route_layer(auth_layer.verify(vec![Roles::Customer])),
+        )
+        .api_route(
+            "/trash",
+            get(regular_payments_handlers::find_trash)
+                .route_layer(auth_layer.verify(vec![Roles::Customer])),
+        )
+        .api_route(
+            "/{id}",
+            get(regular_payments_handlers::find_one)
+                .route_layer(cache_layer)
+                .route_layer(auth_layer.verify(vec![Roles::Customer])),
+        )
+        .api_route(
+            "/",
+            post(regular_payments_handlers::create)
+                .route_layer(cache_invalidation_layer.clone())
+                .route_layer(idempotency_layer)
+                .route_layer(auth_layer.verify(vec![Roles::Customer])),
+        )
+        .api_route(
+            "/{id}/restore",
+            post(regular_payments_handlers::restore_one)
+                .route_layer(cache_invalidation_layer.clone())
+                .route_layer(auth_layer.verify(vec![Roles::Customer])),
+        )
+        .api_route(
+            "/{id}",
+            patch(regular_payments_handlers::update)
+                .route_layer(cache_invalidation_layer.clone())
+                .route_layer(auth_layer.verify(vec![Roles::Customer])),
+        )
+        .api_route(
+            "/{id}",
+            delete(regular_payments_handlers::remove)
+                .route_layer(cache_invalidation_layer)
+                .route_layer(auth_layer.verify(vec![Roles::Customer])),
+        );
+
+    ApiRouter::new()
+        .nest("/regular-payments", routes)
+        .with_state(api_state)
+}
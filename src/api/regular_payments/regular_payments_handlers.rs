@@ -0,0 +1,117 @@
+use axum::extract::State;
+use axum::{Extension, Json};
+use std::sync::Arc;
+
+use crate::api::regular_payments::dto::create_regular_payment_dto::CreateRegularPaymentDto;
+use crate::api::regular_payments::dto::update_regular_payment_dto::UpdateRegularPaymentDto;
+use crate::api::regular_payments::entities::regular_payment_entity::RegularPaymentEntity;
+use crate::api::regular_payments::regular_payments_service::RegularPaymentsService;
+use crate::shared::errors::http_error::HttpError;
+use crate::shared::modules::audit::structs::audit_context::AuditContext;
+use crate::shared::modules::auth::structs::user::User;
+use crate::shared::modules::db::structs::id_path::IdPath;
+use crate::shared::modules::versioning::structs::if_match::IfMatch;
+use crate::shared::modules::versioning::structs::versioned_json::VersionedJson;
+
+pub async fn find_many(
+    Extension(user): Extension<User>,
+    State(regular_payments_service): State<Arc<RegularPaymentsService>>,
+) -> Result<RegularPaymentEntitiesJson, HttpError> {
+    let found_regular_payments = regular_payments_service
+        .find_many_as_customer(&user.id)
+        .await?;
+
+    Ok(Json(found_regular_payments))
+}
+
+pub async fn find_trash(
+    Extension(user): Extension<User>,
+    State(regular_payments_service): State<Arc<RegularPaymentsService>>,
+) -> Result<RegularPaymentEntitiesJson, HttpError> {
+    let deleted_regular_payments = regular_payments_service
+        .find_trash_as_customer(&user.id)
+        .await?;
+
+    Ok(Json(deleted_regular_payments))
+}
+
+pub async fn find_one(
+    IdPath(regular_payment_id): IdPath,
+    Extension(user): Extension<User>,
+    State(regular_payments_service): State<Arc<RegularPaymentsService>>,
+) -> Result<RegularPaymentEntityJson, HttpError> {
+    let found_regular_payment = regular_payments_service
+        .find_one_as_customer(&regular_payment_id, &user.id)
+        .await?;
+
+    Ok(VersionedJson(found_regular_payment))
+}
+
+pub async fn create(
+    Extension(user): Extension<User>,
+    audit_context: AuditContext,
+    State(regular_payments_service): State<Arc<RegularPaymentsService>>,
+    Json(create_regular_payment_dto): Json<CreateRegularPaymentDto>,
+) -> Result<RegularPaymentEntityJson, HttpError> {
+    let created_regular_payment = regular_payments_service
+        .create_as_customer(create_regular_payment_dto, &user.id, &audit_context)
+        .await?;
+
+    Ok(VersionedJson(created_regular_payment))
+}
+
+pub async fn update(
+    Extension(user): Extension<User>,
+    IdPath(regular_payment_id): IdPath,
+    audit_context: AuditContext,
+    IfMatch(expected_versions): IfMatch,
+    State(regular_payments_service): State<Arc<RegularPaymentsService>>,
+    Json(update_regular_payment_dto): Json<UpdateRegularPaymentDto>,
+) -> Result<RegularPaymentEntityJson, HttpError> {
+    let updated_regular_payment = regular_payments_service
+        .update_as_customer(
+            &regular_payment_id,
+            update_regular_payment_dto,
+            &user.id,
+            expected_versions,
+            &audit_context,
+        )
+        .await?;
+
+    Ok(VersionedJson(updated_regular_payment))
+}
+
+pub async fn remove(
+    Extension(user): Extension<User>,
+    IdPath(regular_payment_id): IdPath,
+    audit_context: AuditContext,
+    IfMatch(expected_versions): IfMatch,
+    State(regular_payments_service): State<Arc<RegularPaymentsService>>,
+) -> Result<RegularPaymentEntityJson, HttpError> {
+    let deleted_regular_payment = regular_payments_service
+        .delete_as_customer(
+            &regular_payment_id,
+            &user.id,
+            expected_versions,
+            &audit_context,
+        )
+        .await?;
+
+    Ok(VersionedJson(deleted_regular_payment))
+}
+
+pub async fn restore_one(
+    Extension(user): Extension<User>,
+    IdPath(regular_payment_id): IdPath,
+    audit_context: AuditContext,
+    State(regular_payments_service): State<Arc<RegularPaymentsService>>,
+) -> Result<RegularPaymentEntityJson, HttpError> {
+    let restored_regular_payment = regular_payments_service
+        .restore_as_customer(&regular_payment_id, &user.id, &audit_context)
+        .await?;
+
+    Ok(VersionedJson(restored_regular_payment))
+}
+
+pub type RegularPaymentEntityJson = VersionedJson<RegularPaymentEntity>;
+pub type RegularPaymentEntitiesJson = Json<Vec<RegularPaymentEntity>>;
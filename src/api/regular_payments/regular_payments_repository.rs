@@ -0,0 +1,235 @@
+use async_trait::async_trait;
+use sea_orm::prelude::DateTimeWithTimeZone;
+use sea_orm::sea_query::{Expr, SimpleExpr};
+use sea_orm::{
+    ColumnTrait, DatabaseConnection, DatabaseTransaction, EntityTrait, IntoActiveModel,
+    QueryFilter, QueryOrder, QuerySelect,
+};
+use std::sync::Arc;
+
+use crate::api::regular_payments::dto::create_regular_payment_db_dto::CreateRegularPaymentDbDto;
+use crate::api::regular_payments::dto::update_regular_payment_db_dto::UpdateRegularPaymentDbDto;
+use crate::api::regular_payments::traits::regular_payments_repository::RegularPaymentsRepositoryTrait;
+use crate::api::regular_payments::types::regular_payment_from_db::RegularPaymentFromDb;
+use crate::shared::errors::http_error::HttpError;
+use crate::shared::modules::db::db_connection::DbConnection;
+use crate::shared::modules::db::entities::prelude::RegularPayment;
+use crate::shared::modules::db::entities::regular_payment;
+use crate::shared::modules::db::entities::regular_payment::ActiveModel as RegularPaymentActiveModel;
+
+pub struct RegularPaymentsRepository {
+    connection: DbConnection,
+}
+
+impl RegularPaymentsRepository {
+    pub fn new(sea_orm_client: Arc<DatabaseConnection>) -> Self {
+        Self {
+            connection: DbConnection::Pool(sea_orm_client),
+        }
+    }
+
+    fn get_not_found_error(id: &str) -> HttpError {
+        HttpError::NotFound(format!("Regular payment with id '{id}' was not found"))
+    }
+
+    /// Locks the payment until the end of the transaction and checks the write preconditions:
+    /// the payment is active or in the trash (`is_deleted`), belongs to the customer
+    /// and has one of the expected versions
+    async fn lock_for_write(
+        transaction: &DatabaseTransaction,
+        id: &str,
+        customer_id: &str,
+        expected_versions: Option<&[i64]>,
+        is_deleted: bool,
+    ) -> Result<regular_payment::Model, HttpError> {
+        let found_regular_payment = RegularPayment::find_by_id(id)
+            .filter(regular_payment::Column::CustomerId.eq(customer_id))
+            .lock_exclusive()
+            .one(transaction)
+            .await?
+            .filter(|regular_payment| regular_payment.deleted_at.is_some() == is_deleted)
+            .ok_or_else(|| Self::get_not_found_error(id))?;
+
+        if expected_versions
+            .is_some_and(|versions| !versions.contains(&found_regular_payment.version))
+        {
+            return Err(HttpError::PreconditionFailed(format!(
+                "Regular payment with id '{id}' was modified, If-Match does not match the current version"
+            )));
+        }
+
+        Ok(found_regular_payment)
+    }
+
+    /// Moves the payment to the trash or out of it
+    fn set_deleted_at(id: &str, deleted_at: SimpleExpr) -> sea_orm::UpdateMany<RegularPayment> {
+        RegularPayment::update_many()
+            .col_expr(regular_payment::Column::DeletedAt, deleted_at)
+            .col_expr(
+                regular_payment::Column::Version,
+                Expr::col(regular_payment::Column::Version).add(1),
+            )
+            .col_expr(
+                regular_payment::Column::UpdatedAt,
+                Expr::current_timestamp().into(),
+            )
+            .filter(regular_payment::Column::Id.eq(id))
+    }
+}
+
+#[async_trait]
+impl RegularPaymentsRepositoryTrait for RegularPaymentsRepository {
+    fn with_connection(
+        &self,
+        connection: DbConnection,
+    ) -> Arc<dyn RegularPaymentsRepositoryTrait + Send + Sync> {
+        Arc::new(Self { connection })
+    }
+
+    async fn find_one(
+        &self,
+        id: &str,
+        customer_id: &str,
+        is_deleted: bool,
+    ) -> Result<RegularPaymentFromDb, HttpError> {
+        let found_regular_payment = RegularPayment::find_by_id(id)
+            .filter(regular_payment::Column::CustomerId.eq(customer_id))
+            .one(&self.connection)
+            .await?
+            .filter(|regular_payment| regular_payment.deleted_at.is_some() == is_deleted)
+            .ok_or_else(|| Self::get_not_found_error(id))?;
+
+        Ok(found_regular_payment.into())
+    }
+
+    async fn find_many(
+        &self,
+        customer_id: &str,
+        is_deleted: bool,
+    ) -> Result<Vec<RegularPaymentFromDb>, HttpError> {
+        let found_regular_payments = RegularPayment::find()
+            .filter(regular_payment::Column::CustomerId.eq(customer_id))
+            .filter(if is_deleted {
+                regular_payment::Column::DeletedAt.is_not_null()
+            } else {
+                regular_payment::Column::DeletedAt.is_null()
+            })
+            .order_by_asc(regular_payment::Column::DateOfCharge)
+            .order_by_asc(regular_payment::Column::Id)
+            .all(&self.connection)
+            .await?
+            .into_iter()
+            .map(Into::into)
+            .collect();
+
+        Ok(found_regular_payments)
+    }
+
+    async fn create(
+        &self,
+        create_dto: CreateRegularPaymentDbDto,
+    ) -> Result<RegularPaymentFromDb, HttpError> {
+        let created_regular_payment = RegularPayment::insert(create_dto.into_active_model())
+            .exec_with_returning(&self.connection)
+            .await?;
+
+        Ok(created_regular_payment.into())
+    }
+
+    async fn update(
+        &self,
+        id: &str,
+        update_dto: UpdateRegularPaymentDbDto,
+        customer_id: &str,
+        expected_versions: Option<Vec<i64>>,
+    ) -> Result<RegularPaymentFromDb, HttpError> {
+        let transaction = self.connection.begin().await?;
+
+        Self::lock_for_write(
+            &transaction,
+            id,
+            customer_id,
+            expected_versions.as_deref(),
+            false,
+        )
+        .await?;
+
+        let updated_regular_payment = RegularPayment::update_many()
+            .set(RegularPaymentActiveModel::from(update_dto))
+            .col_expr(
+                regular_payment::Column::Version,
+                Expr::col(regular_payment::Column::Version).add(1),
+            )
+            .col_expr(
+                regular_payment::Column::UpdatedAt,
+                Expr::current_timestamp().into(),
+            )
+            .filter(regular_payment::Column::Id.eq(id))
+            .exec_with_returning(&transaction)
+            .await?
+            .pop()
+            .ok_or_else(|| Self::get_not_found_error(id))?;
+
+        transaction.commit().await?;
+
+        Ok(updated_regular_payment.into())
+    }
+
+    async fn delete(
+        &self,
+        id: &str,
+        customer_id: &str,
+        expected_versions: Option<Vec<i64>>,
+    ) -> Result<RegularPaymentFromDb, HttpError> {
+        let transaction = self.connection.begin().await?;
+
+        Self::lock_for_write(
+            &transaction,
+            id,
+            customer_id,
+            expected_versions.as_deref(),
+            false,
+        )
+        .await?;
+
+        let deleted_regular_payment = Self::set_deleted_at(id, Expr::current_timestamp().into())
+            .exec_with_returning(&transaction)
+            .await?
+            .pop()
+            .ok_or_else(|| Self::get_not_found_error(id))?;
+
+        transaction.commit().await?;
+
+        Ok(deleted_regular_payment.into())
+    }
+
+    async fn restore(
+        &self,
+        id: &str,
+        customer_id: &str,
+    ) -> Result<RegularPaymentFromDb, HttpError> {
+        let transaction = self.connection.begin().await?;
+
+        Self::lock_for_write(&transaction, id, customer_id, None, true).await?;
+
+        let restored_regular_payment =
+            Self::set_deleted_at(id, Expr::value(Option::<DateTimeWithTimeZone>::None))
+                .exec_with_returning(&transaction)
+                .await?
+                .pop()
+                .ok_or_else(|| Self::get_not_found_error(id))?;
+
+        transaction.commit().await?;
+
+        Ok(restored_regular_payment.into())
+    }
+
+    async fn purge_deleted(&self, deleted_before: DateTimeWithTimeZone) -> Result<u64, HttpError> {
+        let delete_result = RegularPayment::delete_many()
+            .filter(regular_payment::Column::DeletedAt.lt(deleted_before))
+            .exec(&self.connection)
+            .await?;
+
+        Ok(delete_result.rows_affected)
+    }
+}
@@ -0,0 +1,378 @@
+use std::slice;
+use std::sync::Arc;
+use validator::Validate;
+
+use crate::api::audit_logs::audit_logs_service::AuditLogsService;
+use crate::api::audit_logs::types::audit_action::AuditAction;
+use crate::api::customers::customers_service::CustomersService;
+use crate::api::regular_payments::dto::create_regular_payment_db_dto::CreateRegularPaymentDbDto;
+use crate::api::regular_payments::dto::create_regular_payment_dto::CreateRegularPaymentDto;
+use crate::api::regular_payments::dto::update_regular_payment_db_dto::UpdateRegularPaymentDbDto;
+use crate::api::regular_payments::dto::update_regular_payment_dto::UpdateRegularPaymentDto;
+use crate::api::regular_payments::entities::regular_payment_entity::RegularPaymentEntity;
+use crate::api::regular_payments::traits::regular_payments_repository::RegularPaymentsRepositoryTrait;
+use crate::shared::errors::http_error::HttpError;
+use crate::shared::modules::audit::structs::audit_context::AuditContext;
+use crate::shared::modules::db::unit_of_work::UnitOfWork;
+use crate::shared::modules::db::utils::{generate_id, to_decimal_amount};
+
+/// Regular payments are managed by the customers they belong to, their changes are audited
+#[derive(Clone)]
+pub struct RegularPaymentsService {
+    regular_payments_repository: Arc<dyn RegularPaymentsRepositoryTrait + Send + Sync>,
+    customers_service: Arc<CustomersService>,
+    audit_logs_service: Arc<AuditLogsService>,
+    unit_of_work: Arc<UnitOfWork>,
+}
+
+impl RegularPaymentsService {
+    pub fn new(
+        regular_payments_repository: Arc<dyn RegularPaymentsRepositoryTrait + Send + Sync>,
+        customers_service: Arc<CustomersService>,
+        audit_logs_service: Arc<AuditLogsService>,
+        unit_of_work: Arc<UnitOfWork>,
+    ) -> Self {
+        Self {
+            regular_payments_repository,
+            customers_service,
+            audit_logs_service,
+            unit_of_work,
+        }
+    }
+
+    pub async fn find_many_as_customer(
+        &self,
+        user_id: &str,
+    ) -> Result<Vec<RegularPaymentEntity>, HttpError> {
+        let customer = self.customers_service.find_one_by_user_id(user_id).await?;
+
+        let regular_payment_entities = self
+            .regular_payments_repository
+            .find_many(&customer.id, false)
+            .await?
+            .into_iter()
+            .map(RegularPaymentEntity::from)
+            .collect();
+
+        Ok(regular_payment_entities)
+    }
+
+    /// Payments of the customer which are in the trash
+    pub async fn find_trash_as_customer(
+        &self,
+        user_id: &str,
+    ) -> Result<Vec<RegularPaymentEntity>, HttpError> {
+        let customer = self.customers_service.find_one_by_user_id(user_id).await?;
+
+        let regular_payment_entities = self
+            .regular_payments_repository
+            .find_many(&customer.id, true)
+            .await?
+            .into_iter()
+            .map(RegularPaymentEntity::from)
+            .collect();
+
+        Ok(regular_payment_entities)
+    }
+
+    pub async fn find_one_as_customer(
+        &self,
+        id: &str,
+        user_id: &str,
+    ) -> Result<RegularPaymentEntity, HttpError> {
+        let customer = self.customers_service.find_one_by_user_id(user_id).await?;
+
+        let regular_payment_entity = self
+            .regular_payments_repository
+            .find_one(id, &customer.id, false)
+            .await?
+            .into();
+
+        Ok(regular_payment_entity)
+    }
+
+    pub async fn create_as_customer(
+        &self,
+        create_dto: CreateRegularPaymentDto,
+        user_id: &str,
+        audit_context: &AuditContext,
+    ) -> Result<RegularPaymentEntity, HttpError> {
+        create_dto
+            .validate()
+            .map_err(|validation_errors| HttpError::BadRequest(validation_errors.to_string()))?;
+
+        let created_regular_payment_entity = self
+            .unit_of_work
+            .run(|connection| async {
+                let customers_service = self.customers_service.with_connection(connection.clone());
+                let regular_payments_repository = self
+                    .regular_payments_repository
+                    .with_connection(connection.clone());
+                let audit_logs_service = self.audit_logs_service.with_connection(connection);
+
+                let customer = customers_service.find_one_by_user_id(user_id).await?;
+
+                let create_db_dto =
+                    Self::map_create_dto_to_create_db_dto(create_dto.clone(), customer.id)?;
+                let created_regular_payment_entity: RegularPaymentEntity =
+                    regular_payments_repository
+                        .create(create_db_dto)
+                        .await?
+                        .into();
+
+                audit_logs_service
+                    .record(
+                        audit_context,
+                        AuditAction::Create,
+                        &[],
+                        slice::from_ref(&created_regular_payment_entity),
+                    )
+                    .await?;
+
+                Ok(created_regular_payment_entity)
+            })
+            .await?;
+
+        Ok(created_regular_payment_entity)
+    }
+
+    pub async fn update_as_customer(
+        &self,
+        id: &str,
+        update_dto: UpdateRegularPaymentDto,
+        user_id: &str,
+        expected_versions: Option<Vec<i64>>,
+        audit_context: &AuditContext,
+    ) -> Result<RegularPaymentEntity, HttpError> {
+        update_dto
+            .validate()
+            .map_err(|validation_errors| HttpError::BadRequest(validation_errors.to_string()))?;
+
+        let update_db_dto = Self::map_update_dto_to_update_db_dto(update_dto)?;
+        let updated_regular_payment_entity = self
+            .unit_of_work
+            .run(|connection| async {
+                let customers_service = self.customers_service.with_connection(connection.clone());
+                let regular_payments_repository = self
+                    .regular_payments_repository
+                    .with_connection(connection.clone());
+                let audit_logs_service = self.audit_logs_service.with_connection(connection);
+
+                let customer = customers_service.find_one_by_user_id(user_id).await?;
+
+                let found_regular_payment_entity: RegularPaymentEntity =
+                    regular_payments_repository
+                        .find_one(id, &customer.id, false)
+                        .await?
+                        .into();
+                let updated_regular_payment_entity: RegularPaymentEntity =
+                    regular_payments_repository
+                        .update(
+                            id,
+                            update_db_dto.clone(),
+                            &customer.id,
+                            expected_versions.clone(),
+                        )
+                        .await?
+                        .into();
+
+                audit_logs_service
+                    .record(
+                        audit_context,
+                        AuditAction::Update,
+                        slice::from_ref(&found_regular_payment_entity),
+                        slice::from_ref(&updated_regular_payment_entity),
+                    )
+                    .await?;
+
+                Ok(updated_regular_payment_entity)
+            })
+            .await?;
+
+        Ok(updated_regular_payment_entity)
+    }
+
+    pub async fn delete_as_customer(
+        &self,
+        id: &str,
+        user_id: &str,
+        expected_versions: Option<Vec<i64>>,
+        audit_context: &AuditContext,
+    ) -> Result<RegularPaymentEntity, HttpError> {
+        let deleted_regular_payment_entity = self
+            .unit_of_work
+            .run(|connection| async {
+                let customers_service = self.customers_service.with_connection(connection.clone());
+                let regular_payments_repository = self
+                    .regular_payments_repository
+                    .with_connection(connection.clone());
+                let audit_logs_service = self.audit_logs_service.with_connection(connection);
+
+                let customer = customers_service.find_one_by_user_id(user_id).await?;
+
+                let found_regular_payment_entity: RegularPaymentEntity =
+                    regular_payments_repository
+                        .find_one(id, &customer.id, false)
+                        .await?
+                        .into();
+                let deleted_regular_payment_entity: RegularPaymentEntity =
+                    regular_payments_repository
+                        .delete(id, &customer.id, expected_versions.clone())
+                        .await?
+                        .into();
+
+                audit_logs_service
+                    .record(
+                        audit_context,
+                        AuditAction::Delete,
+                        slice::from_ref(&found_regular_payment_entity),
+                        slice::from_ref(&deleted_regular_payment_entity),
+                    )
+                    .await?;
+
+                Ok(deleted_regular_payment_entity)
+            })
+            .await?;
+
+        Ok(deleted_regular_payment_entity)
+    }
+
+    pub async fn restore_as_customer(
+        &self,
+        id: &str,
+        user_id: &str,
+        audit_context: &AuditContext,
+    ) -> Result<RegularPaymentEntity, HttpError> {
+        let restored_regular_payment_entity = self
+            .unit_of_work
+            .run(|connection| async {
+                let customers_service = self.customers_service.with_connection(connection.clone());
+                let regular_payments_repository = self
+                    .regular_payments_repository
+                    .with_connection(connection.clone());
+                let audit_logs_service = self.audit_logs_service.with_connection(connection);
+
+                let customer = customers_service.find_one_by_user_id(user_id).await?;
+
+                let found_regular_payment_entity: RegularPaymentEntity =
+                    regular_payments_repository
+                        .find_one(id, &customer.id, true)
+                        .await?
+                        .into();
+                let restored_regular_payment_entity: RegularPaymentEntity =
+                    regular_payments_repository
+                        .restore(id, &customer.id)
+                        .await?
+                        .into();
+
+                audit_logs_service
+                    .record(
+                        audit_context,
+                        AuditAction::Restore,
+                        slice::from_ref(&found_regular_payment_entity),
+                        slice::from_ref(&restored_regular_payment_entity),
+                    )
+                    .await?;
+
+                Ok(restored_regular_payment_entity)
+            })
+            .await?;
+
+        Ok(restored_regular_payment_entity)
+    }
+
+    fn map_create_dto_to_create_db_dto(
+        create_dto: CreateRegularPaymentDto,
+        customer_id: String,
+    ) -> Result<CreateRegularPaymentDbDto, HttpError> {
+        Ok(CreateRegularPaymentDbDto {
+            id: generate_id(),
+            customer_id,
+            amount: to_decimal_amount(create_dto.amount)?,
+            category: create_dto.category,
+            date_of_charge: create_dto.date_of_charge,
+        })
+    }
+
+    fn map_update_dto_to_update_db_dto(
+        update_dto: UpdateRegularPaymentDto,
+    ) -> Result<UpdateRegularPaymentDbDto, HttpError> {
+        Ok(UpdateRegularPaymentDbDto {
+            amount: update_dto.amount.map(to_decimal_amount).transpose()?,
+            category: update_dto.category.map(Into::into),
+            date_of_charge: update_dto.date_of_charge,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn map_create_dto_to_create_db_dto() {
+        let create_dto = serde_json::from_value(json!({
+            "amount": 12.5,
+            "category": "UTILITY_PAYMENTS",
+            "dateOfCharge": "2026-10-19T12:00:00+00:00",
+        }))
+        .unwrap();
+
+        let create_db_dto =
+            RegularPaymentsService::map_create_dto_to_create_db_dto(create_dto, "customer".into())
+                .unwrap();
+
+        assert_eq!(create_db_dto.customer_id, "customer");
+        assert_eq!(create_db_dto.amount.to_string(), "12.5");
+    }
+
+    #[test]
+    fn reject_out_of_range_amounts() {
+        let create_dto: CreateRegularPaymentDto = serde_json::from_value(json!({
+            "amount": 1e12,
+            "category": "UTILITY_PAYMENTS",
+            "dateOfCharge": "2026-10-19T12:00:00+00:00",
+        }))
+        .unwrap();
+        let update_dto: UpdateRegularPaymentDto =
+            serde_json::from_value(json!({"amount": 1e12})).unwrap();
+
+        assert!(matches!(
+            RegularPaymentsService::map_create_dto_to_create_db_dto(create_dto, "customer".into()),
+            Err(HttpError::BadRequest(_))
+        ));
+        assert!(matches!(
+            RegularPaymentsService::map_update_dto_to_update_db_dto(update_dto),
+            Err(HttpError::BadRequest(_))
+        ));
+    }
+
+    #[test]
+    fn map_update_dto_to_update_db_dto() {
+        let update_dto = serde_json::from_value(json!({"category": "FOOD"})).unwrap();
+
+        let update_db_dto =
+            RegularPaymentsService::map_update_dto_to_update_db_dto(update_dto).unwrap();
+
+        assert!(update_db_dto.amount.is_none());
+        assert!(update_db_dto.date_of_charge.is_none());
+        assert!(update_db_dto.category.is_some());
+    }
+
+    #[test]
+    fn validate_amounts() {
+        let cases = [
+            ("positive", json!({"amount": 10.0}), true),
+            ("zero", json!({"amount": 0.0}), true),
+            ("negative", json!({"amount": -10.0}), false),
+            ("not set", json!({}), true),
+        ];
+
+        for (name, update_dto, is_valid) in cases {
+            let update_dto: UpdateRegularPaymentDto = serde_json::from_value(update_dto).unwrap();
+
+            assert_eq!(update_dto.validate().is_ok(), is_valid, "{name}");
+        }
+    }
+}
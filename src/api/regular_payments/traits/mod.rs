@@ -0,0 +1 @@
+pub mod regular_payments_repository;
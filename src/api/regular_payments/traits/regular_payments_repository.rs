@@ -0,0 +1,63 @@
+use async_trait::async_trait;
+use sea_orm::prelude::DateTimeWithTimeZone;
+use std::sync::Arc;
+
+use crate::api::regular_payments::dto::create_regular_payment_db_dto::CreateRegularPaymentDbDto;
+use crate::api::regular_payments::dto::update_regular_payment_db_dto::UpdateRegularPaymentDbDto;
+use crate::api::regular_payments::types::regular_payment_from_db::RegularPaymentFromDb;
+use crate::shared::errors::http_error::HttpError;
+use crate::shared::modules::db::db_connection::DbConnection;
+
+#[async_trait]
+pub trait RegularPaymentsRepositoryTrait {
+    /// Repository which queries run on the connection, e.g. a unit of work transaction
+    fn with_connection(
+        &self,
+        connection: DbConnection,
+    ) -> Arc<dyn RegularPaymentsRepositoryTrait + Send + Sync>;
+
+    /// Payment of the customer, the one in the trash instead of the active one if `is_deleted`
+    async fn find_one(
+        &self,
+        id: &str,
+        customer_id: &str,
+        is_deleted: bool,
+    ) -> Result<RegularPaymentFromDb, HttpError>;
+
+    /// Payments of the customer ordered by the date of charge, the ones in the trash
+    /// instead of the active ones if `is_deleted`
+    async fn find_many(
+        &self,
+        customer_id: &str,
+        is_deleted: bool,
+    ) -> Result<Vec<RegularPaymentFromDb>, HttpError>;
+
+    async fn create(
+        &self,
+        create_dto: CreateRegularPaymentDbDto,
+    ) -> Result<RegularPaymentFromDb, HttpError>;
+
+    /// Writes check that the payment belongs to the customer in the same transaction
+    async fn update(
+        &self,
+        id: &str,
+        update_dto: UpdateRegularPaymentDbDto,
+        customer_id: &str,
+        expected_versions: Option<Vec<i64>>,
+    ) -> Result<RegularPaymentFromDb, HttpError>;
+
+    /// Moves the payment to the trash
+    async fn delete(
+        &self,
+        id: &str,
+        customer_id: &str,
+        expected_versions: Option<Vec<i64>>,
+    ) -> Result<RegularPaymentFromDb, HttpError>;
+
+    /// Moves the payment out of the trash
+    async fn restore(&self, id: &str, customer_id: &str)
+    -> Result<RegularPaymentFromDb, HttpError>;
+
+    /// Hard-deletes payments moved to the trash before the date, returns their amount
+    async fn purge_deleted(&self, deleted_before: DateTimeWithTimeZone) -> Result<u64, HttpError>;
+}
@@ -0,0 +1,15 @@
+use axum::extract::FromRef;
+use std::sync::Arc;
+
+use crate::api::regular_payments::regular_payments_service::RegularPaymentsService;
+
+#[derive(Clone)]
+pub struct RegularPaymentsApiState {
+    pub regular_payments_service: Arc<RegularPaymentsService>,
+}
+
+impl FromRef<RegularPaymentsApiState> for Arc<RegularPaymentsService> {
+    fn from_ref(app_state: &RegularPaymentsApiState) -> Arc<RegularPaymentsService> {
+        app_state.regular_payments_service.clone()
+    }
+}
@@ -0,0 +1,2 @@
+pub mod api_state;
+pub mod regular_payment_from_db;
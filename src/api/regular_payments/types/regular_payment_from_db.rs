@@ -0,0 +1,34 @@
+use chrono::{DateTime, FixedOffset};
+use serde::{Deserialize, Serialize};
+
+use crate::api::expenses::types::expense_category::ExpenseCategory;
+use crate::shared::modules::db::entities::regular_payment;
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct RegularPaymentFromDb {
+    pub id: String,
+    pub customer_id: String,
+    pub amount: f64,
+    pub category: ExpenseCategory,
+    pub date_of_charge: DateTime<FixedOffset>,
+    pub created_at: DateTime<FixedOffset>,
+    pub updated_at: DateTime<FixedOffset>,
+    pub version: i64,
+    pub deleted_at: Option<DateTime<FixedOffset>>,
+}
+
+impl From<regular_payment::Model> for RegularPaymentFromDb {
+    fn from(value: regular_payment::Model) -> Self {
+        Self {
+            id: value.id,
+            customer_id: value.customer_id,
+            amount: value.amount.try_into().unwrap_or_default(),
+            category: value.category.into(),
+            date_of_charge: value.date_of_charge,
+            created_at: value.created_at,
+            updated_at: value.updated_at,
+            version: value.version,
+            deleted_at: value.deleted_at,
+        }
+    }
+}
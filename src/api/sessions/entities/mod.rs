@@ -0,0 +1 @@
+pub mod revoked_sessions_entity;
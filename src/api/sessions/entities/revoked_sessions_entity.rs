@@ -0,0 +1,12 @@
+use aide::OperationIo;
+use chrono::{DateTime, Utc};
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+#[derive(Serialize, Deserialize, Debug, JsonSchema, OperationIo)]
+#[serde(rename_all = "camelCase")]
+pub struct RevokedSessionsEntity {
+    pub user_id: Option<String>,
+    pub token_id: Option<String>,
+    pub revoked_at: DateTime<Utc>,
+}
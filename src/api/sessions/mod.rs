@@ -0,0 +1,65 @@
+use aide::axum::ApiRouter;
+use aide::axum::routing::delete;
+use sea_orm::DatabaseConnection;
+use std::sync::Arc;
+
+use crate::api::audit_logs::audit_logs_repository::AuditLogsRepository;
+use crate::api::audit_logs::audit_logs_service::AuditLogsService;
+use crate::api::customers::customers_repository::CustomerRepository;
+use crate::api::customers::customers_service::CustomersService;
+use crate::api::sessions::sessions_service::SessionsService;
+use crate::api::sessions::types::api_state::SessionsApiState;
+use crate::shared::modules::auth::enums::roles::Roles;
+use crate::shared::modules::auth::middlewares::role_based_bearer_auth::AuthLayer;
+use crate::shared::modules::auth::services::auth0::Auth0Service;
+use crate::shared::modules::auth::traits::token_revocation_list::DynamicTokenRevocationList;
+use crate::shared::modules::db::unit_of_work::UnitOfWork;
+
+mod entities;
+mod types;
+
+mod sessions_handlers;
+mod sessions_service;
+
+pub fn get_router(
+    sea_orm_client: Arc<DatabaseConnection>,
+    auth_service: Arc<Auth0Service>,
+    token_revocation_list: Arc<DynamicTokenRevocationList>,
+) -> ApiRouter {
+    let audit_logs_repository = Arc::new(AuditLogsRepository::new(sea_orm_client.clone()));
+    let audit_logs_service = Arc::new(AuditLogsService::new(audit_logs_repository));
+
+    let customers_repository = Arc::new(CustomerRepository::new(sea_orm_client.clone()));
+    let unit_of_work = Arc::new(UnitOfWork::new(sea_orm_client));
+    let customers_service = Arc::new(CustomersService::new(
+        customers_repository,
+        audit_logs_service,
+        unit_of_work,
+    ));
+
+    let sessions_service = Arc::new(SessionsService::new(token_revocation_list));
+    let api_state = SessionsApiState { sessions_service };
+
+    let auth_layer = AuthLayer::new(auth_service, customers_service);
+
+    let routes = ApiRouter::new()
+        .api_route(
+            "/self",
+            delete(sessions_handlers::revoke_own_sessions)
+                .route_layer(auth_layer.verify(vec![Roles::Admin, Roles::Customer])),
+        )
+        .api_route(
+            "/users/{user_id}",
+            delete(sessions_handlers::revoke_user_sessions)
+                .route_layer(auth_layer.verify(vec![Roles::Admin])),
+        )
+        .api_route(
+            "/tokens/{token_id}",
+            delete(sessions_handlers::revoke_token)
+                .route_layer(auth_layer.verify(vec![Roles::Admin])),
+        );
+
+    ApiRouter::new()
+        .nest("/sessions", routes)
+        .with_state(api_state)
+}
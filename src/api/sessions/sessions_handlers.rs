@@ -0,0 +1,37 @@
+use axum::extract::{Path, State};
+use axum::{Extension, Json};
+use std::sync::Arc;
+
+use crate::api::sessions::entities::revoked_sessions_entity::RevokedSessionsEntity;
+use crate::api::sessions::sessions_service::SessionsService;
+use crate::shared::errors::http_error::HttpError;
+use crate::shared::modules::auth::structs::user::User;
+
+pub async fn revoke_own_sessions(
+    Extension(user): Extension<User>,
+    State(sessions_service): State<Arc<SessionsService>>,
+) -> Result<RevokedSessionsEntityJson, HttpError> {
+    let revoked_sessions = sessions_service.revoke_user_sessions(&user.id).await?;
+
+    Ok(Json(revoked_sessions))
+}
+
+pub async fn revoke_user_sessions(
+    Path(user_id): Path<String>,
+    State(sessions_service): State<Arc<SessionsService>>,
+) -> Result<RevokedSessionsEntityJson, HttpError> {
+    let revoked_sessions = sessions_service.revoke_user_sessions(&user_id).await?;
+
+    Ok(Json(revoked_sessions))
+}
+
+pub async fn revoke_token(
+    Path(token_id): Path<String>,
+    State(sessions_service): State<Arc<SessionsService>>,
+) -> Result<RevokedSessionsEntityJson, HttpError> {
+    let revoked_sessions = sessions_service.revoke_token(&token_id).await?;
+
+    Ok(Json(revoked_sessions))
+}
+
+pub type RevokedSessionsEntityJson = Json<RevokedSessionsEntity>;
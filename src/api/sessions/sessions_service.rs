@@ -0,0 +1,54 @@
+use chrono::Utc;
+use std::sync::Arc;
+
+use crate::api::sessions::entities::revoked_sessions_entity::RevokedSessionsEntity;
+use crate::shared::errors::http_error::HttpError;
+use crate::shared::modules::auth::traits::token_revocation_list::DynamicTokenRevocationList;
+
+#[derive(Clone)]
+pub struct SessionsService {
+    token_revocation_list: Arc<DynamicTokenRevocationList>,
+}
+
+impl SessionsService {
+    pub fn new(token_revocation_list: Arc<DynamicTokenRevocationList>) -> Self {
+        Self {
+            token_revocation_list,
+        }
+    }
+
+    pub async fn revoke_user_sessions(
+        &self,
+        user_id: &str,
+    ) -> Result<RevokedSessionsEntity, HttpError> {
+        let revoked_at = Utc::now();
+
+        self.token_revocation_list
+            .revoke_user_tokens(user_id, revoked_at)
+            .await?;
+
+        tracing::info!("All sessions of user '{user_id}' are revoked");
+
+        Ok(RevokedSessionsEntity {
+            user_id: Some(user_id.to_string()),
+            token_id: None,
+            revoked_at,
+        })
+    }
+
+    pub async fn revoke_token(&self, token_id: &str) -> Result<RevokedSessionsEntity, HttpError> {
+        let revoked_at = Utc::now();
+
+        self.token_revocation_list
+            .revoke_token(token_id, revoked_at)
+            .await?;
+
+        tracing::info!("Token '{token_id}' is revoked");
+
+        Ok(RevokedSessionsEntity {
+            user_id: None,
+            token_id: Some(token_id.to_string()),
+            revoked_at,
+        })
+    }
+}
@@ -0,0 +1,15 @@
+use axum::extract::FromRef;
+use std::sync::Arc;
+
+use crate::api::sessions::sessions_service::SessionsService;
+
+#[derive(Clone)]
+pub struct SessionsApiState {
+    pub sessions_service: Arc<SessionsService>,
+}
+
+impl FromRef<SessionsApiState> for Arc<SessionsService> {
+    fn from_ref(app_state: &SessionsApiState) -> Arc<SessionsService> {
+        app_state.sessions_service.clone()
+    }
+}
@@ -0,0 +1 @@
+pub mod api_state;
@@ -0,0 +1,112 @@
+use chrono::{TimeDelta, Utc};
+use sea_orm::DatabaseConnection;
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::task::JoinHandle;
+
+use crate::api::customers::customers_repository::CustomerRepository;
+use crate::api::customers::traits::customers_repository::CustomersRepositoryTrait;
+use crate::api::expenses::expenses_repository::ExpensesRepository;
+use crate::api::expenses::traits::expenses_repository::ExpensesRepositoryTrait;
+use crate::api::regular_payments::regular_payments_repository::RegularPaymentsRepository;
+use crate::api::regular_payments::traits::regular_payments_repository::RegularPaymentsRepositoryTrait;
+use crate::shared::modules::cache::AppCacheService;
+use crate::shared::modules::cache::traits::cache_service::CacheService;
+use crate::shared::modules::db::utils::generate_id;
+
+const LOCK_KEY: &str = "trash-purge:lock";
+
+/// Periodically hard-deletes expenses, regular payments and customers which are in the trash
+/// for longer than the retention period. Runs on one instance per interval,
+/// the one which locks it in the cache first
+pub struct TrashPurgeJob {
+    expenses_repository: Arc<dyn ExpensesRepositoryTrait + Send + Sync>,
+    regular_payments_repository: Arc<dyn RegularPaymentsRepositoryTrait + Send + Sync>,
+    customers_repository: Arc<dyn CustomersRepositoryTrait + Send + Sync>,
+    cache_service: Arc<AppCacheService>,
+    retention: TimeDelta,
+    interval: Duration,
+}
+
+impl TrashPurgeJob {
+    pub fn new(
+        sea_orm_client: Arc<DatabaseConnection>,
+        cache_service: Arc<AppCacheService>,
+        retention: TimeDelta,
+        interval: Duration,
+    ) -> Self {
+        Self {
+            expenses_repository: Arc::new(ExpensesRepository::new(sea_orm_client.clone())),
+            regular_payments_repository: Arc::new(RegularPaymentsRepository::new(
+                sea_orm_client.clone(),
+            )),
+            customers_repository: Arc::new(CustomerRepository::new(sea_orm_client)),
+            cache_service,
+            retention,
+            interval,
+        }
+    }
+
+    pub fn spawn(self) -> JoinHandle<()> {
+        tokio::spawn(async move {
+            let mut interval = tokio::time::interval(self.interval);
+
+            loop {
+                interval.tick().await;
+
+                if self.lock().await {
+                    self.purge().await;
+                }
+            }
+        })
+    }
+
+    /// The lock is not released, it expires with the interval so other instances skip it
+    async fn lock(&self) -> bool {
+        let lock_ttl = self.interval.as_secs() as usize;
+
+        match self
+            .cache_service
+            .set_str_if_absent_with_ttl(LOCK_KEY, &generate_id(), lock_ttl)
+            .await
+        {
+            Ok(is_locked) => is_locked,
+            Err(err) => {
+                tracing::warn!("Trash purge is skipped, failed to lock it: '{err}'");
+
+                false
+            }
+        }
+    }
+
+    /// Failures are only logged, the next run purges what is left
+    async fn purge(&self) {
+        let deleted_before = (Utc::now() - self.retention).fixed_offset();
+
+        match self.expenses_repository.purge_deleted(deleted_before).await {
+            Ok(0) => {}
+            Ok(count) => tracing::info!("Purged {count} expenses from the trash"),
+            Err(err) => tracing::error!("Failed to purge expenses from the trash: '{err}'"),
+        }
+
+        match self
+            .regular_payments_repository
+            .purge_deleted(deleted_before)
+            .await
+        {
+            Ok(0) => {}
+            Ok(count) => tracing::info!("Purged {count} regular payments from the trash"),
+            Err(err) => tracing::error!("Failed to purge regular payments from the trash: '{err}'"),
+        }
+
+        match self
+            .customers_repository
+            .purge_deleted(deleted_before)
+            .await
+        {
+            Ok(0) => {}
+            Ok(count) => tracing::info!("Purged {count} customers from the trash"),
+            Err(err) => tracing::error!("Failed to purge customers from the trash: '{err}'"),
+        }
+    }
+}
@@ -1,23 +1,41 @@
 use aide::axum::ApiRouter;
 use axum::Extension;
+use chrono::TimeDelta;
+use clap::Parser;
 use sea_orm::{ConnectOptions, Database};
+use std::process::ExitCode;
+use std::time::Duration;
 use std::{env, sync::Arc};
 use tracing::log;
 
 mod api;
-use crate::shared::config::get_config;
+use crate::api::trash_purge_job::TrashPurgeJob;
+use crate::shared::config::{Cli, Commands, ConfigCommands, check_config, get_config};
 
 mod shared;
 use crate::shared::handlers::handle_404_resource;
 use crate::shared::modules::auth::services::auth0::Auth0Service;
+use crate::shared::modules::auth::services::db_token_revocation_list::DbTokenRevocationList;
+use crate::shared::modules::cache::services::memory_cache::MemoryCacheService;
+use crate::shared::modules::cache::services::tiered_cache::TieredCacheService;
+use crate::shared::modules::idempotency::middlewares::idempotency::IdempotencyLayer;
 use crate::shared::modules::logger;
 use crate::shared::modules::logger::middlewares::get_request_id_layer;
 use crate::shared::modules::open_api::{get_api_docs, get_open_api, get_open_api_router};
 use crate::shared::modules::redis::RedisServiceBuilder;
 
 #[tokio::main]
-async fn main() {
-    let config = get_config().expect("Failed to get config");
+async fn main() -> ExitCode {
+    let cli = Cli::parse();
+
+    if let Some(Commands::Config {
+        command: ConfigCommands::Check,
+    }) = &cli.command
+    {
+        return check_config(&cli);
+    }
+
+    let config = get_config(&cli).expect("Failed to get config");
 
     logger::init_logger(&config.log_format, &config.log_level);
 
@@ -29,22 +47,53 @@ async fn main() {
     let sea_orm = Database::connect(sea_orm_opts)
         .await
         .expect("Failed to connect to DB");
-    // Redis Connection manager
-    let redis_service = RedisServiceBuilder::new(&config.redis_host, config.redis_port)
-        .with_default_ttl(config.redis_ttl)
-        .build()
-        .await
-        .expect("Failed to generate redis service");
+    let sea_orm = Arc::new(sea_orm);
+    // Cache: in-memory tier in front of Redis, Redis connects in background
+    let redis_service = RedisServiceBuilder::from_config(&config)
+        .expect("Failed to configure redis service")
+        .build_in_background();
+    let memory_cache_service =
+        MemoryCacheService::new(config.cache_memory_capacity, config.redis_ttl);
+    let cache_service = TieredCacheService::new(memory_cache_service, redis_service)
+        .with_l1_max_ttl(config.cache_memory_ttl)
+        .with_l2_timeout(Duration::from_millis(config.cache_redis_timeout))
+        .with_l2_circuit_breaker(
+            config.cache_circuit_breaker_threshold,
+            Duration::from_secs(config.cache_circuit_breaker_reset_timeout),
+        );
+    let cache_service = Arc::new(cache_service);
+    // Idempotency keys are locked across instances, so they skip the in-memory tier
+    let idempotency_layer = IdempotencyLayer::new(Arc::new(cache_service.clone().into_l2()))
+        .with_ttl(config.idempotency_ttl)
+        .with_lock_ttl(config.idempotency_lock_ttl);
     // Authentication
+    let token_revocation_list = Arc::new(DbTokenRevocationList::new(
+        sea_orm.clone(),
+        cache_service.clone(),
+        config.auth_token_max_lifetime,
+    ));
     let auth_service = Auth0Service::from_auth_domain(&config.auth_auth0_domain)
         .await
-        .expect("Failed to generate auth service");
+        .expect("Failed to generate auth service")
+        .with_token_cache_capacity(config.auth_token_cache_capacity)
+        .with_token_revocation_list(token_revocation_list.clone());
+
+    // Background jobs
+    TrashPurgeJob::new(
+        sea_orm.clone(),
+        cache_service.clone(),
+        TimeDelta::days(config.trash_retention_days),
+        Duration::from_secs(config.trash_purge_interval),
+    )
+    .spawn();
 
     // TODO Add pagination for APIs
     let api_router = api::get_router(
-        Arc::new(sea_orm),
-        Arc::new(redis_service),
+        sea_orm,
+        cache_service,
+        idempotency_layer,
         Arc::new(auth_service),
+        token_revocation_list,
     )
     .await;
 
@@ -73,4 +122,6 @@ async fn main() {
     axum::serve(listener, app.into_make_service())
         .await
         .expect("Failed to start server");
+
+    ExitCode::SUCCESS
 }
@@ -0,0 +1,63 @@
+use clap::{Parser, Subcommand};
+use serde::Serialize;
+use std::path::PathBuf;
+
+use crate::shared::config::types::{LogFormat, LogLevel};
+
+#[derive(Parser, Debug, Clone)]
+#[command(version, about = "Back-end for FinControl App")]
+pub struct Cli {
+    /// Path to the TOML config file, `config.toml` is used if exists
+    #[arg(long, env = "CONFIG_FILE", global = true)]
+    pub config: Option<PathBuf>,
+
+    #[command(flatten)]
+    pub overrides: CliConfigOverrides,
+
+    /// Overrides any config key, e.g. `--set redis_ttl=120`
+    #[arg(long = "set", value_name = "KEY=VALUE", value_parser = parse_key_value, global = true)]
+    pub set: Vec<(String, String)>,
+
+    #[command(subcommand)]
+    pub command: Option<Commands>,
+}
+
+/// Config values which can be set with dedicated CLI flags, they take precedence over
+/// the config file and ENV vars
+#[derive(clap::Args, Serialize, Debug, Clone, Default)]
+pub struct CliConfigOverrides {
+    #[arg(long, global = true)]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub port: Option<u16>,
+
+    #[arg(long, value_enum, global = true)]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub log_level: Option<LogLevel>,
+
+    #[arg(long, value_enum, global = true)]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub log_format: Option<LogFormat>,
+}
+
+#[derive(Subcommand, Debug, Clone)]
+pub enum Commands {
+    /// Config related commands
+    Config {
+        #[command(subcommand)]
+        command: ConfigCommands,
+    },
+}
+
+#[derive(Subcommand, Debug, Clone)]
+pub enum ConfigCommands {
+    /// Validates the config and prints the effective values with secrets redacted
+    Check,
+}
+
+fn parse_key_value(raw: &str) -> Result<(String, String), String> {
+    let (key, value) = raw
+        .split_once('=')
+        .ok_or_else(|| format!("Expected KEY=VALUE, got '{raw}'"))?;
+
+    Ok((key.trim().to_lowercase(), value.to_string()))
+}
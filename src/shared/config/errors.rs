@@ -6,4 +6,7 @@ pub enum ConfigErrors {
     ConfigFileError(String),
     #[error("{0}")]
     ParseError(String),
+    /// Contains every invalid key with the reason, e.g. `port: should be more than 0`
+    #[error("Invalid config values: {0}")]
+    InvalidValues(String),
 }
@@ -1,17 +1,161 @@
 use dotenv::dotenv;
+use figment::Figment;
+use figment::error::Kind;
+use figment::providers::{Env, Format, Serialized, Toml};
+use figment::value::Value;
+use std::path::Path;
+use std::process::ExitCode;
+use validator::{Validate, ValidationErrors};
 
+mod cli;
 mod errors;
 mod types;
 
+pub use cli::*;
 pub use errors::ConfigErrors;
 pub use types::*;
 
-pub fn get_config() -> Result<AppConfig, ConfigErrors> {
-    // fetch ENV vars from the file if exists
-    dotenv().map_err(|err| ConfigErrors::ConfigFileError(err.to_string()))?;
+const DEFAULT_CONFIG_FILE: &str = "config.toml";
+const REDACTED_VALUE: &str = "<redacted>";
+
+/// Builds the config from the layers, each next layer overrides the previous one:
+/// defaults, TOML config file, ENV vars (including `.env` file), CLI flags
+pub fn get_config(cli: &Cli) -> Result<AppConfig, ConfigErrors> {
+    let config = load_config(cli)?;
 
-    let config =
-        envy::from_env::<AppConfig>().map_err(|err| ConfigErrors::ParseError(err.to_string()))?;
+    config.validate().map_err(map_validation_errors)?;
 
     Ok(config)
 }
+
+/// Prints the effective config with secrets redacted and validates it
+pub fn check_config(cli: &Cli) -> ExitCode {
+    let config = match load_config(cli) {
+        Ok(config) => config,
+        Err(err) => {
+            eprintln!("{err}");
+
+            return ExitCode::FAILURE;
+        }
+    };
+
+    match get_redacted_config(&config) {
+        Ok(redacted_config) => println!("{redacted_config}"),
+        Err(err) => eprintln!("{err}"),
+    }
+
+    match config.validate().map_err(map_validation_errors) {
+        Ok(_) => {
+            println!("Config is valid");
+
+            ExitCode::SUCCESS
+        }
+        Err(err) => {
+            eprintln!("{err}");
+
+            ExitCode::FAILURE
+        }
+    }
+}
+
+pub fn get_redacted_config(config: &AppConfig) -> Result<toml::Table, ConfigErrors> {
+    let mut config_table = toml::Table::try_from(config)
+        .map_err(|err| ConfigErrors::ParseError(format!("Failed to serialize config: {err}")))?;
+
+    for secret_key in SECRET_CONFIG_KEYS {
+        if let Some(toml::Value::String(secret)) = config_table.get_mut(*secret_key) {
+            *secret = redact_secret(secret);
+        }
+    }
+
+    Ok(config_table)
+}
+
+fn load_config(cli: &Cli) -> Result<AppConfig, ConfigErrors> {
+    // fetch ENV vars from the file if exists
+    match dotenv() {
+        Ok(_) => {}
+        Err(dotenv::Error::Io(err)) if err.kind() == std::io::ErrorKind::NotFound => {
+            tracing::debug!(".env file is not found, skipping it");
+        }
+        Err(err) => return Err(ConfigErrors::ConfigFileError(err.to_string())),
+    }
+
+    let config_file = match &cli.config {
+        Some(config_file) if !config_file.is_file() => {
+            return Err(ConfigErrors::ConfigFileError(format!(
+                "Config file '{}' does not exist",
+                config_file.display()
+            )));
+        }
+        Some(config_file) => Some(config_file.as_path()),
+        None => Some(Path::new(DEFAULT_CONFIG_FILE)).filter(|path| path.is_file()),
+    };
+
+    let mut figment = Figment::new();
+
+    if let Some(config_file) = config_file {
+        figment = figment.merge(Toml::file_exact(config_file));
+    }
+
+    figment = figment
+        .merge(Env::raw())
+        .merge(Serialized::defaults(&cli.overrides));
+
+    for (key, value) in &cli.set {
+        let Ok(value) = value.parse::<Value>();
+
+        figment = figment.merge(Serialized::default(key, value));
+    }
+
+    figment.extract::<AppConfig>().map_err(map_figment_error)
+}
+
+fn map_figment_error(error: figment::Error) -> ConfigErrors {
+    let messages = error
+        .into_iter()
+        .map(|err| match &err.kind {
+            Kind::MissingField(key) => format!("{key}: is required"),
+            kind => format!("{}: {kind}", err.path.join(".")),
+        })
+        .collect::<Vec<_>>();
+
+    ConfigErrors::ParseError(messages.join("; "))
+}
+
+fn map_validation_errors(errors: ValidationErrors) -> ConfigErrors {
+    let mut messages = errors
+        .field_errors()
+        .into_iter()
+        .flat_map(|(key, field_errors)| {
+            field_errors.iter().map(move |field_error| {
+                let message = field_error.message.as_ref().unwrap_or(&field_error.code);
+
+                // errors of the whole config name the keys in the message
+                if key == "__all__" {
+                    message.to_string()
+                } else {
+                    format!("{key}: {message}")
+                }
+            })
+        })
+        .collect::<Vec<_>>();
+    messages.sort();
+
+    ConfigErrors::InvalidValues(messages.join("; "))
+}
+
+/// Keeps the structure of URLs with credentials visible, e.g. `postgresql://root:<redacted>@host`
+fn redact_secret(secret: &str) -> String {
+    let Some((scheme, rest)) = secret.split_once("://") else {
+        return REDACTED_VALUE.into();
+    };
+
+    let Some((credentials, host)) = rest.rsplit_once('@') else {
+        return secret.into();
+    };
+
+    let user = credentials.split(':').next().unwrap_or_default();
+
+    format!("{scheme}://{user}:{REDACTED_VALUE}@{host}")
+}
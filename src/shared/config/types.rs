@@ -1,36 +1,239 @@
-use serde::Deserialize;
+use clap::ValueEnum;
+use serde::{Deserialize, Serialize};
+use validator::{Validate, ValidationError};
 
-#[derive(Deserialize, Debug, Clone)]
+use crate::shared::modules::auth::structs::token_cache::DEFAULT_TOKEN_CACHE_CAPACITY;
+use crate::shared::modules::redis::parse_redis_nodes;
+
+#[derive(Deserialize, Serialize, Debug, Clone, Validate)]
+#[validate(schema(function = "validate_redis_topology"))]
 pub struct AppConfig {
     // App
+    #[serde(default = "default_port")]
+    #[validate(range(min = 1, message = "should be more than 0"))]
     pub port: u16,
 
     // Logging
+    #[serde(default = "default_log_level")]
     pub log_level: LogLevel,
+    #[serde(default = "default_log_format")]
     pub log_format: LogFormat,
 
     // Redis
-    pub redis_sentinels: String,
-    pub redis_name: String,
+    #[serde(default)]
+    pub redis_mode: RedisMode,
+    #[serde(default)]
+    #[validate(custom(function = "validate_redis_nodes"))]
+    pub redis_sentinels: String, // comma separated host:port list
+    #[serde(default)]
+    pub redis_name: String, // Sentinel master name
+    #[serde(default)]
+    #[validate(custom(function = "validate_redis_nodes"))]
+    pub redis_cluster_nodes: String, // comma separated host:port list
+    #[serde(default = "default_redis_host")]
+    #[validate(length(min = 1, message = "can not be empty"))]
     pub redis_host: String,
+    #[serde(default = "default_redis_port")]
+    #[validate(range(min = 1, message = "should be more than 0"))]
     pub redis_port: u16,
+    #[serde(default)]
+    pub redis_username: Option<String>,
+    #[serde(default)]
+    pub redis_password: Option<String>,
+    #[serde(default)]
+    #[validate(range(min = 0, message = "should not be negative"))]
+    pub redis_db: i64,
+    #[serde(default)]
+    pub redis_tls: bool,
+    #[serde(default = "default_redis_ttl")]
     pub redis_ttl: usize, // seconds
 
+    // Cache
+    #[serde(default = "default_cache_memory_capacity")]
+    #[validate(range(min = 1, message = "should be more than 0"))]
+    pub cache_memory_capacity: usize,
+    #[serde(default = "default_cache_memory_ttl")]
+    pub cache_memory_ttl: usize, // seconds, max time entries are kept in memory
+    #[serde(default = "default_cache_redis_timeout")]
+    #[validate(range(min = 1, message = "should be more than 0"))]
+    pub cache_redis_timeout: u64, // milliseconds
+    #[serde(default = "default_cache_circuit_breaker_threshold")]
+    #[validate(range(min = 1, message = "should be more than 0"))]
+    pub cache_circuit_breaker_threshold: u32,
+    #[serde(default = "default_cache_circuit_breaker_reset_timeout")]
+    pub cache_circuit_breaker_reset_timeout: u64, // seconds
+
+    // Idempotency
+    #[serde(default = "default_idempotency_ttl")]
+    #[validate(range(min = 1, message = "should be more than 0"))]
+    pub idempotency_ttl: usize, // seconds, responses are replayed for this long
+    #[serde(default = "default_idempotency_lock_ttl")]
+    #[validate(range(min = 1, message = "should be more than 0"))]
+    pub idempotency_lock_ttl: usize, // seconds, max time a request holds its key
+
+    // Trash
+    #[serde(default = "default_trash_retention_days")]
+    #[validate(range(min = 1, max = 36_500, message = "should be from 1 to 36500"))]
+    pub trash_retention_days: i64, // deleted rows are purged after this many days
+    #[serde(default = "default_trash_purge_interval")]
+    #[validate(range(min = 1, message = "should be more than 0"))]
+    pub trash_purge_interval: u64, // seconds
+
     // Auth0
+    #[validate(custom(function = "validate_auth0_domain"))]
     pub auth_auth0_domain: String,
+    #[serde(default = "default_auth_token_cache_capacity")]
+    #[validate(range(min = 1, message = "should be more than 0"))]
+    pub auth_token_cache_capacity: usize,
+    #[serde(default = "default_auth_token_max_lifetime")]
+    #[validate(range(min = 1, message = "should be more than 0"))]
+    pub auth_token_max_lifetime: usize, // seconds
 
     // Database
+    #[validate(custom(function = "validate_database_url"))]
     pub database_url: String,
 }
 
-#[derive(Deserialize, Debug, Clone)]
+/// Keys which values are not printed as is
+pub const SECRET_CONFIG_KEYS: &[&str] = &["database_url", "redis_password"];
+
+fn default_port() -> u16 {
+    3000
+}
+
+fn default_log_level() -> LogLevel {
+    LogLevel::Info
+}
+
+fn default_log_format() -> LogFormat {
+    LogFormat::Pretty
+}
+
+fn default_redis_host() -> String {
+    "localhost".into()
+}
+
+fn default_redis_port() -> u16 {
+    6379
+}
+
+fn default_redis_ttl() -> usize {
+    60
+}
+
+fn default_cache_memory_capacity() -> usize {
+    10_000
+}
+
+fn default_cache_memory_ttl() -> usize {
+    10
+}
+
+fn default_cache_redis_timeout() -> u64 {
+    250
+}
+
+fn default_cache_circuit_breaker_threshold() -> u32 {
+    5
+}
+
+fn default_cache_circuit_breaker_reset_timeout() -> u64 {
+    30
+}
+
+fn default_idempotency_ttl() -> usize {
+    86_400
+}
+
+fn default_idempotency_lock_ttl() -> usize {
+    60
+}
+
+fn default_trash_retention_days() -> i64 {
+    30
+}
+
+fn default_trash_purge_interval() -> u64 {
+    3600
+}
+
+fn default_auth_token_cache_capacity() -> usize {
+    DEFAULT_TOKEN_CACHE_CAPACITY
+}
+
+fn default_auth_token_max_lifetime() -> usize {
+    // Default lifetime of Auth0 access tokens
+    86_400
+}
+
+fn validate_auth0_domain(domain: &str) -> Result<(), ValidationError> {
+    if domain.is_empty() {
+        return Err(ValidationError::new("required").with_message("can not be empty".into()));
+    }
+
+    if domain.contains("://") || domain.contains('/') {
+        return Err(ValidationError::new("format")
+            .with_message("should be a domain without a scheme and a path".into()));
+    }
+
+    Ok(())
+}
+
+fn validate_redis_nodes(nodes: &str) -> Result<(), ValidationError> {
+    parse_redis_nodes(nodes)
+        .map(|_| ())
+        .map_err(|err| ValidationError::new("format").with_message(err.to_string().into()))
+}
+
+fn validate_redis_topology(config: &AppConfig) -> Result<(), ValidationError> {
+    match config.redis_mode {
+        RedisMode::Standalone => Ok(()),
+        RedisMode::Sentinel
+            if config.redis_sentinels.is_empty() || config.redis_name.is_empty() =>
+        {
+            Err(ValidationError::new("redis_mode").with_message(
+                "redis_sentinels and redis_name are required in sentinel mode".into(),
+            ))
+        }
+        RedisMode::Sentinel => Ok(()),
+        RedisMode::Cluster if config.redis_cluster_nodes.is_empty() => {
+            Err(ValidationError::new("redis_mode")
+                .with_message("redis_cluster_nodes is required in cluster mode".into()))
+        }
+        RedisMode::Cluster if config.redis_db != 0 => Err(ValidationError::new("redis_mode")
+            .with_message("redis_db is not supported in cluster mode".into())),
+        RedisMode::Cluster => Ok(()),
+    }
+}
+
+fn validate_database_url(url: &str) -> Result<(), ValidationError> {
+    let is_postgres_url = url.starts_with("postgres://") || url.starts_with("postgresql://");
+
+    if !is_postgres_url {
+        return Err(ValidationError::new("format")
+            .with_message("should be a postgres:// or postgresql:// URL".into()));
+    }
+
+    Ok(())
+}
+
+#[derive(Deserialize, Serialize, Debug, Clone, Default)]
+#[serde(rename_all = "lowercase")]
+pub enum RedisMode {
+    #[default]
+    Standalone,
+    Sentinel,
+    Cluster,
+}
+
+#[derive(Deserialize, Serialize, Debug, Clone, ValueEnum)]
 #[serde(rename_all = "lowercase")]
 pub enum LogFormat {
     Json,
     Pretty,
 }
 
-#[derive(Deserialize, Debug, Clone)]
+#[derive(Deserialize, Serialize, Debug, Clone, ValueEnum)]
 #[serde(rename_all = "lowercase")]
 pub enum LogLevel {
     Error,
@@ -40,3 +243,103 @@ pub enum LogLevel {
     Trace,
     Silent,
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn get_config(overrides: serde_json::Value) -> AppConfig {
+        let mut config = json!({
+            "auth_auth0_domain": "example.auth0.com",
+            "database_url": "postgresql://root@localhost:26257/fin_control",
+        });
+        config
+            .as_object_mut()
+            .unwrap()
+            .extend(overrides.as_object().unwrap().clone());
+
+        serde_json::from_value(config).unwrap()
+    }
+
+    #[test]
+    fn validate_redis_topology() {
+        let cases = [
+            ("standalone", json!({}), true),
+            (
+                "sentinel",
+                json!({"redis_mode": "sentinel", "redis_sentinels": "sentinel:26379", "redis_name": "main"}),
+                true,
+            ),
+            (
+                "sentinel without sentinels",
+                json!({"redis_mode": "sentinel", "redis_name": "main"}),
+                false,
+            ),
+            (
+                "sentinel without master name",
+                json!({"redis_mode": "sentinel", "redis_sentinels": "sentinel:26379"}),
+                false,
+            ),
+            (
+                "cluster",
+                json!({"redis_mode": "cluster", "redis_cluster_nodes": "redis-1:6379,redis-2:6379"}),
+                true,
+            ),
+            (
+                "cluster without nodes",
+                json!({"redis_mode": "cluster"}),
+                false,
+            ),
+            (
+                "cluster with db",
+                json!({"redis_mode": "cluster", "redis_cluster_nodes": "redis-1:6379", "redis_db": 1}),
+                false,
+            ),
+        ];
+
+        for (name, overrides, is_valid) in cases {
+            let config = get_config(overrides);
+
+            assert_eq!(
+                super::validate_redis_topology(&config).is_ok(),
+                is_valid,
+                "{name}"
+            );
+            assert_eq!(config.validate().is_ok(), is_valid, "{name}");
+        }
+    }
+
+    #[test]
+    fn validate_auth0_domain() {
+        let cases = [
+            ("domain", "example.auth0.com", true),
+            ("empty", "", false),
+            ("with scheme", "https://example.auth0.com", false),
+            ("with path", "example.auth0.com/", false),
+        ];
+
+        for (name, domain, is_valid) in cases {
+            assert_eq!(
+                super::validate_auth0_domain(domain).is_ok(),
+                is_valid,
+                "{name}"
+            );
+        }
+    }
+
+    #[test]
+    fn validate_redis_nodes() {
+        let cases = [
+            ("empty", "", true),
+            ("nodes", "redis-1:6379, redis-2:6379", true),
+            ("without port", "redis-1", false),
+        ];
+
+        for (name, nodes, is_valid) in cases {
+            let config = get_config(json!({"redis_sentinels": nodes}));
+
+            assert_eq!(config.validate().is_ok(), is_valid, "{name}");
+        }
+    }
+}
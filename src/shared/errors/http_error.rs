@@ -1,10 +1,11 @@
 use crate::shared::modules::auth::errors::AuthError;
 use crate::shared::modules::cache::errors::CacheError;
+use crate::shared::modules::db::utils::{is_serialization_failure, is_unique_violation};
 use aide::OperationIo;
 use axum::{
+    Json,
     http::StatusCode,
     response::{IntoResponse, Response},
-    Json,
 };
 use onlyerror::Error;
 use schemars::JsonSchema;
@@ -23,7 +24,15 @@ pub enum HttpError {
     #[error("{0}")]
     Unauthorized(String),
     #[error("{0}")]
+    PreconditionFailed(String),
+    #[error("{0}")]
+    Conflict(String),
+    #[error("{0}")]
+    TransactionConflict(String),
+    #[error("{0}")]
     Internal(String),
+    #[error("{0}")]
+    ServiceUnavailable(String),
 }
 
 impl HttpError {
@@ -33,7 +42,11 @@ impl HttpError {
             Self::BadRequest(_) => "Bad Request".into(),
             Self::Unauthorized(_) => "Unauthorized".into(),
             Self::Forbidden(_) => "Forbidden".into(),
+            Self::PreconditionFailed(_) => "Precondition Failed".into(),
+            Self::Conflict(_) => "Conflict".into(),
+            Self::TransactionConflict(_) => "Conflict".into(),
             Self::Internal(_) => "Internal Server Error".into(),
+            Self::ServiceUnavailable(_) => "Service Unavailable".into(),
         }
     }
 
@@ -43,7 +56,11 @@ impl HttpError {
             Self::BadRequest(_) => StatusCode::BAD_REQUEST,
             Self::Unauthorized(_) => StatusCode::UNAUTHORIZED,
             Self::Forbidden(_) => StatusCode::FORBIDDEN,
+            Self::PreconditionFailed(_) => StatusCode::PRECONDITION_FAILED,
+            Self::Conflict(_) => StatusCode::CONFLICT,
+            Self::TransactionConflict(_) => StatusCode::CONFLICT,
             Self::Internal(_) => StatusCode::INTERNAL_SERVER_ERROR,
+            Self::ServiceUnavailable(_) => StatusCode::SERVICE_UNAVAILABLE,
         }
     }
 }
@@ -68,6 +85,11 @@ impl From<AuthError> for HttpError {
             AuthError::InvalidAuthHeader(msg) => Self::Unauthorized(msg),
             AuthError::InvalidToken(msg) => Self::Unauthorized(msg),
             AuthError::InvalidUserRoles(msg) => Self::Forbidden(msg),
+            AuthError::UserNotFound(msg) => Self::NotFound(msg),
+            AuthError::ImpersonationNotAllowed(msg) => Self::Forbidden(msg),
+            AuthError::TokenRevoked(msg) => Self::Unauthorized(msg),
+            AuthError::RevocationListUnavailable(msg) => Self::ServiceUnavailable(msg),
+            AuthError::UserResolutionFailed(msg) => Self::Internal(msg),
         }
     }
 }
@@ -78,12 +100,23 @@ impl From<CacheError> for HttpError {
             CacheError::KeyNotFound(msg) => Self::Internal(msg),
             CacheError::Unknown(msg) => Self::Internal(msg),
             CacheError::FailedToParseResponse(msg) => Self::Internal(msg),
+            CacheError::Unavailable(msg) => Self::Internal(msg),
         }
     }
 }
 
 impl From<sea_orm::DbErr> for HttpError {
     fn from(sea_orm_query_error: sea_orm::DbErr) -> Self {
+        if is_serialization_failure(&sea_orm_query_error) {
+            return Self::TransactionConflict(format!(
+                "Concurrent update conflict, retry the request: {sea_orm_query_error}"
+            ));
+        }
+
+        if is_unique_violation(&sea_orm_query_error) {
+            return Self::Conflict(format!("Resource already exists: {sea_orm_query_error}"));
+        }
+
         Self::Internal(format!("SeaORM QueryError: {sea_orm_query_error}"))
     }
 }
@@ -2,5 +2,4 @@ pub mod config;
 pub mod errors;
 pub mod handlers;
 pub mod modules;
-pub mod traits;
 pub mod utils;
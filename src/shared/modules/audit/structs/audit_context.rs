@@ -0,0 +1,43 @@
+use aide::OperationInput;
+use axum::extract::FromRequestParts;
+use axum::http::request::Parts;
+use std::convert::Infallible;
+
+use crate::shared::modules::auth::structs::user::User;
+use crate::shared::modules::logger::middlewares::X_REQUEST_ID_HEADER_NAME;
+
+/// Who made a change and within which request, recorded along with the change
+#[derive(Debug, Clone, Default)]
+pub struct AuditContext {
+    /// `None` for changes made by the app itself
+    pub actor_id: Option<String>,
+    pub actor_is_admin: bool,
+    /// Admin acting as the actor, see `X-Act-As-User`
+    pub impersonated_by: Option<String>,
+    pub request_id: Option<String>,
+}
+
+impl<S> FromRequestParts<S> for AuditContext
+where
+    S: Send + Sync,
+{
+    type Rejection = Infallible;
+
+    async fn from_request_parts(parts: &mut Parts, _state: &S) -> Result<Self, Self::Rejection> {
+        let user = parts.extensions.get::<User>();
+        let request_id = parts
+            .headers
+            .get(X_REQUEST_ID_HEADER_NAME)
+            .and_then(|request_id| request_id.to_str().ok())
+            .map(str::to_string);
+
+        Ok(Self {
+            actor_id: user.map(|user| user.id.clone()),
+            actor_is_admin: user.is_some_and(User::is_admin),
+            impersonated_by: user.and_then(|user| user.impersonated_by.clone()),
+            request_id,
+        })
+    }
+}
+
+impl OperationInput for AuditContext {}
@@ -10,6 +10,16 @@ pub enum AuthError {
     InvalidToken(String),
     #[error("{0}")]
     InvalidUserRoles(String),
+    #[error("{0}")]
+    UserNotFound(String),
+    #[error("{0}")]
+    ImpersonationNotAllowed(String),
+    #[error("{0}")]
+    TokenRevoked(String),
+    #[error("{0}")]
+    RevocationListUnavailable(String),
+    #[error("{0}")]
+    UserResolutionFailed(String),
 }
 
 impl From<alcoholic_jwt::ValidationError> for AuthError {
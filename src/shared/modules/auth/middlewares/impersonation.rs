@@ -0,0 +1,137 @@
+use axum::body::Body;
+use axum::http::{HeaderValue, Request};
+use axum::response::{IntoResponse, Response};
+use futures_util::future::BoxFuture;
+use std::sync::Arc;
+use std::task::{Context, Poll};
+use tower::{Layer, Service};
+
+use crate::shared::errors::http_error::HttpError;
+use crate::shared::modules::auth::structs::user::User;
+use crate::shared::modules::auth::traits::user_resolver::DynamicUserResolver;
+
+pub const X_ACT_AS_USER_HEADER_NAME: &str = "x-act-as-user";
+
+pub fn get_act_as_user_id<B>(req: &Request<B>) -> Option<String> {
+    let act_as_user_header = req.headers().get(X_ACT_AS_USER_HEADER_NAME)?;
+    let user_id = act_as_user_header.to_str().ok()?.trim();
+
+    if user_id.is_empty() {
+        return None;
+    }
+
+    Some(user_id.to_string())
+}
+
+/// Swaps the authenticated admin `User` with the user from the `x-act-as-user` header.
+/// Must be applied inside of the auth layer, which checks that the caller is allowed to impersonate
+#[derive(Clone)]
+pub struct ImpersonationLayer {
+    user_resolver: Arc<DynamicUserResolver>,
+}
+
+impl ImpersonationLayer {
+    pub fn new(user_resolver: Arc<DynamicUserResolver>) -> Self {
+        Self { user_resolver }
+    }
+}
+
+impl<S> Layer<S> for ImpersonationLayer {
+    type Service = ImpersonationMiddleware<S>;
+
+    fn layer(&self, inner: S) -> Self::Service {
+        ImpersonationMiddleware {
+            inner,
+            user_resolver: self.user_resolver.clone(),
+        }
+    }
+}
+
+#[derive(Clone)]
+pub struct ImpersonationMiddleware<S> {
+    inner: S,
+    user_resolver: Arc<DynamicUserResolver>,
+}
+
+impl<S> Service<Request<Body>> for ImpersonationMiddleware<S>
+where
+    S: Service<Request<Body>, Response = Response> + Send + 'static + Clone,
+    S::Future: Send + 'static,
+{
+    type Response = S::Response;
+    type Error = S::Error;
+    type Future = BoxFuture<'static, Result<Self::Response, Self::Error>>;
+
+    fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        self.inner.poll_ready(cx)
+    }
+
+    fn call(&mut self, mut request: Request<Body>) -> Self::Future {
+        let clone = self.inner.clone();
+        let mut inner = std::mem::replace(&mut self.inner, clone);
+        let user_resolver = self.user_resolver.clone();
+
+        Box::pin(async move {
+            let Some(target_user_id) = get_act_as_user_id(&request) else {
+                return inner.call(request).await;
+            };
+
+            let Some(admin) = request.extensions().get::<User>().cloned() else {
+                let err = HttpError::Unauthorized("User is not authenticated".into());
+
+                return Ok(err.into_response());
+            };
+
+            if !admin.is_admin() {
+                let err = HttpError::Forbidden("Only admins can act as another user".into());
+
+                return Ok(err.into_response());
+            }
+
+            let method = request.method().clone();
+            let uri = request.uri().clone();
+
+            let target_user = match user_resolver.resolve_user(&target_user_id).await {
+                Ok(target_user) => target_user,
+                Err(err) => {
+                    tracing::warn!(
+                        target: "impersonation_audit",
+                        admin_id = %admin.id,
+                        target_user_id = %target_user_id,
+                        %method,
+                        %uri,
+                        "Failed to impersonate user: {err}"
+                    );
+
+                    return Ok(HttpError::from(err).into_response());
+                }
+            };
+
+            request.extensions_mut().insert(User {
+                impersonated_by: Some(admin.id.clone()),
+                ..target_user
+            });
+
+            let mut response = inner.call(request).await?;
+
+            tracing::info!(
+                target: "impersonation_audit",
+                admin_id = %admin.id,
+                admin_email = %admin.email,
+                target_user_id = %target_user_id,
+                %method,
+                %uri,
+                status = response.status().as_u16(),
+                "Request is performed on behalf of another user"
+            );
+
+            if let Ok(header_value) = HeaderValue::from_str(&target_user_id) {
+                response
+                    .headers_mut()
+                    .insert(X_ACT_AS_USER_HEADER_NAME, header_value);
+            }
+
+            Ok(response)
+        })
+    }
+}
@@ -1 +1,2 @@
+pub mod impersonation;
 pub mod role_based_bearer_auth;
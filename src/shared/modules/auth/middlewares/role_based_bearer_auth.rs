@@ -1,31 +1,51 @@
 use axum::body::Body;
-use axum::http::Request;
+use axum::http::{Request, Response};
 use axum::response::IntoResponse;
+use futures_util::future::BoxFuture;
 use std::sync::Arc;
-use tower_http::validate_request::{ValidateRequest, ValidateRequestHeaderLayer};
+use tower::ServiceBuilder;
+use tower::layer::util::{Identity, Stack};
+use tower_http::auth::{AsyncAuthorizeRequest, AsyncRequireAuthorizationLayer};
 
 use crate::shared::errors::http_error::HttpError;
 use crate::shared::modules::auth::enums::roles::Roles;
+use crate::shared::modules::auth::errors::AuthError;
+use crate::shared::modules::auth::middlewares::impersonation::{
+    ImpersonationLayer, get_act_as_user_id,
+};
 use crate::shared::modules::auth::traits::role_based_bearer_auth_service::DynamicAuthService;
+use crate::shared::modules::auth::traits::user_resolver::DynamicUserResolver;
 use crate::shared::utils::get_bearer_token;
 
-// TODO Possibly replace ValidateRequest implementation with AsyncAuthorizeRequest implementation
 pub struct AuthLayer {
     auth_service: Arc<DynamicAuthService>,
+    user_resolver: Arc<DynamicUserResolver>,
 }
 
 impl AuthLayer {
-    pub fn new(auth_service: Arc<DynamicAuthService>) -> Self {
-        Self { auth_service }
+    pub fn new(
+        auth_service: Arc<DynamicAuthService>,
+        user_resolver: Arc<DynamicUserResolver>,
+    ) -> Self {
+        Self {
+            auth_service,
+            user_resolver,
+        }
     }
 
-    pub fn verify(&self, required_roles: Vec<Roles>) -> ValidateRequestHeaderLayer<AuthVerify> {
+    pub fn verify(&self, required_roles: Vec<Roles>) -> AuthVerifyLayer {
         let auth_verifier = AuthVerify::new(self.auth_service.clone(), required_roles);
 
-        ValidateRequestHeaderLayer::custom(auth_verifier)
+        ServiceBuilder::new()
+            .layer(AsyncRequireAuthorizationLayer::new(auth_verifier))
+            .layer(ImpersonationLayer::new(self.user_resolver.clone()))
     }
 }
 
+pub type AuthVerifyLayer = ServiceBuilder<
+    Stack<ImpersonationLayer, Stack<AsyncRequireAuthorizationLayer<AuthVerify>, Identity>>,
+>;
+
 #[derive(Clone)]
 pub struct AuthVerify {
     auth_service: Arc<DynamicAuthService>,
@@ -39,33 +59,59 @@ impl AuthVerify {
             required_roles,
         }
     }
+
+    /// Impersonated users are always customers, so only admins can act as them
+    /// and only on resources available for customers
+    fn get_impersonation_required_roles(&self) -> Result<Vec<Roles>, AuthError> {
+        let is_customer_resource =
+            self.required_roles.is_empty() || self.required_roles.contains(&Roles::Customer);
+
+        if !is_customer_resource {
+            return Err(AuthError::ImpersonationNotAllowed(
+                "Acting as another user is not allowed for this resource".into(),
+            ));
+        }
+
+        Ok(vec![Roles::Admin])
+    }
 }
 
-impl<B> ValidateRequest<B> for AuthVerify {
+impl<B> AsyncAuthorizeRequest<B> for AuthVerify
+where
+    B: Send + 'static,
+{
+    type RequestBody = B;
     type ResponseBody = Body;
+    type Future = BoxFuture<'static, Result<Request<B>, Response<Self::ResponseBody>>>;
 
-    fn validate(
-        &mut self,
-        req: &mut Request<B>,
-    ) -> Result<(), axum::http::Response<Self::ResponseBody>> {
-        let Some(token) = get_bearer_token(req) else {
-            let err = HttpError::Unauthorized("Missing Authorization header".into());
-
-            return Err(err.into_response());
+    fn authorize(&mut self, mut req: Request<B>) -> Self::Future {
+        let auth_service = self.auth_service.clone();
+        let required_roles = if get_act_as_user_id(&req).is_some() {
+            self.get_impersonation_required_roles()
+        } else {
+            Ok(self.required_roles.clone())
         };
 
-        let user_result = self
-            .auth_service
-            .authenticate(&token, self.required_roles.clone());
+        Box::pin(async move {
+            let Some(token) = get_bearer_token(&req) else {
+                let err = HttpError::Unauthorized("Missing Authorization header".into());
 
-        match user_result {
-            Ok(user) => {
-                req.extensions_mut().insert(user);
+                return Err(err.into_response());
+            };
 
-                Ok(())
-            }
+            let required_roles =
+                required_roles.map_err(|err| HttpError::from(err).into_response())?;
+            let user_result = auth_service.authenticate(&token, required_roles).await;
 
-            Err(err) => Err(HttpError::from(err).into_response()),
-        }
+            match user_result {
+                Ok(user) => {
+                    req.extensions_mut().insert(user);
+
+                    Ok(req)
+                }
+
+                Err(err) => Err(HttpError::from(err).into_response()),
+            }
+        })
     }
 }
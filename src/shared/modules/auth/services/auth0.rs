@@ -1,24 +1,36 @@
-use alcoholic_jwt::{token_kid, validate, Validation, JWKS};
+use alcoholic_jwt::{JWKS, Validation, token_kid, validate};
+use async_trait::async_trait;
 use base64::Engine;
+use chrono::DateTime;
 use serde::de::DeserializeOwned;
+use std::sync::Arc;
 
 use crate::shared::errors::http_error::HttpError;
 use crate::shared::modules::auth::enums::roles::Roles;
 use crate::shared::modules::auth::errors::AuthError;
 use crate::shared::modules::auth::structs::auth0_claims::Auth0JwtClaims;
+use crate::shared::modules::auth::structs::token_cache::TokenCache;
 use crate::shared::modules::auth::structs::user::User;
 use crate::shared::modules::auth::traits::role_based_bearer_auth_service::AuthService;
+use crate::shared::modules::auth::traits::token_revocation_list::DynamicTokenRevocationList;
 
 #[derive(Clone)]
 pub struct Auth0Service {
     jwks: JWKS,
     issuer: String,
+    token_cache: Arc<TokenCache>,
+    token_revocation_list: Option<Arc<DynamicTokenRevocationList>>,
 }
 
+#[async_trait]
 impl AuthService for Auth0Service {
-    fn authenticate(&self, token: &str, required_roles: Vec<Roles>) -> Result<User, AuthError> {
-        let claims = self.validate_token(token)?;
-        tracing::debug!("Token is validated successfully");
+    async fn authenticate(
+        &self,
+        token: &str,
+        required_roles: Vec<Roles>,
+    ) -> Result<User, AuthError> {
+        let claims = self.get_validated_claims(token)?;
+        self.check_not_revoked(&claims).await?;
 
         let roles_match = Self::check_roles_match(&required_roles, &claims.roles);
 
@@ -53,7 +65,27 @@ impl Auth0Service {
 
         tracing::debug!("JWKS was successfully fetched");
 
-        Ok(Self { jwks, issuer })
+        Ok(Self {
+            jwks,
+            issuer,
+            token_cache: Arc::new(TokenCache::default()),
+            token_revocation_list: None,
+        })
+    }
+
+    pub fn with_token_cache_capacity(mut self, capacity: usize) -> Self {
+        self.token_cache = Arc::new(TokenCache::new(capacity));
+
+        self
+    }
+
+    pub fn with_token_revocation_list(
+        mut self,
+        token_revocation_list: Arc<DynamicTokenRevocationList>,
+    ) -> Self {
+        self.token_revocation_list = Some(token_revocation_list);
+
+        self
     }
 }
 
@@ -84,6 +116,45 @@ impl Auth0Service {
         false
     }
 
+    async fn check_not_revoked(&self, claims: &Auth0JwtClaims) -> Result<(), AuthError> {
+        let Some(token_revocation_list) = &self.token_revocation_list else {
+            return Ok(());
+        };
+
+        // Second precision of `iat` makes tokens issued in the second of the revocation revoked too
+        let issued_at = DateTime::from_timestamp(claims.iat.into(), 0)
+            .ok_or_else(|| AuthError::InvalidToken("Token issue time is out of range".into()))?;
+
+        let is_revoked = token_revocation_list
+            .is_revoked(&claims.sub, claims.jti.as_deref(), issued_at)
+            .await
+            // Revoked tokens must not pass while the revocation list can not be read
+            .inspect_err(|err| tracing::error!("Token revocation check failed: {err}"))?;
+
+        if is_revoked {
+            tracing::debug!("Token is revoked");
+
+            return Err(AuthError::TokenRevoked("Token has been revoked".into()));
+        }
+
+        Ok(())
+    }
+
+    fn get_validated_claims(&self, token: &str) -> Result<Auth0JwtClaims, AuthError> {
+        if let Some(claims) = self.token_cache.get(token) {
+            tracing::debug!("Token is found in the validated tokens cache");
+
+            return Ok(claims);
+        }
+
+        let claims = self.validate_token(token)?;
+        tracing::debug!("Token is validated successfully");
+
+        self.token_cache.insert(token, claims.clone());
+
+        Ok(claims)
+    }
+
     fn validate_token(&self, token: &str) -> Result<Auth0JwtClaims, AuthError> {
         let validations = vec![
             Validation::Issuer(self.issuer.to_string()),
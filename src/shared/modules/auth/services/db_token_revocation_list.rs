@@ -0,0 +1,170 @@
+use async_trait::async_trait;
+use chrono::{DateTime, TimeDelta, Utc};
+use sea_orm::sea_query::OnConflict;
+use sea_orm::{ActiveValue, ColumnTrait, DatabaseConnection, EntityTrait, QueryFilter};
+use std::sync::Arc;
+
+use crate::shared::modules::auth::errors::AuthError;
+use crate::shared::modules::auth::traits::token_revocation_list::TokenRevocationList;
+use crate::shared::modules::cache::errors::CacheError;
+use crate::shared::modules::cache::traits::cache_service::CacheService;
+use crate::shared::modules::db::entities::prelude::TokenRevocation;
+use crate::shared::modules::db::entities::token_revocation;
+
+const REVOKED_KEY_PREFIX: &str = "auth:revoked";
+/// Short, so instances which cached "not revoked" while L2 was down catch up quickly
+const NOT_REVOKED_CACHE_TTL: usize = 10; // seconds
+
+/// Revocation list stored in the DB, lookups are cached. Entries live as long as the longest
+/// living token, after that every revoked token is expired anyway
+pub struct DbTokenRevocationList<C>
+where
+    C: CacheService + Send + Sync,
+{
+    sea_orm_client: Arc<DatabaseConnection>,
+    cache_service: Arc<C>,
+    token_max_lifetime: usize, // seconds
+}
+
+impl<C> DbTokenRevocationList<C>
+where
+    C: CacheService + Send + Sync,
+{
+    pub fn new(
+        sea_orm_client: Arc<DatabaseConnection>,
+        cache_service: Arc<C>,
+        token_max_lifetime: usize,
+    ) -> Self {
+        Self {
+            sea_orm_client,
+            cache_service,
+            token_max_lifetime,
+        }
+    }
+
+    /// The cache is only a shortcut, the DB decides when the cache can not be read
+    async fn get_revoked_at(&self, id: &str) -> Result<Option<DateTime<Utc>>, AuthError> {
+        let key = format!("{REVOKED_KEY_PREFIX}:{id}");
+
+        match self.cache_service.get::<Option<DateTime<Utc>>>(&key).await {
+            Ok(revoked_at) => return Ok(revoked_at),
+            Err(CacheError::KeyNotFound(_)) => {}
+            Err(err) => tracing::warn!("Failed to read cached token revocation: {err}"),
+        }
+
+        let revoked_at = TokenRevocation::find_by_id(id)
+            .filter(token_revocation::Column::ExpiresAt.gt(Utc::now()))
+            .one(self.sea_orm_client.as_ref())
+            .await
+            .map_err(|err| {
+                AuthError::RevocationListUnavailable(format!(
+                    "Failed to read token revocation list: {err}"
+                ))
+            })?
+            .map(|token_revocation| token_revocation.revoked_at.to_utc());
+
+        let ttl = match revoked_at {
+            Some(_) => self.token_max_lifetime,
+            None => NOT_REVOKED_CACHE_TTL,
+        };
+        self.cache_revoked_at(&key, revoked_at, ttl).await;
+
+        Ok(revoked_at)
+    }
+
+    /// The revocation is stored before it is cached, so a cache failure does not lose it
+    async fn set_revoked_at(&self, id: &str, revoked_at: DateTime<Utc>) -> Result<(), AuthError> {
+        let expires_at = revoked_at + TimeDelta::seconds(self.token_max_lifetime as i64);
+        let token_revocation = token_revocation::ActiveModel {
+            id: ActiveValue::Set(id.to_string()),
+            revoked_at: ActiveValue::Set(revoked_at.fixed_offset()),
+            expires_at: ActiveValue::Set(expires_at.fixed_offset()),
+        };
+
+        TokenRevocation::insert(token_revocation)
+            .on_conflict(
+                OnConflict::column(token_revocation::Column::Id)
+                    .update_columns([
+                        token_revocation::Column::RevokedAt,
+                        token_revocation::Column::ExpiresAt,
+                    ])
+                    .to_owned(),
+            )
+            .exec(self.sea_orm_client.as_ref())
+            .await
+            .map_err(|err| {
+                AuthError::RevocationListUnavailable(format!(
+                    "Failed to update token revocation list: {err}"
+                ))
+            })?;
+
+        let key = format!("{REVOKED_KEY_PREFIX}:{id}");
+        self.cache_revoked_at(&key, Some(revoked_at), self.token_max_lifetime)
+            .await;
+        self.delete_expired().await;
+
+        Ok(())
+    }
+
+    async fn cache_revoked_at(&self, key: &str, revoked_at: Option<DateTime<Utc>>, ttl: usize) {
+        if let Err(err) = self.cache_service.set_with_ttl(key, &revoked_at, ttl).await {
+            tracing::warn!("Failed to cache token revocation: {err}");
+        }
+    }
+
+    /// Failures are only logged, the next revocation deletes what is left
+    async fn delete_expired(&self) {
+        let delete_result = TokenRevocation::delete_many()
+            .filter(token_revocation::Column::ExpiresAt.lte(Utc::now()))
+            .exec(self.sea_orm_client.as_ref())
+            .await;
+
+        if let Err(err) = delete_result {
+            tracing::warn!("Failed to delete expired token revocations: {err}");
+        }
+    }
+}
+
+#[async_trait]
+impl<C> TokenRevocationList for DbTokenRevocationList<C>
+where
+    C: CacheService + Send + Sync,
+{
+    async fn is_revoked(
+        &self,
+        user_id: &str,
+        token_id: Option<&str>,
+        issued_at: DateTime<Utc>,
+    ) -> Result<bool, AuthError> {
+        if let Some(token_id) = token_id
+            && self
+                .get_revoked_at(&format!("token:{token_id}"))
+                .await?
+                .is_some()
+        {
+            return Ok(true);
+        }
+
+        let issued_before = self.get_revoked_at(&format!("user:{user_id}")).await?;
+
+        Ok(issued_before.is_some_and(|issued_before| issued_at <= issued_before))
+    }
+
+    async fn revoke_token(
+        &self,
+        token_id: &str,
+        revoked_at: DateTime<Utc>,
+    ) -> Result<(), AuthError> {
+        self.set_revoked_at(&format!("token:{token_id}"), revoked_at)
+            .await
+    }
+
+    async fn revoke_user_tokens(
+        &self,
+        user_id: &str,
+        issued_before: DateTime<Utc>,
+    ) -> Result<(), AuthError> {
+        self.set_revoked_at(&format!("user:{user_id}"), issued_before)
+            .await
+    }
+}
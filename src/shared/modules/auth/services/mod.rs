@@ -1 +1,2 @@
 pub mod auth0;
+pub mod db_token_revocation_list;
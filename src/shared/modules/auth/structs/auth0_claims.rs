@@ -19,6 +19,7 @@ pub struct Auth0JwtClaims {
     pub iss: String,
     pub aud: String,
     pub sub: String,
+    pub jti: Option<String>,
     #[serde(rename(deserialize = "https://meta.com/roles"))]
     pub roles: Vec<Roles>,
 }
@@ -1,2 +1,3 @@
 pub mod auth0_claims;
+pub mod token_cache;
 pub mod user;
@@ -0,0 +1,158 @@
+use lru::LruCache;
+use sha2::{Digest, Sha256};
+use std::num::NonZeroUsize;
+use std::sync::Mutex;
+
+use crate::shared::modules::auth::structs::auth0_claims::Auth0JwtClaims;
+
+pub const DEFAULT_TOKEN_CACHE_CAPACITY: usize = 1024;
+
+type TokenHash = [u8; 32];
+
+/// LRU cache of already validated tokens, so signatures are not re-validated on every request.
+/// Tokens are stored by their SHA-256 hash and are evicted once their `exp` is reached
+pub struct TokenCache {
+    entries: Mutex<LruCache<TokenHash, Auth0JwtClaims>>,
+}
+
+impl TokenCache {
+    pub fn new(capacity: usize) -> Self {
+        let capacity = NonZeroUsize::new(capacity).unwrap_or(NonZeroUsize::MIN);
+
+        Self {
+            entries: Mutex::new(LruCache::new(capacity)),
+        }
+    }
+
+    pub fn get(&self, token: &str) -> Option<Auth0JwtClaims> {
+        let token_hash = Self::hash_token(token);
+        let mut entries = self.entries.lock().ok()?;
+        let claims = entries.get(&token_hash)?;
+
+        if Self::is_expired(claims) {
+            entries.pop(&token_hash);
+
+            return None;
+        }
+
+        Some(claims.clone())
+    }
+
+    pub fn insert(&self, token: &str, claims: Auth0JwtClaims) {
+        if Self::is_expired(&claims) {
+            return;
+        }
+
+        if let Ok(mut entries) = self.entries.lock() {
+            entries.put(Self::hash_token(token), claims);
+        }
+    }
+
+    fn is_expired(claims: &Auth0JwtClaims) -> bool {
+        chrono::Utc::now().timestamp() >= i64::from(claims.exp)
+    }
+
+    fn hash_token(token: &str) -> TokenHash {
+        Sha256::digest(token.as_bytes()).into()
+    }
+}
+
+impl Default for TokenCache {
+    fn default() -> Self {
+        Self::new(DEFAULT_TOKEN_CACHE_CAPACITY)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn get_claims(exp: i64) -> Auth0JwtClaims {
+        serde_json::from_value(json!({
+            "nickname": "john",
+            "name": "John Doe",
+            "picture": "https://example.com/john.png",
+            "updated_at": "2026-10-19T12:00:00+00:00",
+            "email": "john@example.com",
+            "email_verified": true,
+            "iat": 0,
+            "exp": exp,
+            "nonce": "nonce",
+            "iss": "https://example.auth0.com/",
+            "aud": "audience",
+            "sub": "auth0|john",
+            "https://meta.com/roles": ["customer"],
+        }))
+        .unwrap()
+    }
+
+    #[test]
+    fn get_inserted_token() {
+        let token_cache = TokenCache::default();
+        let exp = chrono::Utc::now().timestamp() + 60;
+
+        token_cache.insert("token", get_claims(exp));
+
+        assert_eq!(
+            token_cache.get("token").map(|claims| claims.sub).as_deref(),
+            Some("auth0|john")
+        );
+        assert!(token_cache.get("other-token").is_none());
+    }
+
+    #[test]
+    fn skip_token_expired_at_exp() {
+        let token_cache = TokenCache::default();
+        let now = chrono::Utc::now().timestamp();
+        let cases = [
+            ("expired at exp", now, false),
+            ("expired before exp", now - 60, false),
+            ("valid until exp", now + 60, true),
+        ];
+
+        for (name, exp, is_cached) in cases {
+            token_cache.insert(name, get_claims(exp));
+
+            assert_eq!(token_cache.get(name).is_some(), is_cached, "{name}");
+        }
+    }
+
+    #[test]
+    fn evict_token_once_expired() {
+        let token_cache = TokenCache::default();
+        let token_hash = TokenCache::hash_token("token");
+
+        // Inserted while still valid, `exp` is reached while it is cached
+        token_cache
+            .entries
+            .lock()
+            .unwrap()
+            .put(token_hash, get_claims(chrono::Utc::now().timestamp()));
+
+        assert!(token_cache.get("token").is_none());
+        assert!(
+            token_cache
+                .entries
+                .lock()
+                .unwrap()
+                .peek(&token_hash)
+                .is_none()
+        );
+    }
+
+    #[test]
+    fn evict_least_recently_used_token() {
+        let token_cache = TokenCache::new(2);
+        let exp = chrono::Utc::now().timestamp() + 60;
+
+        token_cache.insert("first", get_claims(exp));
+        token_cache.insert("second", get_claims(exp));
+        token_cache.get("first");
+        token_cache.insert("third", get_claims(exp));
+
+        assert!(token_cache.get("first").is_some());
+        assert!(token_cache.get("second").is_none());
+        assert!(token_cache.get("third").is_some());
+    }
+}
@@ -9,6 +9,8 @@ pub struct User {
     pub email: String,
     pub email_verified: bool,
     pub roles: Vec<Roles>,
+    /// ID of the admin acting as this user, set only for impersonated requests
+    pub impersonated_by: Option<String>,
 }
 impl User {
     pub fn is_admin(&self) -> bool {
@@ -25,6 +27,7 @@ impl From<Auth0JwtClaims> for User {
             email: value.email,
             email_verified: value.email_verified,
             roles: value.roles,
+            impersonated_by: None,
         }
     }
 }
@@ -1 +1,3 @@
 pub mod role_based_bearer_auth_service;
+pub mod token_revocation_list;
+pub mod user_resolver;
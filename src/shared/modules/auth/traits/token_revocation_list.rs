@@ -0,0 +1,30 @@
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+
+use crate::shared::modules::auth::errors::AuthError;
+
+pub type DynamicTokenRevocationList = dyn TokenRevocationList + Send + Sync;
+
+#[async_trait]
+pub trait TokenRevocationList {
+    /// Checks if the token was revoked by its ID or by revoking all the tokens of the user
+    async fn is_revoked(
+        &self,
+        user_id: &str,
+        token_id: Option<&str>,
+        issued_at: DateTime<Utc>,
+    ) -> Result<bool, AuthError>;
+
+    async fn revoke_token(
+        &self,
+        token_id: &str,
+        revoked_at: DateTime<Utc>,
+    ) -> Result<(), AuthError>;
+
+    /// Revokes all the tokens of the user issued at or before `issued_before`
+    async fn revoke_user_tokens(
+        &self,
+        user_id: &str,
+        issued_before: DateTime<Utc>,
+    ) -> Result<(), AuthError>;
+}
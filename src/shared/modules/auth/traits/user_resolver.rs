@@ -0,0 +1,12 @@
+use async_trait::async_trait;
+
+use crate::shared::modules::auth::errors::AuthError;
+use crate::shared::modules::auth::structs::user::User;
+
+pub type DynamicUserResolver = dyn UserResolver + Send + Sync;
+
+/// Resolves the `User` a request is performed on behalf of, e.g. the target of an impersonation
+#[async_trait]
+pub trait UserResolver {
+    async fn resolve_user(&self, user_id: &str) -> Result<User, AuthError>;
+}
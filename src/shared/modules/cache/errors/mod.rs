@@ -9,6 +9,8 @@ pub enum CacheError {
     KeyNotFound(String),
     #[error("{0}")]
     FailedToParseResponse(String),
+    #[error("{0}")]
+    Unavailable(String),
 }
 
 impl From<RedisError> for CacheError {
@@ -1,27 +1,47 @@
 use crate::shared::errors::http_error::HttpError;
-use axum::body::Body;
+use axum::RequestPartsExt;
+use axum::body::{Body, Bytes};
 use axum::extract::OriginalUri;
-use axum::http::{header, Method, Request};
+use axum::http::request::Parts;
+use axum::http::{HeaderMap, Method, Request, StatusCode, header, response};
 use axum::response::{IntoResponse, Response};
-use axum::RequestPartsExt;
 use futures_util::future::BoxFuture;
 use std::sync::Arc;
 use std::task::{Context, Poll};
+use std::time::Instant;
 use tower::{Layer, Service};
 
+use crate::shared::modules::auth::structs::user::User;
 use crate::shared::modules::auth::traits::role_based_bearer_auth_service::AuthService;
+use crate::shared::modules::cache::structs::cache_generation::CacheGeneration;
+use crate::shared::modules::cache::structs::cached_response::CachedResponse;
+use crate::shared::modules::cache::structs::single_flight::SingleFlight;
 use crate::shared::modules::cache::traits::cache_service::CacheService;
+use crate::shared::modules::cache::utils::etag::{get_conditional_response, get_etag};
 use crate::shared::utils::get_bearer_token;
 
+pub const X_CACHE_HEADER_NAME: &str = "x-cache";
+
 pub type DynAuthService = dyn AuthService + Send + Sync;
 
-#[derive(Clone)]
+/// Result of a handler call shared between the requests waiting for the same cache key.
+/// `None` means the response body could not be read
+type SharedResponse<E> = Result<Option<BufferedResponse>, E>;
+
+#[derive(Clone, Copy, Default)]
+pub struct JsonCacheOptions {
+    stale_ttl: usize,
+    early_expiration_beta: f64,
+    cache_control: Option<&'static str>,
+}
+
 pub struct JsonCacheLayer<C>
 where
     C: CacheService + Send + Sync,
 {
     cache_service: Arc<C>,
     auth_service: Arc<DynAuthService>,
+    options: JsonCacheOptions,
 }
 
 impl<C> JsonCacheLayer<C>
@@ -32,6 +52,43 @@ where
         Self {
             cache_service,
             auth_service,
+            options: JsonCacheOptions::default(),
+        }
+    }
+
+    /// Seconds a response is served after it became stale while it is refreshed in background
+    pub fn with_stale_while_revalidate(mut self, stale_ttl: usize) -> Self {
+        self.options.stale_ttl = stale_ttl;
+
+        self
+    }
+
+    /// Refresh responses shortly before they expire with a probability growing with
+    /// the time left and the time it takes to produce them, see `CachedResponse::is_stale`
+    pub fn with_early_expiration(mut self, beta: f64) -> Self {
+        self.options.early_expiration_beta = beta;
+
+        self
+    }
+
+    /// `Cache-Control` header of successful responses unless the handler sets its own
+    pub fn with_cache_control(mut self, cache_control: &'static str) -> Self {
+        self.options.cache_control = Some(cache_control);
+
+        self
+    }
+}
+
+// Not derived, `C` itself does not have to be `Clone`
+impl<C> Clone for JsonCacheLayer<C>
+where
+    C: CacheService + Send + Sync,
+{
+    fn clone(&self) -> Self {
+        Self {
+            cache_service: self.cache_service.clone(),
+            auth_service: self.auth_service.clone(),
+            options: self.options,
         }
     }
 }
@@ -39,6 +96,8 @@ where
 impl<S, C> Layer<S> for JsonCacheLayer<C>
 where
     C: CacheService + Send + Sync,
+    S: Service<Request<Body>>,
+    S::Error: Clone + Send + Sync + 'static,
     S: Clone,
 {
     type Service = JsonCacheMiddleware<S, C>;
@@ -47,25 +106,54 @@ where
         JsonCacheMiddleware {
             inner,
             cache_service: self.cache_service.clone(),
+            cache_generation: CacheGeneration::new(self.cache_service.clone()),
             auth_service: self.auth_service.clone(),
+            options: self.options,
+            // Per route, the cache key already includes the path
+            single_flight: Arc::new(SingleFlight::new()),
         }
     }
 }
 
-#[derive(Clone)]
 pub struct JsonCacheMiddleware<S, C>
 where
     C: CacheService + Send + Sync,
+    S: Service<Request<Body>>,
+    S::Error: Clone + Send + Sync + 'static,
     S: Clone,
 {
     inner: S,
     cache_service: Arc<C>,
+    cache_generation: CacheGeneration<C>,
     auth_service: Arc<DynAuthService>,
+    options: JsonCacheOptions,
+    single_flight: Arc<SingleFlight<SharedResponse<S::Error>>>,
+}
+
+impl<S, C> Clone for JsonCacheMiddleware<S, C>
+where
+    C: CacheService + Send + Sync,
+    S: Service<Request<Body>>,
+    S::Error: Clone + Send + Sync + 'static,
+    S: Clone,
+{
+    fn clone(&self) -> Self {
+        Self {
+            inner: self.inner.clone(),
+            cache_service: self.cache_service.clone(),
+            cache_generation: self.cache_generation.clone(),
+            auth_service: self.auth_service.clone(),
+            options: self.options,
+            single_flight: self.single_flight.clone(),
+        }
+    }
 }
 
 impl<S, C> JsonCacheMiddleware<S, C>
 where
     C: CacheService + Send + Sync,
+    S: Service<Request<Body>>,
+    S::Error: Clone + Send + Sync + 'static,
     S: Clone,
 {
     fn is_admin(&self, bearer_token: Option<String>) -> bool {
@@ -89,6 +177,7 @@ impl<S, C> Service<Request<Body>> for JsonCacheMiddleware<S, C>
 where
     S: Service<Request<Body>, Response = Response> + Send + 'static + Clone,
     S::Future: Send + 'static,
+    S::Error: Clone + Send + Sync + 'static,
     C: CacheService + Send + Sync + 'static,
 {
     type Response = S::Response;
@@ -106,82 +195,248 @@ where
         let clone = self.inner.clone();
         let mut inner = std::mem::replace(&mut self.inner, clone);
         let cache_service = self.cache_service.clone();
+        let cache_generation = self.cache_generation.clone();
+        let options = self.options;
+        let single_flight = self.single_flight.clone();
 
         Box::pin(async move {
             if request.method() != Method::GET || is_admin {
-                return inner.call(request).await;
+                let response = inner.call(request).await?;
+
+                return Ok(with_cache_status(response, CacheStatus::Bypass));
             }
 
+            let if_none_match = request.headers().get(header::IF_NONE_MATCH).cloned();
+
             let (mut parts, body) = request.into_parts();
 
-            let Ok(original_uri) = parts.extract::<OriginalUri>().await else {
-                let request = Request::from_parts(parts, body);
+            let Ok(original_uri) = parts.extract::<OriginalUri>().await;
+
+            let Some(cache_key) = get_cache_key(&cache_generation, &parts, &original_uri).await
+            else {
+                let response = inner.call(Request::from_parts(parts, body)).await?;
 
-                return inner.call(request).await;
+                return Ok(with_cache_status(response, CacheStatus::Bypass));
+            };
+            let ttl = cache_service.get_default_ttl();
+            let refresh = ResponseRefresh {
+                cache_key: cache_key.clone(),
+                ttl,
+                stale_ttl: options.stale_ttl,
+                cache_control: options.cache_control,
+                cache_service: cache_service.clone(),
             };
 
-            let cache_key = original_uri.to_string();
-            let cached_response = cache_service.get_str(&cache_key).await;
+            if let Some((cached_response, response)) =
+                get_cached_response(cache_service.as_ref(), &cache_key).await
+            {
+                let is_stale = cached_response.is_stale(options.early_expiration_beta);
+                let is_expired = cached_response.get_age().as_secs() as usize
+                    >= cached_response.ttl + options.stale_ttl;
 
-            let mut response: Response = match cached_response {
-                Ok(cached_response_body) => cached_response_body.into_response(),
-                Err(_) => {
-                    let request = Request::from_parts(parts, body);
-                    let original_response = inner.call(request).await?;
-                    let response_status = original_response.status();
+                if !is_stale {
+                    return Ok(get_conditional_response(response, if_none_match.as_ref()));
+                }
 
-                    let is_error =
-                        response_status.is_server_error() || response_status.is_client_error();
+                if !is_expired && options.stale_ttl > 0 {
+                    let request = Request::from_parts(parts, Body::empty());
 
-                    if is_error {
-                        return Ok(original_response);
-                    }
+                    tokio::spawn(async move {
+                        single_flight
+                            .run(&cache_key, || refresh.run(inner, request))
+                            .await
+                    });
 
-                    set_response_cache(&cache_key, original_response, cache_service.clone()).await
+                    return Ok(get_conditional_response(response, if_none_match.as_ref()));
                 }
+            }
+
+            let request = Request::from_parts(parts, body);
+            let shared_response = single_flight
+                .run(&cache_key, || refresh.run(inner, request))
+                .await?;
+
+            let Some(shared_response) = shared_response else {
+                return Ok(HttpError::Internal("Internal server error".to_string()).into_response());
             };
 
-            response.headers_mut().insert(
-                header::CONTENT_TYPE,
-                header::HeaderValue::from_static("application/json"),
-            );
+            let response = with_cache_status(shared_response.into_response(), CacheStatus::Miss);
 
-            Ok(response)
+            Ok(get_conditional_response(response, if_none_match.as_ref()))
         })
     }
 }
 
-async fn set_response_cache<R>(
+enum CacheStatus {
+    Hit,
+    Miss,
+    Bypass,
+}
+
+fn with_cache_status(mut response: Response, cache_status: CacheStatus) -> Response {
+    let cache_status = match cache_status {
+        CacheStatus::Hit => "HIT",
+        CacheStatus::Miss => "MISS",
+        CacheStatus::Bypass => "BYPASS",
+    };
+
+    response.headers_mut().insert(
+        X_CACHE_HEADER_NAME,
+        header::HeaderValue::from_static(cache_status),
+    );
+
+    response
+}
+
+/// Responses of authenticated routes are cached per user, otherwise users would get each other's data.
+/// They are also cached per user's generation, see `JsonCacheInvalidationLayer`
+async fn get_cache_key<C>(
+    cache_generation: &CacheGeneration<C>,
+    parts: &Parts,
+    original_uri: &OriginalUri,
+) -> Option<String>
+where
+    C: CacheService + Send + Sync,
+{
+    let Some(user) = parts.extensions.get::<User>() else {
+        return Some(original_uri.0.to_string());
+    };
+
+    let generation = cache_generation.get(&user.id).await?;
+
+    Some(format!("{}:{generation}:{}", user.id, original_uri.0))
+}
+
+/// Entries which can't be read, e.g. stored by another version of the app, are treated as a miss
+async fn get_cached_response<C>(
+    cache_service: &C,
     cache_key: &str,
-    response: Response,
-    cache_service: Arc<R>,
-) -> Response
+) -> Option<(CachedResponse, Response)>
 where
-    R: CacheService + Send + Sync,
+    C: CacheService + Send + Sync,
 {
-    let response_data = axum::body::to_bytes(response.into_body(), usize::MAX).await;
+    let cached_response = cache_service
+        .get::<CachedResponse>(cache_key)
+        .await
+        .ok()
+        .filter(CachedResponse::is_current_version)?;
+
+    let mut response = cached_response
+        .to_response()
+        .inspect_err(|err| {
+            tracing::warn!("Cache for endpoint '{cache_key}' is failed to read with err: '{err}'")
+        })
+        .ok()?;
+
+    let age = cached_response.get_age().as_secs();
+    response
+        .headers_mut()
+        .insert(header::AGE, header::HeaderValue::from(age));
+
+    Some((
+        cached_response,
+        with_cache_status(response, CacheStatus::Hit),
+    ))
+}
+
+/// Response with the body read into memory, so it can be handed to several requests
+#[derive(Clone)]
+struct BufferedResponse {
+    status: StatusCode,
+    headers: HeaderMap,
+    body: Bytes,
+}
 
-    if let Ok(ref response_body_bytes) = response_data {
-        let response_body_vec = response_body_bytes.to_vec();
+impl IntoResponse for BufferedResponse {
+    fn into_response(self) -> Response {
+        let mut response = Response::new(Body::from(self.body));
+        *response.status_mut() = self.status;
+        *response.headers_mut() = self.headers;
 
-        let set_result = cache_service.set_bytes(cache_key, &response_body_vec).await;
+        response
+    }
+}
+
+struct ResponseRefresh<C>
+where
+    C: CacheService + Send + Sync,
+{
+    cache_key: String,
+    ttl: usize,
+    stale_ttl: usize,
+    cache_control: Option<&'static str>,
+    cache_service: Arc<C>,
+}
+
+impl<C> ResponseRefresh<C>
+where
+    C: CacheService + Send + Sync + 'static,
+{
+    /// Calls the handler and caches its successful response along with its ETag
+    async fn run<S>(self, mut inner: S, request: Request<Body>) -> SharedResponse<S::Error>
+    where
+        S: Service<Request<Body>, Response = Response>,
+    {
+        let started_at = Instant::now();
+        let response = inner.call(request).await?;
+        let (mut parts, body) = response.into_parts();
+
+        let body = match axum::body::to_bytes(body, usize::MAX).await {
+            Ok(body) => body,
+            Err(err) => {
+                tracing::error!(
+                    "Cache for endpoint '{}' is failed to set, because of err: '{err}'",
+                    self.cache_key
+                );
+
+                return Ok(None);
+            }
+        };
+
+        let is_error = parts.status.is_server_error() || parts.status.is_client_error();
+
+        if !is_error {
+            if !parts.headers.contains_key(header::ETAG) {
+                parts.headers.insert(header::ETAG, get_etag(&body));
+            }
+
+            if let Some(cache_control) = self.cache_control {
+                parts
+                    .headers
+                    .entry(header::CACHE_CONTROL)
+                    .or_insert(header::HeaderValue::from_static(cache_control));
+            }
+
+            self.set_response_cache(&parts, &body, started_at).await;
+        }
+
+        Ok(Some(BufferedResponse {
+            status: parts.status,
+            headers: parts.headers,
+            body,
+        }))
+    }
+
+    async fn set_response_cache(&self, parts: &response::Parts, body: &Bytes, started_at: Instant) {
+        let cache_key = &self.cache_key;
+
+        let cached_response = CachedResponse::new(
+            parts.status,
+            &parts.headers,
+            body,
+            self.ttl,
+            started_at.elapsed(),
+        );
+        // Kept longer than it is fresh to be served while revalidating
+        let set_result = self
+            .cache_service
+            .set_with_ttl(cache_key, &cached_response, self.ttl + self.stale_ttl)
+            .await;
 
         if let Err(err) = set_result {
             tracing::warn!("Cache for endpoint '{cache_key}' is failed to set with err: '{err}'");
         } else {
             tracing::debug!("Cache for endpoint '{cache_key}' is set successfully");
         }
-
-        return response_body_vec.into_response();
     }
-
-    response_data
-        .map(|data| data.into_response())
-        .unwrap_or_else(|err| {
-            tracing::error!(
-                "Cache for endpoint '{cache_key}' is failed to set, because of err: '{err}'"
-            );
-
-            HttpError::Internal("Internal server error".to_string()).into_response()
-        })
 }
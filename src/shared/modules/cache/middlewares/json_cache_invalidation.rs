@@ -0,0 +1,117 @@
+use axum::body::Body;
+use axum::http::Request;
+use axum::response::Response;
+use futures_util::future::BoxFuture;
+use std::sync::Arc;
+use std::task::{Context, Poll};
+use tower::{Layer, Service};
+
+use crate::shared::modules::auth::structs::user::User;
+use crate::shared::modules::cache::structs::cache_generation::CacheGeneration;
+use crate::shared::modules::cache::traits::cache_service::CacheService;
+
+/// Bumps the cache generation of the user after a successful mutation,
+/// so `JsonCacheLayer` stops serving the user's responses cached before it
+pub struct JsonCacheInvalidationLayer<C>
+where
+    C: CacheService + Send + Sync,
+{
+    cache_generation: CacheGeneration<C>,
+}
+
+impl<C> JsonCacheInvalidationLayer<C>
+where
+    C: CacheService + Send + Sync,
+{
+    pub fn new(cache_service: Arc<C>) -> Self {
+        Self {
+            cache_generation: CacheGeneration::new(cache_service),
+        }
+    }
+}
+
+// Not derived, `C` itself does not have to be `Clone`
+impl<C> Clone for JsonCacheInvalidationLayer<C>
+where
+    C: CacheService + Send + Sync,
+{
+    fn clone(&self) -> Self {
+        Self {
+            cache_generation: self.cache_generation.clone(),
+        }
+    }
+}
+
+impl<S, C> Layer<S> for JsonCacheInvalidationLayer<C>
+where
+    C: CacheService + Send + Sync,
+{
+    type Service = JsonCacheInvalidationMiddleware<S, C>;
+
+    fn layer(&self, inner: S) -> Self::Service {
+        JsonCacheInvalidationMiddleware {
+            inner,
+            cache_generation: self.cache_generation.clone(),
+        }
+    }
+}
+
+pub struct JsonCacheInvalidationMiddleware<S, C>
+where
+    C: CacheService + Send + Sync,
+{
+    inner: S,
+    cache_generation: CacheGeneration<C>,
+}
+
+impl<S, C> Clone for JsonCacheInvalidationMiddleware<S, C>
+where
+    S: Clone,
+    C: CacheService + Send + Sync,
+{
+    fn clone(&self) -> Self {
+        Self {
+            inner: self.inner.clone(),
+            cache_generation: self.cache_generation.clone(),
+        }
+    }
+}
+
+impl<S, C> Service<Request<Body>> for JsonCacheInvalidationMiddleware<S, C>
+where
+    S: Service<Request<Body>, Response = Response> + Send + 'static + Clone,
+    S::Future: Send + 'static,
+    S::Error: Send + 'static,
+    C: CacheService + Send + Sync + 'static,
+{
+    type Response = S::Response;
+    type Error = S::Error;
+    type Future = BoxFuture<'static, Result<Self::Response, Self::Error>>;
+
+    fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        self.inner.poll_ready(cx)
+    }
+
+    fn call(&mut self, request: Request<Body>) -> Self::Future {
+        let clone = self.inner.clone();
+        let mut inner = std::mem::replace(&mut self.inner, clone);
+        let cache_generation = self.cache_generation.clone();
+        // Impersonated users are set by the auth layer, so their cache is the one invalidated
+        let user_id = request
+            .extensions()
+            .get::<User>()
+            .map(|user| user.id.clone());
+
+        Box::pin(async move {
+            let response = inner.call(request).await?;
+
+            if let Some(user_id) = user_id
+                && response.status().is_success()
+            {
+                cache_generation.bump(&user_id).await;
+            }
+
+            Ok(response)
+        })
+    }
+}
@@ -1 +1,2 @@
 pub mod json_cache;
+pub mod json_cache_invalidation;
@@ -1,3 +1,16 @@
+use crate::shared::modules::cache::services::memory_cache::MemoryCacheService;
+use crate::shared::modules::cache::services::tiered_cache::{L2CacheService, TieredCacheService};
+use crate::shared::modules::redis::redis_service::RedisService;
+
 pub mod errors;
 pub mod middlewares;
+pub mod services;
+pub mod structs;
 pub mod traits;
+pub mod utils;
+
+/// Cache used by the app: in-memory L1 in front of Redis L2
+pub type AppCacheService = TieredCacheService<MemoryCacheService, RedisService>;
+
+/// Redis tier of `AppCacheService` on its own, see `TieredCacheService::into_l2`
+pub type AppL2CacheService = L2CacheService<MemoryCacheService, RedisService>;
@@ -0,0 +1,142 @@
+use async_trait::async_trait;
+use lru::LruCache;
+use std::num::NonZeroUsize;
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+use crate::shared::modules::cache::errors::CacheError;
+use crate::shared::modules::cache::traits::cache_service::CacheService;
+
+const OK_RESPONSE: &str = "OK";
+
+/// In-process cache bounded by the amount of entries, the least recently used entries
+/// are evicted first. Expired entries are removed on read
+pub struct MemoryCacheService {
+    entries: Mutex<LruCache<String, MemoryCacheEntry>>,
+    default_ttl: usize,
+}
+
+struct MemoryCacheEntry {
+    value: Vec<u8>,
+    expires_at: Instant,
+}
+
+impl MemoryCacheService {
+    pub fn new(capacity: usize, default_ttl: usize) -> Self {
+        let capacity = NonZeroUsize::new(capacity).unwrap_or(NonZeroUsize::MIN);
+
+        Self {
+            entries: Mutex::new(LruCache::new(capacity)),
+            default_ttl,
+        }
+    }
+
+    /// Value of the key along with its remaining TTL
+    fn get_bytes(&self, key: &str) -> Result<(Vec<u8>, Duration), CacheError> {
+        let mut entries = self.lock_entries()?;
+
+        let Some(entry) = entries.get(key) else {
+            return Err(CacheError::KeyNotFound(key.to_string()));
+        };
+
+        let now = Instant::now();
+
+        if entry.expires_at <= now {
+            entries.pop(key);
+
+            return Err(CacheError::KeyNotFound(key.to_string()));
+        }
+
+        Ok((entry.value.clone(), entry.expires_at - now))
+    }
+
+    fn put_bytes(&self, key: &str, value: &[u8], ttl: usize) -> Result<String, CacheError> {
+        let mut entries = self.lock_entries()?;
+
+        // Same as in Redis, a value with 0 TTL expires right away
+        if ttl == 0 {
+            entries.pop(key);
+
+            return Ok(OK_RESPONSE.into());
+        }
+
+        let entry = MemoryCacheEntry {
+            value: value.to_vec(),
+            expires_at: Instant::now() + Duration::from_secs(ttl as u64),
+        };
+        entries.put(key.to_string(), entry);
+
+        Ok(OK_RESPONSE.into())
+    }
+
+    fn lock_entries(
+        &self,
+    ) -> Result<std::sync::MutexGuard<'_, LruCache<String, MemoryCacheEntry>>, CacheError> {
+        self.entries
+            .lock()
+            .map_err(|err| CacheError::Unknown(format!("Memory cache lock is poisoned: {err}")))
+    }
+}
+
+#[async_trait]
+impl CacheService for MemoryCacheService {
+    fn get_default_ttl(&self) -> usize {
+        self.default_ttl
+    }
+
+    async fn get_str(&self, key: &str) -> Result<String, CacheError> {
+        let (value, _) = self.get_str_with_ttl(key).await?;
+
+        Ok(value)
+    }
+
+    async fn get_str_with_ttl(&self, key: &str) -> Result<(String, Option<usize>), CacheError> {
+        let (bytes, ttl) = self.get_bytes(key)?;
+        let value = String::from_utf8(bytes)
+            .map_err(|err| CacheError::FailedToParseResponse(err.to_string()))?;
+
+        Ok((value, Some(ttl.as_secs() as usize)))
+    }
+
+    async fn set_str_with_ttl(
+        &self,
+        key: &str,
+        value: &str,
+        ttl: usize,
+    ) -> Result<String, CacheError> {
+        self.put_bytes(key, value.as_bytes(), ttl)
+    }
+
+    async fn set_str_if_absent_with_ttl(
+        &self,
+        key: &str,
+        value: &str,
+        ttl: usize,
+    ) -> Result<bool, CacheError> {
+        let mut entries = self.lock_entries()?;
+
+        let is_present = entries
+            .peek(key)
+            .is_some_and(|entry| entry.expires_at > Instant::now());
+
+        if is_present {
+            return Ok(false);
+        }
+
+        entries.put(
+            key.to_string(),
+            MemoryCacheEntry {
+                value: value.as_bytes().to_vec(),
+                expires_at: Instant::now() + Duration::from_secs(ttl as u64),
+            },
+        );
+
+        Ok(true)
+    }
+
+    async fn delete(&self, key: &str) -> Result<(), CacheError> {
+        self.lock_entries()?.pop(key);
+
+        Ok(())
+    }
+}
@@ -0,0 +1,2 @@
+pub mod memory_cache;
+pub mod tiered_cache;
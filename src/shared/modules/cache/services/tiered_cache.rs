@@ -0,0 +1,454 @@
+use async_trait::async_trait;
+use std::future::Future;
+use std::sync::Arc;
+use std::time::Duration;
+
+use crate::shared::modules::cache::errors::CacheError;
+use crate::shared::modules::cache::structs::circuit_breaker::CircuitBreaker;
+use crate::shared::modules::cache::traits::cache_service::CacheService;
+
+const DEFAULT_L2_TIMEOUT: Duration = Duration::from_millis(250);
+const DEFAULT_L2_FAILURE_THRESHOLD: u32 = 5;
+const DEFAULT_L2_RESET_TIMEOUT: Duration = Duration::from_secs(30);
+
+/// Two-tier cache: values are read from L1 first and from L2 on a miss, writes go to both.
+/// L1 entries live at most `l1_max_ttl` seconds and never longer than in L2, so other instances'
+/// writes and deletes in L2 are picked up within `l1_max_ttl`.
+/// L2 calls are guarded by a timeout and a circuit breaker, while the circuit is open
+/// the cache works with L1 only
+pub struct TieredCacheService<L1, L2>
+where
+    L1: CacheService + Send + Sync,
+    L2: CacheService + Send + Sync,
+{
+    l1: L1,
+    l2: L2,
+    l1_max_ttl: usize,
+    l2_timeout: Duration,
+    l2_circuit_breaker: CircuitBreaker,
+}
+
+impl<L1, L2> TieredCacheService<L1, L2>
+where
+    L1: CacheService + Send + Sync,
+    L2: CacheService + Send + Sync,
+{
+    pub fn new(l1: L1, l2: L2) -> Self {
+        let l1_max_ttl = l1.get_default_ttl();
+
+        Self {
+            l1,
+            l2,
+            l1_max_ttl,
+            l2_timeout: DEFAULT_L2_TIMEOUT,
+            l2_circuit_breaker: CircuitBreaker::new(
+                "L2 cache",
+                DEFAULT_L2_FAILURE_THRESHOLD,
+                DEFAULT_L2_RESET_TIMEOUT,
+            ),
+        }
+    }
+
+    pub fn with_l1_max_ttl(mut self, l1_max_ttl: usize) -> Self {
+        self.l1_max_ttl = l1_max_ttl;
+
+        self
+    }
+
+    pub fn with_l2_timeout(mut self, l2_timeout: Duration) -> Self {
+        self.l2_timeout = l2_timeout;
+
+        self
+    }
+
+    pub fn with_l2_circuit_breaker(
+        mut self,
+        failure_threshold: u32,
+        reset_timeout: Duration,
+    ) -> Self {
+        self.l2_circuit_breaker = CircuitBreaker::new("L2 cache", failure_threshold, reset_timeout);
+
+        self
+    }
+
+    /// L2 tier on its own, for values which have to be the same on all instances.
+    /// Calls share the timeout and the circuit breaker of this cache
+    pub fn into_l2(self: Arc<Self>) -> L2CacheService<L1, L2> {
+        L2CacheService {
+            tiered_cache_service: self,
+        }
+    }
+
+    fn get_l1_ttl(&self, ttl: usize) -> usize {
+        ttl.min(self.l1_max_ttl)
+    }
+
+    async fn call_l2<T, Fut>(&self, call: Fut) -> Result<T, CacheError>
+    where
+        Fut: Future<Output = Result<T, CacheError>>,
+    {
+        // Dropped with this future if the caller is cancelled, so the circuit is not left waiting
+        // for a result which never comes
+        let Some(permit) = self.l2_circuit_breaker.try_call() else {
+            return Err(CacheError::Unavailable("L2 cache circuit is open".into()));
+        };
+
+        let result = tokio::time::timeout(self.l2_timeout, call)
+            .await
+            .unwrap_or_else(|_| {
+                Err(CacheError::Unavailable(format!(
+                    "L2 cache did not respond in {:?}",
+                    self.l2_timeout
+                )))
+            });
+
+        match &result {
+            Ok(_) | Err(CacheError::KeyNotFound(_)) => permit.record_success(),
+            Err(_) => permit.record_failure(),
+        }
+
+        result
+    }
+}
+
+#[async_trait]
+impl<L1, L2> CacheService for TieredCacheService<L1, L2>
+where
+    L1: CacheService + Send + Sync,
+    L2: CacheService + Send + Sync,
+{
+    fn get_default_ttl(&self) -> usize {
+        self.l2.get_default_ttl()
+    }
+
+    async fn get_str(&self, key: &str) -> Result<String, CacheError> {
+        let (value, _) = self.get_str_with_ttl(key).await?;
+
+        Ok(value)
+    }
+
+    /// Values read from L2 are kept in L1 no longer than they are left to live in L2
+    async fn get_str_with_ttl(&self, key: &str) -> Result<(String, Option<usize>), CacheError> {
+        if let Ok(l1_value) = self.l1.get_str_with_ttl(key).await {
+            return Ok(l1_value);
+        }
+
+        let (l2_value, l2_ttl) = self.call_l2(self.l2.get_str_with_ttl(key)).await?;
+        let l1_ttl = self.get_l1_ttl(l2_ttl.unwrap_or(self.l1_max_ttl));
+
+        if let Err(err) = self.l1.set_str_with_ttl(key, &l2_value, l1_ttl).await {
+            tracing::warn!("Failed to populate L1 cache for key '{key}', err: '{err}'");
+        }
+
+        Ok((l2_value, l2_ttl))
+    }
+
+    /// Succeeds once L1 is written, a failed L2 write degrades the value to this instance only
+    async fn set_str_with_ttl(
+        &self,
+        key: &str,
+        value: &str,
+        ttl: usize,
+    ) -> Result<String, CacheError> {
+        let l1_result = self
+            .l1
+            .set_str_with_ttl(key, value, self.get_l1_ttl(ttl))
+            .await?;
+
+        match self
+            .call_l2(self.l2.set_str_with_ttl(key, value, ttl))
+            .await
+        {
+            Ok(l2_result) => Ok(l2_result),
+            Err(err) => {
+                tracing::warn!(
+                    degraded = true,
+                    "Failed to write L2 cache for key '{key}', the value is cached in L1 only, \
+                    err: '{err}'"
+                );
+
+                Ok(l1_result)
+            }
+        }
+    }
+
+    /// Decided by L2 only, so it holds across instances. L1 is cleared to not shadow the new value
+    async fn set_str_if_absent_with_ttl(
+        &self,
+        key: &str,
+        value: &str,
+        ttl: usize,
+    ) -> Result<bool, CacheError> {
+        self.l1.delete(key).await?;
+
+        self.call_l2(self.l2.set_str_if_absent_with_ttl(key, value, ttl))
+            .await
+    }
+
+    async fn delete(&self, key: &str) -> Result<(), CacheError> {
+        self.l1.delete(key).await?;
+
+        self.call_l2(self.l2.delete(key)).await
+    }
+}
+
+/// See `TieredCacheService::into_l2`
+pub struct L2CacheService<L1, L2>
+where
+    L1: CacheService + Send + Sync,
+    L2: CacheService + Send + Sync,
+{
+    tiered_cache_service: Arc<TieredCacheService<L1, L2>>,
+}
+
+#[async_trait]
+impl<L1, L2> CacheService for L2CacheService<L1, L2>
+where
+    L1: CacheService + Send + Sync,
+    L2: CacheService + Send + Sync,
+{
+    fn get_default_ttl(&self) -> usize {
+        self.tiered_cache_service.get_default_ttl()
+    }
+
+    async fn get_str(&self, key: &str) -> Result<String, CacheError> {
+        let cache = &self.tiered_cache_service;
+
+        cache.call_l2(cache.l2.get_str(key)).await
+    }
+
+    async fn get_str_with_ttl(&self, key: &str) -> Result<(String, Option<usize>), CacheError> {
+        let cache = &self.tiered_cache_service;
+
+        cache.call_l2(cache.l2.get_str_with_ttl(key)).await
+    }
+
+    async fn set_str_with_ttl(
+        &self,
+        key: &str,
+        value: &str,
+        ttl: usize,
+    ) -> Result<String, CacheError> {
+        let cache = &self.tiered_cache_service;
+
+        cache
+            .call_l2(cache.l2.set_str_with_ttl(key, value, ttl))
+            .await
+    }
+
+    async fn set_str_if_absent_with_ttl(
+        &self,
+        key: &str,
+        value: &str,
+        ttl: usize,
+    ) -> Result<bool, CacheError> {
+        let cache = &self.tiered_cache_service;
+
+        cache
+            .call_l2(cache.l2.set_str_if_absent_with_ttl(key, value, ttl))
+            .await
+    }
+
+    async fn delete(&self, key: &str) -> Result<(), CacheError> {
+        let cache = &self.tiered_cache_service;
+
+        cache.call_l2(cache.l2.delete(key)).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::Mutex;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    use crate::shared::modules::cache::services::memory_cache::MemoryCacheService;
+
+    #[derive(Clone, Copy)]
+    enum L2Mode {
+        Available,
+        Failing,
+        Hanging,
+    }
+
+    struct StubL2CacheService {
+        cache: MemoryCacheService,
+        mode: Mutex<L2Mode>,
+        calls: AtomicUsize,
+    }
+
+    impl StubL2CacheService {
+        fn new() -> Self {
+            Self {
+                cache: MemoryCacheService::new(100, 60),
+                mode: Mutex::new(L2Mode::Available),
+                calls: AtomicUsize::new(0),
+            }
+        }
+
+        fn set_mode(&self, mode: L2Mode) {
+            *self.mode.lock().unwrap() = mode;
+        }
+
+        async fn call(&self) -> Result<(), CacheError> {
+            self.calls.fetch_add(1, Ordering::SeqCst);
+            let mode = *self.mode.lock().unwrap();
+
+            match mode {
+                L2Mode::Available => Ok(()),
+                L2Mode::Failing => Err(CacheError::Unknown("L2 is down".into())),
+                L2Mode::Hanging => std::future::pending().await,
+            }
+        }
+    }
+
+    #[async_trait]
+    impl CacheService for StubL2CacheService {
+        fn get_default_ttl(&self) -> usize {
+            self.cache.get_default_ttl()
+        }
+
+        async fn get_str(&self, key: &str) -> Result<String, CacheError> {
+            self.call().await?;
+            self.cache.get_str(key).await
+        }
+
+        async fn get_str_with_ttl(&self, key: &str) -> Result<(String, Option<usize>), CacheError> {
+            self.call().await?;
+            self.cache.get_str_with_ttl(key).await
+        }
+
+        async fn set_str_with_ttl(
+            &self,
+            key: &str,
+            value: &str,
+            ttl: usize,
+        ) -> Result<String, CacheError> {
+            self.call().await?;
+            self.cache.set_str_with_ttl(key, value, ttl).await
+        }
+
+        async fn set_str_if_absent_with_ttl(
+            &self,
+            key: &str,
+            value: &str,
+            ttl: usize,
+        ) -> Result<bool, CacheError> {
+            self.call().await?;
+            self.cache.set_str_if_absent_with_ttl(key, value, ttl).await
+        }
+
+        async fn delete(&self, key: &str) -> Result<(), CacheError> {
+            self.call().await?;
+            self.cache.delete(key).await
+        }
+    }
+
+    fn get_tiered_cache(
+        failure_threshold: u32,
+        reset_timeout: Duration,
+    ) -> TieredCacheService<MemoryCacheService, StubL2CacheService> {
+        TieredCacheService::new(MemoryCacheService::new(100, 10), StubL2CacheService::new())
+            .with_l2_circuit_breaker(failure_threshold, reset_timeout)
+    }
+
+    #[tokio::test]
+    async fn keep_l1_ttl_within_l2_ttl() {
+        let cases = [
+            ("expiring in L2 first", 3, 3),
+            ("capped by L1 max TTL", 100, 10),
+        ];
+
+        for (name, l2_ttl, max_l1_ttl) in cases {
+            let tiered_cache = get_tiered_cache(5, Duration::from_secs(30));
+            tiered_cache
+                .l2
+                .cache
+                .set_str_with_ttl(name, "value", l2_ttl)
+                .await
+                .unwrap();
+
+            assert_eq!(tiered_cache.get_str(name).await.unwrap(), "value", "{name}");
+
+            let (_, l1_ttl) = tiered_cache.l1.get_str_with_ttl(name).await.unwrap();
+
+            assert!(l1_ttl.is_some_and(|l1_ttl| l1_ttl <= max_l1_ttl), "{name}");
+        }
+    }
+
+    #[tokio::test]
+    async fn keep_writes_in_l1_when_l2_fails() {
+        let tiered_cache = get_tiered_cache(5, Duration::from_secs(30));
+        tiered_cache.l2.set_mode(L2Mode::Failing);
+
+        tiered_cache
+            .set_str_with_ttl("key", "value", 60)
+            .await
+            .unwrap();
+
+        assert_eq!(tiered_cache.get_str("key").await.unwrap(), "value");
+    }
+
+    #[tokio::test]
+    async fn skip_l2_while_circuit_is_open() {
+        let tiered_cache = get_tiered_cache(2, Duration::from_secs(30));
+        tiered_cache.l2.set_mode(L2Mode::Failing);
+
+        for _ in 0..2 {
+            assert!(tiered_cache.get_str("key").await.is_err());
+        }
+
+        let result = tiered_cache.get_str("key").await;
+
+        assert!(matches!(result, Err(CacheError::Unavailable(_))));
+        assert_eq!(tiered_cache.l2.calls.load(Ordering::SeqCst), 2);
+    }
+
+    #[tokio::test]
+    async fn probe_l2_again_after_cancelled_probe() {
+        let tiered_cache =
+            get_tiered_cache(1, Duration::ZERO).with_l2_timeout(Duration::from_secs(60));
+        tiered_cache.l2.set_mode(L2Mode::Failing);
+
+        assert!(tiered_cache.get_str("key").await.is_err());
+
+        // The caller gives up while the probe waits for L2
+        tiered_cache.l2.set_mode(L2Mode::Hanging);
+        let cancelled_get =
+            tokio::time::timeout(Duration::from_millis(10), tiered_cache.get_str("key")).await;
+
+        assert!(cancelled_get.is_err());
+
+        tiered_cache.l2.set_mode(L2Mode::Available);
+        tiered_cache
+            .l2
+            .cache
+            .set_str_with_ttl("key", "value", 60)
+            .await
+            .unwrap();
+
+        assert_eq!(tiered_cache.get_str("key").await.unwrap(), "value");
+    }
+
+    #[tokio::test]
+    async fn skip_l1_in_l2_cache() {
+        let tiered_cache = Arc::new(get_tiered_cache(5, Duration::from_secs(30)));
+        let l2_cache = tiered_cache.clone().into_l2();
+
+        l2_cache.set_str_with_ttl("key", "value", 60).await.unwrap();
+
+        assert!(
+            tiered_cache.l1.get_str("key").await.is_err(),
+            "not written to L1"
+        );
+
+        // Stale value of this instance, e.g. written before another instance updated the key
+        tiered_cache
+            .l1
+            .set_str_with_ttl("key", "stale value", 60)
+            .await
+            .unwrap();
+
+        assert_eq!(l2_cache.get_str("key").await.unwrap(), "value");
+    }
+}
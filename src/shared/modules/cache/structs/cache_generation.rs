@@ -0,0 +1,76 @@
+use std::sync::Arc;
+
+use crate::shared::modules::cache::errors::CacheError;
+use crate::shared::modules::cache::traits::cache_service::CacheService;
+use crate::shared::modules::db::utils::generate_id;
+
+const GENERATION_KEY_PREFIX: &str = "json-cache:generation";
+const GENERATION_TTL: usize = 24 * 60 * 60; // seconds
+
+/// Generation of the cached responses of a user. Responses are cached under the current
+/// generation, so bumping it after a mutation turns all of them into misses at once
+pub struct CacheGeneration<C>
+where
+    C: CacheService + Send + Sync,
+{
+    cache_service: Arc<C>,
+}
+
+impl<C> CacheGeneration<C>
+where
+    C: CacheService + Send + Sync,
+{
+    pub fn new(cache_service: Arc<C>) -> Self {
+        Self { cache_service }
+    }
+
+    /// `None` if the generation can't be read, responses must not be cached then
+    pub async fn get(&self, user_id: &str) -> Option<String> {
+        let key = format!("{GENERATION_KEY_PREFIX}:{user_id}");
+
+        match self.cache_service.get_str(&key).await {
+            Ok(generation) => Some(generation),
+            Err(CacheError::KeyNotFound(_)) => self.set_new(&key).await,
+            Err(err) => {
+                tracing::warn!("Cache generation of user '{user_id}' is failed to read: '{err}'");
+
+                None
+            }
+        }
+    }
+
+    pub async fn bump(&self, user_id: &str) {
+        let key = format!("{GENERATION_KEY_PREFIX}:{user_id}");
+
+        self.set_new(&key).await;
+    }
+
+    async fn set_new(&self, key: &str) -> Option<String> {
+        let generation = generate_id();
+        let set_result = self
+            .cache_service
+            .set_str_with_ttl(key, &generation, GENERATION_TTL)
+            .await;
+
+        match set_result {
+            Ok(_) => Some(generation),
+            Err(err) => {
+                tracing::warn!("Cache generation '{key}' is failed to set with err: '{err}'");
+
+                None
+            }
+        }
+    }
+}
+
+// Not derived, `C` itself does not have to be `Clone`
+impl<C> Clone for CacheGeneration<C>
+where
+    C: CacheService + Send + Sync,
+{
+    fn clone(&self) -> Self {
+        Self {
+            cache_service: self.cache_service.clone(),
+        }
+    }
+}
@@ -0,0 +1,120 @@
+use axum::body::Body;
+use axum::http::{HeaderMap, HeaderName, HeaderValue, StatusCode, header};
+use axum::response::Response;
+use base64::Engine;
+use base64::engine::general_purpose::STANDARD as BASE64;
+use serde::{Deserialize, Serialize};
+use std::time::Duration;
+
+use crate::shared::modules::cache::errors::CacheError;
+
+/// Bumped on every incompatible change of `CachedResponse`, entries of other versions are ignored
+pub const CACHED_RESPONSE_VERSION: u32 = 2;
+
+/// Only these headers are cached, the rest are specific to a single response (e.g. `x-request-id`)
+const CACHED_HEADERS: [HeaderName; 6] = [
+    header::CONTENT_TYPE,
+    header::CONTENT_LANGUAGE,
+    header::CACHE_CONTROL,
+    header::ETAG,
+    header::LAST_MODIFIED,
+    header::VARY,
+];
+
+/// Response stored by `JsonCacheMiddleware` along with the data needed
+/// to decide when it has to be refreshed
+#[derive(Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CachedResponse {
+    pub version: u32,
+    pub status: u16,
+    pub headers: Vec<(String, String)>,
+    pub body: String,      // base64
+    pub stored_at: i64,    // unix time in milliseconds
+    pub ttl: usize,        // seconds
+    pub compute_time: u64, // milliseconds it took to produce the response
+}
+
+impl CachedResponse {
+    pub fn new(
+        status: StatusCode,
+        headers: &HeaderMap,
+        body: &[u8],
+        ttl: usize,
+        compute_time: Duration,
+    ) -> Self {
+        let headers = CACHED_HEADERS
+            .iter()
+            .flat_map(|name| {
+                headers.get_all(name).iter().filter_map(|value| {
+                    let value = value.to_str().ok()?;
+
+                    Some((name.to_string(), value.to_string()))
+                })
+            })
+            .collect();
+
+        Self {
+            version: CACHED_RESPONSE_VERSION,
+            status: status.as_u16(),
+            headers,
+            body: BASE64.encode(body),
+            stored_at: chrono::Utc::now().timestamp_millis(),
+            ttl,
+            compute_time: compute_time.as_millis() as u64,
+        }
+    }
+
+    pub fn is_current_version(&self) -> bool {
+        self.version == CACHED_RESPONSE_VERSION
+    }
+
+    pub fn get_age(&self) -> Duration {
+        let age = chrono::Utc::now().timestamp_millis() - self.stored_at;
+
+        Duration::from_millis(age.max(0) as u64)
+    }
+
+    /// Probabilistic early expiration (XFetch): the closer the entry is to its expiration
+    /// and the longer it takes to compute, the more likely it is treated as stale.
+    /// `early_expiration_beta` of 0 disables early expiration, values above 1 favor earlier refreshes
+    pub fn is_stale(&self, early_expiration_beta: f64) -> bool {
+        let ttl = Duration::from_secs(self.ttl as u64);
+        let Some(remaining) = ttl.checked_sub(self.get_age()) else {
+            return true;
+        };
+
+        if early_expiration_beta <= 0.0 {
+            return false;
+        }
+
+        // 1 - f64() is in (0, 1], so ln() is finite
+        let random_factor = -(1.0 - fastrand::f64()).ln();
+        let early_expiration =
+            self.compute_time as f64 / 1000.0 * early_expiration_beta * random_factor;
+
+        early_expiration >= remaining.as_secs_f64()
+    }
+
+    pub fn to_response(&self) -> Result<Response, CacheError> {
+        let body = BASE64
+            .decode(&self.body)
+            .map_err(|err| CacheError::FailedToParseResponse(err.to_string()))?;
+        let status = StatusCode::from_u16(self.status)
+            .map_err(|err| CacheError::FailedToParseResponse(err.to_string()))?;
+
+        let mut response = Response::new(Body::from(body));
+        *response.status_mut() = status;
+
+        for (name, value) in &self.headers {
+            let name = HeaderName::try_from(name)
+                .map_err(|err| CacheError::FailedToParseResponse(err.to_string()))?;
+            let value = HeaderValue::try_from(value)
+                .map_err(|err| CacheError::FailedToParseResponse(err.to_string()))?;
+
+            response.headers_mut().append(name, value);
+        }
+
+        Ok(response)
+    }
+}
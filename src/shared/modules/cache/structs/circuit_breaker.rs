@@ -0,0 +1,212 @@
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+/// Stops calling a failing dependency after `failure_threshold` consecutive failures.
+/// After `reset_timeout` a single probe call is allowed, its result closes or reopens the circuit
+pub struct CircuitBreaker {
+    name: String,
+    failure_threshold: u32,
+    reset_timeout: Duration,
+    state: Mutex<CircuitBreakerState>,
+}
+
+#[derive(Default)]
+struct CircuitBreakerState {
+    consecutive_failures: u32,
+    opened_at: Option<Instant>,
+    is_probe_in_flight: bool,
+}
+
+/// Call allowed by the circuit, its result is recorded with `record_success` or `record_failure`.
+/// A probe dropped without a result, e.g. when its caller is cancelled, lets the next call probe
+pub struct CircuitBreakerPermit<'a> {
+    circuit_breaker: &'a CircuitBreaker,
+    is_probe: bool,
+}
+
+impl CircuitBreaker {
+    pub fn new(name: &str, failure_threshold: u32, reset_timeout: Duration) -> Self {
+        Self {
+            name: name.to_string(),
+            failure_threshold: failure_threshold.max(1),
+            reset_timeout,
+            state: Mutex::new(CircuitBreakerState::default()),
+        }
+    }
+
+    /// `None` while the circuit is open
+    pub fn try_call(&self) -> Option<CircuitBreakerPermit<'_>> {
+        let Ok(mut state) = self.state.lock() else {
+            return Some(self.get_permit(false));
+        };
+
+        let Some(opened_at) = state.opened_at else {
+            return Some(self.get_permit(false));
+        };
+
+        if state.is_probe_in_flight || opened_at.elapsed() < self.reset_timeout {
+            return None;
+        }
+
+        state.is_probe_in_flight = true;
+
+        Some(self.get_permit(true))
+    }
+
+    fn get_permit(&self, is_probe: bool) -> CircuitBreakerPermit<'_> {
+        CircuitBreakerPermit {
+            circuit_breaker: self,
+            is_probe,
+        }
+    }
+
+    fn record_success(&self) {
+        let Ok(mut state) = self.state.lock() else {
+            return;
+        };
+
+        if state.opened_at.is_some() {
+            tracing::info!("Circuit breaker '{}' is closed", self.name);
+        }
+
+        *state = CircuitBreakerState::default();
+    }
+
+    fn record_failure(&self, is_probe: bool) {
+        let Ok(mut state) = self.state.lock() else {
+            return;
+        };
+
+        state.consecutive_failures += 1;
+
+        let is_threshold_reached =
+            state.opened_at.is_none() && state.consecutive_failures >= self.failure_threshold;
+
+        if is_probe || is_threshold_reached {
+            if is_threshold_reached {
+                tracing::warn!(
+                    "Circuit breaker '{}' is opened after {} consecutive failures",
+                    self.name,
+                    state.consecutive_failures
+                );
+            }
+
+            state.opened_at = Some(Instant::now());
+            state.is_probe_in_flight = false;
+        }
+    }
+
+    fn release_probe(&self) {
+        if let Ok(mut state) = self.state.lock() {
+            state.is_probe_in_flight = false;
+        }
+    }
+}
+
+impl CircuitBreakerPermit<'_> {
+    pub fn record_success(mut self) {
+        self.is_probe = false;
+        self.circuit_breaker.record_success();
+    }
+
+    pub fn record_failure(mut self) {
+        let is_probe = std::mem::take(&mut self.is_probe);
+
+        self.circuit_breaker.record_failure(is_probe);
+    }
+}
+
+impl Drop for CircuitBreakerPermit<'_> {
+    fn drop(&mut self) {
+        if self.is_probe {
+            self.circuit_breaker.release_probe();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const LONG_RESET_TIMEOUT: Duration = Duration::from_secs(60);
+    const SHORT_RESET_TIMEOUT: Duration = Duration::from_millis(50);
+
+    fn open(circuit_breaker: &CircuitBreaker) {
+        while let Some(permit) = circuit_breaker.try_call() {
+            permit.record_failure();
+        }
+    }
+
+    #[test]
+    fn open_after_consecutive_failures() {
+        let circuit_breaker = CircuitBreaker::new("test", 3, LONG_RESET_TIMEOUT);
+
+        circuit_breaker.try_call().unwrap().record_failure();
+        circuit_breaker.try_call().unwrap().record_failure();
+        circuit_breaker.try_call().unwrap().record_success();
+        circuit_breaker.try_call().unwrap().record_failure();
+        circuit_breaker.try_call().unwrap().record_failure();
+
+        assert!(
+            circuit_breaker.try_call().is_some(),
+            "success resets failures"
+        );
+
+        circuit_breaker.try_call().unwrap().record_failure();
+
+        assert!(circuit_breaker.try_call().is_none());
+    }
+
+    #[test]
+    fn close_after_successful_probe() {
+        let circuit_breaker = CircuitBreaker::new("test", 1, SHORT_RESET_TIMEOUT);
+        open(&circuit_breaker);
+
+        assert!(circuit_breaker.try_call().is_none(), "open");
+
+        std::thread::sleep(SHORT_RESET_TIMEOUT);
+        let probe = circuit_breaker.try_call().expect("half-open");
+
+        assert!(circuit_breaker.try_call().is_none(), "one probe at a time");
+
+        probe.record_success();
+
+        assert!(circuit_breaker.try_call().is_some(), "closed");
+        assert!(circuit_breaker.try_call().is_some(), "closed");
+    }
+
+    #[test]
+    fn reopen_after_failed_probe() {
+        let circuit_breaker = CircuitBreaker::new("test", 3, SHORT_RESET_TIMEOUT);
+        open(&circuit_breaker);
+
+        std::thread::sleep(SHORT_RESET_TIMEOUT);
+        circuit_breaker
+            .try_call()
+            .expect("half-open")
+            .record_failure();
+
+        assert!(circuit_breaker.try_call().is_none(), "reopened");
+
+        std::thread::sleep(SHORT_RESET_TIMEOUT);
+
+        assert!(circuit_breaker.try_call().is_some(), "half-open again");
+    }
+
+    #[test]
+    fn allow_next_probe_after_cancelled_probe() {
+        let circuit_breaker = CircuitBreaker::new("test", 1, SHORT_RESET_TIMEOUT);
+        open(&circuit_breaker);
+
+        std::thread::sleep(SHORT_RESET_TIMEOUT);
+        let probe = circuit_breaker.try_call().expect("half-open");
+        drop(probe);
+
+        circuit_breaker
+            .try_call()
+            .expect("next probe")
+            .record_success();
+
+        assert!(circuit_breaker.try_call().is_some(), "closed");
+    }
+}
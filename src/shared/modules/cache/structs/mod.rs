@@ -0,0 +1,4 @@
+pub mod cache_generation;
+pub mod cached_response;
+pub mod circuit_breaker;
+pub mod single_flight;
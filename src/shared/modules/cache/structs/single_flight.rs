@@ -0,0 +1,122 @@
+use futures_util::future::{BoxFuture, FutureExt, Shared};
+use std::collections::HashMap;
+use std::future::Future;
+use std::sync::Mutex;
+
+/// Coalesces concurrent calls with the same key: only the first caller runs the future,
+/// the rest wait for its result
+pub struct SingleFlight<T>
+where
+    T: Clone + Send + Sync + 'static,
+{
+    calls: Mutex<HashMap<String, Shared<BoxFuture<'static, T>>>>,
+}
+
+impl<T> SingleFlight<T>
+where
+    T: Clone + Send + Sync + 'static,
+{
+    pub fn new() -> Self {
+        Self {
+            calls: Mutex::new(HashMap::new()),
+        }
+    }
+
+    /// `get_future` is called only if there is no call in flight for the key
+    pub async fn run<F, Fut>(&self, key: &str, get_future: F) -> T
+    where
+        F: FnOnce() -> Fut,
+        Fut: Future<Output = T> + Send + 'static,
+    {
+        let call = {
+            let mut calls = self.calls.lock().unwrap_or_else(|err| err.into_inner());
+
+            calls
+                .entry(key.to_string())
+                .or_insert_with(|| get_future().boxed().shared())
+                .clone()
+        };
+
+        let result = call.clone().await;
+
+        let mut calls = self.calls.lock().unwrap_or_else(|err| err.into_inner());
+        // The key might be already taken by a newer call
+        if calls
+            .get(key)
+            .is_some_and(|current_call| current_call.ptr_eq(&call))
+        {
+            calls.remove(key);
+        }
+
+        result
+    }
+}
+
+impl<T> Default for SingleFlight<T>
+where
+    T: Clone + Send + Sync + 'static,
+{
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::Arc;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use tokio::sync::Notify;
+
+    #[tokio::test]
+    async fn run_concurrent_calls_once() {
+        let single_flight = SingleFlight::new();
+        let runs = Arc::new(AtomicUsize::new(0));
+        let release = Arc::new(Notify::new());
+
+        let get_call = || {
+            let runs = runs.clone();
+            let release = release.clone();
+
+            single_flight.run("key", move || async move {
+                runs.fetch_add(1, Ordering::SeqCst);
+                release.notified().await;
+
+                "value".to_string()
+            })
+        };
+        let first_call = get_call();
+        let second_call = get_call();
+        let release_calls = async {
+            tokio::task::yield_now().await;
+            release.notify_one();
+        };
+
+        let (first_result, second_result, _) = tokio::join!(first_call, second_call, release_calls);
+
+        assert_eq!(first_result, "value");
+        assert_eq!(second_result, "value");
+        assert_eq!(runs.load(Ordering::SeqCst), 1);
+    }
+
+    #[tokio::test]
+    async fn run_calls_of_other_keys_separately() {
+        let single_flight = SingleFlight::new();
+
+        let (first_result, second_result) = tokio::join!(
+            single_flight.run("first", || async { 1 }),
+            single_flight.run("second", || async { 2 }),
+        );
+
+        assert_eq!((first_result, second_result), (1, 2));
+    }
+
+    #[tokio::test]
+    async fn run_again_once_call_is_done() {
+        let single_flight = SingleFlight::new();
+
+        assert_eq!(single_flight.run("key", || async { 1 }).await, 1);
+        assert_eq!(single_flight.run("key", || async { 2 }).await, 2);
+        assert!(single_flight.calls.lock().unwrap().is_empty());
+    }
+}
@@ -1,27 +1,19 @@
 use async_trait::async_trait;
-use std::future::Future;
 
 use crate::shared::modules::cache::errors::CacheError;
 
+/// Only the string and bytes primitives have to be implemented,
+/// the typed methods (de)serialize values as JSON on top of them
 #[async_trait]
 pub trait CacheService {
-    async fn get_str(&self, key: &str) -> Result<String, CacheError>;
-
-    async fn get<T>(&self, key: &str) -> Result<T, CacheError>
-    where
-        T: serde::de::DeserializeOwned;
+    fn get_default_ttl(&self) -> usize;
 
-    async fn set<T>(&self, key: &str, value: &T) -> Result<String, CacheError>
-    where
-        T: serde::Serialize + Send + Sync;
-
-    async fn set_with_ttl<T>(&self, key: &str, value: &T, ttl: usize) -> Result<String, CacheError>
-    where
-        T: serde::Serialize + Send + Sync;
-
-    async fn set_str(&self, key: &str, value: &str) -> Result<String, CacheError>;
+    /// Returns `CacheError::KeyNotFound` if there is no value for the key
+    async fn get_str(&self, key: &str) -> Result<String, CacheError>;
 
-    async fn set_bytes(&self, key: &str, value: &[u8]) -> Result<String, CacheError>;
+    /// Same as `get_str`, along with the remaining TTL of the value in seconds,
+    /// `None` if the value does not expire
+    async fn get_str_with_ttl(&self, key: &str) -> Result<(String, Option<usize>), CacheError>;
 
     async fn set_str_with_ttl(
         &self,
@@ -30,16 +22,55 @@ pub trait CacheService {
         ttl: usize,
     ) -> Result<String, CacheError>;
 
-    async fn set_bytes_with_ttl(
+    /// Sets the value only if there is no value for the key, returns `false` if there is one
+    async fn set_str_if_absent_with_ttl(
         &self,
         key: &str,
-        value: &[u8],
+        value: &str,
         ttl: usize,
-    ) -> Result<String, CacheError>;
+    ) -> Result<bool, CacheError>;
+
+    async fn delete(&self, key: &str) -> Result<(), CacheError>;
 
-    async fn wrap_fn<T, F, Fut>(&self, func: F, cache_key: &str) -> Result<T, CacheError>
+    async fn get<T>(&self, key: &str) -> Result<T, CacheError>
+    where
+        T: serde::de::DeserializeOwned,
+    {
+        let cached_str = self.get_str(key).await?;
+
+        serde_json::from_str(&cached_str)
+            .map_err(|err| CacheError::FailedToParseResponse(err.to_string()))
+    }
+
+    async fn set_with_ttl<T>(&self, key: &str, value: &T, ttl: usize) -> Result<String, CacheError>
+    where
+        T: serde::Serialize + Send + Sync,
+    {
+        let serialized_value = serde_json::to_string(value).map_err(|err| {
+            CacheError::Unknown(format!(
+                "Failed to serialize value for key '{key}' to set in cache, err: '{err}'"
+            ))
+        })?;
+
+        self.set_str_with_ttl(key, &serialized_value, ttl).await
+    }
+
+    async fn set_if_absent_with_ttl<T>(
+        &self,
+        key: &str,
+        value: &T,
+        ttl: usize,
+    ) -> Result<bool, CacheError>
     where
-        T: serde::Serialize + serde::de::DeserializeOwned + Send + Sync,
-        F: FnOnce() -> Fut + Send + Sync,
-        Fut: Future<Output = Result<T, CacheError>> + Send + Sync;
+        T: serde::Serialize + Send + Sync,
+    {
+        let serialized_value = serde_json::to_string(value).map_err(|err| {
+            CacheError::Unknown(format!(
+                "Failed to serialize value for key '{key}' to set in cache, err: '{err}'"
+            ))
+        })?;
+
+        self.set_str_if_absent_with_ttl(key, &serialized_value, ttl)
+            .await
+    }
 }
@@ -0,0 +1,162 @@
+use axum::body::Body;
+use axum::http::{HeaderValue, StatusCode, header};
+use axum::response::Response;
+use sha2::{Digest, Sha256};
+
+/// Strong ETag of a response body
+pub fn get_etag(body: &[u8]) -> HeaderValue {
+    let hash = Sha256::digest(body);
+    let hash_hex: String = hash[..16]
+        .iter()
+        .map(|byte| format!("{byte:02x}"))
+        .collect();
+
+    HeaderValue::try_from(format!("\"{hash_hex}\"")).expect("hex ETag is a valid header value")
+}
+
+/// `If-None-Match` uses the weak comparison, so `W/` prefixes are ignored
+pub fn is_etag_matching(if_none_match: &HeaderValue, etag: &HeaderValue) -> bool {
+    let Ok(if_none_match) = if_none_match.to_str() else {
+        return false;
+    };
+    let Ok(etag) = etag.to_str() else {
+        return false;
+    };
+
+    if if_none_match.trim() == "*" {
+        return true;
+    }
+
+    let etag = etag.trim_start_matches("W/");
+
+    if_none_match
+        .split(',')
+        .map(|value| value.trim().trim_start_matches("W/"))
+        .any(|value| value == etag)
+}
+
+/// Returns `304 Not Modified` if the response ETag matches `If-None-Match`,
+/// otherwise the response as is
+pub fn get_conditional_response(
+    response: Response,
+    if_none_match: Option<&HeaderValue>,
+) -> Response {
+    let Some(if_none_match) = if_none_match else {
+        return response;
+    };
+
+    let is_not_modified = response.status().is_success()
+        && response
+            .headers()
+            .get(header::ETAG)
+            .is_some_and(|etag| is_etag_matching(if_none_match, etag));
+
+    if !is_not_modified {
+        return response;
+    }
+
+    let (mut parts, _) = response.into_parts();
+    parts.status = StatusCode::NOT_MODIFIED;
+    parts.headers.remove(header::CONTENT_LENGTH);
+    parts.headers.remove(header::CONTENT_TYPE);
+
+    Response::from_parts(parts, Body::empty())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn get_etag() {
+        let etag = super::get_etag(b"body");
+
+        assert_eq!(etag, super::get_etag(b"body"));
+        assert_ne!(etag, super::get_etag(b"other body"));
+        // Quoted 16 bytes of the hash in hex
+        assert_eq!(etag.len(), 34);
+        assert!(etag.to_str().unwrap().starts_with('"'));
+    }
+
+    #[test]
+    fn is_etag_matching() {
+        let etag = HeaderValue::from_static("\"abc\"");
+        let cases = [
+            ("same", "\"abc\"", true),
+            ("any", " * ", true),
+            ("weak", "W/\"abc\"", true),
+            ("in a list", "\"xyz\", W/\"abc\"", true),
+            ("other", "\"xyz\"", false),
+            ("unquoted", "abc", false),
+        ];
+
+        for (name, if_none_match, expected) in cases {
+            let if_none_match = HeaderValue::from_static(if_none_match);
+
+            assert_eq!(
+                super::is_etag_matching(&if_none_match, &etag),
+                expected,
+                "{name}"
+            );
+        }
+    }
+
+    #[test]
+    fn get_conditional_response() {
+        let etag = super::get_etag(b"body");
+        let get_response = |status: StatusCode| {
+            Response::builder()
+                .status(status)
+                .header(header::ETAG, etag.clone())
+                .header(header::CONTENT_TYPE, "application/json")
+                .header(header::CONTENT_LENGTH, 4)
+                .body(Body::from("body"))
+                .unwrap()
+        };
+        let other_etag = HeaderValue::from_static("\"other\"");
+        let cases = [
+            (
+                "matching",
+                StatusCode::OK,
+                Some(&etag),
+                StatusCode::NOT_MODIFIED,
+            ),
+            (
+                "not matching",
+                StatusCode::OK,
+                Some(&other_etag),
+                StatusCode::OK,
+            ),
+            (
+                "without If-None-Match",
+                StatusCode::OK,
+                None,
+                StatusCode::OK,
+            ),
+            (
+                "not successful",
+                StatusCode::NOT_FOUND,
+                Some(&etag),
+                StatusCode::NOT_FOUND,
+            ),
+        ];
+
+        for (name, status, if_none_match, expected_status) in cases {
+            let response = super::get_conditional_response(get_response(status), if_none_match);
+
+            assert_eq!(response.status(), expected_status, "{name}");
+
+            if expected_status == StatusCode::NOT_MODIFIED {
+                assert_eq!(response.headers().get(header::ETAG), Some(&etag), "{name}");
+                assert!(
+                    response.headers().get(header::CONTENT_LENGTH).is_none(),
+                    "{name}"
+                );
+                assert!(
+                    response.headers().get(header::CONTENT_TYPE).is_none(),
+                    "{name}"
+                );
+            }
+        }
+    }
+}
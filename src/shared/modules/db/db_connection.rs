@@ -0,0 +1,81 @@
+use async_trait::async_trait;
+use futures_util::StreamExt;
+use futures_util::stream::BoxStream;
+use sea_orm::{
+    ConnectionTrait, DatabaseConnection, DatabaseTransaction, DbBackend, DbErr, ExecResult,
+    QueryResult, Statement, StreamTrait, TransactionTrait,
+};
+use std::future::Future;
+use std::pin::Pin;
+use std::sync::Arc;
+
+/// Connection repositories run their queries on: the pool or a transaction of a unit of work
+#[derive(Clone)]
+pub enum DbConnection {
+    Pool(Arc<DatabaseConnection>),
+    Transaction(Arc<DatabaseTransaction>),
+}
+
+impl DbConnection {
+    /// Begins a transaction, or a savepoint if the connection is a transaction already
+    pub async fn begin(&self) -> Result<DatabaseTransaction, DbErr> {
+        match self {
+            Self::Pool(connection) => connection.begin().await,
+            Self::Transaction(transaction) => transaction.begin().await,
+        }
+    }
+}
+
+#[async_trait]
+impl ConnectionTrait for DbConnection {
+    fn get_database_backend(&self) -> DbBackend {
+        match self {
+            Self::Pool(connection) => connection.get_database_backend(),
+            Self::Transaction(transaction) => transaction.get_database_backend(),
+        }
+    }
+
+    async fn execute(&self, stmt: Statement) -> Result<ExecResult, DbErr> {
+        match self {
+            Self::Pool(connection) => connection.execute(stmt).await,
+            Self::Transaction(transaction) => transaction.execute(stmt).await,
+        }
+    }
+
+    async fn execute_unprepared(&self, sql: &str) -> Result<ExecResult, DbErr> {
+        match self {
+            Self::Pool(connection) => connection.execute_unprepared(sql).await,
+            Self::Transaction(transaction) => transaction.execute_unprepared(sql).await,
+        }
+    }
+
+    async fn query_one(&self, stmt: Statement) -> Result<Option<QueryResult>, DbErr> {
+        match self {
+            Self::Pool(connection) => connection.query_one(stmt).await,
+            Self::Transaction(transaction) => transaction.query_one(stmt).await,
+        }
+    }
+
+    async fn query_all(&self, stmt: Statement) -> Result<Vec<QueryResult>, DbErr> {
+        match self {
+            Self::Pool(connection) => connection.query_all(stmt).await,
+            Self::Transaction(transaction) => transaction.query_all(stmt).await,
+        }
+    }
+}
+
+impl StreamTrait for DbConnection {
+    type Stream<'a> = BoxStream<'a, Result<QueryResult, DbErr>>;
+
+    fn stream<'a>(
+        &'a self,
+        stmt: Statement,
+    ) -> Pin<Box<dyn Future<Output = Result<Self::Stream<'a>, DbErr>> + 'a + Send>> {
+        Box::pin(async move {
+            match self {
+                Self::Pool(connection) => Ok(connection.stream(stmt).await?.boxed()),
+                Self::Transaction(transaction) => Ok(transaction.stream(stmt).await?.boxed()),
+            }
+        })
+    }
+}
@@ -0,0 +1,57 @@
+use sea_orm::entity::prelude::*;
+use serde::{Deserialize, Serialize};
+
+#[derive(Clone, Debug, PartialEq, DeriveEntityModel, Eq, Serialize, Deserialize)]
+#[sea_orm(schema_name = "public", table_name = "AuditLog")]
+pub struct Model {
+    #[sea_orm(primary_key, auto_increment = false, column_type = "Text")]
+    pub id: String,
+    #[sea_orm(column_name = "entityType")]
+    pub entity_type: AuditEntityType,
+    #[sea_orm(column_name = "entityId", column_type = "Text")]
+    pub entity_id: String,
+    #[sea_orm(column_name = "ownerId", column_type = "Text", nullable)]
+    pub owner_id: Option<String>,
+    pub action: AuditAction,
+    #[sea_orm(column_name = "actorId", column_type = "Text", nullable)]
+    pub actor_id: Option<String>,
+    #[sea_orm(column_name = "actorIsAdmin")]
+    pub actor_is_admin: bool,
+    #[sea_orm(column_name = "impersonatedBy", column_type = "Text", nullable)]
+    pub impersonated_by: Option<String>,
+    #[sea_orm(column_name = "requestId", column_type = "Text", nullable)]
+    pub request_id: Option<String>,
+    #[sea_orm(column_type = "JsonBinary")]
+    pub changes: Json,
+    #[sea_orm(column_name = "createdAt")]
+    pub created_at: DateTimeWithTimeZone,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, EnumIter, DeriveActiveEnum, Serialize, Deserialize)]
+#[sea_orm(rs_type = "String", db_type = "Text")]
+pub enum AuditEntityType {
+    #[sea_orm(string_value = "CUSTOMER")]
+    Customer,
+    #[sea_orm(string_value = "EXPENSE")]
+    Expense,
+    #[sea_orm(string_value = "REGULAR_PAYMENT")]
+    RegularPayment,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, EnumIter, DeriveActiveEnum, Serialize, Deserialize)]
+#[sea_orm(rs_type = "String", db_type = "Text")]
+pub enum AuditAction {
+    #[sea_orm(string_value = "CREATE")]
+    Create,
+    #[sea_orm(string_value = "UPDATE")]
+    Update,
+    #[sea_orm(string_value = "DELETE")]
+    Delete,
+    #[sea_orm(string_value = "RESTORE")]
+    Restore,
+}
+
+#[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+pub enum Relation {}
+
+impl ActiveModelBehavior for ActiveModel {}
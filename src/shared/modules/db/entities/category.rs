@@ -0,0 +1,72 @@
+use sea_orm::entity::prelude::*;
+use serde::{Deserialize, Serialize};
+
+#[derive(Clone, Debug, PartialEq, DeriveEntityModel, Eq, Serialize, Deserialize)]
+#[sea_orm(schema_name = "public", table_name = "Category")]
+pub struct Model {
+    #[sea_orm(primary_key, auto_increment = false, column_type = "Text")]
+    pub id: String,
+    #[sea_orm(column_name = "customerId", column_type = "Text", nullable)]
+    pub customer_id: Option<String>,
+    #[sea_orm(column_name = "parentId", column_type = "Text", nullable)]
+    pub parent_id: Option<String>,
+    #[sea_orm(column_type = "Text", nullable, unique)]
+    pub code: Option<String>,
+    #[sea_orm(column_type = "Text")]
+    pub name: String,
+    #[sea_orm(column_type = "Text", nullable)]
+    pub icon: Option<String>,
+    #[sea_orm(column_type = "Text", nullable)]
+    pub color: Option<String>,
+    #[sea_orm(column_name = "archivedAt")]
+    pub archived_at: Option<DateTimeWithTimeZone>,
+    #[sea_orm(column_name = "createdAt")]
+    pub created_at: DateTimeWithTimeZone,
+    #[sea_orm(column_name = "updatedAt")]
+    pub updated_at: DateTimeWithTimeZone,
+    pub version: i64,
+}
+
+#[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+pub enum Relation {
+    #[sea_orm(
+        belongs_to = "super::customer::Entity",
+        from = "Column::CustomerId",
+        to = "super::customer::Column::Id",
+        on_update = "Cascade",
+        on_delete = "Restrict"
+    )]
+    Customer,
+    #[sea_orm(
+        belongs_to = "Entity",
+        from = "Column::ParentId",
+        to = "Column::Id",
+        on_update = "Cascade",
+        on_delete = "Restrict"
+    )]
+    SelfRef,
+    #[sea_orm(has_many = "super::category_rule::Entity")]
+    CategoryRule,
+    #[sea_orm(has_many = "super::expense::Entity")]
+    Expense,
+}
+
+impl Related<super::customer::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::Customer.def()
+    }
+}
+
+impl Related<super::category_rule::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::CategoryRule.def()
+    }
+}
+
+impl Related<super::expense::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::Expense.def()
+    }
+}
+
+impl ActiveModelBehavior for ActiveModel {}
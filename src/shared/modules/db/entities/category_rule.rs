@@ -0,0 +1,74 @@
+use sea_orm::entity::prelude::*;
+use serde::{Deserialize, Serialize};
+
+#[derive(Clone, Debug, PartialEq, DeriveEntityModel, Eq, Serialize, Deserialize)]
+#[sea_orm(schema_name = "public", table_name = "CategoryRule")]
+pub struct Model {
+    #[sea_orm(primary_key, auto_increment = false, column_type = "Text")]
+    pub id: String,
+    #[sea_orm(column_name = "customerId", column_type = "Text")]
+    pub customer_id: String,
+    #[sea_orm(column_name = "categoryId", column_type = "Text")]
+    pub category_id: String,
+    #[sea_orm(column_type = "Text")]
+    pub name: String,
+    pub priority: i64,
+    #[sea_orm(column_name = "isEnabled")]
+    pub is_enabled: bool,
+    #[sea_orm(column_name = "noteContains", column_type = "Text", nullable)]
+    pub note_contains: Option<String>,
+    #[sea_orm(column_name = "notePattern", column_type = "Text", nullable)]
+    pub note_pattern: Option<String>,
+    #[sea_orm(
+        column_name = "amountMin",
+        column_type = "Decimal(Some((10, 2)))",
+        nullable
+    )]
+    pub amount_min: Option<Decimal>,
+    #[sea_orm(
+        column_name = "amountMax",
+        column_type = "Decimal(Some((10, 2)))",
+        nullable
+    )]
+    pub amount_max: Option<Decimal>,
+    pub weekdays: Option<i64>,
+    #[sea_orm(column_name = "createdAt")]
+    pub created_at: DateTimeWithTimeZone,
+    #[sea_orm(column_name = "updatedAt")]
+    pub updated_at: DateTimeWithTimeZone,
+    pub version: i64,
+}
+
+#[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+pub enum Relation {
+    #[sea_orm(
+        belongs_to = "super::category::Entity",
+        from = "Column::CategoryId",
+        to = "super::category::Column::Id",
+        on_update = "Cascade",
+        on_delete = "Restrict"
+    )]
+    Category,
+    #[sea_orm(
+        belongs_to = "super::customer::Entity",
+        from = "Column::CustomerId",
+        to = "super::customer::Column::Id",
+        on_update = "Cascade",
+        on_delete = "Restrict"
+    )]
+    Customer,
+}
+
+impl Related<super::category::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::Category.def()
+    }
+}
+
+impl Related<super::customer::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::Customer.def()
+    }
+}
+
+impl ActiveModelBehavior for ActiveModel {}
@@ -9,15 +9,15 @@ use serde::{Deserialize, Serialize};
 pub struct Model {
     #[sea_orm(primary_key, auto_increment = false, column_type = "Text")]
     pub id: String,
-    #[sea_orm(column_name = "userId", column_type = "Text", unique)]
+    #[sea_orm(column_name = "userId", column_type = "Text")]
     pub user_id: String,
     #[sea_orm(column_name = "firstName", column_type = "Text")]
     pub first_name: String,
     #[sea_orm(column_name = "lastName", column_type = "Text")]
     pub last_name: String,
-    #[sea_orm(column_type = "Text", unique)]
+    #[sea_orm(column_type = "Text")]
     pub email: String,
-    #[sea_orm(column_type = "Text", nullable, unique)]
+    #[sea_orm(column_type = "Text", nullable)]
     pub phone: Option<String>,
     pub birthdate: DateTimeWithTimeZone,
     pub sex: Sex,
@@ -25,16 +25,35 @@ pub struct Model {
     pub created_at: DateTimeWithTimeZone,
     #[sea_orm(column_name = "updatedAt")]
     pub updated_at: DateTimeWithTimeZone,
+    pub version: i64,
+    #[sea_orm(column_name = "deletedAt")]
+    pub deleted_at: Option<DateTimeWithTimeZone>,
 }
 
 #[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
 pub enum Relation {
+    #[sea_orm(has_many = "super::category::Entity")]
+    Category,
+    #[sea_orm(has_many = "super::category_rule::Entity")]
+    CategoryRule,
     #[sea_orm(has_many = "super::expense::Entity")]
     Expense,
     #[sea_orm(has_many = "super::regular_payment::Entity")]
     RegularPayment,
 }
 
+impl Related<super::category::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::Category.def()
+    }
+}
+
+impl Related<super::category_rule::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::CategoryRule.def()
+    }
+}
+
 impl Related<super::expense::Entity> for Entity {
     fn to() -> RelationDef {
         Relation::Expense.def()
@@ -0,0 +1,44 @@
+use sea_orm::entity::prelude::*;
+use serde::{Deserialize, Serialize};
+
+#[derive(Clone, Debug, PartialEq, DeriveEntityModel, Eq, Serialize, Deserialize)]
+#[sea_orm(schema_name = "public", table_name = "ErasureRequest")]
+pub struct Model {
+    #[sea_orm(primary_key, auto_increment = false, column_type = "Text")]
+    pub id: String,
+    #[sea_orm(column_name = "customerId", column_type = "Text")]
+    pub customer_id: String,
+    pub status: ErasureStatus,
+    #[sea_orm(column_name = "requestedBy", column_type = "Text", nullable)]
+    pub requested_by: Option<String>,
+    #[sea_orm(column_name = "requestId", column_type = "Text", nullable)]
+    pub request_id: Option<String>,
+    #[sea_orm(column_type = "Text", nullable)]
+    pub error: Option<String>,
+    #[sea_orm(column_name = "erasedExpenses")]
+    pub erased_expenses: i64,
+    #[sea_orm(column_name = "erasedRegularPayments")]
+    pub erased_regular_payments: i64,
+    #[sea_orm(column_name = "anonymizedAuditLogs")]
+    pub anonymized_audit_logs: i64,
+    #[sea_orm(column_name = "createdAt")]
+    pub created_at: DateTimeWithTimeZone,
+    #[sea_orm(column_name = "completedAt")]
+    pub completed_at: Option<DateTimeWithTimeZone>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, EnumIter, DeriveActiveEnum, Serialize, Deserialize)]
+#[sea_orm(rs_type = "String", db_type = "Text")]
+pub enum ErasureStatus {
+    #[sea_orm(string_value = "PENDING")]
+    Pending,
+    #[sea_orm(string_value = "COMPLETED")]
+    Completed,
+    #[sea_orm(string_value = "FAILED")]
+    Failed,
+}
+
+#[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+pub enum Relation {}
+
+impl ActiveModelBehavior for ActiveModel {}
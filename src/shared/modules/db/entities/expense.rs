@@ -1,6 +1,5 @@
 //! `SeaORM` Entity, @generated by sea-orm-codegen 1.1.14
 
-use super::sea_orm_active_enums::ExpenseCategory;
 use sea_orm::entity::prelude::*;
 use serde::{Deserialize, Serialize};
 
@@ -14,15 +13,31 @@ pub struct Model {
     #[sea_orm(column_type = "Decimal(Some((10, 2)))")]
     pub amount: Decimal,
     pub date: DateTimeWithTimeZone,
-    pub category: ExpenseCategory,
+    #[sea_orm(column_name = "categoryId", column_type = "Text")]
+    pub category_id: String,
+    #[sea_orm(column_type = "Text", nullable)]
+    pub note: Option<String>,
     #[sea_orm(column_name = "createdAt")]
     pub created_at: DateTimeWithTimeZone,
     #[sea_orm(column_name = "updatedAt")]
     pub updated_at: DateTimeWithTimeZone,
+    pub version: i64,
+    #[sea_orm(column_name = "deletedAt")]
+    pub deleted_at: Option<DateTimeWithTimeZone>,
+    #[sea_orm(column_name = "externalId", column_type = "Text", nullable)]
+    pub external_id: Option<String>,
 }
 
 #[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
 pub enum Relation {
+    #[sea_orm(
+        belongs_to = "super::category::Entity",
+        from = "Column::CategoryId",
+        to = "super::category::Column::Id",
+        on_update = "Cascade",
+        on_delete = "Restrict"
+    )]
+    Category,
     #[sea_orm(
         belongs_to = "super::customer::Entity",
         from = "Column::CustomerId",
@@ -33,6 +48,12 @@ pub enum Relation {
     Customer,
 }
 
+impl Related<super::category::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::Category.def()
+    }
+}
+
 impl Related<super::customer::Entity> for Entity {
     fn to() -> RelationDef {
         Relation::Customer.def()
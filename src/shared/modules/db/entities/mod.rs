@@ -2,7 +2,12 @@
 
 pub mod prelude;
 
+pub mod audit_log;
+pub mod category;
+pub mod category_rule;
 pub mod customer;
+pub mod erasure_request;
 pub mod expense;
 pub mod regular_payment;
 pub mod sea_orm_active_enums;
+pub mod token_revocation;
@@ -1,5 +1,10 @@
 //! `SeaORM` Entity, @generated by sea-orm-codegen 1.1.14
 
+pub use super::audit_log::Entity as AuditLog;
+pub use super::category::Entity as Category;
+pub use super::category_rule::Entity as CategoryRule;
 pub use super::customer::Entity as Customer;
+pub use super::erasure_request::Entity as ErasureRequest;
 pub use super::expense::Entity as Expense;
 pub use super::regular_payment::Entity as RegularPayment;
+pub use super::token_revocation::Entity as TokenRevocation;
@@ -17,9 +17,12 @@ pub struct Model {
     pub created_at: DateTimeWithTimeZone,
     #[sea_orm(column_name = "updatedAt")]
     pub updated_at: DateTimeWithTimeZone,
+    pub version: i64,
     pub category: ExpenseCategory,
     #[sea_orm(column_name = "dateOfCharge")]
     pub date_of_charge: DateTimeWithTimeZone,
+    #[sea_orm(column_name = "deletedAt")]
+    pub deleted_at: Option<DateTimeWithTimeZone>,
 }
 
 #[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
@@ -1,2 +1,5 @@
+pub mod db_connection;
 pub mod entities;
-pub mod utils;
\ No newline at end of file
+pub mod structs;
+pub mod unit_of_work;
+pub mod utils;
@@ -0,0 +1,45 @@
+use aide::OperationInput;
+use aide::generate::GenContext;
+use aide::openapi::Operation;
+use axum::extract::{FromRequestParts, Path};
+use axum::http::request::Parts;
+use schemars::JsonSchema;
+use serde::Deserialize;
+use uuid::Uuid;
+
+use crate::shared::errors::http_error::HttpError;
+
+/// `{id}` path param, rejected with 400 before reaching the DB unless it is a UUID
+pub struct IdPath(pub String);
+
+/// Used only to document the param in OpenAPI
+#[allow(dead_code)]
+#[derive(Deserialize, JsonSchema)]
+struct IdPathParams {
+    /// Time-ordered UUID (v7) generated by the server
+    id: Uuid,
+}
+
+impl<S> FromRequestParts<S> for IdPath
+where
+    S: Send + Sync,
+{
+    type Rejection = HttpError;
+
+    async fn from_request_parts(parts: &mut Parts, state: &S) -> Result<Self, Self::Rejection> {
+        let Path(id) = Path::<String>::from_request_parts(parts, state)
+            .await
+            .map_err(|err| HttpError::BadRequest(err.body_text()))?;
+
+        Uuid::try_parse(&id)
+            .map_err(|_| HttpError::BadRequest(format!("Invalid id '{id}', expected a UUID")))?;
+
+        Ok(Self(id))
+    }
+}
+
+impl OperationInput for IdPath {
+    fn operation_input(ctx: &mut GenContext, operation: &mut Operation) {
+        Path::<IdPathParams>::operation_input(ctx, operation);
+    }
+}
@@ -0,0 +1,186 @@
+use sea_orm::{DatabaseConnection, DbErr, TransactionTrait};
+use std::future::Future;
+use std::sync::Arc;
+use std::time::Duration;
+
+use crate::shared::errors::http_error::HttpError;
+use crate::shared::modules::db::db_connection::DbConnection;
+
+const MAX_RETRIES: u32 = 3;
+const BASE_RETRY_DELAY: Duration = Duration::from_millis(10);
+
+/// Runs work of several repositories in one transaction. The work is retried
+/// from scratch if the transaction fails with a serialization failure (SQLSTATE 40001),
+/// which CockroachDB returns on conflicting concurrent transactions
+pub struct UnitOfWork {
+    sea_orm_client: Arc<DatabaseConnection>,
+}
+
+impl UnitOfWork {
+    pub fn new(sea_orm_client: Arc<DatabaseConnection>) -> Self {
+        Self { sea_orm_client }
+    }
+
+    /// `work` gets the transaction connection to bind repositories to,
+    /// the transaction is committed if it succeeds and rolled back otherwise
+    pub async fn run<T, F, Fut>(&self, work: F) -> Result<T, HttpError>
+    where
+        F: Fn(DbConnection) -> Fut,
+        Fut: Future<Output = Result<T, HttpError>>,
+    {
+        let mut retry = 0;
+
+        loop {
+            match self.run_once(&work).await {
+                Err(HttpError::TransactionConflict(msg)) if retry < MAX_RETRIES => {
+                    retry += 1;
+                    tracing::debug!("Retrying unit of work ({retry}) after conflict: '{msg}'");
+
+                    let jitter = fastrand::u64(0..=BASE_RETRY_DELAY.as_millis() as u64);
+                    let delay = BASE_RETRY_DELAY * 2u32.pow(retry) + Duration::from_millis(jitter);
+                    tokio::time::sleep(delay).await;
+                }
+                result => return result,
+            }
+        }
+    }
+
+    async fn run_once<T, F, Fut>(&self, work: &F) -> Result<T, HttpError>
+    where
+        F: Fn(DbConnection) -> Fut,
+        Fut: Future<Output = Result<T, HttpError>>,
+    {
+        let transaction = Arc::new(self.sea_orm_client.begin().await?);
+
+        // Dropping the transaction rolls it back
+        let value = work(DbConnection::Transaction(transaction.clone())).await?;
+
+        let transaction = Arc::try_unwrap(transaction).map_err(|_| {
+            DbErr::Custom("Transaction is still used after the unit of work is done".into())
+        })?;
+        transaction.commit().await?;
+
+        Ok(value)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use sea_orm::sqlx::error::{DatabaseError, ErrorKind};
+    use sea_orm::{DbBackend, MockDatabase, RuntimeErr};
+    use std::borrow::Cow;
+    use std::sync::Mutex;
+    use std::sync::atomic::{AtomicU32, Ordering};
+
+    #[derive(Debug)]
+    struct StubDatabaseError(&'static str);
+
+    impl std::fmt::Display for StubDatabaseError {
+        fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+            write!(f, "SQLSTATE {}", self.0)
+        }
+    }
+
+    impl std::error::Error for StubDatabaseError {}
+
+    impl DatabaseError for StubDatabaseError {
+        fn message(&self) -> &str {
+            "stub database error"
+        }
+
+        fn code(&self) -> Option<Cow<'_, str>> {
+            Some(Cow::Borrowed(self.0))
+        }
+
+        fn as_error(&self) -> &(dyn std::error::Error + Send + Sync + 'static) {
+            self
+        }
+
+        fn as_error_mut(&mut self) -> &mut (dyn std::error::Error + Send + Sync + 'static) {
+            self
+        }
+
+        fn into_error(self: Box<Self>) -> Box<dyn std::error::Error + Send + Sync + 'static> {
+            self
+        }
+
+        fn kind(&self) -> ErrorKind {
+            ErrorKind::Other
+        }
+    }
+
+    fn get_db_err(sqlstate: &'static str) -> HttpError {
+        let sqlx_err = sea_orm::sqlx::Error::Database(Box::new(StubDatabaseError(sqlstate)));
+
+        DbErr::Exec(RuntimeErr::SqlxError(sqlx_err)).into()
+    }
+
+    fn get_unit_of_work() -> UnitOfWork {
+        let connection = MockDatabase::new(DbBackend::Postgres).into_connection();
+
+        UnitOfWork::new(Arc::new(connection))
+    }
+
+    #[tokio::test]
+    async fn retry_on_serialization_failure() {
+        let unit_of_work = get_unit_of_work();
+        let attempts = AtomicU32::new(0);
+
+        let result = unit_of_work
+            .run(|_| async {
+                let attempt = attempts.fetch_add(1, Ordering::SeqCst) + 1;
+
+                if attempt < 3 {
+                    return Err(get_db_err("40001"));
+                }
+
+                Ok(attempt)
+            })
+            .await;
+
+        assert_eq!(result.ok(), Some(3));
+    }
+
+    #[tokio::test]
+    async fn give_up_after_max_retries() {
+        let unit_of_work = get_unit_of_work();
+        let attempts = AtomicU32::new(0);
+
+        let result: Result<(), HttpError> = unit_of_work
+            .run(|_| async {
+                attempts.fetch_add(1, Ordering::SeqCst);
+
+                Err(get_db_err("40001"))
+            })
+            .await;
+
+        assert!(matches!(result, Err(HttpError::TransactionConflict(_))));
+        assert_eq!(attempts.load(Ordering::SeqCst), MAX_RETRIES + 1);
+    }
+
+    #[tokio::test]
+    async fn skip_retries_on_other_errors() {
+        let cases = [
+            ("unique violation", get_db_err("23505")),
+            ("not found", HttpError::NotFound("Expense not found".into())),
+        ];
+
+        for (name, err) in cases {
+            let unit_of_work = get_unit_of_work();
+            let attempts = AtomicU32::new(0);
+            let err = Mutex::new(Some(err));
+
+            let result: Result<(), HttpError> = unit_of_work
+                .run(|_| async {
+                    attempts.fetch_add(1, Ordering::SeqCst);
+
+                    Err(err.lock().unwrap().take().unwrap())
+                })
+                .await;
+
+            assert!(result.is_err(), "{name}");
+            assert_eq!(attempts.load(Ordering::SeqCst), 1, "{name}");
+        }
+    }
+}
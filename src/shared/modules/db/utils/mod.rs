@@ -1,8 +1,95 @@
+use sea_orm::prelude::Decimal;
 use sea_orm::{ActiveValue, Value};
+use uuid::Uuid;
 
-pub fn optional_to_active_value<T>(optional: Option<T>) -> ActiveValue<T> where T: Into<Value> {
+use crate::shared::errors::http_error::HttpError;
+
+/// Largest amount fitting the `DECIMAL(10,2)` amount columns
+const MAX_AMOUNT: f64 = 99_999_999.99;
+
+pub fn optional_to_active_value<T>(optional: Option<T>) -> ActiveValue<T>
+where
+    T: Into<Value>,
+{
     match optional {
         Some(value) => ActiveValue::Set(value),
-        None => ActiveValue::NotSet
+        None => ActiveValue::NotSet,
+    }
+}
+
+/// IDs are generated by the app, UUIDv7 keeps them ordered by creation time
+pub fn generate_id() -> String {
+    Uuid::now_v7().to_string()
+}
+
+/// Amount for a `DECIMAL(10,2)` column, amounts out of its range are rejected
+/// instead of being stored as 0
+pub fn to_decimal_amount(amount: f64) -> Result<Decimal, HttpError> {
+    let out_of_range_err = || {
+        HttpError::BadRequest(format!(
+            "Amount {amount} should be a number between -{MAX_AMOUNT} and {MAX_AMOUNT}"
+        ))
+    };
+
+    if !amount.is_finite() || amount.abs() > MAX_AMOUNT {
+        return Err(out_of_range_err());
+    }
+
+    Decimal::try_from(amount).map_err(|_| out_of_range_err())
+}
+
+/// CockroachDB asks to retry the transaction with SQLSTATE 40001 on conflicts
+pub fn is_serialization_failure(db_err: &sea_orm::DbErr) -> bool {
+    has_sqlstate(db_err, "40001")
+}
+
+/// SQLSTATE 23505, e.g. a row with the same unique columns is created concurrently
+pub fn is_unique_violation(db_err: &sea_orm::DbErr) -> bool {
+    has_sqlstate(db_err, "23505")
+}
+
+fn has_sqlstate(db_err: &sea_orm::DbErr, sqlstate: &str) -> bool {
+    use sea_orm::{DbErr, RuntimeErr};
+
+    let (DbErr::Conn(RuntimeErr::SqlxError(sqlx_err))
+    | DbErr::Exec(RuntimeErr::SqlxError(sqlx_err))
+    | DbErr::Query(RuntimeErr::SqlxError(sqlx_err))) = db_err
+    else {
+        return false;
+    };
+
+    sqlx_err
+        .as_database_error()
+        .and_then(|database_err| database_err.code())
+        .is_some_and(|code| code == sqlstate)
+}
+
+#[cfg(test)]
+mod tests {
+    #[test]
+    fn to_decimal_amount() {
+        let cases = [
+            ("cents", 10.5, Some("10.5")),
+            ("zero", 0.0, Some("0")),
+            ("negative", -1.25, Some("-1.25")),
+            ("max", 99_999_999.99, Some("99999999.99")),
+            ("above max", 100_000_000.0, None),
+            ("below min", -100_000_000.0, None),
+            ("too large for Decimal", 1e30, None),
+            ("infinite", f64::INFINITY, None),
+            ("not a number", f64::NAN, None),
+        ];
+
+        for (name, amount, expected) in cases {
+            let decimal_amount = super::to_decimal_amount(amount).ok();
+
+            assert_eq!(
+                decimal_amount
+                    .map(|amount| amount.normalize().to_string())
+                    .as_deref(),
+                expected,
+                "{name}"
+            );
+        }
     }
-}
\ No newline at end of file
+}
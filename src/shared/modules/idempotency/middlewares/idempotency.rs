@@ -0,0 +1,515 @@
+use axum::body::{Body, Bytes, HttpBody};
+use axum::extract::FromRequest;
+use axum::http::request::Parts;
+use axum::http::{HeaderValue, Request, StatusCode};
+use axum::response::{IntoResponse, Response};
+use futures_util::future::BoxFuture;
+use sha2::{Digest, Sha256};
+use std::sync::Arc;
+use std::task::{Context, Poll};
+use std::time::Instant;
+use tower::{Layer, Service};
+
+use crate::shared::errors::http_error::HttpError;
+use crate::shared::modules::auth::structs::user::User;
+use crate::shared::modules::cache::structs::cached_response::CachedResponse;
+use crate::shared::modules::cache::traits::cache_service::CacheService;
+use crate::shared::modules::idempotency::structs::idempotency_record::IdempotencyRecord;
+
+pub const IDEMPOTENCY_KEY_HEADER_NAME: &str = "idempotency-key";
+pub const IDEMPOTENT_REPLAYED_HEADER_NAME: &str = "idempotent-replayed";
+
+const MAX_IDEMPOTENCY_KEY_LENGTH: usize = 255;
+const DEFAULT_TTL: usize = 86_400;
+const DEFAULT_LOCK_TTL: usize = 60;
+/// Bigger responses are not replayed, the key is released and retries are handled again
+const MAX_STORED_RESPONSE_SIZE: u64 = 2 * 1024 * 1024;
+
+/// Handles requests with an `Idempotency-Key` header once per key and user: retries get
+/// the stored response of the first request, reusing the key for another request is a conflict.
+/// Requests without the header are passed through. Has to run after authentication.
+/// Records have to be read and written in a cache shared by all instances, without a local tier
+/// (e.g. `AppL2CacheService`), otherwise a stale record or a local-only write lets a key be reused
+pub struct IdempotencyLayer<C>
+where
+    C: CacheService + Send + Sync,
+{
+    cache_service: Arc<C>,
+    ttl: usize,
+    lock_ttl: usize,
+}
+
+impl<C> IdempotencyLayer<C>
+where
+    C: CacheService + Send + Sync,
+{
+    pub fn new(cache_service: Arc<C>) -> Self {
+        Self {
+            cache_service,
+            ttl: DEFAULT_TTL,
+            lock_ttl: DEFAULT_LOCK_TTL,
+        }
+    }
+
+    /// Seconds responses are replayed for
+    pub fn with_ttl(mut self, ttl: usize) -> Self {
+        self.ttl = ttl;
+
+        self
+    }
+
+    /// Seconds a key stays locked by a request, after that the key is free again
+    /// in case the request never finished (e.g. the instance went down)
+    pub fn with_lock_ttl(mut self, lock_ttl: usize) -> Self {
+        self.lock_ttl = lock_ttl;
+
+        self
+    }
+}
+
+// Not derived, `C` itself does not have to be `Clone`
+impl<C> Clone for IdempotencyLayer<C>
+where
+    C: CacheService + Send + Sync,
+{
+    fn clone(&self) -> Self {
+        Self {
+            cache_service: self.cache_service.clone(),
+            ttl: self.ttl,
+            lock_ttl: self.lock_ttl,
+        }
+    }
+}
+
+impl<S, C> Layer<S> for IdempotencyLayer<C>
+where
+    C: CacheService + Send + Sync,
+{
+    type Service = IdempotencyMiddleware<S, C>;
+
+    fn layer(&self, inner: S) -> Self::Service {
+        IdempotencyMiddleware {
+            inner,
+            cache_service: self.cache_service.clone(),
+            ttl: self.ttl,
+            lock_ttl: self.lock_ttl,
+        }
+    }
+}
+
+pub struct IdempotencyMiddleware<S, C>
+where
+    C: CacheService + Send + Sync,
+{
+    inner: S,
+    cache_service: Arc<C>,
+    ttl: usize,
+    lock_ttl: usize,
+}
+
+impl<S, C> Clone for IdempotencyMiddleware<S, C>
+where
+    S: Clone,
+    C: CacheService + Send + Sync,
+{
+    fn clone(&self) -> Self {
+        Self {
+            inner: self.inner.clone(),
+            cache_service: self.cache_service.clone(),
+            ttl: self.ttl,
+            lock_ttl: self.lock_ttl,
+        }
+    }
+}
+
+impl<S, C> Service<Request<Body>> for IdempotencyMiddleware<S, C>
+where
+    S: Service<Request<Body>, Response = Response> + Send + 'static + Clone,
+    S::Future: Send + 'static,
+    S::Error: Send + 'static,
+    C: CacheService + Send + Sync + 'static,
+{
+    type Response = S::Response;
+    type Error = S::Error;
+    type Future = BoxFuture<'static, Result<Self::Response, Self::Error>>;
+
+    fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        self.inner.poll_ready(cx)
+    }
+
+    fn call(&mut self, request: Request<Body>) -> Self::Future {
+        let clone = self.inner.clone();
+        let mut inner = std::mem::replace(&mut self.inner, clone);
+        let cache_service = self.cache_service.clone();
+        let ttl = self.ttl;
+        let lock_ttl = self.lock_ttl;
+
+        Box::pin(async move {
+            let idempotency_key = match get_idempotency_key(&request) {
+                Ok(Some(idempotency_key)) => idempotency_key,
+                Ok(None) => return inner.call(request).await,
+                Err(err) => return Ok(err.into_response()),
+            };
+
+            let (parts, body) = request.into_parts();
+            let body = match read_body(&parts, body).await {
+                Ok(body) => body,
+                Err(rejection) => return Ok(rejection),
+            };
+
+            let cache_key = get_cache_key(&parts, &idempotency_key);
+            let fingerprint = get_fingerprint(&parts, &body);
+            let request = Request::from_parts(parts, Body::from(body));
+
+            let lock_record = IdempotencyRecord::InProgress {
+                fingerprint: fingerprint.clone(),
+            };
+            let lock_result = cache_service
+                .set_if_absent_with_ttl(&cache_key, &lock_record, lock_ttl)
+                .await;
+
+            match lock_result {
+                Ok(true) => {}
+                Ok(false) => {
+                    return Ok(get_stored_response(
+                        cache_service.as_ref(),
+                        &cache_key,
+                        &fingerprint,
+                    )
+                    .await
+                    .unwrap_or_else(IntoResponse::into_response));
+                }
+                // Without the key the request could be handled twice, so it's not handled at all
+                Err(err) => {
+                    tracing::error!(
+                        "Idempotency key '{cache_key}' is failed to lock with err: '{err}'"
+                    );
+
+                    return Ok(HttpError::ServiceUnavailable(
+                        "Idempotency-Key can not be checked at the moment, retry the request later"
+                            .into(),
+                    )
+                    .into_response());
+                }
+            }
+
+            let started_at = Instant::now();
+            let response = inner.call(request).await;
+
+            let Ok(response) = response else {
+                release_key(cache_service.as_ref(), &cache_key).await;
+
+                return response;
+            };
+
+            Ok(store_response(
+                cache_service.as_ref(),
+                &cache_key,
+                fingerprint,
+                response,
+                ttl,
+                started_at,
+            )
+            .await)
+        })
+    }
+}
+
+fn get_idempotency_key(request: &Request<Body>) -> Result<Option<String>, HttpError> {
+    let Some(idempotency_key) = request.headers().get(IDEMPOTENCY_KEY_HEADER_NAME) else {
+        return Ok(None);
+    };
+
+    let idempotency_key = idempotency_key.to_str().map(str::trim).unwrap_or_default();
+
+    if idempotency_key.is_empty() || idempotency_key.len() > MAX_IDEMPOTENCY_KEY_LENGTH {
+        return Err(HttpError::BadRequest(format!(
+            "Idempotency-Key should be a visible ASCII string of 1 to {MAX_IDEMPOTENCY_KEY_LENGTH} characters"
+        )));
+    }
+
+    Ok(Some(idempotency_key.to_string()))
+}
+
+/// Reads the body within the `DefaultBodyLimit` of the route, as the `Bytes` extractor does
+async fn read_body(parts: &Parts, body: Body) -> Result<Bytes, Response> {
+    let mut request = Request::new(body);
+    *request.extensions_mut() = parts.extensions.clone();
+
+    Bytes::from_request(request, &())
+        .await
+        .map_err(IntoResponse::into_response)
+}
+
+/// Keys are per user. An admin acting as a user has own keys, so they don't replay the user's requests
+fn get_cache_key(parts: &Parts, idempotency_key: &str) -> String {
+    let Some(user) = parts.extensions.get::<User>() else {
+        return format!("idempotency::{idempotency_key}");
+    };
+
+    match &user.impersonated_by {
+        Some(admin_id) => format!("idempotency:{admin_id}:{}:{idempotency_key}", user.id),
+        None => format!("idempotency:{}:{idempotency_key}", user.id),
+    }
+}
+
+/// Same key with another method, path or body is another request
+fn get_fingerprint(parts: &axum::http::request::Parts, body: &[u8]) -> String {
+    let mut hasher = Sha256::new();
+    hasher.update(parts.method.as_str());
+    hasher.update(b" ");
+    hasher.update(parts.uri.to_string());
+    hasher.update(b"\n");
+    hasher.update(body);
+
+    hasher
+        .finalize()
+        .iter()
+        .map(|byte| format!("{byte:02x}"))
+        .collect()
+}
+
+/// Response for a key which is already taken
+async fn get_stored_response<C>(
+    cache_service: &C,
+    cache_key: &str,
+    fingerprint: &str,
+) -> Result<Response, HttpError>
+where
+    C: CacheService + Send + Sync,
+{
+    let record = cache_service
+        .get::<IdempotencyRecord>(cache_key)
+        .await
+        .map_err(|_| get_in_progress_error())?;
+
+    if record.get_fingerprint() != fingerprint {
+        return Err(HttpError::Conflict(
+            "Idempotency-Key is already used for a request with different parameters".into(),
+        ));
+    }
+
+    let IdempotencyRecord::Completed { response, .. } = record else {
+        return Err(get_in_progress_error());
+    };
+
+    let mut response = response.to_response()?;
+    response.headers_mut().insert(
+        IDEMPOTENT_REPLAYED_HEADER_NAME,
+        HeaderValue::from_static("true"),
+    );
+
+    Ok(response)
+}
+
+fn get_in_progress_error() -> HttpError {
+    HttpError::Conflict(
+        "A request with this Idempotency-Key is still in progress, retry it later".into(),
+    )
+}
+
+/// Stores the response to be replayed. Server errors and conflicts are worth retrying,
+/// so the key is released for them instead
+async fn store_response<C>(
+    cache_service: &C,
+    cache_key: &str,
+    fingerprint: String,
+    response: Response,
+    ttl: usize,
+    started_at: Instant,
+) -> Response
+where
+    C: CacheService + Send + Sync,
+{
+    let status = response.status();
+    let is_too_big = response
+        .body()
+        .size_hint()
+        .upper()
+        .is_none_or(|size| size > MAX_STORED_RESPONSE_SIZE);
+
+    if status.is_server_error() || status == StatusCode::CONFLICT || is_too_big {
+        release_key(cache_service, cache_key).await;
+
+        return response;
+    }
+
+    let (parts, body) = response.into_parts();
+    let body = match axum::body::to_bytes(body, MAX_STORED_RESPONSE_SIZE as usize).await {
+        Ok(body) => body,
+        Err(err) => {
+            tracing::error!(
+                "Response for idempotency key '{cache_key}' is failed to read: '{err}'"
+            );
+            release_key(cache_service, cache_key).await;
+
+            return HttpError::Internal("Internal server error".to_string()).into_response();
+        }
+    };
+
+    let record = IdempotencyRecord::Completed {
+        fingerprint,
+        response: CachedResponse::new(
+            parts.status,
+            &parts.headers,
+            &body,
+            ttl,
+            started_at.elapsed(),
+        ),
+    };
+
+    if let Err(err) = cache_service.set_with_ttl(cache_key, &record, ttl).await {
+        tracing::warn!(
+            "Response for idempotency key '{cache_key}' is failed to set with err: '{err}'"
+        );
+    }
+
+    Response::from_parts(parts, Body::from(body))
+}
+
+async fn release_key<C>(cache_service: &C, cache_key: &str)
+where
+    C: CacheService + Send + Sync,
+{
+    if let Err(err) = cache_service.delete(cache_key).await {
+        tracing::warn!("Idempotency key '{cache_key}' is failed to release with err: '{err}'");
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::convert::Infallible;
+    use std::sync::Mutex;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use tower::ServiceExt;
+
+    use crate::shared::modules::cache::services::memory_cache::MemoryCacheService;
+
+    /// Responds with the queued statuses in order, then with `201 Created`
+    #[derive(Clone)]
+    struct StubHandler {
+        statuses: Arc<Mutex<Vec<StatusCode>>>,
+        calls: Arc<AtomicUsize>,
+    }
+
+    impl StubHandler {
+        fn new(statuses: Vec<StatusCode>) -> Self {
+            Self {
+                statuses: Arc::new(Mutex::new(statuses)),
+                calls: Arc::new(AtomicUsize::new(0)),
+            }
+        }
+
+        fn get_calls(&self) -> usize {
+            self.calls.load(Ordering::SeqCst)
+        }
+    }
+
+    impl Service<Request<Body>> for StubHandler {
+        type Response = Response;
+        type Error = Infallible;
+        type Future = BoxFuture<'static, Result<Self::Response, Self::Error>>;
+
+        fn poll_ready(&mut self, _: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+            Poll::Ready(Ok(()))
+        }
+
+        fn call(&mut self, _: Request<Body>) -> Self::Future {
+            let call = self.calls.fetch_add(1, Ordering::SeqCst) + 1;
+            let mut statuses = self.statuses.lock().unwrap();
+            let status = match statuses.is_empty() {
+                true => StatusCode::CREATED,
+                false => statuses.remove(0),
+            };
+
+            Box::pin(async move { Ok((status, format!("call {call}")).into_response()) })
+        }
+    }
+
+    fn get_request(idempotency_key: &str, body: &'static str) -> Request<Body> {
+        Request::post("/expenses")
+            .header(IDEMPOTENCY_KEY_HEADER_NAME, idempotency_key)
+            .body(Body::from(body))
+            .unwrap()
+    }
+
+    async fn send(
+        middleware: &IdempotencyMiddleware<StubHandler, MemoryCacheService>,
+        request: Request<Body>,
+    ) -> (StatusCode, bool, String) {
+        let response = middleware.clone().oneshot(request).await.unwrap();
+        let status = response.status();
+        let is_replayed = response
+            .headers()
+            .contains_key(IDEMPOTENT_REPLAYED_HEADER_NAME);
+        let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+            .await
+            .unwrap();
+
+        (
+            status,
+            is_replayed,
+            String::from_utf8(body.to_vec()).unwrap(),
+        )
+    }
+
+    fn get_middleware(
+        handler: &StubHandler,
+    ) -> IdempotencyMiddleware<StubHandler, MemoryCacheService> {
+        let cache_service = Arc::new(MemoryCacheService::new(100, 60));
+
+        IdempotencyLayer::new(cache_service).layer(handler.clone())
+    }
+
+    #[tokio::test]
+    async fn replay_stored_response() {
+        let handler = StubHandler::new(vec![]);
+        let middleware = get_middleware(&handler);
+
+        let first = send(&middleware, get_request("key", "{}")).await;
+        let retry = send(&middleware, get_request("key", "{}")).await;
+
+        assert_eq!(first, (StatusCode::CREATED, false, "call 1".into()));
+        assert_eq!(retry, (StatusCode::CREATED, true, "call 1".into()));
+        assert_eq!(handler.get_calls(), 1);
+    }
+
+    #[tokio::test]
+    async fn reject_key_reused_for_another_request() {
+        let handler = StubHandler::new(vec![]);
+        let middleware = get_middleware(&handler);
+
+        send(&middleware, get_request("key", r#"{"amount":1}"#)).await;
+        let (status, is_replayed, _) =
+            send(&middleware, get_request("key", r#"{"amount":2}"#)).await;
+
+        assert_eq!((status, is_replayed), (StatusCode::CONFLICT, false));
+        assert_eq!(handler.get_calls(), 1);
+    }
+
+    #[tokio::test]
+    async fn release_key_after_server_error() {
+        let handler = StubHandler::new(vec![StatusCode::INTERNAL_SERVER_ERROR]);
+        let middleware = get_middleware(&handler);
+
+        let first = send(&middleware, get_request("key", "{}")).await;
+        let retry = send(&middleware, get_request("key", "{}")).await;
+
+        assert_eq!(first.0, StatusCode::INTERNAL_SERVER_ERROR);
+        assert_eq!(retry, (StatusCode::CREATED, false, "call 2".into()));
+        assert_eq!(handler.get_calls(), 2);
+    }
+
+    #[tokio::test]
+    async fn pass_through_requests_without_key() {
+        let handler = StubHandler::new(vec![]);
+        let middleware = get_middleware(&handler);
+        let get_request = || Request::post("/expenses").body(Body::from("{}")).unwrap();
+
+        send(&middleware, get_request()).await;
+        send(&middleware, get_request()).await;
+
+        assert_eq!(handler.get_calls(), 2);
+    }
+}
@@ -0,0 +1,2 @@
+pub mod middlewares;
+pub mod structs;
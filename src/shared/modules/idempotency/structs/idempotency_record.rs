@@ -0,0 +1,27 @@
+use serde::{Deserialize, Serialize};
+
+use crate::shared::modules::cache::structs::cached_response::CachedResponse;
+
+/// Stored under an `Idempotency-Key` by `IdempotencyMiddleware`. The fingerprint identifies
+/// the request the key was first used with
+#[derive(Serialize, Deserialize)]
+#[serde(tag = "state", rename_all = "camelCase")]
+pub enum IdempotencyRecord {
+    /// The first request is still being handled
+    #[serde(rename_all = "camelCase")]
+    InProgress { fingerprint: String },
+    #[serde(rename_all = "camelCase")]
+    Completed {
+        fingerprint: String,
+        response: CachedResponse,
+    },
+}
+
+impl IdempotencyRecord {
+    pub fn get_fingerprint(&self) -> &str {
+        match self {
+            Self::InProgress { fingerprint } => fingerprint,
+            Self::Completed { fingerprint, .. } => fingerprint,
+        }
+    }
+}
@@ -1,6 +1,10 @@
+pub mod audit;
 pub mod auth;
 pub mod cache;
+pub mod db;
+pub mod idempotency;
 pub mod logger;
 pub mod open_api;
 pub mod redis;
-pub mod db;
+pub mod upload;
+pub mod versioning;
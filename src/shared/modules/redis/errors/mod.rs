@@ -6,4 +6,10 @@ pub enum RedisServiceError {
     Client(String),
     #[error("{0}")]
     ConnectionManager(String),
+    #[error("{0}")]
+    Sentinel(String),
+    #[error("{0}")]
+    Cluster(String),
+    #[error("{0}")]
+    InvalidAddress(String),
 }
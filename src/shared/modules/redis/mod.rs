@@ -1,43 +1,355 @@
+use redis::cluster::ClusterClient;
+use redis::sentinel::{SentinelClient, SentinelNodeConnectionInfo, SentinelServerType};
+use redis::{ConnectionAddr, ConnectionInfo, IntoConnectionInfo, RedisConnectionInfo, TlsMode};
+use std::sync::{Arc, OnceLock};
+use std::time::Duration;
+
+use crate::shared::config::{AppConfig, RedisMode};
 use crate::shared::modules::redis::errors::RedisServiceError;
+use crate::shared::modules::redis::redis_connection::RedisConnection;
 use crate::shared::modules::redis::redis_service::RedisService;
+use crate::shared::modules::redis::sentinel_connection::SentinelConnection;
 
 pub mod errors;
+pub mod redis_connection;
 pub mod redis_service;
+pub mod sentinel_connection;
+
+const MIN_RECONNECT_DELAY: Duration = Duration::from_secs(1);
+const MAX_RECONNECT_DELAY: Duration = Duration::from_secs(30);
 
 pub struct RedisServiceBuilder {
-    host: String,
-    port: u16,
+    topology: RedisTopology,
+    username: Option<String>,
+    password: Option<String>,
+    db: i64,
+    tls: bool,
     default_ttl: Option<usize>,
 }
 
+enum RedisTopology {
+    Standalone {
+        host: String,
+        port: u16,
+    },
+    Sentinel {
+        master_name: String,
+        sentinels: Vec<(String, u16)>,
+    },
+    Cluster {
+        nodes: Vec<(String, u16)>,
+    },
+}
+
 impl RedisServiceBuilder {
     pub fn new(host: &str, port: u16) -> Self {
         Self {
-            host: host.to_string(),
-            port,
+            topology: RedisTopology::Standalone {
+                host: host.to_string(),
+                port,
+            },
+            username: None,
+            password: None,
+            db: 0,
+            tls: false,
             default_ttl: None,
         }
     }
 
+    pub fn from_config(config: &AppConfig) -> Result<Self, RedisServiceError> {
+        let builder = match config.redis_mode {
+            RedisMode::Standalone => Self::new(&config.redis_host, config.redis_port),
+            RedisMode::Sentinel => Self::new(&config.redis_host, config.redis_port).with_sentinel(
+                &config.redis_name,
+                parse_redis_nodes(&config.redis_sentinels)?,
+            ),
+            RedisMode::Cluster => Self::new(&config.redis_host, config.redis_port)
+                .with_cluster(parse_redis_nodes(&config.redis_cluster_nodes)?),
+        };
+
+        let builder = builder
+            .with_credentials(
+                config.redis_username.as_deref(),
+                config.redis_password.as_deref(),
+            )
+            .with_db(config.redis_db)
+            .with_tls(config.redis_tls)
+            .with_default_ttl(config.redis_ttl);
+
+        Ok(builder)
+    }
+
+    /// Connects to the master of `master_name` discovered through the sentinels
+    pub fn with_sentinel(mut self, master_name: &str, sentinels: Vec<(String, u16)>) -> Self {
+        self.topology = RedisTopology::Sentinel {
+            master_name: master_name.to_string(),
+            sentinels,
+        };
+
+        self
+    }
+
+    pub fn with_cluster(mut self, nodes: Vec<(String, u16)>) -> Self {
+        self.topology = RedisTopology::Cluster { nodes };
+
+        self
+    }
+
+    /// `username` is used for ACL authentication, only `password` is needed for the legacy one
+    pub fn with_credentials(mut self, username: Option<&str>, password: Option<&str>) -> Self {
+        self.username = username
+            .filter(|username| !username.is_empty())
+            .map(ToString::to_string);
+        self.password = password
+            .filter(|password| !password.is_empty())
+            .map(ToString::to_string);
+
+        self
+    }
+
+    pub fn with_db(mut self, db: i64) -> Self {
+        self.db = db;
+
+        self
+    }
+
+    pub fn with_tls(mut self, tls: bool) -> Self {
+        self.tls = tls;
+
+        self
+    }
+
     pub fn with_default_ttl(mut self, default_ttl: usize) -> Self {
         self.default_ttl = Some(default_ttl);
 
         self
     }
 
-    pub async fn build(self) -> Result<RedisService, RedisServiceError> {
-        let redis_uri = format!("redis://{}:{}", self.host, self.port);
+    /// Returns the service right away and connects in the background, retrying with a backoff.
+    /// Until the connection is established every call fails with `CacheError::Unavailable`
+    pub fn build_in_background(self) -> RedisService {
+        let connection = Arc::new(OnceLock::new());
+        let redis_service = RedisService::new(connection.clone(), self.default_ttl.unwrap_or(0));
+
+        tokio::spawn(async move {
+            let mut retry_delay = MIN_RECONNECT_DELAY;
+
+            loop {
+                match self.connect().await {
+                    Ok(redis_connection) => {
+                        let _ = connection.set(redis_connection);
+                        tracing::info!("Redis connection is established");
+
+                        break;
+                    }
+                    Err(err) => {
+                        tracing::warn!(
+                            "Failed to connect to Redis, retrying in {retry_delay:?}, err: '{err}'"
+                        );
+
+                        tokio::time::sleep(retry_delay).await;
+                        retry_delay = (retry_delay * 2).min(MAX_RECONNECT_DELAY);
+                    }
+                }
+            }
+        });
+
+        redis_service
+    }
+
+    async fn connect(&self) -> Result<RedisConnection, RedisServiceError> {
+        match &self.topology {
+            RedisTopology::Standalone { host, port } => {
+                let connection_info = self.get_connection_info(host, *port)?;
+
+                self.connect_single(connection_info).await
+            }
+            RedisTopology::Sentinel {
+                master_name,
+                sentinels,
+            } => self.connect_sentinel(master_name, sentinels).await,
+            RedisTopology::Cluster { nodes } => self.connect_cluster(nodes).await,
+        }
+    }
 
-        let redis_client = redis::Client::open(redis_uri)
+    async fn connect_single(
+        &self,
+        connection_info: ConnectionInfo,
+    ) -> Result<RedisConnection, RedisServiceError> {
+        let redis_client = redis::Client::open(connection_info)
             .map_err(|err| RedisServiceError::Client(err.to_string()))?;
         let redis_connection_manager = redis_client
             .get_connection_manager()
             .await
             .map_err(|err| RedisServiceError::ConnectionManager(err.to_string()))?;
 
-        let redis_service =
-            RedisService::new(redis_connection_manager, self.default_ttl.unwrap_or(0));
+        Ok(RedisConnection::Single(redis_connection_manager))
+    }
+
+    // The sentinels are asked for the master again whenever the master connection is lost
+    async fn connect_sentinel(
+        &self,
+        master_name: &str,
+        sentinels: &[(String, u16)],
+    ) -> Result<RedisConnection, RedisServiceError> {
+        let sentinels_connection_info = sentinels
+            .iter()
+            .map(|(host, port)| self.get_addr(host, *port).into_connection_info())
+            .collect::<Result<Vec<_>, _>>()
+            .map_err(|err| RedisServiceError::InvalidAddress(err.to_string()))?;
+
+        let mut node_connection_info = SentinelNodeConnectionInfo::default()
+            .set_redis_connection_info(self.get_redis_connection_info());
 
-        Ok(redis_service)
+        if self.tls {
+            node_connection_info = node_connection_info.set_tls_mode(TlsMode::Secure);
+        }
+
+        let sentinel_client = SentinelClient::build(
+            sentinels_connection_info,
+            master_name.to_string(),
+            Some(node_connection_info),
+            SentinelServerType::Master,
+        )
+        .map_err(|err| RedisServiceError::Sentinel(err.to_string()))?;
+
+        let sentinel_connection = SentinelConnection::connect(sentinel_client, self.db)
+            .await
+            .map_err(|err| {
+                RedisServiceError::Sentinel(format!(
+                    "Failed to resolve master '{master_name}' from sentinels: {err}"
+                ))
+            })?;
+
+        Ok(RedisConnection::Sentinel(sentinel_connection))
+    }
+
+    async fn connect_cluster(
+        &self,
+        nodes: &[(String, u16)],
+    ) -> Result<RedisConnection, RedisServiceError> {
+        let nodes_connection_info = nodes
+            .iter()
+            .map(|(host, port)| self.get_connection_info(host, *port))
+            .collect::<Result<Vec<_>, _>>()?;
+
+        let mut cluster_client_builder = ClusterClient::builder(nodes_connection_info);
+
+        if let Some(username) = &self.username {
+            cluster_client_builder = cluster_client_builder.username(username);
+        }
+
+        if let Some(password) = &self.password {
+            cluster_client_builder = cluster_client_builder.password(password);
+        }
+
+        if self.tls {
+            cluster_client_builder = cluster_client_builder.tls(TlsMode::Secure);
+        }
+
+        let cluster_connection = cluster_client_builder
+            .build()
+            .map_err(|err| RedisServiceError::Cluster(err.to_string()))?
+            .get_async_connection()
+            .await
+            .map_err(|err| RedisServiceError::Cluster(err.to_string()))?;
+
+        Ok(RedisConnection::Cluster(cluster_connection))
+    }
+
+    fn get_connection_info(
+        &self,
+        host: &str,
+        port: u16,
+    ) -> Result<ConnectionInfo, RedisServiceError> {
+        let connection_info = self
+            .get_addr(host, port)
+            .into_connection_info()
+            .map_err(|err| RedisServiceError::InvalidAddress(err.to_string()))?
+            .set_redis_settings(self.get_redis_connection_info());
+
+        Ok(connection_info)
+    }
+
+    fn get_addr(&self, host: &str, port: u16) -> ConnectionAddr {
+        if self.tls {
+            ConnectionAddr::TcpTls {
+                host: host.to_string(),
+                port,
+                insecure: false,
+                tls_params: None,
+            }
+        } else {
+            ConnectionAddr::Tcp(host.to_string(), port)
+        }
+    }
+
+    fn get_redis_connection_info(&self) -> RedisConnectionInfo {
+        let mut redis_connection_info = RedisConnectionInfo::default().set_db(self.db);
+
+        if let Some(username) = &self.username {
+            redis_connection_info = redis_connection_info.set_username(username);
+        }
+
+        if let Some(password) = &self.password {
+            redis_connection_info = redis_connection_info.set_password(password);
+        }
+
+        redis_connection_info
+    }
+}
+
+/// Parses comma separated `host:port` list, e.g. `redis-1:26379,redis-2:26379`
+pub fn parse_redis_nodes(nodes: &str) -> Result<Vec<(String, u16)>, RedisServiceError> {
+    nodes
+        .split(',')
+        .map(str::trim)
+        .filter(|node| !node.is_empty())
+        .map(|node| {
+            let invalid_address_err =
+                || RedisServiceError::InvalidAddress(format!("'{node}' is not a host:port pair"));
+
+            let (host, port) = node.rsplit_once(':').ok_or_else(invalid_address_err)?;
+            let port = port.parse::<u16>().map_err(|_| invalid_address_err())?;
+
+            if host.is_empty() {
+                return Err(invalid_address_err());
+            }
+
+            Ok((host.to_string(), port))
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    #[test]
+    fn parse_redis_nodes() {
+        let cases = [
+            ("empty", "", Some(vec![])),
+            ("one node", "redis:6379", Some(vec![("redis", 6379)])),
+            (
+                "nodes with spaces and a trailing comma",
+                " redis-1:26379 , redis-2:26380,",
+                Some(vec![("redis-1", 26379), ("redis-2", 26380)]),
+            ),
+            ("IPv6 host", "[::1]:6379", Some(vec![("[::1]", 6379)])),
+            ("without port", "redis", None),
+            ("without host", ":6379", None),
+            ("invalid port", "redis:port", None),
+            ("port out of range", "redis:65536", None),
+            ("one invalid node", "redis-1:6379,redis-2", None),
+        ];
+
+        for (name, nodes, expected) in cases {
+            let expected = expected.map(|nodes| {
+                nodes
+                    .into_iter()
+                    .map(|(host, port)| (host.to_string(), port))
+                    .collect::<Vec<_>>()
+            });
+
+            assert_eq!(super::parse_redis_nodes(nodes).ok(), expected, "{name}");
+        }
     }
 }
@@ -1,92 +1,63 @@
 use async_trait::async_trait;
-use redis::{aio::ConnectionManager, AsyncCommands, RedisResult};
-use serde::de::DeserializeOwned;
-use serde::Serialize;
-use std::future::Future;
+use redis::{AsyncCommands, RedisResult};
+use std::sync::{Arc, OnceLock};
 
 use crate::shared::modules::cache::errors::CacheError;
 use crate::shared::modules::cache::traits::cache_service::CacheService;
+use crate::shared::modules::redis::redis_connection::RedisConnection;
 
 #[derive(Clone)]
 pub struct RedisService {
-    connection_manager: ConnectionManager,
+    // Set once the connection is established, see `RedisServiceBuilder::build_in_background`
+    connection: Arc<OnceLock<RedisConnection>>,
     default_ttl: usize,
 }
 
 impl RedisService {
-    pub fn new(redis_connection_manager: ConnectionManager, default_ttl: usize) -> Self {
+    pub fn new(connection: Arc<OnceLock<RedisConnection>>, default_ttl: usize) -> Self {
         Self {
-            connection_manager: redis_connection_manager,
+            connection,
             default_ttl,
         }
     }
+
+    fn get_connection(&self) -> Result<RedisConnection, CacheError> {
+        self.connection
+            .get()
+            .cloned()
+            .ok_or_else(|| CacheError::Unavailable("Redis is not connected yet".into()))
+    }
 }
 
 #[async_trait]
 impl CacheService for RedisService {
-    async fn get_str(&self, key: &str) -> Result<String, CacheError> {
-        let cached_value = self.connection_manager.clone().get(key).await?;
-
-        Ok(cached_value)
+    fn get_default_ttl(&self) -> usize {
+        // TODO Find out if 0 TTL is valid value
+        self.default_ttl
     }
 
-    async fn get<T>(&self, key: &str) -> Result<T, CacheError>
-    where
-        T: DeserializeOwned,
-    {
+    async fn get_str(&self, key: &str) -> Result<String, CacheError> {
         let cached_value = self
-            .connection_manager
-            .clone()
+            .get_connection()?
             .get::<_, Option<String>>(key)
-            .await
-            .map_err(|err| CacheError::Unknown(err.to_string()))?
-            .ok_or(CacheError::KeyNotFound(key.to_string()))
-            .map(|cached_str| serde_json::from_str(&cached_str))?
-            .map_err(|err| CacheError::FailedToParseResponse(err.to_string()))?;
+            .await?
+            .ok_or(CacheError::KeyNotFound(key.to_string()))?;
 
         Ok(cached_value)
     }
 
-    async fn set<T>(&self, key: &str, value: &T) -> Result<String, CacheError>
-    where
-        T: Serialize + Send + Sync,
-    {
-        // TODO Find out if 0 TTL is valid value
-        self.set_with_ttl(key, value, self.default_ttl).await
-    }
-
-    async fn set_with_ttl<T>(&self, key: &str, value: &T, ttl: usize) -> Result<String, CacheError>
-    where
-        T: Serialize + Send + Sync,
-    {
-        let serialized_value = serde_json::to_string(value).map_err(|err| {
-            CacheError::Unknown(format!(
-                "Failed to serialize value for key '{key}' to set in Redis, err: '{err}'"
-            ))
-        })?;
-
-        let mut connection_manager = self.connection_manager.clone();
-        let redis_result: RedisResult<(String, i32)> = redis::pipe()
+    async fn get_str_with_ttl(&self, key: &str) -> Result<(String, Option<usize>), CacheError> {
+        let mut connection = self.get_connection()?;
+        // TTL replies with -1 if the key does not expire and with -2 if it does not exist
+        let (cached_value, ttl): (Option<String>, i64) = redis::pipe()
             .atomic()
-            .set(key, &serialized_value)
-            .expire(key, ttl as i64)
-            .query_async(&mut connection_manager)
-            .await;
-
-        redis_result
-            .map(|result| result.0)
-            .map_err(|err| CacheError::Unknown(err.to_string()))
-    }
+            .get(key)
+            .ttl(key)
+            .query_async(&mut connection)
+            .await?;
+        let cached_value = cached_value.ok_or(CacheError::KeyNotFound(key.to_string()))?;
 
-    // TODO Possibly move to the default impl
-    async fn set_str(&self, key: &str, value: &str) -> Result<String, CacheError> {
-        // TODO Find out if 0 TTL is valid value
-        self.set_str_with_ttl(key, value, self.default_ttl).await
-    }
-
-    // TODO Possibly move to the default impl
-    async fn set_bytes(&self, key: &str, value: &[u8]) -> Result<String, CacheError> {
-        self.set_bytes_with_ttl(key, value, self.default_ttl).await
+        Ok((cached_value, usize::try_from(ttl).ok()))
     }
 
     async fn set_str_with_ttl(
@@ -95,12 +66,12 @@ impl CacheService for RedisService {
         value: &str,
         ttl: usize,
     ) -> Result<String, CacheError> {
-        let mut connection_manager = self.connection_manager.clone();
+        let mut connection = self.get_connection()?;
         let redis_result: RedisResult<(String, i32)> = redis::pipe()
             .atomic()
             .set(key, value)
             .expire(key, ttl as i64)
-            .query_async(&mut connection_manager)
+            .query_async(&mut connection)
             .await;
 
         redis_result
@@ -108,47 +79,29 @@ impl CacheService for RedisService {
             .map_err(|err| CacheError::Unknown(err.to_string()))
     }
 
-    async fn set_bytes_with_ttl(
+    async fn set_str_if_absent_with_ttl(
         &self,
         key: &str,
-        value: &[u8],
+        value: &str,
         ttl: usize,
-    ) -> Result<String, CacheError> {
-        let mut connection_manager = self.connection_manager.clone();
-        let redis_result: RedisResult<(String, i32)> = redis::pipe()
-            .atomic()
-            .set(key, value)
-            .expire(key, ttl as i64)
-            .query_async(&mut connection_manager)
-            .await;
-
-        redis_result
-            .map(|result| result.0)
-            .map_err(|err| CacheError::Unknown(err.to_string()))
+    ) -> Result<bool, CacheError> {
+        let mut connection = self.get_connection()?;
+        // Replies with nil if the key exists
+        let set_result: Option<String> = redis::cmd("SET")
+            .arg(key)
+            .arg(value)
+            .arg("NX")
+            .arg("EX")
+            .arg(ttl)
+            .query_async(&mut connection)
+            .await?;
+
+        Ok(set_result.is_some())
     }
 
-    async fn wrap_fn<T, F, Fut>(&self, func: F, cache_key: &str) -> Result<T, CacheError>
-    where
-        T: Serialize + DeserializeOwned + Send + Sync,
-        F: FnOnce() -> Fut + Send + Sync,
-        Fut: Future<Output = Result<T, CacheError>> + Send + Sync,
-    {
-        let Ok(cached_value) = self.get(cache_key).await else {
-            let func_value = func().await?;
-            let set_result = self.set(cache_key, &func_value).await;
-
-            if set_result.is_ok() {
-                tracing::debug!("Cache for endpoint '{cache_key}' is set successfully")
-            } else {
-                tracing::warn!(
-                    "Cache for endpoint '{cache_key}' is failed to set with err: '{}'",
-                    set_result.unwrap_err()
-                )
-            }
+    async fn delete(&self, key: &str) -> Result<(), CacheError> {
+        self.get_connection()?.del::<_, ()>(key).await?;
 
-            return Ok(func_value);
-        };
-
-        Ok(cached_value)
+        Ok(())
     }
 }
@@ -0,0 +1,112 @@
+use redis::aio::{ConnectionLike, MultiplexedConnection};
+use redis::sentinel::SentinelClient;
+use redis::{
+    Cmd, ErrorKind, Pipeline, RedisError, RedisFuture, RedisResult, ServerErrorKind, Value,
+};
+use std::sync::Arc;
+use tokio::sync::{Mutex, RwLock};
+
+/// Connection to the master resolved through the sentinels. When the connection drops or the
+/// node turns into a replica after a failover, the master is resolved again in the background
+#[derive(Clone)]
+pub struct SentinelConnection {
+    sentinel_client: Arc<Mutex<SentinelClient>>,
+    master: Arc<RwLock<MasterConnection>>,
+    db: i64,
+}
+
+struct MasterConnection {
+    connection: MultiplexedConnection,
+    // Bumped on every reconnect, so concurrent failures of one connection reconnect once
+    generation: u64,
+}
+
+impl SentinelConnection {
+    pub async fn connect(mut sentinel_client: SentinelClient, db: i64) -> RedisResult<Self> {
+        let connection = sentinel_client.get_async_connection().await?;
+
+        Ok(Self {
+            sentinel_client: Arc::new(Mutex::new(sentinel_client)),
+            master: Arc::new(RwLock::new(MasterConnection {
+                connection,
+                generation: 0,
+            })),
+            db,
+        })
+    }
+
+    async fn get_master(&self) -> (MultiplexedConnection, u64) {
+        let master = self.master.read().await;
+
+        (master.connection.clone(), master.generation)
+    }
+
+    /// Failed commands are not retried, they might have been executed already
+    fn handle_result<T>(&self, result: RedisResult<T>, generation: u64) -> RedisResult<T> {
+        if let Err(err) = &result
+            && Self::is_master_lost(err)
+        {
+            let sentinel_connection = self.clone();
+
+            tokio::spawn(async move { sentinel_connection.reconnect(generation).await });
+        }
+
+        result
+    }
+
+    async fn reconnect(&self, failed_generation: u64) {
+        let mut sentinel_client = self.sentinel_client.lock().await;
+
+        if self.master.read().await.generation != failed_generation {
+            return;
+        }
+
+        match sentinel_client.get_async_connection().await {
+            Ok(connection) => {
+                let mut master = self.master.write().await;
+                master.connection = connection;
+                master.generation += 1;
+
+                tracing::info!("Redis master is resolved from sentinels again");
+            }
+            Err(err) => {
+                tracing::warn!("Failed to resolve Redis master from sentinels, err: '{err}'");
+            }
+        }
+    }
+
+    fn is_master_lost(err: &RedisError) -> bool {
+        err.is_io_error()
+            || err.is_unrecoverable_error()
+            || err.kind() == ErrorKind::Server(ServerErrorKind::ReadOnly)
+    }
+}
+
+impl ConnectionLike for SentinelConnection {
+    fn req_packed_command<'a>(&'a mut self, cmd: &'a Cmd) -> RedisFuture<'a, Value> {
+        Box::pin(async move {
+            let (mut connection, generation) = self.get_master().await;
+            let result = connection.req_packed_command(cmd).await;
+
+            self.handle_result(result, generation)
+        })
+    }
+
+    fn req_packed_commands<'a>(
+        &'a mut self,
+        cmd: &'a Pipeline,
+        offset: usize,
+        count: usize,
+    ) -> RedisFuture<'a, Vec<Value>> {
+        Box::pin(async move {
+            let (mut connection, generation) = self.get_master().await;
+            let result = connection.req_packed_commands(cmd, offset, count).await;
+
+            self.handle_result(result, generation)
+        })
+    }
+
+    fn get_db(&self) -> i64 {
+        self.db
+    }
+}
@@ -0,0 +1 @@
+pub mod uploaded_file;
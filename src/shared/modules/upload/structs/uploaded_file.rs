@@ -0,0 +1,79 @@
+use aide::OperationInput;
+use axum::body::Bytes;
+use axum::extract::{FromRequest, Multipart, Request};
+use axum::http::header::CONTENT_TYPE;
+
+use crate::shared::errors::http_error::HttpError;
+
+/// Name of the multipart field with the file
+pub const FILE_FIELD_NAME: &str = "file";
+
+/// File sent either as the raw body or as the `file` field of a `multipart/form-data` body
+pub struct UploadedFile {
+    pub content: Bytes,
+}
+
+impl<S> FromRequest<S> for UploadedFile
+where
+    S: Send + Sync,
+{
+    type Rejection = HttpError;
+
+    async fn from_request(request: Request, state: &S) -> Result<Self, Self::Rejection> {
+        let is_multipart = request
+            .headers()
+            .get(CONTENT_TYPE)
+            .and_then(|content_type| content_type.to_str().ok())
+            .is_some_and(|content_type| content_type.starts_with("multipart/form-data"));
+
+        let uploaded_file = if is_multipart {
+            Self::from_multipart(request, state).await?
+        } else {
+            let content = Bytes::from_request(request, state)
+                .await
+                .map_err(|err| HttpError::BadRequest(format!("Failed to read body: {err}")))?;
+
+            Self { content }
+        };
+
+        if uploaded_file.content.is_empty() {
+            return Err(HttpError::BadRequest("File is empty".into()));
+        }
+
+        Ok(uploaded_file)
+    }
+}
+
+impl UploadedFile {
+    async fn from_multipart<S>(request: Request, state: &S) -> Result<Self, HttpError>
+    where
+        S: Send + Sync,
+    {
+        let mut multipart = Multipart::from_request(request, state)
+            .await
+            .map_err(|err| HttpError::BadRequest(format!("Failed to read body: {err}")))?;
+
+        while let Some(field) = multipart
+            .next_field()
+            .await
+            .map_err(|err| HttpError::BadRequest(format!("Failed to read body: {err}")))?
+        {
+            if field.name() != Some(FILE_FIELD_NAME) {
+                continue;
+            }
+
+            let content = field
+                .bytes()
+                .await
+                .map_err(|err| HttpError::BadRequest(format!("Failed to read file: {err}")))?;
+
+            return Ok(Self { content });
+        }
+
+        Err(HttpError::BadRequest(format!(
+            "Multipart body should have a '{FILE_FIELD_NAME}' field"
+        )))
+    }
+}
+
+impl OperationInput for UploadedFile {}
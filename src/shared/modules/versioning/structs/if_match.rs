@@ -0,0 +1,103 @@
+use aide::OperationInput;
+use axum::extract::FromRequestParts;
+use axum::http::header;
+use axum::http::request::Parts;
+
+use crate::shared::errors::http_error::HttpError;
+
+/// Versions accepted by `If-Match` header, `None` if any version is accepted
+/// (the header is missing or `*`). Versions are taken from strong ETags, e.g. `"3"`
+pub struct IfMatch(pub Option<Vec<i64>>);
+
+impl<S> FromRequestParts<S> for IfMatch
+where
+    S: Send + Sync,
+{
+    type Rejection = HttpError;
+
+    async fn from_request_parts(parts: &mut Parts, _state: &S) -> Result<Self, Self::Rejection> {
+        let Some(if_match) = parts.headers.get(header::IF_MATCH) else {
+            return Ok(Self(None));
+        };
+
+        let if_match = if_match
+            .to_str()
+            .map_err(|_| HttpError::BadRequest("Invalid If-Match header".into()))?
+            .trim();
+
+        if if_match == "*" {
+            return Ok(Self(None));
+        }
+
+        // Weak ETags never match with the strong comparison used by If-Match
+        let versions: Vec<i64> = if_match
+            .split(',')
+            .filter_map(|etag| {
+                etag.trim()
+                    .strip_prefix('"')?
+                    .strip_suffix('"')?
+                    .parse()
+                    .ok()
+            })
+            .collect();
+
+        if versions.is_empty() {
+            return Err(HttpError::PreconditionFailed(
+                "If-Match does not match the current version".into(),
+            ));
+        }
+
+        Ok(Self(Some(versions)))
+    }
+}
+
+impl OperationInput for IfMatch {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use axum::http::Request;
+
+    async fn parse(if_match: Option<&str>) -> Result<Option<Vec<i64>>, HttpError> {
+        let mut request = Request::builder();
+
+        if let Some(if_match) = if_match {
+            request = request.header(header::IF_MATCH, if_match);
+        }
+
+        let (mut parts, _) = request.body(()).unwrap().into_parts();
+        let IfMatch(versions) = IfMatch::from_request_parts(&mut parts, &()).await?;
+
+        Ok(versions)
+    }
+
+    #[tokio::test]
+    async fn from_request_parts() {
+        let cases = [
+            ("missing", None, Some(None)),
+            ("any", Some(" * "), Some(None)),
+            ("one version", Some("\"3\""), Some(Some(vec![3]))),
+            ("list", Some("\"3\", \"4\""), Some(Some(vec![3, 4]))),
+            (
+                "weak tags are skipped",
+                Some("W/\"3\", \"4\""),
+                Some(Some(vec![4])),
+            ),
+            ("only weak tags", Some("W/\"3\""), None),
+            ("unquoted", Some("3"), None),
+            ("not a version", Some("\"abc\""), None),
+        ];
+
+        for (name, if_match, expected) in cases {
+            let result = parse(if_match).await;
+
+            match expected {
+                Some(expected) => assert_eq!(result.ok(), Some(expected), "{name}"),
+                None => assert!(
+                    matches!(result, Err(HttpError::PreconditionFailed(_))),
+                    "{name}"
+                ),
+            }
+        }
+    }
+}
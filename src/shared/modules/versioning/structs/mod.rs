@@ -0,0 +1,2 @@
+pub mod if_match;
+pub mod versioned_json;
@@ -0,0 +1,47 @@
+use aide::OperationOutput;
+use aide::generate::GenContext;
+use aide::openapi::{Operation, Response as OpenApiResponse};
+use axum::Json;
+use axum::http::header;
+use axum::response::{IntoResponse, Response};
+use schemars::JsonSchema;
+use serde::Serialize;
+
+use crate::shared::modules::versioning::traits::versioned::Versioned;
+
+/// JSON response with `ETag` header set to the version of the resource
+pub struct VersionedJson<T>(pub T)
+where
+    T: Serialize + Versioned;
+
+impl<T> IntoResponse for VersionedJson<T>
+where
+    T: Serialize + Versioned,
+{
+    fn into_response(self) -> Response {
+        let etag = self.0.get_etag();
+
+        ([(header::ETAG, etag)], Json(self.0)).into_response()
+    }
+}
+
+impl<T> OperationOutput for VersionedJson<T>
+where
+    T: Serialize + Versioned + JsonSchema,
+{
+    type Inner = T;
+
+    fn operation_response(
+        ctx: &mut GenContext,
+        operation: &mut Operation,
+    ) -> Option<OpenApiResponse> {
+        Json::<T>::operation_response(ctx, operation)
+    }
+
+    fn inferred_responses(
+        ctx: &mut GenContext,
+        operation: &mut Operation,
+    ) -> Vec<(Option<u16>, OpenApiResponse)> {
+        Json::<T>::inferred_responses(ctx, operation)
+    }
+}
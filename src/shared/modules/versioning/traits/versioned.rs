@@ -0,0 +1,12 @@
+use axum::http::HeaderValue;
+
+/// Resources which version is bumped on every update, used for optimistic concurrency
+pub trait Versioned {
+    fn get_version(&self) -> i64;
+
+    /// Strong ETag of the current version, compared with `If-Match`
+    fn get_etag(&self) -> HeaderValue {
+        HeaderValue::try_from(format!("\"{}\"", self.get_version()))
+            .expect("quoted number is a valid header value")
+    }
+}
@@ -1,4 +1,5 @@
-use axum::http::{header, Request};
+use axum::http::{Request, header};
+use serde::{Deserialize, Deserializer};
 
 pub fn get_bearer_token<B>(req: &Request<B>) -> Option<String> {
     let authorization_header = req.headers().get(header::AUTHORIZATION)?;
@@ -7,3 +8,12 @@ pub fn get_bearer_token<B>(req: &Request<B>) -> Option<String> {
 
     Some(token.to_string())
 }
+
+/// Tells an explicit `null` (`Some(None)`) from an omitted field (`None`)
+pub fn deserialize_nullable<'de, T, D>(deserializer: D) -> Result<Option<Option<T>>, D::Error>
+where
+    T: Deserialize<'de>,
+    D: Deserializer<'de>,
+{
+    Option::<T>::deserialize(deserializer).map(Some)
+}